@@ -3,7 +3,7 @@ extern crate serde;
 mod test_utils;
 
 use flexi_logger::LoggerHandle;
-use hdbconnect::{Connection, HdbResult, TypeId};
+use hdbconnect::{types::Wkb, Connection, HdbResult, TypeId};
 use log::{debug, info};
 use serde_bytes::{ByteBuf, Bytes};
 
@@ -66,6 +66,22 @@ fn test_geometries(_loghandle: &mut LoggerHandle, connection: &Connection) -> Hd
         .query("select count(*) from SpatialShapes")?
         .try_into()?;
     assert_eq!(count, 34);
+
+    debug!("deserialize into Wkb");
+    let wkb_shapes: Vec<(i32, Wkb)> = connection
+        .query("select ShapeID, shape from SpatialShapes where ShapeID < 100 order by ShapeID")?
+        .try_into()?;
+    assert_eq!(wkb_shapes.len(), shapes.len());
+    assert_eq!(wkb_shapes[0].1.as_bytes(), shapes[0].as_slice());
+
+    debug!("deserialize into Vec<u8>");
+    for row in connection
+        .query("select ShapeID, shape from SpatialShapes where ShapeID < 100 order by ShapeID")?
+    {
+        let row = row?;
+        let (_id, shape): (i32, Vec<u8>) = row.try_borrow_into()?;
+        assert!(!shape.is_empty());
+    }
     Ok(())
 }
 
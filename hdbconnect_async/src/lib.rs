@@ -48,6 +48,15 @@ mod bb8;
 /// It is possible to reset the statistics using [`Connection::reset_statistics`].
 pub use hdbconnect_impl::ConnectionStatistics;
 
+/// Provides process-wide statistics about the disconnect requests that are sent
+/// when connections are dropped.
+///
+/// Sending the disconnect request is limited to one second, and the socket is closed
+/// in any case; since the outcome cannot be reported to the code that drops the connection,
+/// it is counted here.
+/// A snapshot of the statistics can be obtained with [`DisconnectStatistics::snapshot`].
+pub use hdbconnect_impl::DisconnectStatistics;
+
 /// A collection of settings that influence the runtime behavior of a connection.
 ///
 /// To create a connection with non-default settings, use [`Connection::with_configuration`].
@@ -68,20 +77,94 @@ pub use hdbconnect_impl::ConnectionStatistics;
 pub use hdbconnect_impl::ConnectionConfiguration;
 
 /// Holdability of cursors in the database.
+///
+/// Defines whether open result sets survive the end of the transaction.
+/// The protocol differentiates between commit and rollback,
+/// so e.g. the cursors of a report can be kept open over a rollback of unrelated work
+/// on the same connection.
+/// The holdability can be set for the connection, for single queries
+/// (see `QueryOptions`), and for single prepared statements.
+///
+/// It can be parsed from `none`, `commit`, `rollback`, and `commit_and_rollback`.
 pub use hdbconnect_impl::CursorHoldability;
 
 pub use hdbconnect_impl::{
-    time, url, ConnectParams, ConnectParamsBuilder, DeserializationError, ExecutionResult,
-    FieldMetadata, HdbError, HdbResult, HdbValue, IntoConnectParams, IntoConnectParamsBuilder,
-    OutputParameters, ParameterBinding, ParameterDescriptor, ParameterDescriptors,
-    ParameterDirection, ResultSetMetadata, Row, SerializationError, ServerCerts, ServerError,
-    ServerUsage, Severity, ToHana, TypeId,
+    quote_ident, quote_qualified, time, url, AffectedRowCount, BulkLoadReport,
+    ClientDistributionMode, ClientInfo, ConnectParams, ConnectParamsBuilder, DeserializationError,
+    ExecutionResult, ExecutionResults, ExplainPlan, FailedBatch, FieldMetadata, GenericConnection,
+    GenericPreparedStatement, HdbError, HdbResult, HdbValue, HintedStatement, IdentifierCase,
+    IntoConnectParams, IntoConnectParamsBuilder, OutputParameters, ParameterBinding,
+    ParameterDescriptor, ParameterDescriptors, ParameterDirection, ParamsBuilder, PlanNode,
+    QueryOptions, ResultSetBuilder, ResultSetMetadata, Row, Rows, SerializationError, ServerCerts,
+    ServerConnectOptions, ServerError, ServerUsage, ServerVersion, SessionInfo, Severity,
+    SlowRequest, StatementBatch, StatementMetrics, ToHana, TypeId,
 };
 
 pub use hdbconnect_impl::a_sync::{
-    Connection, HdbResponse, HdbReturnValue, PreparedStatement, ResultSet,
+    BulkLoader, ChunkedDml, Connection, ConnectionGuard, HdbResponse, HdbResponseParts,
+    HdbReturnValue, PreparedStatement, ResultSet,
 };
 
+#[cfg_attr(docsrs, doc(cfg(feature = "migrations")))]
+#[cfg(feature = "migrations")]
+pub use hdbconnect_impl::{Migration, Migrations};
+
+/// Verifies at compile time that the result set of a query fits to a struct,
+/// and evaluates to the query string.
+///
+/// The struct must derive [`HanaRow`], besides `serde::Deserialize`.
+///
+/// If the environment variable `DATABASE_URL` is set at build time,
+/// the macro connects to this database and prepares the statement.
+/// The build then fails
+/// - if a column of the result set has no field in the struct,
+/// - if the type of a field cannot take the values of its column
+///   (nullable columns need `Option` fields),
+/// - or if a field that has neither a default nor an `Option` type has no column.
+///
+/// The columns are mapped to the fields as serde does it, i.e., the column name
+/// (or alias) is compared with the field name, considering `#[serde(rename)]`,
+/// `#[serde(rename_all)]`, `#[serde(default)]`, and `#[serde(skip)]`.
+///
+/// Without `DATABASE_URL`, the macro evaluates to the query string
+/// and emits a warning that the query is not verified.
+///
+/// ```rust,ignore
+/// #[derive(serde::Deserialize, hdbconnect_async::HanaRow)]
+/// #[serde(rename_all = "UPPERCASE")]
+/// struct Employee {
+///     id: i32,
+///     #[serde(rename = "EMP_NAME")]
+///     name: Option<String>,
+/// }
+/// let query = hdbconnect_async::hana_query!(Employee, "SELECT ID, EMP_NAME FROM EMPLOYEES");
+/// let employees: Vec<Employee> = connection.query(query).await?.try_into().await?;
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+#[cfg(feature = "macros")]
+#[macro_export]
+macro_rules! hana_query {
+    ($($args:tt)*) => {
+        $crate::__hana_query!($crate, $($args)*)
+    };
+}
+
+/// Derives the code with which [`hana_query!`] verifies a struct.
+///
+/// The derived code reads the `serde` attributes of the struct and its fields.
+/// `#[serde(flatten)]` is not supported.
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+#[cfg(feature = "macros")]
+pub use hdbconnect_macros::HanaRow;
+
+#[doc(hidden)]
+#[cfg(feature = "macros")]
+pub use hdbconnect_macros::__hana_query;
+
+#[doc(hidden)]
+#[cfg(feature = "macros")]
+pub use hdbconnect_impl::macro_support as __macro_support;
+
 /// Non-standard types that are used to represent database values.
 ///
 /// A `ResultSet` contains a sequence of `Row`s, each row is a sequence of `HdbValue`s.
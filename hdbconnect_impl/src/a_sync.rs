@@ -1,6 +1,9 @@
 mod blob;
+mod bulk_loader;
+mod chunked_dml;
 mod clob;
 mod connection;
+mod db_connection;
 mod hdb_response;
 mod hdb_return_value;
 mod nclob;
@@ -8,9 +11,11 @@ mod prepared_statement;
 mod result_set;
 
 pub use blob::BLob;
+pub use bulk_loader::BulkLoader;
+pub use chunked_dml::ChunkedDml;
 pub use clob::CLob;
-pub use connection::Connection;
-pub use hdb_response::HdbResponse;
+pub use connection::{Connection, ConnectionGuard};
+pub use hdb_response::{HdbResponse, HdbResponseParts};
 pub use hdb_return_value::HdbReturnValue;
 pub use nclob::NCLob;
 pub use prepared_statement::PreparedStatement;
@@ -0,0 +1,61 @@
+use crate::{
+    a_sync::{Connection, HdbResponse, PreparedStatement},
+    AffectedRowCount, GenericConnection, GenericPreparedStatement, HdbResult,
+};
+use serde::{de::DeserializeOwned, ser::Serialize};
+
+impl GenericConnection for Connection {
+    type PreparedStatement = PreparedStatement;
+
+    async fn query_into<T>(&self, stmt: &str) -> HdbResult<T>
+    where
+        T: DeserializeOwned + Send,
+    {
+        Connection::query(self, stmt).await?.try_into().await
+    }
+    async fn dml(&self, stmt: &str) -> HdbResult<AffectedRowCount> {
+        Connection::dml(self, stmt).await
+    }
+    async fn exec(&self, stmt: &str) -> HdbResult<()> {
+        Connection::exec(self, stmt).await
+    }
+    async fn prepare(&self, stmt: &str) -> HdbResult<PreparedStatement> {
+        Connection::prepare(self, stmt).await
+    }
+    async fn commit(&self) -> HdbResult<()> {
+        Connection::commit(self).await
+    }
+    async fn rollback(&self) -> HdbResult<()> {
+        Connection::rollback(self).await
+    }
+}
+
+impl GenericPreparedStatement for PreparedStatement {
+    async fn query_into<I, T>(&mut self, input: &I) -> HdbResult<T>
+    where
+        I: Serialize + Sync,
+        T: DeserializeOwned + Send,
+    {
+        PreparedStatement::execute(self, input)
+            .await?
+            .into_result_set()?
+            .try_into()
+            .await
+    }
+    async fn dml<I>(&mut self, input: &I) -> HdbResult<AffectedRowCount>
+    where
+        I: Serialize + Sync,
+    {
+        PreparedStatement::execute(self, input)
+            .await
+            .and_then(HdbResponse::into_affected_row_count)
+    }
+    fn add_batch<I: Serialize>(&mut self, input: &I) -> HdbResult<()> {
+        PreparedStatement::add_batch(self, input)
+    }
+    async fn execute_batch(&mut self) -> HdbResult<Vec<AffectedRowCount>> {
+        PreparedStatement::execute_batch(self)
+            .await
+            .and_then(HdbResponse::into_affected_rows)
+    }
+}
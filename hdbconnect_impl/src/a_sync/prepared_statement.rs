@@ -1,16 +1,18 @@
 use crate::{
     a_sync::HdbResponse,
     base::{new_am_async, InternalReturnValue, PreparedStatementCore, AM},
-    conn::{AmConnCore, CursorHoldability},
+    conn::{
+        check_statement, AmConnCore, CommandOptions, CursorHoldability, CURRENT_IDENTITY_VALUE,
+    },
     impl_err,
     protocol::{
         parts::{
             HdbValue, LobFlags, ParameterDescriptors, ParameterRows, ResultSetMetadata, TypeId,
         },
-        MessageType, Part, PartKind, Request, ServerUsage,
+        MessageType, Part, PartKind, Request, ServerUsage, MAX_SEGMENTS,
     },
     types_impl::lob::async_lob_writer,
-    usage_err, ConnectionConfiguration, HdbResult,
+    usage_err, ConnectionConfiguration, HdbError, HdbResult, QueryOptions,
 };
 use std::sync::Arc;
 
@@ -76,6 +78,8 @@ use std::sync::Arc;
 pub struct PreparedStatement {
     am_ps_core: AM<PreparedStatementCore>,
     config: ConnectionConfiguration,
+    sql: String,
+    statement_id: u64,
     server_usage: ServerUsage,
     a_descriptors: Arc<ParameterDescriptors>,
     o_a_rsmd: Option<Arc<ResultSetMetadata>>,
@@ -133,9 +137,34 @@ impl<'a> PreparedStatement {
         if self.a_descriptors.has_in() {
             let mut par_rows = ParameterRows::new();
             par_rows.push(input, &self.a_descriptors)?;
-            return self.execute_parameter_rows(Some(par_rows)).await;
+            return self.execute_parameter_rows(Some(par_rows), None).await;
         }
-        self.execute_parameter_rows(None).await
+        self.execute_parameter_rows(None, None).await
+    }
+
+    /// Executes the statement like
+    /// [`PreparedStatement::execute()`](crate::PreparedStatement::execute),
+    /// using the given options rather than the connection's configuration.
+    ///
+    /// See [`QueryOptions`](crate::QueryOptions) for the scope of the single options.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    pub async fn execute_with<T: serde::ser::Serialize>(
+        &mut self,
+        input: &T,
+        options: QueryOptions,
+    ) -> HdbResult<HdbResponse> {
+        trace!("PreparedStatement::execute_with()");
+        let o_rows = if self.a_descriptors.has_in() {
+            let mut par_rows = ParameterRows::new();
+            par_rows.push(input, &self.a_descriptors)?;
+            Some(par_rows)
+        } else {
+            None
+        };
+        self.execute_parameter_rows(o_rows, Some(options)).await
     }
 
     /// Consumes the given `HdbValue`s as a row of parameters for immediate execution.
@@ -189,9 +218,28 @@ impl<'a> PreparedStatement {
     pub async fn execute_row(
         &'a mut self,
         hdb_values: Vec<HdbValue<'a>>,
+    ) -> HdbResult<HdbResponse> {
+        let o_error_context = self.error_context(Some(1));
+        #[cfg(feature = "otel")]
+        let span = self.statement_span().await;
+        let future = self.execute_row_impl(hdb_values);
+        #[cfg(feature = "otel")]
+        let future = tracing::Instrument::instrument(future, span.clone());
+        let result = future
+            .await
+            .map_err(|e| Self::add_error_context(e, o_error_context));
+        #[cfg(feature = "otel")]
+        crate::conn::otel::record_outcome(&span, &result, HdbResponse::affected_rows_count);
+        result
+    }
+
+    async fn execute_row_impl(
+        &'a mut self,
+        hdb_values: Vec<HdbValue<'a>>,
     ) -> HdbResult<HdbResponse> {
         if self.a_descriptors.has_in() {
             let ps_core_guard = self.am_ps_core.lock_async().await;
+            self.check_statement(&ps_core_guard.am_conn_core).await?;
             let mut request = Request::new(MessageType::Execute, self.config.command_options());
 
             request.push(Part::StatementId(ps_core_guard.statement_id));
@@ -246,7 +294,7 @@ impl<'a> PreparedStatement {
             let (mut internal_return_values, replytype) = (
                 main_reply
                     .parts
-                    .into_internal_return_values_async(&ps_core_guard.am_conn_core, None)
+                    .into_internal_return_values_async(&ps_core_guard.am_conn_core, None, None)
                     .await?,
                 main_reply.replytype,
             );
@@ -288,9 +336,11 @@ impl<'a> PreparedStatement {
                         .await?;
                 }
             }
-            HdbResponse::try_new(internal_return_values, replytype)
+            let response = HdbResponse::try_new(internal_return_values, replytype)?;
+            let conn_core = ps_core_guard.am_conn_core.lock_async().await;
+            Ok(response.with_server_feedback(&conn_core))
         } else {
-            self.execute_parameter_rows(None).await
+            self.execute_parameter_rows(None, None).await
         }
     }
 
@@ -333,6 +383,11 @@ impl<'a> PreparedStatement {
         ))
     }
 
+    // Discards the rows that were added to the batch.
+    pub(crate) fn clear_batch(&mut self) {
+        self.batch = ParameterRows::new();
+    }
+
     /// Returns the number of parameter rows that were already added to the batch.
     #[must_use]
     pub fn current_batch_size(&self) -> usize {
@@ -346,6 +401,10 @@ impl<'a> PreparedStatement {
     /// If the statement does not need input and the batch is empty,
     /// a single execution is triggered.
     ///
+    /// A batch that exceeds the number of rows the server accepts with a single request,
+    /// or the maximum request size, is split and executed with several roundtrips,
+    /// whose responses are merged. With auto-commit, each of them is committed on its own.
+    ///
     /// # Errors
     ///
     /// Several variants of `HdbError` can occur.
@@ -359,7 +418,94 @@ impl<'a> PreparedStatement {
             self.batch.count()
         );
         std::mem::swap(&mut self.batch, &mut batch2);
-        self.execute_parameter_rows(Some(batch2)).await
+        let (max_rows, max_size) = {
+            let ps_core = self.am_ps_core.lock_async().await;
+            let conn_core = ps_core.am_conn_core.lock_async().await;
+            conn_core.batch_limits()
+        };
+        let mut o_response: Option<HdbResponse> = None;
+        for rows in batch2.split(max_rows, max_size, &self.a_descriptors)? {
+            let response = self.execute_parameter_rows(Some(rows), None).await?;
+            match o_response {
+                Some(ref mut merged) => merged.append_batch_response(response),
+                None => o_response = Some(response),
+            }
+        }
+        o_response.ok_or_else(|| impl_err!("execute_batch(): no chunk was executed"))
+    }
+
+    // Executes the statement for each of the rows, and returns for each of them the value
+    // that was generated for an IDENTITY column, if any.
+    // Each execution is followed by a query of the generated value; both are sent
+    // as segments of a request message, so that a few roundtrips suffice for all rows.
+    pub(crate) async fn execute_returning_identities<T: serde::ser::Serialize>(
+        &mut self,
+        rows: &[T],
+    ) -> HdbResult<Vec<Option<i64>>> {
+        let mut parameter_rows = ParameterRows::new();
+        for row in rows {
+            parameter_rows.push(row, &self.a_descriptors)?;
+        }
+        let ps_core_guard = self.am_ps_core.lock_async().await;
+        let am_conn_core = &ps_core_guard.am_conn_core;
+        self.check_statement(am_conn_core).await?;
+        let (max_rows, max_size) = am_conn_core.lock_async().await.batch_limits();
+        let command_options = self.config.command_options();
+
+        let mut ids = Vec::with_capacity(rows.len());
+        for chunk in parameter_rows.split(
+            max_rows.min(MAX_SEGMENTS / 2),
+            max_size,
+            &self.a_descriptors,
+        )? {
+            let expected = ids.len() + chunk.count();
+            let mut o_request: Option<Request> = None;
+            for row in chunk.split(1, usize::MAX, &self.a_descriptors)? {
+                let mut execute = Request::new(MessageType::Execute, command_options);
+                execute.push(Part::StatementId(ps_core_guard.statement_id));
+                execute.push(Part::ParameterRows(row));
+                let mut query = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+                query.push(Part::Command(CURRENT_IDENTITY_VALUE));
+                let request = match o_request {
+                    Some(ref mut request) => {
+                        request.append_segment(execute);
+                        request
+                    }
+                    None => o_request.insert(execute),
+                };
+                request.append_segment(query);
+            }
+            let Some(request) = o_request else {
+                continue;
+            };
+
+            let mut reply = am_conn_core
+                .full_send_async(request, None, Some(&self.a_descriptors), &mut None)
+                .await?;
+            let following = reply.take_following();
+            for (i, mut reply) in std::iter::once(reply).chain(following).enumerate() {
+                reply.handle_db_error(&mut *am_conn_core.lock_async().await)?;
+                if i % 2 == 1 {
+                    let (internal_return_values, replytype) = reply
+                        .into_internal_return_values_async(am_conn_core, None, None)
+                        .await?;
+                    ids.push(
+                        HdbResponse::try_new(internal_return_values, replytype)?
+                            .into_result_set()?
+                            .try_into()
+                            .await?,
+                    );
+                }
+            }
+            if ids.len() != expected {
+                return Err(impl_err!(
+                    "server replied to {} of {} inserts",
+                    ids.len(),
+                    expected
+                ));
+            }
+        }
+        Ok(ids)
     }
 
     /// Descriptors of all parameters of the prepared statement (in, out, inout).
@@ -368,14 +514,82 @@ impl<'a> PreparedStatement {
         Arc::clone(&self.a_descriptors)
     }
 
+    /// Metadata of the result set that the prepared statement produces, if any.
+    #[must_use]
+    pub fn result_metadata(&self) -> Option<Arc<ResultSetMetadata>> {
+        self.o_a_rsmd.clone()
+    }
+
+    /// Describes the statement without executing it: returns the descriptors of all parameters,
+    /// and the metadata of the result set that the statement produces, if any.
+    ///
+    /// Both are provided by the server when the statement is prepared, so no roundtrip is needed.
+    #[must_use]
+    pub fn describe(&self) -> (Arc<ParameterDescriptors>, Option<Arc<ResultSetMetadata>>) {
+        (self.parameter_descriptors(), self.result_metadata())
+    }
+
+    /// The SQL statement that was prepared.
+    #[must_use]
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The id with which the server identifies the prepared statement.
+    ///
+    /// The id is unique only within the session, and can be used e.g. for correlating
+    /// the statement with monitoring views like `M_PREPARED_STATEMENTS`.
+    #[must_use]
+    pub fn server_statement_id(&self) -> u64 {
+        self.statement_id
+    }
+
     async fn execute_parameter_rows(
         &mut self,
         o_rows: Option<ParameterRows<'_>>,
+        o_options: Option<QueryOptions>,
     ) -> HdbResult<HdbResponse> {
         trace!("PreparedStatement::execute_parameter_rows()");
+        let o_error_context = self.error_context(o_rows.as_ref().map(ParameterRows::count));
+        #[cfg(feature = "otel")]
+        let span = self.statement_span().await;
+        let future = self.execute_parameter_rows_impl(o_rows, o_options);
+        #[cfg(feature = "otel")]
+        let future = tracing::Instrument::instrument(future, span.clone());
+        let result = future
+            .await
+            .map_err(|e| Self::add_error_context(e, o_error_context));
+        #[cfg(feature = "otel")]
+        crate::conn::otel::record_outcome(&span, &result, HdbResponse::affected_rows_count);
+        result
+    }
+
+    #[cfg(feature = "otel")]
+    async fn statement_span(&self) -> tracing::Span {
+        let ps_core = self.am_ps_core.lock_async().await;
+        let conn_core = ps_core.am_conn_core.lock_async().await;
+        crate::conn::otel::statement_span(&self.sql, &conn_core)
+    }
 
+    // Lets the statement hook of the connection, if any, decide about the execution.
+    async fn check_statement(&self, am_conn_core: &AmConnCore) -> HdbResult<()> {
+        let o_hook = am_conn_core.lock_async().await.statement_hook();
+        check_statement(o_hook, &self.sql, self.a_descriptors.iter_in().count())
+    }
+
+    async fn execute_parameter_rows_impl(
+        &mut self,
+        o_rows: Option<ParameterRows<'_>>,
+        o_options: Option<QueryOptions>,
+    ) -> HdbResult<HdbResponse> {
         let ps_core_guard = self.am_ps_core.lock_async().await;
-        let mut request = Request::new(MessageType::Execute, self.config.command_options());
+        self.check_statement(&ps_core_guard.am_conn_core).await?;
+        let command_options =
+            QueryOptions::applied_to(o_options.as_ref(), &self.config).command_options();
+        let mut request = Request::new(MessageType::Execute, command_options);
+        if let Some(options) = o_options {
+            request.set_options(options);
+        }
         request.push(Part::StatementId(ps_core_guard.statement_id));
         if let Some(rows) = o_rows {
             request.push(Part::ParameterRows(rows));
@@ -390,7 +604,11 @@ impl<'a> PreparedStatement {
                 &mut None,
             )
             .await?
-            .into_internal_return_values_async(&ps_core_guard.am_conn_core, None)
+            .into_internal_return_values_async(
+                &ps_core_guard.am_conn_core,
+                None,
+                o_options.as_ref(),
+            )
             .await?;
 
         // inject statement id
@@ -402,10 +620,16 @@ impl<'a> PreparedStatement {
             }
         }
 
-        HdbResponse::try_new(internal_return_values, replytype)
+        let response = HdbResponse::try_new(internal_return_values, replytype)?;
+        let conn_core = ps_core_guard.am_conn_core.lock_async().await;
+        Ok(response.with_server_feedback(&conn_core))
     }
 
-    /// Sets the statement's cursor holdability.
+    /// Sets the statement's cursor holdability, which overrides the connection's one
+    /// for all subsequent executions of this statement.
+    ///
+    /// With [`CursorHoldability::CommitAndRollback`], e.g., the result sets of a report
+    /// survive also a rollback of unrelated work on the same connection.
     ///
     /// # Errors
     ///
@@ -423,6 +647,30 @@ impl<'a> PreparedStatement {
         Ok(self.config.cursor_holdability())
     }
 
+    // Returns the statement text and a redacted parameter summary,
+    // if these are configured to be added to errors.
+    fn error_context(&self, o_row_count: Option<usize>) -> Option<(String, Option<String>)> {
+        if self.config.is_sql_in_errors() {
+            Some((
+                self.sql.clone(),
+                o_row_count.map(|row_count| self.a_descriptors.redacted_summary(row_count)),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn add_error_context(
+        e: HdbError,
+        o_error_context: Option<(String, Option<String>)>,
+    ) -> HdbError {
+        if let Some((statement, o_parameters)) = o_error_context {
+            e.with_statement(statement, o_parameters)
+        } else {
+            e
+        }
+    }
+
     /// Provides information about the the server-side resource consumption that
     /// is related to this `PreparedStatement` object.
     #[must_use]
@@ -433,6 +681,39 @@ impl<'a> PreparedStatement {
     // Prepare a statement.
     pub(crate) async fn try_new(am_conn_core: AmConnCore, stmt: &str) -> HdbResult<Self> {
         let config = am_conn_core.lock_async().await.configuration().clone();
+        let sql_in_errors = config.is_sql_in_errors();
+        Self::try_new_impl(am_conn_core, config, stmt)
+            .await
+            .map_err(|e| {
+                if sql_in_errors {
+                    e.with_statement(stmt.to_string(), None)
+                } else {
+                    e
+                }
+            })
+    }
+
+    // Lets the server-side statement, once it is not used anymore, be dropped
+    // together with the next request, rather than with a roundtrip of its own.
+    pub(crate) async fn defer_drop(&self) {
+        self.am_ps_core.lock_async().await.deferred_drop = true;
+    }
+
+    // Creates another handle for the same server-side statement, e.g. for a statement
+    // from the statement cache, with the given configuration and an empty batch.
+    pub(crate) fn reuse(&self, config: ConnectionConfiguration) -> Self {
+        let mut reused = self.clone();
+        reused.config = config;
+        reused.server_usage = ServerUsage::default();
+        reused.batch = ParameterRows::new();
+        reused
+    }
+
+    async fn try_new_impl(
+        am_conn_core: AmConnCore,
+        config: ConnectionConfiguration,
+        stmt: &str,
+    ) -> HdbResult<Self> {
         let mut request = Request::new(MessageType::Prepare, config.command_options());
         request.push(Part::Command(stmt));
 
@@ -478,9 +759,12 @@ impl<'a> PreparedStatement {
         }
 
         let statement_id = o_stmt_id.ok_or_else(|| impl_err!("No StatementId received"))?;
+        let session_id = am_conn_core.lock_async().await.session_id();
         let am_ps_core = new_am_async(PreparedStatementCore {
             am_conn_core,
             statement_id,
+            session_id,
+            deferred_drop: false,
         });
         debug!(
             "PreparedStatement created with parameter descriptors = {:?}",
@@ -489,6 +773,8 @@ impl<'a> PreparedStatement {
         Ok(Self {
             am_ps_core,
             config,
+            sql: stmt.to_string(),
+            statement_id,
             server_usage,
             batch: ParameterRows::new(),
             a_descriptors,
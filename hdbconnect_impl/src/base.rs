@@ -1,26 +1,61 @@
+#[cfg(feature = "display")]
+mod ascii_table;
+pub(crate) mod bulk_load;
+mod chunked_dml;
+pub(crate) mod ddl;
+mod explain_plan;
+mod generic_connection;
 mod hdb_error;
+mod hinted_statement;
+mod identifier;
 mod internal_returnvalue;
+#[cfg(feature = "migrations")]
+mod migrations;
+mod params_builder;
 mod prepared_statement_core;
+mod result_set_builder;
 mod row;
 mod rows;
 mod rs_core;
 mod rs_state;
+mod server_version;
+mod session_info;
+mod statement_batch;
+mod statement_cache;
 mod xmutexed;
 
+#[cfg(feature = "migrations")]
+pub use migrations::{Migration, Migrations};
+
 #[cfg(feature = "async")]
 pub(crate) use xmutexed::new_am_async;
 #[cfg(feature = "sync")]
 pub(crate) use xmutexed::new_am_sync;
 
 pub use {
+    bulk_load::{BulkLoadReport, FailedBatch},
+    explain_plan::{ExplainPlan, PlanNode},
+    generic_connection::{GenericConnection, GenericPreparedStatement},
     hdb_error::{HdbError, HdbResult},
+    hinted_statement::HintedStatement,
+    identifier::{quote_ident, quote_qualified, IdentifierCase},
+    params_builder::ParamsBuilder,
+    result_set_builder::ResultSetBuilder,
     row::Row,
     rows::Rows,
+    server_version::ServerVersion,
+    session_info::SessionInfo,
+    statement_batch::StatementBatch,
 };
 pub(crate) use {
+    chunked_dml::{ChunkPlan, ChunkStep},
+    explain_plan::PlanRow,
+    identifier::delimit,
     internal_returnvalue::InternalReturnValue,
     prepared_statement_core::PreparedStatementCore,
     rs_core::RsCore,
     rs_state::RsState,
+    statement_batch::BatchStep,
+    statement_cache::StatementCache,
     xmutexed::{XMutexed, AM, OAM},
 };
@@ -0,0 +1,158 @@
+use crate::{HdbValue, ResultSetMetadata, Row, TypeId};
+use std::fmt::Write;
+
+// Renders the given rows as a text table with aligned columns, like hdbsql does.
+//
+// Cells that are longer than max_col_width characters are truncated, and marked with "…".
+// If `more_rows` is true, a final line indicates that the table is incomplete.
+pub(crate) fn ascii_table<'a, I>(
+    metadata: &ResultSetMetadata,
+    rows: I,
+    max_col_width: usize,
+    more_rows: bool,
+) -> String
+where
+    I: IntoIterator<Item = &'a Row>,
+{
+    let max_col_width = max_col_width.max(1);
+    let header: Vec<String> = metadata
+        .iter()
+        .map(|field_md| truncate(field_md.displayname(), max_col_width))
+        .collect();
+    let right_aligned: Vec<bool> = metadata
+        .iter()
+        .map(|field_md| is_numeric(field_md.type_id()))
+        .collect();
+    let cells: Vec<Vec<String>> = rows
+        .into_iter()
+        .map(|row| {
+            (0..row.len())
+                .map(|idx| truncate(&cell_text(&row[idx]), max_col_width))
+                .collect()
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let separator = widths.iter().fold(String::from("+"), |mut line, width| {
+        line.push_str(&"-".repeat(width + 2));
+        line.push('+');
+        line
+    });
+
+    let mut table = String::new();
+    writeln!(table, "{separator}").ok();
+    write_line(&mut table, &header, &widths, &vec![false; widths.len()]);
+    writeln!(table, "{separator}").ok();
+    for row in &cells {
+        write_line(&mut table, row, &widths, &right_aligned);
+    }
+    writeln!(table, "{separator}").ok();
+    let count = cells.len();
+    writeln!(
+        table,
+        "{count} row{}{}",
+        if count == 1 { "" } else { "s" },
+        if more_rows {
+            " shown, more rows exist"
+        } else {
+            ""
+        }
+    )
+    .ok();
+    table
+}
+
+fn write_line(table: &mut String, cells: &[String], widths: &[usize], right_aligned: &[bool]) {
+    table.push('|');
+    for ((cell, width), right) in cells.iter().zip(widths).zip(right_aligned) {
+        if *right {
+            write!(table, " {cell:>width$} |").ok();
+        } else {
+            write!(table, " {cell:<width$} |").ok();
+        }
+    }
+    table.push('\n');
+}
+
+// NULL is shown as "?", like hdbsql does; line breaks and tabs would break the layout.
+fn cell_text(value: &HdbValue<'static>) -> String {
+    match value {
+        HdbValue::NULL => "?".to_string(),
+        value => value
+            .to_string()
+            .chars()
+            .map(|c| if c.is_control() { ' ' } else { c })
+            .collect(),
+    }
+}
+
+fn truncate(text: &str, max_width: usize) -> String {
+    if text.chars().count() <= max_width {
+        text.to_string()
+    } else {
+        let mut truncated: String = text.chars().take(max_width - 1).collect();
+        truncated.push('…');
+        truncated
+    }
+}
+
+fn is_numeric(type_id: TypeId) -> bool {
+    matches!(
+        type_id,
+        TypeId::TINYINT
+            | TypeId::SMALLINT
+            | TypeId::INT
+            | TypeId::BIGINT
+            | TypeId::DECIMAL
+            | TypeId::FIXED8
+            | TypeId::FIXED12
+            | TypeId::FIXED16
+            | TypeId::REAL
+            | TypeId::DOUBLE
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::ascii_table;
+    use crate::{HdbValue, ResultSetBuilder, TypeId};
+
+    #[test]
+    fn test_ascii_table() {
+        let (metadata, rows) = ResultSetBuilder::new()
+            .column("ID", TypeId::INT)
+            .column("NAME", TypeId::NVARCHAR)
+            .row(vec![
+                HdbValue::INT(1),
+                HdbValue::STRING("Alice".to_string()),
+            ])
+            .row(vec![
+                HdbValue::INT(22),
+                HdbValue::STRING("Bartholomew\nthe second".to_string()),
+            ])
+            .row(vec![HdbValue::INT(333), HdbValue::NULL])
+            .into_metadata_and_rows()
+            .unwrap();
+
+        assert_eq!(
+            ascii_table(&metadata, &rows, 8, false),
+            "+-----+----------+\n\
+             | ID  | NAME     |\n\
+             +-----+----------+\n\
+             |   1 | Alice    |\n\
+             |  22 | Barthol… |\n\
+             | 333 | ?        |\n\
+             +-----+----------+\n\
+             3 rows\n"
+        );
+        assert!(
+            ascii_table(&metadata, &rows[..1], 8, true).ends_with("1 row shown, more rows exist\n")
+        );
+    }
+}
@@ -0,0 +1,500 @@
+use crate::{
+    base::identifier::delimit, HdbError, HdbResult, HdbValue, IdentifierCase, ParameterDescriptors,
+};
+use bigdecimal::BigDecimal;
+use std::{cmp::Ordering, collections::VecDeque, str::FromStr, sync::Mutex};
+
+/// The outcome of a bulk load with `BulkLoader::load()`.
+#[derive(Debug)]
+pub struct BulkLoadReport {
+    rows_loaded: usize,
+    rows_routed: usize,
+    partitions: usize,
+    connections: usize,
+    failed_batches: Vec<FailedBatch>,
+}
+
+impl BulkLoadReport {
+    pub(crate) fn new(
+        rows_loaded: usize,
+        plan: &LoadPlan,
+        connections: usize,
+        mut failed_batches: Vec<FailedBatch>,
+    ) -> Self {
+        failed_batches.sort_by_key(|failed_batch| failed_batch.rows.first().copied());
+        Self {
+            rows_loaded,
+            rows_routed: plan.rows_routed,
+            partitions: plan.partitions,
+            connections,
+            failed_batches,
+        }
+    }
+
+    /// The number of rows that were loaded successfully.
+    #[must_use]
+    pub fn rows_loaded(&self) -> usize {
+        self.rows_loaded
+    }
+
+    /// The number of rows that were assigned to their partition on the client,
+    /// and sent to the host that owns the partition.
+    #[must_use]
+    pub fn rows_routed(&self) -> usize {
+        self.rows_routed
+    }
+
+    /// The number of partitions of the table.
+    #[must_use]
+    pub fn partitions(&self) -> usize {
+        self.partitions
+    }
+
+    /// The number of connections that were used in parallel.
+    #[must_use]
+    pub fn connections(&self) -> usize {
+        self.connections
+    }
+
+    /// The batches that could not be loaded, ordered by their first row.
+    #[must_use]
+    pub fn failed_batches(&self) -> &[FailedBatch] {
+        &self.failed_batches
+    }
+
+    /// Returns true if all rows were loaded.
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failed_batches.is_empty()
+    }
+}
+
+/// A batch of rows that could not be loaded.
+#[derive(Debug)]
+pub struct FailedBatch {
+    rows: Vec<usize>,
+    error: HdbError,
+}
+
+impl FailedBatch {
+    /// The indices of the rows of the batch, within the rows given to `BulkLoader::load()`.
+    ///
+    /// The rows of a batch belong to the same partition, so they are not necessarily adjacent.
+    #[must_use]
+    pub fn rows(&self) -> &[usize] {
+        &self.rows
+    }
+
+    /// The reason why the batch could not be loaded.
+    #[must_use]
+    pub fn error(&self) -> &HdbError {
+        &self.error
+    }
+}
+
+// A partition of a column table, as it is read with partitions_statement().
+#[derive(Debug, serde::Deserialize)]
+pub(crate) struct Partition {
+    #[serde(rename = "HOST")]
+    host: String,
+    #[serde(rename = "SQL_PORT")]
+    sql_port: u16,
+    #[serde(rename = "LEVEL_1_TYPE")]
+    o_type: Option<String>,
+    #[serde(rename = "LEVEL_1_EXPRESSION")]
+    o_expression: Option<String>,
+    #[serde(rename = "LEVEL_1_RANGE_MIN_VALUE")]
+    o_min: Option<String>,
+    #[serde(rename = "LEVEL_1_RANGE_MAX_VALUE")]
+    o_max: Option<String>,
+    #[serde(rename = "LEVEL_2_TYPE")]
+    o_level_2_type: Option<String>,
+}
+
+impl Partition {
+    pub(crate) fn host(&self) -> (&str, u16) {
+        (&self.host, self.sql_port)
+    }
+}
+
+// Returns the statement that reads the partitions of a table, with the index server
+// that owns them, ordered by their id; the parameters are the schema name, if `qualified`,
+// and the table name.
+// A column table that is not partitioned has a single entry without partitioning type,
+// a row table has none.
+pub(crate) fn partitions_statement(qualified: bool) -> String {
+    format!(
+        "SELECT T.PART_ID, T.HOST, S.SQL_PORT, \
+         P.LEVEL_1_TYPE, P.LEVEL_1_EXPRESSION, P.LEVEL_1_RANGE_MIN_VALUE, \
+         P.LEVEL_1_RANGE_MAX_VALUE, P.LEVEL_2_TYPE \
+         FROM SYS.M_CS_TABLES T \
+         JOIN SYS.M_SERVICES S ON S.HOST = T.HOST AND S.PORT = T.PORT \
+         LEFT JOIN SYS.TABLE_PARTITIONS P ON P.SCHEMA_NAME = T.SCHEMA_NAME \
+         AND P.TABLE_NAME = T.TABLE_NAME AND P.PART_ID = T.PART_ID \
+         WHERE T.SCHEMA_NAME = {} AND T.TABLE_NAME = ? \
+         ORDER BY T.PART_ID",
+        if qualified { "?" } else { "CURRENT_SCHEMA" }
+    )
+}
+
+// Assigns rows to the partitions of a table.
+//
+// Only a single-level range partitioning on a plain column can be evaluated on the client;
+// the hash functions that the server uses for hash partitioning are not known.
+#[derive(Debug)]
+pub(crate) struct Router {
+    column: usize,
+    ranges: Vec<(usize, RangeSpec)>,
+    o_others: Option<usize>,
+}
+
+#[derive(Debug)]
+enum RangeSpec {
+    Value(String),
+    Range(Option<String>, Option<String>),
+}
+
+impl Router {
+    // Returns None if the rows cannot be assigned to the partitions on the client.
+    // `fields` are the names of the serialized fields, in the order of the parameters.
+    pub(crate) fn new(
+        partitions: &[Partition],
+        fields: &[&str],
+        identifier_case: IdentifierCase,
+    ) -> Option<Self> {
+        let first = partitions.first()?;
+        if first.o_type.as_deref() != Some("RANGE")
+            || partitions.iter().any(|p| p.o_level_2_type.is_some())
+        {
+            return None;
+        }
+        let expression = first.o_expression.as_deref()?.trim();
+        if expression.contains(['(', ',']) {
+            return None;
+        }
+        let column = delimit(&IdentifierCase::Verbatim.catalog_name(expression));
+        let column = fields
+            .iter()
+            .position(|field| identifier_case.column(field) == column)?;
+
+        let mut ranges = Vec::with_capacity(partitions.len());
+        let mut o_others = None;
+        for (idx, partition) in partitions.iter().enumerate() {
+            let non_empty = |o_bound: &Option<String>| o_bound.clone().filter(|b| !b.is_empty());
+            match (non_empty(&partition.o_min), non_empty(&partition.o_max)) {
+                (None, None) => o_others = Some(idx),
+                (Some(value), None) => ranges.push((idx, RangeSpec::Value(value))),
+                (o_min, o_max) => ranges.push((idx, RangeSpec::Range(o_min, o_max))),
+            }
+        }
+        Some(Self {
+            column,
+            ranges,
+            o_others,
+        })
+    }
+
+    // Returns the index of the partition that owns the row, if it can be determined.
+    pub(crate) fn route(&self, row: &[HdbValue]) -> Option<usize> {
+        let value = row.get(self.column)?;
+        for (idx, spec) in &self.ranges {
+            let fits = match spec {
+                RangeSpec::Value(v) => compare(value, v)? == Ordering::Equal,
+                RangeSpec::Range(o_min, o_max) => {
+                    o_min
+                        .as_ref()
+                        .map_or(Some(true), |min| Some(compare(value, min)?.is_ge()))?
+                        && o_max
+                            .as_ref()
+                            .map_or(Some(true), |max| Some(compare(value, max)?.is_lt()))?
+                }
+            };
+            if fits {
+                return Some(*idx);
+            }
+        }
+        self.o_others
+    }
+}
+
+// Compares a value with a bound of a range partition; numbers are compared numerically,
+// strings, like the text representation of dates, lexically.
+fn compare(value: &HdbValue, bound: &str) -> Option<Ordering> {
+    let number = |n: BigDecimal| Some(n.cmp(&BigDecimal::from_str(bound).ok()?));
+    match value {
+        HdbValue::TINYINT(i) => number(BigDecimal::from(*i)),
+        HdbValue::SMALLINT(i) => number(BigDecimal::from(*i)),
+        HdbValue::INT(i) => number(BigDecimal::from(*i)),
+        HdbValue::BIGINT(i) => number(BigDecimal::from(*i)),
+        HdbValue::DECIMAL(bd) => number(bd.clone()),
+        HdbValue::STRING(s) => Some(s.as_str().cmp(bound)),
+        HdbValue::STR(s) => Some((*s).cmp(bound)),
+        _ => None,
+    }
+}
+
+// A batch of rows, with their indices within the rows given to the loader.
+#[derive(Debug, Default)]
+pub(crate) struct Batch {
+    pub(crate) rows: Vec<usize>,
+    pub(crate) values: Vec<Vec<HdbValue<'static>>>,
+}
+impl Batch {
+    pub(crate) fn fail(self, error: HdbError) -> FailedBatch {
+        FailedBatch {
+            rows: self.rows,
+            error,
+        }
+    }
+}
+
+// The batches of a load, in a queue per host.
+#[derive(Debug)]
+pub(crate) struct LoadPlan {
+    hosts: Vec<(String, u16)>,
+    queues: Mutex<Vec<VecDeque<Batch>>>,
+    batch_count: usize,
+    rows_routed: usize,
+    partitions: usize,
+}
+
+impl LoadPlan {
+    // Distributes the rows into batches.
+    //
+    // The rows of a routed partition are queued for the host that owns the partition;
+    // the other rows are queued for the hosts in turn.
+    // Without host affinity, all batches are in a single queue.
+    pub(crate) fn new(
+        partitions: &[Partition],
+        o_router: Option<&Router>,
+        rows: Vec<Vec<HdbValue<'static>>>,
+        batch_size: usize,
+        host_affinity: bool,
+    ) -> Self {
+        let mut hosts = Vec::<(String, u16)>::new();
+        let mut partition_hosts = Vec::with_capacity(partitions.len());
+        for partition in partitions {
+            let host = (partition.host.clone(), partition.sql_port);
+            partition_hosts.push(hosts.iter().position(|h| *h == host).unwrap_or_else(|| {
+                hosts.push(host);
+                hosts.len() - 1
+            }));
+        }
+        if !host_affinity {
+            hosts.clear();
+            partition_hosts.fill(0);
+        }
+        let mut queues = Vec::new();
+        queues.resize_with(hosts.len().max(1), VecDeque::new);
+        let mut batch_count = 0;
+        let mut enqueue = |batch: Batch, o_partition: Option<usize>| {
+            // batches of rows that are not routed go to the hosts in turn
+            let queue = o_partition.map_or(batch_count % queues.len(), |p| partition_hosts[p]);
+            queues[queue].push_back(batch);
+            batch_count += 1;
+        };
+
+        let mut routed = Vec::new();
+        routed.resize_with(partitions.len(), Batch::default);
+        let mut unrouted = Batch::default();
+        let mut rows_routed = 0;
+        for (idx, values) in rows.into_iter().enumerate() {
+            let o_partition = o_router.and_then(|router| router.route(&values));
+            let batch = match o_partition {
+                Some(partition) => {
+                    rows_routed += 1;
+                    &mut routed[partition]
+                }
+                None => &mut unrouted,
+            };
+            batch.rows.push(idx);
+            batch.values.push(values);
+            if batch.rows.len() == batch_size {
+                enqueue(std::mem::take(batch), o_partition);
+            }
+        }
+        for (partition, batch) in routed.into_iter().enumerate() {
+            if !batch.rows.is_empty() {
+                enqueue(batch, Some(partition));
+            }
+        }
+        if !unrouted.rows.is_empty() {
+            enqueue(unrouted, None);
+        }
+
+        Self {
+            hosts,
+            queues: Mutex::new(queues),
+            batch_count,
+            rows_routed,
+            partitions: partitions.len(),
+        }
+    }
+
+    pub(crate) fn batch_count(&self) -> usize {
+        self.batch_count
+    }
+
+    // The queue that the worker with the given number takes its batches from, and the host
+    // it connects to, if the connections go directly to the hosts.
+    pub(crate) fn queue_of_worker(&self, worker: usize) -> (usize, Option<(&str, u16)>) {
+        let queue = worker % self.hosts.len().max(1);
+        (
+            queue,
+            self.hosts
+                .get(queue)
+                .map(|(host, port)| (host.as_str(), *port)),
+        )
+    }
+
+    // Takes the next batch from the given queue, or, if that is empty, from the longest
+    // other queue, so that no batch is left behind if a host has no worker.
+    pub(crate) fn next_batch(&self, queue: usize) -> Option<Batch> {
+        let mut queues = self.queues.lock().ok()?;
+        if let Some(batch) = queues[queue].pop_front() {
+            return Some(batch);
+        }
+        queues
+            .iter_mut()
+            .max_by_key(|queue| queue.len())
+            .and_then(VecDeque::pop_front)
+    }
+}
+
+// Converts the rows into parameter values, so that they can be routed.
+pub(crate) fn to_parameter_rows<T: serde::ser::Serialize>(
+    rows: &[T],
+    descriptors: &ParameterDescriptors,
+) -> HdbResult<Vec<Vec<HdbValue<'static>>>> {
+    rows.iter()
+        .map(|row| crate::protocol::parts::to_hdb_values(row, descriptors))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{LoadPlan, Partition, Router};
+    use crate::{HdbValue, IdentifierCase};
+
+    fn partition(host: &str, o_type: Option<&str>, o_min: &str, o_max: &str) -> Partition {
+        Partition {
+            host: host.to_string(),
+            sql_port: 30015,
+            o_type: o_type.map(ToString::to_string),
+            o_expression: o_type.map(|_| "ID".to_string()),
+            o_min: Some(o_min.to_string()),
+            o_max: Some(o_max.to_string()),
+            o_level_2_type: None,
+        }
+    }
+
+    fn range_partitions() -> Vec<Partition> {
+        vec![
+            partition("host1", Some("RANGE"), "", "100"),
+            partition("host2", Some("RANGE"), "100", "200"),
+            partition("host1", Some("RANGE"), "500", ""),
+            partition("host2", Some("RANGE"), "", ""),
+        ]
+    }
+
+    fn rows(ids: &[i32]) -> Vec<Vec<HdbValue<'static>>> {
+        ids.iter()
+            .map(|id| vec![HdbValue::STRING("x".to_string()), HdbValue::INT(*id)])
+            .collect()
+    }
+
+    #[test]
+    fn test_router() {
+        let partitions = range_partitions();
+        let router = Router::new(&partitions, &["NAME", "ID"], IdentifierCase::default()).unwrap();
+        let route = |value: HdbValue<'static>| router.route(&[HdbValue::NULL, value]);
+        assert_eq!(route(HdbValue::INT(-5)), Some(0));
+        assert_eq!(route(HdbValue::BIGINT(100)), Some(1));
+        assert_eq!(route(HdbValue::SMALLINT(199)), Some(1));
+        assert_eq!(route(HdbValue::INT(500)), Some(2));
+        assert_eq!(route(HdbValue::INT(300)), Some(3));
+        assert_eq!(route(HdbValue::STRING("150".to_string())), Some(1));
+        // values that cannot be compared are not routed
+        assert_eq!(route(HdbValue::DOUBLE(1.0)), None);
+
+        // the partitioning column must be among the fields
+        assert!(Router::new(&partitions, &["NAME"], IdentifierCase::default()).is_none());
+        // column names are case-sensitive with the default identifier case
+        assert!(Router::new(&partitions, &["id"], IdentifierCase::default()).is_none());
+        assert!(Router::new(&partitions, &["id"], IdentifierCase::Upper).is_some());
+    }
+
+    #[test]
+    fn test_not_routable() {
+        let fields = ["ID"];
+        let hashed = vec![
+            partition("host1", Some("HASH"), "", ""),
+            partition("host2", Some("HASH"), "", ""),
+        ];
+        assert!(Router::new(&hashed, &fields, IdentifierCase::default()).is_none());
+
+        let mut multi_level = range_partitions();
+        multi_level[0].o_level_2_type = Some("HASH".to_string());
+        assert!(Router::new(&multi_level, &fields, IdentifierCase::default()).is_none());
+
+        let mut expression = range_partitions();
+        expression[0].o_expression = Some("YEAR(ID)".to_string());
+        assert!(Router::new(&expression, &fields, IdentifierCase::default()).is_none());
+
+        let not_partitioned = vec![partition("host1", None, "", "")];
+        assert!(Router::new(&not_partitioned, &fields, IdentifierCase::default()).is_none());
+        assert!(Router::new(&[], &fields, IdentifierCase::default()).is_none());
+    }
+
+    #[test]
+    fn test_load_plan_with_host_affinity() {
+        let partitions = range_partitions();
+        let router = Router::new(&partitions, &["NAME", "ID"], IdentifierCase::default());
+        let plan = LoadPlan::new(
+            &partitions,
+            router.as_ref(),
+            rows(&[1, 150, 2, 600, 3, 160]),
+            2,
+            true,
+        );
+        assert_eq!(plan.rows_routed, 6);
+        assert_eq!(plan.batch_count(), 4);
+        assert_eq!(plan.queue_of_worker(0), (0, Some(("host1", 30015))));
+        assert_eq!(plan.queue_of_worker(1), (1, Some(("host2", 30015))));
+        assert_eq!(plan.queue_of_worker(2), (0, Some(("host1", 30015))));
+
+        // the batches of a partition are queued for its host
+        let first = plan.next_batch(0).unwrap();
+        assert_eq!(first.rows, vec![0, 2]);
+        assert_eq!(plan.next_batch(1).unwrap().rows, vec![1, 5]);
+        assert_eq!(plan.next_batch(0).unwrap().rows, vec![4]);
+        assert_eq!(plan.next_batch(0).unwrap().rows, vec![3]);
+        assert!(plan.next_batch(0).is_none());
+        assert!(plan.next_batch(1).is_none());
+    }
+
+    #[test]
+    fn test_load_plan_without_routing() {
+        let partitions = vec![
+            partition("host1", Some("HASH"), "", ""),
+            partition("host2", Some("HASH"), "", ""),
+        ];
+        let plan = LoadPlan::new(&partitions, None, rows(&[1, 2, 3, 4, 5]), 2, true);
+        assert_eq!(plan.rows_routed, 0);
+        assert_eq!(plan.batch_count(), 3);
+
+        // the batches go to the hosts in turn; an idle worker takes over the batches
+        // of the other hosts
+        assert_eq!(plan.next_batch(1).unwrap().rows, vec![2, 3]);
+        assert_eq!(plan.next_batch(1).unwrap().rows, vec![0, 1]);
+        assert_eq!(plan.next_batch(1).unwrap().rows, vec![4]);
+        assert!(plan.next_batch(0).is_none());
+
+        // without host affinity, all batches are in a single queue
+        let plan = LoadPlan::new(&partitions, None, rows(&[1, 2, 3]), 2, false);
+        assert_eq!(plan.queue_of_worker(3), (0, None));
+        assert_eq!(plan.next_batch(0).unwrap().values.len(), 2);
+        assert_eq!(plan.next_batch(0).unwrap().values.len(), 1);
+        assert!(plan.next_batch(0).is_none());
+    }
+}
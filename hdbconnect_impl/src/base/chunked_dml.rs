@@ -0,0 +1,143 @@
+use crate::{impl_err, usage_err, HdbResult, IdentifierCase};
+
+// The state of a chunked delete or update that is shared by the sync and the async ChunkedDml.
+#[derive(Debug)]
+pub(crate) struct ChunkPlan {
+    table: String,
+    o_assignments: Option<String>,
+    condition: String,
+    chunk_size: usize,
+    commit_interval: usize,
+    total: usize,
+    uncommitted_chunks: usize,
+}
+
+// What to do after a chunk was executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChunkStep {
+    Continue,
+    CommitAndContinue,
+    CommitAndStop,
+}
+
+impl ChunkPlan {
+    const DEFAULT_CHUNK_SIZE: usize = 10_000;
+    const DEFAULT_COMMIT_INTERVAL: usize = 1;
+
+    pub(crate) fn new(table: &str, o_assignments: Option<&str>, condition: &str) -> Self {
+        Self {
+            table: table.to_string(),
+            o_assignments: o_assignments.map(ToString::to_string),
+            condition: condition.to_string(),
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            commit_interval: Self::DEFAULT_COMMIT_INTERVAL,
+            total: 0,
+            uncommitted_chunks: 0,
+        }
+    }
+
+    pub(crate) fn set_chunk_size(&mut self, chunk_size: usize) {
+        self.chunk_size = chunk_size.max(1);
+    }
+
+    pub(crate) fn set_commit_interval(&mut self, commit_interval: usize) {
+        self.commit_interval = commit_interval.max(1);
+    }
+
+    pub(crate) fn total(&self) -> usize {
+        self.total
+    }
+
+    pub(crate) fn statement(&self, identifier_case: IdentifierCase) -> String {
+        let table = identifier_case.table(&self.table);
+        match self.o_assignments {
+            Some(ref assignments) => format!(
+                "UPDATE {table} SET {assignments} WHERE ({}) LIMIT {}",
+                self.condition, self.chunk_size
+            ),
+            None => format!(
+                "DELETE FROM {table} WHERE ({}) LIMIT {}",
+                self.condition, self.chunk_size
+            ),
+        }
+    }
+
+    // The commits of the chunks would also commit the changes of an open write transaction
+    pub(crate) fn check_no_pending_write_transaction(pending: bool) -> HdbResult<()> {
+        if pending {
+            Err(usage_err!(
+                "ChunkedDml cannot be executed while a write transaction is open; \
+                 commit or roll back first"
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    // Counts the rows of an executed chunk; a chunk with fewer rows than the chunk size
+    // is the last one.
+    pub(crate) fn count_chunk(&mut self, o_affected: Option<u64>) -> HdbResult<ChunkStep> {
+        let affected = o_affected.ok_or_else(|| {
+            impl_err!("the server did not report the number of affected rows of a chunk")
+        })?;
+        // a chunk has at most chunk_size rows
+        let affected = usize::try_from(affected)
+            .map_err(|_| impl_err!("unexpected number of affected rows {affected}"))?;
+        self.total += affected;
+        self.uncommitted_chunks += 1;
+        if affected < self.chunk_size {
+            self.uncommitted_chunks = 0;
+            Ok(ChunkStep::CommitAndStop)
+        } else if self.uncommitted_chunks == self.commit_interval {
+            self.uncommitted_chunks = 0;
+            Ok(ChunkStep::CommitAndContinue)
+        } else {
+            Ok(ChunkStep::Continue)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChunkPlan, ChunkStep};
+    use crate::{HdbError, IdentifierCase};
+
+    #[test]
+    fn test_statement() {
+        let mut plan = ChunkPlan::new("sales.orders", None, "CREATED_AT < '2015-01-01'");
+        plan.set_chunk_size(500);
+        assert_eq!(
+            plan.statement(IdentifierCase::Upper),
+            "DELETE FROM \"SALES\".\"ORDERS\" WHERE (CREATED_AT < '2015-01-01') LIMIT 500"
+        );
+
+        let mut plan = ChunkPlan::new("ORDERS", Some("STATUS = 'archived'"), "STATUS = 'closed'");
+        plan.set_chunk_size(0);
+        assert_eq!(
+            plan.statement(IdentifierCase::Upper),
+            "UPDATE \"ORDERS\" SET STATUS = 'archived' WHERE (STATUS = 'closed') LIMIT 1"
+        );
+    }
+
+    #[test]
+    fn test_count_chunk() {
+        let mut plan = ChunkPlan::new("T", None, "1 = 1");
+        plan.set_chunk_size(10);
+        plan.set_commit_interval(2);
+        assert_eq!(plan.count_chunk(Some(10)).unwrap(), ChunkStep::Continue);
+        assert_eq!(
+            plan.count_chunk(Some(10)).unwrap(),
+            ChunkStep::CommitAndContinue
+        );
+        assert_eq!(plan.count_chunk(Some(10)).unwrap(), ChunkStep::Continue);
+        assert_eq!(plan.count_chunk(Some(3)).unwrap(), ChunkStep::CommitAndStop);
+        assert_eq!(plan.total(), 33);
+
+        assert!(matches!(plan.count_chunk(None), Err(HdbError::Impl(_))));
+        assert!(matches!(
+            ChunkPlan::check_no_pending_write_transaction(true),
+            Err(HdbError::Usage(_))
+        ));
+        assert!(ChunkPlan::check_no_pending_write_transaction(false).is_ok());
+    }
+}
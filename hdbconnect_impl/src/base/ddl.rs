@@ -0,0 +1,85 @@
+use crate::IdentifierCase;
+
+// Statements for the DDL helpers of Connection, like Connection::drop_table_if_exists().
+// The names are converted into identifiers or literals according to the identifier case,
+// so they cannot be used to inject SQL.
+
+// Counts the schemas with the given name (0 or 1).
+pub(crate) fn schema_exists_statement(schema: &str, identifier_case: IdentifierCase) -> String {
+    format!(
+        "SELECT COUNT(*) FROM SYS.SCHEMAS WHERE SCHEMA_NAME = {}",
+        literal(&identifier_case.catalog_name(schema))
+    )
+}
+
+pub(crate) fn create_schema_statement(schema: &str, identifier_case: IdentifierCase) -> String {
+    format!("CREATE SCHEMA {}", identifier_case.schema(schema))
+}
+
+// Counts the tables with the given, possibly qualified name (0 or 1).
+pub(crate) fn table_exists_statement(table: &str, identifier_case: IdentifierCase) -> String {
+    let mut names = identifier_case.catalog_names(table);
+    let table = literal(&names.pop().unwrap_or_default());
+    let schema = names
+        .pop()
+        .map_or_else(|| "CURRENT_SCHEMA".to_string(), |schema| literal(&schema));
+    format!("SELECT COUNT(*) FROM SYS.TABLES WHERE SCHEMA_NAME = {schema} AND TABLE_NAME = {table}")
+}
+
+pub(crate) fn drop_table_statement(table: &str, identifier_case: IdentifierCase) -> String {
+    format!("DROP TABLE {}", identifier_case.table(table))
+}
+
+pub(crate) fn truncate_table_statement(table: &str, identifier_case: IdentifierCase) -> String {
+    format!("TRUNCATE TABLE {}", identifier_case.table(table))
+}
+
+// Encloses the name in single quotes, escaping embedded quotes.
+fn literal(name: &str) -> String {
+    format!("'{}'", name.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        create_schema_statement, drop_table_statement, schema_exists_statement,
+        table_exists_statement, truncate_table_statement,
+    };
+    use crate::IdentifierCase;
+
+    #[test]
+    fn test_ddl_statements() {
+        let case = IdentifierCase::default();
+        assert_eq!(
+            schema_exists_statement("o'reilly", case),
+            "SELECT COUNT(*) FROM SYS.SCHEMAS WHERE SCHEMA_NAME = 'o''reilly'"
+        );
+        assert_eq!(
+            create_schema_statement("sales", case),
+            r#"CREATE SCHEMA "SALES""#
+        );
+        assert_eq!(
+            table_exists_statement("sales.orders", case),
+            "SELECT COUNT(*) FROM SYS.TABLES WHERE SCHEMA_NAME = 'SALES' AND TABLE_NAME = 'ORDERS'"
+        );
+        assert_eq!(
+            drop_table_statement(r#"sales."Orders""#, case),
+            r#"DROP TABLE "SALES"."Orders""#
+        );
+        assert_eq!(
+            truncate_table_statement("orders\"; DROP TABLE x", case),
+            r#"TRUNCATE TABLE "orders""; DROP TABLE x""#
+        );
+
+        let case = IdentifierCase::Verbatim;
+        assert_eq!(
+            create_schema_statement("Sales.2024", case),
+            r#"CREATE SCHEMA "Sales.2024""#
+        );
+        assert_eq!(
+            table_exists_statement("orders", case),
+            "SELECT COUNT(*) FROM SYS.TABLES \
+             WHERE SCHEMA_NAME = CURRENT_SCHEMA AND TABLE_NAME = 'orders'"
+        );
+    }
+}
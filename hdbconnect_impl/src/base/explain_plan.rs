@@ -0,0 +1,248 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static STATEMENT_COUNTER: AtomicU32 = AtomicU32::new(0);
+
+/// The execution plan of a statement, as produced by `EXPLAIN PLAN`.
+///
+/// The plan is a tree of operators; usually it has a single root.
+#[derive(Clone, Debug, Default)]
+pub struct ExplainPlan {
+    roots: Vec<PlanNode>,
+}
+
+/// A single operator of an [`ExplainPlan`].
+#[derive(Clone, Debug)]
+pub struct PlanNode {
+    operator_name: String,
+    operator_details: Option<String>,
+    schema_name: Option<String>,
+    table_name: Option<String>,
+    table_size: Option<f64>,
+    output_size: Option<f64>,
+    subtree_cost: Option<f64>,
+    children: Vec<PlanNode>,
+}
+
+// A row of the EXPLAIN_PLAN_TABLE
+#[derive(Debug, Deserialize)]
+pub(crate) struct PlanRow {
+    #[serde(rename = "OPERATOR_ID")]
+    operator_id: i32,
+    #[serde(rename = "PARENT_OPERATOR_ID")]
+    parent_operator_id: Option<i32>,
+    #[serde(rename = "OPERATOR_NAME")]
+    operator_name: Option<String>,
+    #[serde(rename = "OPERATOR_DETAILS")]
+    operator_details: Option<String>,
+    #[serde(rename = "SCHEMA_NAME")]
+    schema_name: Option<String>,
+    #[serde(rename = "TABLE_NAME")]
+    table_name: Option<String>,
+    #[serde(rename = "TABLE_SIZE")]
+    table_size: Option<f64>,
+    #[serde(rename = "OUTPUT_SIZE")]
+    output_size: Option<f64>,
+    #[serde(rename = "SUBTREE_COST")]
+    subtree_cost: Option<f64>,
+}
+
+impl ExplainPlan {
+    // Returns a statement name that is unique within this process.
+    pub(crate) fn unique_statement_name(connection_id: u32) -> String {
+        let count = STATEMENT_COUNTER.fetch_add(1, Ordering::Relaxed);
+        format!("HDBCONNECT_EXPLAIN_{connection_id}_{count}")
+    }
+
+    pub(crate) fn explain_statement(statement_name: &str, stmt: &str) -> String {
+        format!("EXPLAIN PLAN SET STATEMENT_NAME = '{statement_name}' FOR {stmt}")
+    }
+
+    pub(crate) fn select_statement(statement_name: &str) -> String {
+        format!(
+            "SELECT OPERATOR_ID, PARENT_OPERATOR_ID, OPERATOR_NAME, OPERATOR_DETAILS, \
+             SCHEMA_NAME, TABLE_NAME, TABLE_SIZE, OUTPUT_SIZE, SUBTREE_COST \
+             FROM EXPLAIN_PLAN_TABLE WHERE STATEMENT_NAME = '{statement_name}' \
+             ORDER BY OPERATOR_ID"
+        )
+    }
+
+    pub(crate) fn delete_statement(statement_name: &str) -> String {
+        format!("DELETE FROM EXPLAIN_PLAN_TABLE WHERE STATEMENT_NAME = '{statement_name}'")
+    }
+
+    // Builds the tree from the flat list of rows; rows with an unknown parent become roots.
+    pub(crate) fn from_rows(rows: Vec<PlanRow>) -> Self {
+        let ids: Vec<i32> = rows.iter().map(|row| row.operator_id).collect();
+        let mut children_of = std::collections::HashMap::<i32, Vec<PlanRow>>::new();
+        let mut root_rows = Vec::new();
+        for row in rows {
+            match row.parent_operator_id {
+                Some(parent_id) if ids.contains(&parent_id) => {
+                    children_of.entry(parent_id).or_default().push(row);
+                }
+                _ => root_rows.push(row),
+            }
+        }
+        Self {
+            roots: root_rows
+                .into_iter()
+                .map(|row| PlanNode::from_row(row, &mut children_of))
+                .collect(),
+        }
+    }
+
+    /// Returns the root operators of the plan.
+    #[must_use]
+    pub fn roots(&self) -> &[PlanNode] {
+        &self.roots
+    }
+}
+
+impl PlanNode {
+    fn from_row(
+        row: PlanRow,
+        children_of: &mut std::collections::HashMap<i32, Vec<PlanRow>>,
+    ) -> Self {
+        let children = children_of
+            .remove(&row.operator_id)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|child| Self::from_row(child, children_of))
+            .collect();
+        Self {
+            operator_name: row.operator_name.unwrap_or_default(),
+            operator_details: row.operator_details,
+            schema_name: row.schema_name,
+            table_name: row.table_name,
+            table_size: row.table_size,
+            output_size: row.output_size,
+            subtree_cost: row.subtree_cost,
+            children,
+        }
+    }
+
+    /// Returns the name of the operator, e.g. `COLUMN SEARCH`.
+    #[must_use]
+    pub fn operator_name(&self) -> &str {
+        &self.operator_name
+    }
+
+    /// Returns details of the operator, like filter conditions.
+    #[must_use]
+    pub fn operator_details(&self) -> Option<&str> {
+        self.operator_details.as_deref()
+    }
+
+    /// Returns the schema of the table that is accessed by the operator.
+    #[must_use]
+    pub fn schema_name(&self) -> Option<&str> {
+        self.schema_name.as_deref()
+    }
+
+    /// Returns the name of the table that is accessed by the operator.
+    #[must_use]
+    pub fn table_name(&self) -> Option<&str> {
+        self.table_name.as_deref()
+    }
+
+    /// Returns the estimated number of rows of the accessed table.
+    #[must_use]
+    pub fn table_size(&self) -> Option<f64> {
+        self.table_size
+    }
+
+    /// Returns the estimated number of rows that the operator produces.
+    #[must_use]
+    pub fn output_size(&self) -> Option<f64> {
+        self.output_size
+    }
+
+    /// Returns the estimated cost of the operator, including its children.
+    #[must_use]
+    pub fn subtree_cost(&self) -> Option<f64> {
+        self.subtree_cost
+    }
+
+    /// Returns the operators that provide the input of this operator.
+    #[must_use]
+    pub fn children(&self) -> &[PlanNode] {
+        &self.children
+    }
+}
+
+impl std::fmt::Display for ExplainPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        for root in &self.roots {
+            root.fmt_indented(f, 0)?;
+        }
+        Ok(())
+    }
+}
+
+impl PlanNode {
+    fn fmt_indented(&self, f: &mut std::fmt::Formatter, level: usize) -> std::fmt::Result {
+        write!(
+            f,
+            "{:indent$}{}",
+            "",
+            self.operator_name,
+            indent = 2 * level
+        )?;
+        if let Some(ref table_name) = self.table_name {
+            write!(f, " {table_name}")?;
+        }
+        if let Some(subtree_cost) = self.subtree_cost {
+            write!(f, " (cost: {subtree_cost})")?;
+        }
+        writeln!(f)?;
+        for child in &self.children {
+            child.fmt_indented(f, level + 1)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ExplainPlan, PlanNode, PlanRow};
+
+    fn row(operator_id: i32, parent_operator_id: Option<i32>, name: &str) -> PlanRow {
+        PlanRow {
+            operator_id,
+            parent_operator_id,
+            operator_name: Some(name.to_string()),
+            operator_details: None,
+            schema_name: None,
+            table_name: None,
+            table_size: None,
+            output_size: None,
+            subtree_cost: None,
+        }
+    }
+
+    #[test]
+    fn test_from_rows() {
+        let plan = ExplainPlan::from_rows(vec![
+            row(1, None, "PROJECT"),
+            row(2, Some(1), "JOIN"),
+            row(3, Some(2), "COLUMN TABLE"),
+            row(4, Some(2), "ROW TABLE"),
+        ]);
+        assert_eq!(plan.roots().len(), 1);
+        let root = &plan.roots()[0];
+        assert_eq!(root.operator_name(), "PROJECT");
+        assert_eq!(root.children().len(), 1);
+        let join = &root.children()[0];
+        assert_eq!(join.operator_name(), "JOIN");
+        let names: Vec<&str> = join
+            .children()
+            .iter()
+            .map(PlanNode::operator_name)
+            .collect();
+        assert_eq!(names, vec!["COLUMN TABLE", "ROW TABLE"]);
+        assert_eq!(
+            plan.to_string(),
+            "PROJECT\n  JOIN\n    COLUMN TABLE\n    ROW TABLE\n"
+        );
+    }
+}
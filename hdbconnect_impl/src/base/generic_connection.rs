@@ -0,0 +1,117 @@
+use crate::{AffectedRowCount, HdbResult};
+use serde::{de::DeserializeOwned, ser::Serialize};
+use std::future::Future;
+
+/// The operations that `hdbconnect::Connection` and `hdbconnect_async::Connection`
+/// have in common, as one abstraction over both driver variants.
+///
+/// Libraries that are written against this trait compile for either driver, and
+/// are called with an instance of either connection type.
+/// All operations return futures. The implementation for the synchronous driver
+/// does its work already when the method is called and returns a future that is
+/// immediately ready, so any executor can resolve it, e.g. `futures::executor::block_on`.
+///
+/// The trait is implemented for all implementations of `hdbconnect::DbConnection`,
+/// including `MockConnection`, and for `hdbconnect_async::Connection`.
+///
+/// ```rust,no_run
+/// use hdbconnect::{GenericConnection, HdbResult};
+///
+/// // works with both hdbconnect::Connection and hdbconnect_async::Connection
+/// async fn count_users<C: GenericConnection>(conn: &C) -> HdbResult<i64> {
+///     conn.query_into("SELECT COUNT(*) FROM USERS").await
+/// }
+/// ```
+pub trait GenericConnection {
+    /// The type of the prepared statements.
+    type PreparedStatement: GenericPreparedStatement + Send;
+
+    /// Executes a query and deserializes the complete result set into the given type.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn query_into<T>(&self, stmt: &str) -> impl Future<Output = HdbResult<T>> + Send
+    where
+        T: DeserializeOwned + Send;
+
+    /// Executes a statement and expects a single number of affected rows.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn dml(&self, stmt: &str) -> impl Future<Output = HdbResult<AffectedRowCount>> + Send;
+
+    /// Executes a statement and expects a plain success.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn exec(&self, stmt: &str) -> impl Future<Output = HdbResult<()>> + Send;
+
+    /// Prepares a statement.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn prepare(
+        &self,
+        stmt: &str,
+    ) -> impl Future<Output = HdbResult<Self::PreparedStatement>> + Send;
+
+    /// Commits the current transaction.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn commit(&self) -> impl Future<Output = HdbResult<()>> + Send;
+
+    /// Rolls back the current transaction.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn rollback(&self) -> impl Future<Output = HdbResult<()>> + Send;
+}
+
+/// The operations that the prepared statements of `hdbconnect` and `hdbconnect_async`
+/// have in common.
+///
+/// See [`GenericConnection`].
+pub trait GenericPreparedStatement {
+    /// Executes the statement with the given input, and deserializes the returned
+    /// result set into the given type.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn query_into<I, T>(&mut self, input: &I) -> impl Future<Output = HdbResult<T>> + Send
+    where
+        I: Serialize + Sync,
+        T: DeserializeOwned + Send;
+
+    /// Executes the statement with the given input and expects a single number
+    /// of affected rows.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn dml<I>(&mut self, input: &I) -> impl Future<Output = HdbResult<AffectedRowCount>> + Send
+    where
+        I: Serialize + Sync;
+
+    /// Adds the given input to the batch.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn add_batch<I: Serialize>(&mut self, input: &I) -> HdbResult<()>;
+
+    /// Executes the statement with the collected batch, and returns the numbers
+    /// of affected rows.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn execute_batch(&mut self) -> impl Future<Output = HdbResult<Vec<AffectedRowCount>>> + Send;
+}
@@ -1,4 +1,4 @@
-use crate::protocol::parts::{ExecutionResults, ServerError};
+use crate::protocol::parts::{ExecutionResults, ServerError, TypeId};
 // use std::backtrace::Backtrace;
 use thiserror::Error;
 
@@ -34,6 +34,46 @@ pub enum HdbError {
         // backtrace: Backtrace,
     },
 
+    /// Deserialization of a specific value of a `ResultSet` or a `Row` failed.
+    ///
+    /// The row index is only known when a complete `ResultSet` is deserialized.
+    #[error(
+        "Error occured in deserialization of {}column {column_name} ({type_id}): {}",
+        row.map_or_else(String::new, |r| format!("row {r}, ")),
+        error_details(source)
+    )]
+    DeserializationAt {
+        /// The causing Error.
+        source: serde_db::de::DeserializationError,
+        /// The index of the row, if known.
+        row: Option<usize>,
+        /// The index of the column.
+        column: usize,
+        /// The name of the column.
+        column_name: String,
+        /// The type of the column.
+        type_id: TypeId,
+    },
+
+    /// Deserialization of a `ResultSet` or a `Row` failed because the fields of the target
+    /// structure do not fit to the columns of the result.
+    #[error(
+        "The deserialization target does not fit to the result ({}); \
+         result columns: {columns:?}, target fields: {fields:?}{}",
+        error_details(source),
+        hints_details(hints)
+    )]
+    DeserializationMismatch {
+        /// The causing Error.
+        source: serde_db::de::DeserializationError,
+        /// The names of the result columns.
+        columns: Vec<String>,
+        /// The names of the fields of the target structure.
+        fields: Vec<String>,
+        /// Hints on possible reasons for the mismatch.
+        hints: Vec<String>,
+    },
+
     /// Serialization of a `ParameterDescriptor` or a `ParameterRow` failed.
     #[error("Error occured in serialization")]
     Serialization {
@@ -106,6 +146,15 @@ pub enum HdbError {
         source: rustls::Error,
     },
 
+    /// The TLS handshake did not complete within the configured time.
+    ///
+    /// See `ConnectionConfiguration::set_tls_handshake_timeout()`.
+    #[error("TLS handshake did not complete within {timeout:?}")]
+    TlsHandshakeTimeout {
+        /// The configured timeout.
+        timeout: std::time::Duration,
+    },
+
     /// Error occured while evaluating an `HdbResponse` or an `HdbReturnValue`.
     #[error("Error occured while evaluating a HdbResponse or an HdbReturnValue")]
     Evaluation(&'static str),
@@ -143,6 +192,44 @@ pub enum HdbError {
         second: Box<HdbError>,
     },
 
+    /// A request was not sent because it exceeds the maximum request size.
+    ///
+    /// Large batches can be split into several smaller ones.
+    /// See `ConnectionConfiguration::set_max_request_size()`.
+    #[error("Request of {size} bytes exceeds the maximum request size of {limit} bytes")]
+    RequestTooLarge {
+        /// The size of the request, in bytes.
+        size: usize,
+        /// The maximum request size, in bytes.
+        limit: usize,
+    },
+
+    /// The data format version that was negotiated with the server is too low,
+    /// for using the server at all, or for sending a value of a specific type.
+    ///
+    /// See `Connection::data_format_version_2()`.
+    #[error(
+        "{feature} requires data format version {required}, \
+         but version {negotiated} was negotiated with the server"
+    )]
+    DataFormatVersion {
+        /// What needs the higher data format version.
+        feature: String,
+        /// The data format version that is required.
+        required: u8,
+        /// The data format version that was negotiated with the server.
+        negotiated: u8,
+    },
+
+    /// A statement was not executed because the statement hook of the connection vetoed it.
+    ///
+    /// See `Connection::set_statement_hook()`.
+    #[error("Execution of statement was vetoed: {reason}")]
+    Vetoed {
+        /// The reason that the hook returned.
+        reason: String,
+    },
+
     /// Error caused by wrong usage.
     #[error("Wrong usage: {}", _0)]
     Usage(std::borrow::Cow<'static, str>),
@@ -150,6 +237,44 @@ pub enum HdbError {
     /// Connection is dead
     #[error("Connection is broken")]
     ConnectionBroken { source: Option<Box<HdbError>> },
+
+    /// Error occurred while executing a statement.
+    ///
+    /// Is only produced if the connection is configured accordingly, see
+    /// `ConnectionConfiguration::set_sql_in_errors()`.
+    #[error(
+        "{source}; statement: \"{statement}\"{}",
+        parameters.as_ref().map_or_else(String::new, |p| format!("; parameters: {p}"))
+    )]
+    WithStatement {
+        /// The causing Error.
+        source: Box<HdbError>,
+        /// The text of the failing statement.
+        statement: String,
+        /// A summary of the parameters, without their values.
+        parameters: Option<String>,
+    },
+}
+
+fn error_details(e: &serde_db::de::DeserializationError) -> String {
+    use serde_db::de::{ConversionError, DeserializationError};
+    match e {
+        DeserializationError::ConversionError(
+            ConversionError::ValueType(s)
+            | ConversionError::NumberRange(s)
+            | ConversionError::Incomplete(s),
+        ) => s.clone(),
+        DeserializationError::ConversionError(ConversionError::Other(e)) => e.to_string(),
+        e => format!("{e:?}"),
+    }
+}
+
+fn hints_details(hints: &[String]) -> String {
+    if hints.is_empty() {
+        String::new()
+    } else {
+        format!("; hint: {}", hints.join("; hint: "))
+    }
 }
 
 /// Abbreviation of `Result<T, HdbError>`.
@@ -187,6 +312,19 @@ impl HdbError {
             Self::DbError {
                 source: server_error,
             } => Some(server_error),
+            Self::WithStatement { source, .. } => source.server_error(),
+            _ => None,
+        }
+    }
+
+    /// Returns the execution results if the error was caused by failures in a batch execution.
+    ///
+    /// Use [`ExecutionResults::row_errors`] to find out which input rows have failed.
+    #[must_use]
+    pub fn execution_results(&self) -> Option<&ExecutionResults> {
+        match self {
+            Self::ExecutionResults(execution_results) => Some(execution_results),
+            Self::WithStatement { source, .. } => source.execution_results(),
             _ => None,
         }
     }
@@ -196,7 +334,9 @@ impl HdbError {
     pub fn inner(&self) -> Option<&dyn std::error::Error> {
         match self {
             Self::Authentication { source } => Some(source),
-            Self::Deserialization { source } => Some(source),
+            Self::Deserialization { source }
+            | Self::DeserializationAt { source, .. }
+            | Self::DeserializationMismatch { source, .. } => Some(source),
             Self::Serialization { source } => Some(source),
             Self::ConnParams { source } | Self::TlsInit { source } => Some(&**source),
             Self::DbError { source } => Some(source),
@@ -204,6 +344,7 @@ impl HdbError {
             Self::TlsInvalidDnsName { source } => Some(source),
             Self::Io { source } => Some(source),
             Self::TlsProtocol { source } => Some(source),
+            Self::WithStatement { source, .. } => source.inner(),
             _ => None,
         }
     }
@@ -215,6 +356,46 @@ impl HdbError {
         }
     }
 
+    /// Returns the text of the failing statement, if it was attached to the error.
+    #[must_use]
+    pub fn statement(&self) -> Option<&str> {
+        match self {
+            Self::WithStatement { statement, .. } => Some(statement),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn with_statement(self, statement: String, o_parameters: Option<String>) -> Self {
+        match self {
+            Self::WithStatement { .. } => self,
+            _ => Self::WithStatement {
+                source: Box::new(self),
+                statement,
+                parameters: o_parameters,
+            },
+        }
+    }
+
+    pub(crate) fn deserialization_at(
+        source: serde_db::de::DeserializationError,
+        row: Option<usize>,
+        column: usize,
+        column_name: &str,
+        type_id: TypeId,
+    ) -> Self {
+        if let serde_db::de::DeserializationError::ConversionError(_) = source {
+            Self::DeserializationAt {
+                source,
+                row,
+                column,
+                column_name: column_name.to_string(),
+                type_id,
+            }
+        } else {
+            Self::Deserialization { source }
+        }
+    }
+
     pub(crate) fn conn_params(error: Box<dyn std::error::Error + Send + Sync + 'static>) -> Self {
         Self::ConnParams { source: error }
     }
@@ -249,3 +430,29 @@ macro_rules! impl_err {
         $crate::HdbError::Impl(std::borrow::Cow::from(format!($($arg)*)))
     }};
 }
+
+#[cfg(test)]
+mod test {
+    use super::HdbError;
+
+    #[test]
+    fn test_with_statement() {
+        let err = crate::usage_err!("foo").with_statement(
+            "select * from BAR where ID = ?".to_string(),
+            Some("1 row(s) of [INT]".to_string()),
+        );
+        assert_eq!(
+            err.to_string(),
+            "Wrong usage: foo; statement: \"select * from BAR where ID = ?\"; \
+             parameters: 1 row(s) of [INT]"
+        );
+        assert_eq!(err.statement(), Some("select * from BAR where ID = ?"));
+
+        // wrapping is not repeated
+        let err = err.with_statement("other".to_string(), None);
+        assert!(matches!(
+            err,
+            HdbError::WithStatement { ref source, .. } if matches!(**source, HdbError::Usage(_))
+        ));
+    }
+}
@@ -0,0 +1,246 @@
+use crate::{usage_err, HdbResult};
+
+/// An SQL statement with a `WITH HINT(...)` clause, composed from validated HANA hints.
+///
+/// The hints are checked when they are added, so typos and accidental SQL fragments
+/// are detected before the statement is sent to the server.
+/// `HintedStatement` implements `AsRef<str>` and can thus be used directly with
+/// `Connection::statement()`, `Connection::query()`, `Connection::prepare()`, etc.
+///
+/// ```rust
+/// use hdbconnect::HintedStatement;
+/// # fn main() -> hdbconnect::HdbResult<()> {
+/// let mut stmt = HintedStatement::new("SELECT * FROM ORDERS");
+/// stmt.hint("no_use_olap_plan")?.hint("RESULT_LAG('hana_sr', 30)")?;
+/// assert_eq!(
+///     stmt.as_ref(),
+///     "SELECT * FROM ORDERS WITH HINT(NO_USE_OLAP_PLAN, RESULT_LAG('hana_sr', 30))"
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct HintedStatement {
+    stmt: String,
+    hints: Vec<String>,
+    sql: String,
+}
+
+impl HintedStatement {
+    /// Creates a new instance for the given statement, without hints.
+    ///
+    /// A trailing semicolon is removed.
+    #[must_use]
+    pub fn new<S: AsRef<str>>(stmt: S) -> Self {
+        let stmt = stmt.as_ref().trim_end().trim_end_matches(';').to_string();
+        Self {
+            sql: stmt.clone(),
+            stmt,
+            hints: Vec::new(),
+        }
+    }
+
+    /// Adds a hint, like `NO_USE_OLAP_PLAN` or `RESULT_LAG('hana_sr', 30)`.
+    ///
+    /// The hint name is converted to upper case; hints that were already added are ignored.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the hint is not a hint name, optionally followed by
+    /// a parenthesized list of literals and names, or if the statement already
+    /// contains a `WITH HINT` clause.
+    pub fn hint(&mut self, hint: &str) -> HdbResult<&mut Self> {
+        let (words, in_line_comment) = scan(&self.stmt);
+        if self.hints.is_empty() && contains_hint_clause(&words) {
+            return Err(usage_err!("statement already contains a WITH HINT clause"));
+        }
+        let hint = validate(hint)?;
+        if !self.hints.contains(&hint) {
+            self.hints.push(hint);
+            // a trailing line comment must not swallow the clause
+            let separator = if in_line_comment { '\n' } else { ' ' };
+            self.sql = format!(
+                "{}{separator}WITH HINT({})",
+                self.stmt,
+                self.hints.join(", ")
+            );
+        }
+        Ok(self)
+    }
+
+    /// Returns the hints that were added so far.
+    #[must_use]
+    pub fn hints(&self) -> &[String] {
+        &self.hints
+    }
+}
+
+impl AsRef<str> for HintedStatement {
+    fn as_ref(&self) -> &str {
+        &self.sql
+    }
+}
+
+impl std::fmt::Display for HintedStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(&self.sql)
+    }
+}
+
+// Returns the normalized hint, or an error if the hint is malformed.
+fn validate(hint: &str) -> HdbResult<String> {
+    let hint = hint.trim();
+    let (name, args) = match hint.find('(') {
+        Some(i) => (hint[..i].trim_end(), Some(&hint[i..])),
+        None => (hint, None),
+    };
+    let mut chars = name.chars();
+    if !chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(usage_err!("invalid hint name in {hint:?}"));
+    }
+    let name = name.to_ascii_uppercase();
+    match args {
+        None => Ok(name),
+        Some(args) => {
+            let inner = args
+                .strip_prefix('(')
+                .and_then(|a| a.strip_suffix(')'))
+                .filter(|inner| is_valid_argument_list(inner))
+                .ok_or_else(|| usage_err!("invalid hint arguments in {hint:?}"))?;
+            Ok(format!("{name}({})", inner.trim()))
+        }
+    }
+}
+
+// Accepts string literals, (signed) numbers, names, commas, dots, and whitespace;
+// a minus sign must be followed by a digit, so that comments cannot be started.
+fn is_valid_argument_list(inner: &str) -> bool {
+    let mut in_literal = false;
+    let mut chars = inner.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            // an escaped quote ('') toggles twice
+            in_literal = !in_literal;
+        } else if in_literal {
+            // anything goes
+        } else if c == '-' {
+            if !chars.peek().is_some_and(char::is_ascii_digit) {
+                return false;
+            }
+        } else if !(c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | ',' | ' ')) {
+            return false;
+        }
+    }
+    !in_literal
+}
+
+// Returns the upper-cased words of the statement that are not part of string literals,
+// quoted identifiers, or comments, and whether the statement ends within a line comment.
+fn scan(stmt: &str) -> (Vec<String>, bool) {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_line_comment = false;
+    let mut chars = stmt.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_ascii_alphanumeric() || c == '_' => {
+                word.push(c.to_ascii_uppercase());
+                continue;
+            }
+            '\'' | '"' => {
+                // an escaped quote ends the quoted text and starts a new one
+                chars.by_ref().find(|&next| next == c);
+            }
+            '-' if chars.peek() == Some(&'-') => {
+                in_line_comment = chars.by_ref().find(|&next| next == '\n').is_none();
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                chars.by_ref().find(|&next| {
+                    let end = previous == '*' && next == '/';
+                    previous = next;
+                    end
+                });
+            }
+            _ => {}
+        }
+        if !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    (words, in_line_comment)
+}
+
+fn contains_hint_clause(words: &[String]) -> bool {
+    words.windows(2).any(|w| w[0] == "WITH" && w[1] == "HINT")
+}
+
+#[cfg(test)]
+mod test {
+    use super::HintedStatement;
+
+    #[test]
+    fn test_hinted_statement() {
+        let mut stmt = HintedStatement::new("SELECT 1 FROM DUMMY;");
+        assert_eq!(stmt.as_ref(), "SELECT 1 FROM DUMMY");
+
+        stmt.hint("no_use_olap_plan")
+            .unwrap()
+            .hint(" RESULT_LAG ( 'hana_sr', 30 ) ")
+            .unwrap()
+            .hint("NO_USE_OLAP_PLAN")
+            .unwrap();
+        assert_eq!(
+            stmt.to_string(),
+            "SELECT 1 FROM DUMMY WITH HINT(NO_USE_OLAP_PLAN, RESULT_LAG('hana_sr', 30))"
+        );
+        assert_eq!(stmt.hints().len(), 2);
+
+        assert!(stmt.hint("").is_err());
+        assert!(stmt.hint("1ST").is_err());
+        assert!(stmt.hint("X; DROP TABLE T").is_err());
+        assert!(stmt.hint("X(1)) --").is_err());
+        assert!(stmt.hint("X('unterminated)").is_err());
+        assert!(stmt.hint("X(');DROP TABLE T--')").is_ok());
+        assert!(stmt.hint("X(1 --)").is_err());
+        assert!(stmt.hint("X(1, -- )").is_err());
+        assert!(stmt.hint("X(-1, 'a--b')").is_ok());
+
+        assert!(HintedStatement::new("SELECT 1 FROM DUMMY with hint (X)")
+            .hint("Y")
+            .is_err());
+        assert!(HintedStatement::new("SELECT 1 FROM DUMMY WITH HINT(X)")
+            .hint("Y")
+            .is_err());
+    }
+
+    #[test]
+    fn test_quoted_text_and_comments() {
+        // hint clauses within literals, quoted identifiers, and comments do not count
+        for stmt in [
+            "SELECT 'with hint' FROM DUMMY",
+            "SELECT 'it''s with hint' FROM DUMMY",
+            r#"SELECT 1 AS "WITH HINT" FROM DUMMY"#,
+            "SELECT 1 FROM DUMMY /* with hint(X) */",
+            "SELECT 1 FROM DUMMY -- with hint(X)\nWHERE 1 = 1",
+        ] {
+            let mut hinted = HintedStatement::new(stmt);
+            assert!(hinted.hint("Y").is_ok(), "{stmt}");
+            assert!(hinted.as_ref().ends_with(" WITH HINT(Y)"), "{stmt}");
+        }
+
+        // a trailing line comment does not swallow the hint clause
+        let mut hinted = HintedStatement::new("SELECT 1 FROM DUMMY -- the dummy");
+        hinted.hint("Y").unwrap();
+        assert_eq!(
+            hinted.as_ref(),
+            "SELECT 1 FROM DUMMY -- the dummy\nWITH HINT(Y)"
+        );
+    }
+}
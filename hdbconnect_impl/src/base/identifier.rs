@@ -0,0 +1,202 @@
+/// Converts a name, e.g. of a table or column, into an identifier that can safely be
+/// embedded into dynamically built SQL.
+///
+/// HANA converts undelimited identifiers to upper case, so a name that would be valid
+/// as undelimited identifier (letters, digits, `_`, `#`, and `$`, not starting with a digit)
+/// is converted to upper case, like it would be when written into the SQL directly.
+/// Other names, and names that are already enclosed in double quotes, are used as they are.
+/// The result is always enclosed in double quotes, with embedded quotes being escaped,
+/// so it cannot be used to inject SQL, and it does not collide with reserved words.
+///
+/// ```rust
+/// use hdbconnect::quote_ident;
+/// assert_eq!(quote_ident("orders"), r#""ORDERS""#);
+/// assert_eq!(quote_ident(r#""MixedCase""#), r#""MixedCase""#);
+/// assert_eq!(quote_ident("my table\"; DROP TABLE x"), r#""my table""; DROP TABLE x""#);
+/// ```
+#[must_use]
+pub fn quote_ident(name: &str) -> String {
+    delimit(&normalize(name))
+}
+
+/// Converts a possibly qualified name, like `my_schema.orders`, into a qualified
+/// identifier that can safely be embedded into dynamically built SQL.
+///
+/// The name is split at the dots that are not enclosed in double quotes,
+/// and each part is converted with [`quote_ident`].
+///
+/// ```rust
+/// use hdbconnect::quote_qualified;
+/// assert_eq!(quote_qualified("sales.orders"), r#""SALES"."ORDERS""#);
+/// assert_eq!(quote_qualified(r#"sales."Order.Items""#), r#""SALES"."Order.Items""#);
+/// ```
+#[must_use]
+pub fn quote_qualified(name: &str) -> String {
+    split_qualified(name)
+        .iter()
+        .map(|part| delimit(part))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Defines how the helpers that build SQL for a given table, like `Connection::insert_rows`,
+/// `BulkLoader`, or `Connection::apply_migrations`, convert table and column names
+/// into identifiers.
+///
+/// Names that are enclosed in double quotes are always used as they are.
+/// Column names are taken from the field names of the serialized rows.
+///
+/// The identifier case of a connection can be set with
+/// `ConnectionConfiguration::set_identifier_case()` or `Connection::set_identifier_case()`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum IdentifierCase {
+    /// Table names are converted like with [`quote_qualified`], column names are quoted
+    /// verbatim (this is the default).
+    #[default]
+    UpperTables,
+    /// Table names are converted like with [`quote_qualified`], column names like with
+    /// [`quote_ident`]; so names that are valid undelimited identifiers are upper-cased.
+    Upper,
+    /// Table and column names are quoted verbatim, so they are case-sensitive;
+    /// a qualified table name is still split at the dots that are not enclosed in double quotes.
+    Verbatim,
+}
+
+impl IdentifierCase {
+    // Returns the identifier for a possibly qualified table name.
+    pub(crate) fn table(self, name: &str) -> String {
+        self.catalog_names(name)
+            .iter()
+            .map(|part| delimit(part))
+            .collect::<Vec<_>>()
+            .join(".")
+    }
+
+    // Returns the parts of a possibly qualified table name as HANA stores them in the catalog.
+    pub(crate) fn catalog_names(self, name: &str) -> Vec<String> {
+        match self {
+            Self::UpperTables | Self::Upper => split_qualified(name),
+            Self::Verbatim => split_parts(name)
+                .into_iter()
+                .map(|part| strip_delimiters(part).unwrap_or_else(|| part.to_string()))
+                .collect(),
+        }
+    }
+
+    // Returns the identifier for a name that cannot be qualified, like a schema name.
+    pub(crate) fn schema(self, name: &str) -> String {
+        delimit(&self.catalog_name(name))
+    }
+
+    // Returns a name that cannot be qualified as HANA stores it in the catalog.
+    pub(crate) fn catalog_name(self, name: &str) -> String {
+        match self {
+            Self::UpperTables | Self::Upper => normalize(name),
+            Self::Verbatim => strip_delimiters(name).unwrap_or_else(|| name.to_string()),
+        }
+    }
+
+    // Returns the identifier for a column name.
+    pub(crate) fn column(self, name: &str) -> String {
+        match self {
+            Self::Upper => quote_ident(name),
+            Self::UpperTables | Self::Verbatim => delimit(name),
+        }
+    }
+}
+
+// Returns the name as HANA stores it in the catalog, see quote_ident().
+pub(crate) fn normalize(name: &str) -> String {
+    if let Some(delimited) = strip_delimiters(name) {
+        delimited
+    } else if is_regular_identifier(name) {
+        name.to_uppercase()
+    } else {
+        name.to_string()
+    }
+}
+
+// Splits a possibly qualified name at the dots outside of double quotes,
+// and normalizes the parts.
+pub(crate) fn split_qualified(name: &str) -> Vec<String> {
+    split_parts(name).into_iter().map(normalize).collect()
+}
+
+// Splits a possibly qualified name at the dots outside of double quotes.
+fn split_parts(name: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    for (i, c) in name.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            '.' if !in_quotes => {
+                parts.push(&name[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&name[start..]);
+    parts
+}
+
+// Encloses the name in double quotes, escaping embedded quotes; the name is case-sensitive.
+pub(crate) fn delimit(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// Returns the unescaped content of a name that is already enclosed in double quotes.
+fn strip_delimiters(name: &str) -> Option<String> {
+    let inner = name.strip_prefix('"')?.strip_suffix('"')?;
+    let unescaped = inner.replace("\"\"", "\"");
+    // a single quote within would have terminated the identifier
+    if unescaped.matches('"').count() * 2 == inner.matches('"').count() {
+        Some(unescaped)
+    } else {
+        None
+    }
+}
+
+fn is_regular_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '#' | '$'))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{quote_ident, quote_qualified, IdentifierCase};
+
+    #[test]
+    fn test_quote_ident() {
+        assert_eq!(quote_ident("Orders_2024"), r#""ORDERS_2024""#);
+        assert_eq!(quote_ident("2024_orders"), r#""2024_orders""#);
+        assert_eq!(quote_ident(r#""a""b""#), r#""a""b""#);
+        assert_eq!(quote_ident(r#""a"b""#), r#""""a""b""""#);
+        assert_eq!(quote_ident(""), r#""""#);
+        assert_eq!(quote_qualified(r#"s."t.x".c"#), r#""S"."t.x"."C""#);
+    }
+
+    #[test]
+    fn test_identifier_case() {
+        let case = IdentifierCase::default();
+        assert_eq!(case.table("sales.orders"), r#""SALES"."ORDERS""#);
+        assert_eq!(case.column("id"), r#""id""#);
+
+        let case = IdentifierCase::Upper;
+        assert_eq!(case.table(r#"sales."Orders""#), r#""SALES"."Orders""#);
+        assert_eq!(case.column("id"), r#""ID""#);
+        assert_eq!(case.column(r#""id""#), r#""id""#);
+
+        let case = IdentifierCase::Verbatim;
+        assert_eq!(
+            case.table(r#"sales."Order.Items""#),
+            r#""sales"."Order.Items""#
+        );
+        assert_eq!(case.catalog_names("sales.orders"), vec!["sales", "orders"]);
+        assert_eq!(case.column("id"), r#""id""#);
+    }
+}
@@ -1,5 +1,8 @@
+use crate::conn::AmConnCore;
+
+#[cfg(feature = "sync")]
 use crate::{
-    conn::{AmConnCore, CommandOptions},
+    conn::CommandOptions,
     protocol::{MessageType, Part, PartKind, Request},
 };
 
@@ -8,6 +11,10 @@ use crate::{
 pub(crate) struct PreparedStatementCore {
     pub am_conn_core: AmConnCore,
     pub statement_id: u64,
+    // the session in which the statement was prepared
+    pub session_id: i64,
+    // lets the statement be dropped together with the next request, rather than immediately
+    pub deferred_drop: bool,
 }
 
 impl Drop for PreparedStatementCore {
@@ -15,6 +22,16 @@ impl Drop for PreparedStatementCore {
     fn drop(&mut self) {
         #[cfg(feature = "sync")]
         {
+            if let Ok(mut conn_core) = self.am_conn_core.lock_sync() {
+                if conn_core.session_id() != self.session_id {
+                    // the statement ended with its session
+                    return;
+                }
+                if self.deferred_drop {
+                    conn_core.defer_statement_drop(self.statement_id);
+                    return;
+                }
+            }
             let mut request = Request::new(MessageType::DropStatementId, CommandOptions::EMPTY);
             request.push(Part::StatementId(self.statement_id));
             if let Ok(mut reply) = self.am_conn_core.send_sync(request) {
@@ -22,16 +39,18 @@ impl Drop for PreparedStatementCore {
             }
         }
 
+        // the session is checked when the cleanup is processed
         #[cfg(feature = "async")]
         {
-            let mut request = Request::new(MessageType::DropStatementId, CommandOptions::EMPTY);
-            request.push(Part::StatementId(self.statement_id));
-            let am_conn_core = self.am_conn_core.clone();
-            tokio::task::spawn(async move {
-                if let Ok(mut reply) = am_conn_core.send_async(request).await {
-                    reply.parts.pop_if_kind(PartKind::StatementContext);
-                }
-            });
+            let cleanup = crate::conn::Cleanup::DropStatement {
+                statement_id: self.statement_id,
+                session_id: self.session_id,
+            };
+            if self.deferred_drop {
+                self.am_conn_core.defer_cleanup(cleanup);
+            } else {
+                self.am_conn_core.schedule_cleanup(cleanup);
+            }
         }
     }
 }
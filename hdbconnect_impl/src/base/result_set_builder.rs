@@ -0,0 +1,115 @@
+use crate::{FieldMetadata, HdbResult, HdbValue, ResultSetMetadata, Row, TypeId};
+use std::sync::Arc;
+
+/// Builds `ResultSet`s and `Row`s from literal data, without a database.
+///
+/// This is useful for unit tests of application code, e.g. together with a mock connection,
+/// or whenever a result set must be fabricated.
+/// All columns are nullable.
+///
+/// ```rust
+/// use hdbconnect::{HdbValue, ResultSet, ResultSetBuilder, TypeId};
+/// # fn main() -> hdbconnect::HdbResult<()> {
+/// let builder = ResultSetBuilder::new()
+///     .column("ID", TypeId::INT)
+///     .column("NAME", TypeId::NVARCHAR)
+///     .row(vec![HdbValue::INT(1), HdbValue::STRING("Alice".to_string())])
+///     .row(vec![HdbValue::INT(2), HdbValue::NULL]);
+/// let result_set = ResultSet::try_from(builder)?;
+/// let names: Vec<(i32, Option<String>)> = result_set.try_into()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ResultSetBuilder {
+    columns: Vec<(String, TypeId)>,
+    rows: Vec<Vec<HdbValue<'static>>>,
+}
+
+impl ResultSetBuilder {
+    /// Creates a new builder without columns and rows.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a column with the given name and type.
+    #[must_use]
+    pub fn column<S: AsRef<str>>(mut self, name: S, type_id: TypeId) -> Self {
+        self.columns.push((name.as_ref().to_string(), type_id));
+        self
+    }
+
+    /// Adds a row with the given values, which must match the columns.
+    #[must_use]
+    pub fn row<I: IntoIterator<Item = HdbValue<'static>>>(mut self, values: I) -> Self {
+        self.rows.push(values.into_iter().collect());
+        self
+    }
+
+    /// Returns the metadata that is described by the columns.
+    #[must_use]
+    pub fn metadata(&self) -> Arc<ResultSetMetadata> {
+        Arc::new(ResultSetMetadata::new(
+            self.columns
+                .iter()
+                .map(|(name, type_id)| FieldMetadata::new_column(name, *type_id))
+                .collect(),
+        ))
+    }
+
+    /// Converts the builder into a list of `Row`s.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if a row does not have as many values as there are columns.
+    pub fn into_rows(self) -> HdbResult<Vec<Row>> {
+        Ok(self.into_metadata_and_rows()?.1)
+    }
+
+    // Provides the metadata and the rows, which share the metadata.
+    pub(crate) fn into_metadata_and_rows(self) -> HdbResult<(Arc<ResultSetMetadata>, Vec<Row>)> {
+        let metadata = self.metadata();
+        let rows = self
+            .rows
+            .into_iter()
+            .map(|values| Row::try_new(Arc::clone(&metadata), values))
+            .collect::<HdbResult<Vec<Row>>>()?;
+        Ok((metadata, rows))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ResultSetBuilder;
+    use crate::{HdbValue, TypeId};
+
+    #[test]
+    fn test_into_rows() {
+        let builder = ResultSetBuilder::new()
+            .column("ID", TypeId::INT)
+            .column("NAME", TypeId::NVARCHAR)
+            .row(vec![
+                HdbValue::INT(1),
+                HdbValue::STRING("Alice".to_string()),
+            ])
+            .row(vec![HdbValue::INT(2), HdbValue::NULL]);
+        let metadata = builder.metadata();
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata[1].columnname(), "NAME");
+        assert_eq!(metadata[1].displayname(), "NAME");
+        assert_eq!(metadata[1].type_id(), TypeId::NVARCHAR);
+        assert!(metadata[1].is_nullable());
+
+        let mut rows = builder.into_rows().unwrap();
+        assert_eq!(rows.len(), 2);
+        let (id, name): (i32, Option<String>) = rows.remove(1).try_into().unwrap();
+        assert_eq!((id, name), (2, None));
+
+        assert!(ResultSetBuilder::new()
+            .column("ID", TypeId::INT)
+            .row(vec![HdbValue::INT(1), HdbValue::INT(2)])
+            .into_rows()
+            .is_err());
+    }
+}
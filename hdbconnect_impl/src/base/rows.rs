@@ -1,15 +1,19 @@
 use crate::{HdbResult, HdbValue, ResultSetMetadata, Row};
-use std::sync::Arc;
+use std::{collections::VecDeque, sync::Arc};
 
 /// Representation of a `ResultSet` that is fully loaded.
 ///
 /// Since serde is completely sync, we cannot use asynchronous fetching during deserialization,
 /// which makes it necessary to fetch all data before we call serde.
+///
+/// The rows can be inspected as often as needed, with [`Rows::get`], indexing, and
+/// [`Rows::iter`]; iterating over `Rows` itself consumes the rows, front to back,
+/// and the accessors only see the rows that were not yet consumed.
 #[derive(Debug)]
 pub struct Rows {
     pub(crate) metadata: Arc<ResultSetMetadata>,
     pub(crate) number_of_rows: usize,
-    pub(crate) row_iter: <Vec<Row> as IntoIterator>::IntoIter,
+    pub(crate) row_queue: VecDeque<Row>,
 }
 impl Rows {
     #[cfg(feature = "sync")]
@@ -38,7 +42,7 @@ impl Rows {
         Ok(Rows {
             metadata,
             number_of_rows,
-            row_iter: rows.into_iter(),
+            row_queue: rows.into(),
         })
     }
 
@@ -67,14 +71,98 @@ impl Rows {
         Ok(Rows {
             metadata,
             number_of_rows,
-            row_iter: rows.into_iter(),
+            row_queue: rows.into(),
         })
     }
 }
 
+impl Rows {
+    /// Returns the metadata of the rows.
+    #[must_use]
+    pub fn metadata(&self) -> &ResultSetMetadata {
+        &self.metadata
+    }
+
+    /// Returns the number of rows.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.row_queue.len()
+    }
+
+    /// Returns true if there are no rows.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.row_queue.is_empty()
+    }
+
+    /// Returns the row with the given index, or `None` if the index is out of bounds.
+    #[must_use]
+    pub fn get(&self, idx: usize) -> Option<&Row> {
+        self.row_queue.get(idx)
+    }
+
+    /// Returns an iterator over the rows, which leaves the rows in place.
+    #[must_use]
+    pub fn iter(&self) -> std::collections::vec_deque::Iter<'_, Row> {
+        self.row_queue.iter()
+    }
+}
+
+impl std::ops::Index<usize> for Rows {
+    type Output = Row;
+    fn index(&self, idx: usize) -> &Row {
+        &self.row_queue[idx]
+    }
+}
+
 impl Iterator for Rows {
     type Item = Row;
     fn next(&mut self) -> Option<Row> {
-        self.row_iter.next()
+        self.row_queue.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.row_queue.len(), Some(self.row_queue.len()))
+    }
+}
+
+impl ExactSizeIterator for Rows {}
+
+impl<'a> IntoIterator for &'a Rows {
+    type Item = &'a Row;
+    type IntoIter = std::collections::vec_deque::Iter<'a, Row>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.row_queue.iter()
+    }
+}
+
+#[cfg(all(test, feature = "sync"))]
+mod test {
+    use super::Rows;
+    use crate::{HdbValue, ResultSetBuilder, TypeId};
+
+    #[test]
+    fn test_rows_access() {
+        let (metadata, rows) = ResultSetBuilder::new()
+            .column("ID", TypeId::INT)
+            .row(vec![HdbValue::INT(1)])
+            .row(vec![HdbValue::INT(2)])
+            .row(vec![HdbValue::INT(3)])
+            .into_metadata_and_rows()
+            .unwrap();
+        let mut rows = Rows::new_sync(metadata, rows).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows.metadata().len(), 1);
+        assert!(matches!(rows[1][0], HdbValue::INT(2)));
+        assert!(rows.get(3).is_none());
+        for _ in 0..2 {
+            assert_eq!((&rows).into_iter().count(), 3);
+        }
+
+        assert!(matches!(rows.next().unwrap()[0], HdbValue::INT(1)));
+        assert_eq!(rows.len(), 2);
+        assert!(matches!(rows.get(0).unwrap()[0], HdbValue::INT(2)));
+        assert_eq!(rows.iter().len(), 2);
     }
 }
@@ -1,10 +1,16 @@
 use crate::{
     base::{PreparedStatementCore, XMutexed, OAM},
-    conn::{AmConnCore, CommandOptions},
-    protocol::{MessageType, Part, PartAttributes, PartKind, Request},
+    conn::AmConnCore,
+    protocol::PartAttributes,
 };
 use std::sync::Arc;
 
+#[cfg(feature = "sync")]
+use crate::{
+    conn::CommandOptions,
+    protocol::{MessageType, Part, PartKind, Request},
+};
+
 // Keeps the connection core and eventually a prepared statement core alive.
 // (Note: if either of these is dropped, then the respective server representation will be dropped,
 // which would break the owning result set if it is not yet fully fetched)
@@ -15,6 +21,7 @@ pub(crate) struct RsCore {
     // todo: move attributes into RsState to reduce locking
     attributes: PartAttributes,
     result_set_id: u64,
+    o_lob_read_length: Option<u32>,
 }
 
 impl RsCore {
@@ -28,6 +35,7 @@ impl RsCore {
             o_am_pscore: None,
             attributes,
             result_set_id,
+            o_lob_read_length: None,
         }
     }
 
@@ -46,6 +54,14 @@ impl RsCore {
     pub(super) fn attributes(&self) -> &PartAttributes {
         &self.attributes
     }
+    // Lets the LOBs of the result set use the given lob read length
+    // rather than the connection's one.
+    pub(super) fn set_lob_read_length(&mut self, lob_read_length: u32) {
+        self.o_lob_read_length = Some(lob_read_length);
+    }
+    pub(crate) fn lob_read_length(&self) -> Option<u32> {
+        self.o_lob_read_length
+    }
 }
 
 impl Drop for RsCore {
@@ -63,16 +79,8 @@ impl Drop for RsCore {
                 }
             }
             #[cfg(feature = "async")]
-            {
-                let mut request = Request::new(MessageType::CloseResultSet, CommandOptions::EMPTY);
-                request.push(Part::ResultSetId(rs_id));
-                let am_conn_core = self.am_conn_core.clone();
-                tokio::task::spawn(async move {
-                    if let Ok(mut reply) = am_conn_core.send_async(request).await {
-                        reply.parts.pop_if_kind(PartKind::StatementContext);
-                    }
-                });
-            }
+            self.am_conn_core
+                .schedule_cleanup(crate::conn::Cleanup::CloseResultSet(rs_id));
         }
     }
 }
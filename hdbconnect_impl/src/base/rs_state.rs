@@ -1,6 +1,6 @@
 use crate::{
     base::{PreparedStatementCore, RsCore, XMutexed, OAM},
-    conn::{AmConnCore, CommandOptions},
+    conn::{AmConnCore, CommandOptions, ConnectionConfiguration, QueryOptions},
     impl_err,
     protocol::{
         parts::{Parts, StatementContext},
@@ -23,6 +23,15 @@ pub(crate) struct RsState {
     row_iter: <Vec<Row> as IntoIterator>::IntoIter,
     server_usage: ServerUsage,
     o_am_rscore: OAM<RsCore>,
+    o_fetch_size: Option<u32>,
+    trim_char_padding: bool,
+    o_max_rows: Option<usize>,
+    received_rows: usize,
+    discarded_rows: bool,
+    // the approximate heap size of the rows in next_rows and row_iter
+    buffered_bytes: usize,
+    o_max_result_bytes: Option<usize>,
+    received_bytes: usize,
 }
 
 impl RsState {
@@ -38,6 +47,14 @@ impl RsState {
             row_iter: Vec::<Row>::new().into_iter(),
             server_usage: ServerUsage::default(),
             o_am_rscore: Some(new_am_sync(RsCore::new(am_conn_core, attrs, rs_id))),
+            o_fetch_size: None,
+            trim_char_padding: false,
+            o_max_rows: None,
+            received_rows: 0,
+            discarded_rows: false,
+            buffered_bytes: 0,
+            o_max_result_bytes: None,
+            received_bytes: 0,
         };
         if let Some(stmt_ctx) = o_stmt_ctx {
             new_instance.server_usage.update(
@@ -61,6 +78,14 @@ impl RsState {
             row_iter: Vec::<Row>::new().into_iter(),
             server_usage: ServerUsage::default(),
             o_am_rscore: Some(new_am_async(RsCore::new(am_conn_core, attrs, rs_id))),
+            o_fetch_size: None,
+            trim_char_padding: false,
+            o_max_rows: None,
+            received_rows: 0,
+            discarded_rows: false,
+            buffered_bytes: 0,
+            o_max_result_bytes: None,
+            received_bytes: 0,
         };
         if let Some(stmt_ctx) = o_stmt_ctx {
             new_instance.server_usage.update(
@@ -72,6 +97,105 @@ impl RsState {
         new_instance
     }
 
+    // Creates a state that contains all rows and is not bound to a connection.
+    pub(crate) fn new_detached(rows: Vec<Row>) -> Self {
+        let buffered_bytes = rows.iter().map(Row::heap_size).sum();
+        Self {
+            received_rows: rows.len(),
+            buffered_bytes,
+            o_max_result_bytes: None,
+            received_bytes: buffered_bytes,
+            next_rows: rows,
+            row_iter: Vec::<Row>::new().into_iter(),
+            server_usage: ServerUsage::default(),
+            o_am_rscore: None,
+            o_fetch_size: None,
+            trim_char_padding: false,
+            o_max_rows: None,
+            discarded_rows: false,
+        }
+    }
+
+    // Removes the blank padding of CHAR and NCHAR values from the buffered rows,
+    // and from all rows that are fetched later.
+    pub(crate) fn set_trim_char_padding(&mut self) {
+        self.trim_char_padding = true;
+        for row in self.row_iter.as_mut_slice() {
+            row.trim_char_padding();
+        }
+        for row in &mut self.next_rows {
+            row.trim_char_padding();
+        }
+        self.buffered_bytes = self.count_buffered_bytes();
+    }
+
+    // Limits the number of rows that are received; surplus rows are discarded,
+    // and no more rows are fetched once the limit is reached.
+    pub(crate) fn set_max_rows(&mut self, max_rows: usize) {
+        self.o_max_rows = Some(max_rows);
+        if self.received_rows > max_rows {
+            let surplus = self.received_rows - max_rows;
+            self.next_rows
+                .truncate(self.next_rows.len().saturating_sub(surplus));
+            self.received_rows = max_rows;
+            self.discarded_rows = true;
+            self.buffered_bytes = self.count_buffered_bytes();
+        }
+    }
+
+    // Limits the approximate number of bytes that are received;
+    // no more rows are fetched once the limit is reached.
+    pub(crate) fn set_max_result_bytes(&mut self, max_result_bytes: usize) {
+        self.o_max_result_bytes = Some(max_result_bytes);
+    }
+
+    fn is_limit_reached(&self) -> bool {
+        self.o_max_rows
+            .is_some_and(|max_rows| self.received_rows >= max_rows)
+            || self
+                .o_max_result_bytes
+                .is_some_and(|max_bytes| self.received_bytes >= max_bytes)
+    }
+
+    // Fetches not more than one row beyond the limit, which suffices to detect truncation.
+    fn capped_fetch_size(&self, fetch_size: u32) -> u32 {
+        match self.o_max_rows {
+            Some(max_rows) => {
+                let remaining = max_rows.saturating_sub(self.received_rows) + 1;
+                fetch_size.min(u32::try_from(remaining).unwrap_or(u32::MAX))
+            }
+            None => fetch_size,
+        }
+    }
+
+    pub(crate) fn received_rows(&self) -> usize {
+        self.received_rows
+    }
+
+    #[cfg(feature = "sync")]
+    pub(crate) fn is_truncated_sync(&self) -> HdbResult<bool> {
+        Ok(self.discarded_rows || (self.is_limit_reached() && !self.is_last_packet_sync()?))
+    }
+    #[cfg(feature = "async")]
+    pub(crate) async fn is_truncated_async(&self) -> bool {
+        self.discarded_rows || (self.is_limit_reached() && !self.is_last_packet_async().await)
+    }
+
+    #[cfg(feature = "sync")]
+    fn is_last_packet_sync(&self) -> HdbResult<bool> {
+        Ok(match self.o_am_rscore {
+            Some(ref am_rscore) => am_rscore.lock_sync()?.attributes().is_last_packet(),
+            None => true,
+        })
+    }
+    #[cfg(feature = "async")]
+    async fn is_last_packet_async(&self) -> bool {
+        match self.o_am_rscore {
+            Some(ref am_rscore) => am_rscore.lock_async().await.attributes().is_last_packet(),
+            None => true,
+        }
+    }
+
     #[cfg(feature = "sync")]
     fn rs_core_sync(&self) -> HdbResult<std::sync::MutexGuard<'_, RsCore>> {
         match self.o_am_rscore {
@@ -113,6 +237,65 @@ impl RsState {
         &self.server_usage
     }
 
+    // Lets later fetches use the given fetch size rather than the connection's one.
+    pub(crate) fn set_fetch_size(&mut self, fetch_size: u32) {
+        self.o_fetch_size = Some(fetch_size);
+    }
+
+    // Applies the settings of the connection's configuration, or of the given options,
+    // that concern result sets.
+    #[cfg(feature = "sync")]
+    pub(crate) fn apply_settings_sync(
+        &mut self,
+        config: &ConnectionConfiguration,
+        o_options: Option<&QueryOptions>,
+    ) -> HdbResult<()> {
+        self.apply_common_settings(config, o_options);
+        if let (Some(lob_read_length), Some(am_rscore)) = (
+            o_options.and_then(QueryOptions::lob_read_length),
+            &self.o_am_rscore,
+        ) {
+            am_rscore.lock_sync()?.set_lob_read_length(lob_read_length);
+        }
+        Ok(())
+    }
+    #[cfg(feature = "async")]
+    pub(crate) async fn apply_settings_async(
+        &mut self,
+        config: &ConnectionConfiguration,
+        o_options: Option<&QueryOptions>,
+    ) {
+        self.apply_common_settings(config, o_options);
+        if let (Some(lob_read_length), Some(am_rscore)) = (
+            o_options.and_then(QueryOptions::lob_read_length),
+            &self.o_am_rscore,
+        ) {
+            am_rscore
+                .lock_async()
+                .await
+                .set_lob_read_length(lob_read_length);
+        }
+    }
+    fn apply_common_settings(
+        &mut self,
+        config: &ConnectionConfiguration,
+        o_options: Option<&QueryOptions>,
+    ) {
+        let config = QueryOptions::applied_to(o_options, config);
+        if config.is_trim_char_padding() {
+            self.set_trim_char_padding();
+        }
+        if let Some(max_rows) = config.max_rows() {
+            self.set_max_rows(max_rows);
+        }
+        if let Some(max_result_bytes) = config.max_result_bytes() {
+            self.set_max_result_bytes(max_result_bytes);
+        }
+        if let Some(fetch_size) = o_options.and_then(QueryOptions::fetch_size) {
+            self.set_fetch_size(fetch_size);
+        }
+    }
+
     #[cfg(feature = "sync")]
     pub(crate) fn inject_ps_core_sync(
         &mut self,
@@ -169,11 +352,78 @@ impl RsState {
         Ok(())
     }
 
+    // Fetches until more than max_rows rows are buffered, or all rows are fetched,
+    // and returns the rendered table.
+    #[cfg(all(feature = "display", feature = "sync"))]
+    pub(crate) fn ascii_table_sync(
+        &mut self,
+        a_rsmd: &Arc<ResultSetMetadata>,
+        max_rows: usize,
+        max_col_width: usize,
+    ) -> HdbResult<String> {
+        while self.len() <= max_rows && !self.is_complete_sync()? {
+            self.fetch_next_sync(a_rsmd)?;
+        }
+        let more_rows = self.len() > max_rows || !self.is_complete_sync()?;
+        Ok(self.ascii_table(a_rsmd, max_rows, max_col_width, more_rows))
+    }
+    #[cfg(all(feature = "display", feature = "async"))]
+    pub(crate) async fn ascii_table_async(
+        &mut self,
+        a_rsmd: &Arc<ResultSetMetadata>,
+        max_rows: usize,
+        max_col_width: usize,
+    ) -> HdbResult<String> {
+        while self.len() <= max_rows && !self.is_complete_async().await? {
+            self.fetch_next_async(a_rsmd).await?;
+        }
+        let more_rows = self.len() > max_rows || !self.is_complete_async().await?;
+        Ok(self.ascii_table(a_rsmd, max_rows, max_col_width, more_rows))
+    }
+    #[cfg(feature = "display")]
+    fn ascii_table(
+        &self,
+        a_rsmd: &ResultSetMetadata,
+        max_rows: usize,
+        max_col_width: usize,
+        more_rows: bool,
+    ) -> String {
+        let rows = self
+            .row_iter
+            .as_slice()
+            .iter()
+            .chain(&self.next_rows)
+            .take(max_rows);
+        super::ascii_table::ascii_table(a_rsmd, rows, max_col_width, more_rows)
+    }
+
     #[allow(clippy::len_without_is_empty)]
     pub(crate) fn len(&self) -> usize {
         self.next_rows.len() + self.row_iter.len()
     }
 
+    pub(crate) fn buffered_bytes(&self) -> usize {
+        self.buffered_bytes
+    }
+
+    // Only needed when buffered rows are modified or removed in bulk.
+    fn count_buffered_bytes(&self) -> usize {
+        self.row_iter
+            .as_slice()
+            .iter()
+            .chain(self.next_rows.iter())
+            .map(Row::heap_size)
+            .sum()
+    }
+
+    // Accounts for the row that leaves the buffer.
+    fn hand_out(&mut self, o_row: Option<Row>) -> Option<Row> {
+        if let Some(ref row) = o_row {
+            self.buffered_bytes = self.buffered_bytes.saturating_sub(row.heap_size());
+        }
+        o_row
+    }
+
     #[cfg(feature = "sync")]
     pub(crate) fn total_number_of_rows_sync(
         &mut self,
@@ -197,7 +447,7 @@ impl RsState {
         a_rsmd: &Arc<ResultSetMetadata>,
     ) -> HdbResult<Option<Row>> {
         if let Some(r) = self.row_iter.next() {
-            Ok(Some(r))
+            Ok(self.hand_out(Some(r)))
         } else {
             if self.next_rows.is_empty() {
                 if self.is_complete_sync()? {
@@ -208,7 +458,8 @@ impl RsState {
             let mut tmp_vec = Vec::<Row>::new();
             std::mem::swap(&mut tmp_vec, &mut self.next_rows);
             self.row_iter = tmp_vec.into_iter();
-            Ok(self.row_iter.next())
+            let o_row = self.row_iter.next();
+            Ok(self.hand_out(o_row))
         }
     }
     #[cfg(feature = "async")]
@@ -217,7 +468,7 @@ impl RsState {
         a_rsmd: &Arc<ResultSetMetadata>,
     ) -> HdbResult<Option<Row>> {
         if let Some(r) = self.row_iter.next() {
-            Ok(Some(r))
+            Ok(self.hand_out(Some(r)))
         } else {
             if self.next_rows.is_empty() {
                 if self.is_complete_async().await? {
@@ -228,13 +479,14 @@ impl RsState {
             let mut tmp_vec = Vec::<Row>::new();
             std::mem::swap(&mut tmp_vec, &mut self.next_rows);
             self.row_iter = tmp_vec.into_iter();
-            Ok(self.row_iter.next())
+            let o_row = self.row_iter.next();
+            Ok(self.hand_out(o_row))
         }
     }
 
     pub(crate) fn next_row_no_fetch(&mut self) -> Option<Row> {
         if let Some(r) = self.row_iter.next() {
-            Some(r)
+            self.hand_out(Some(r))
         } else {
             if self.next_rows.is_empty() {
                 return None;
@@ -242,7 +494,8 @@ impl RsState {
             let mut tmp_vec = Vec::<Row>::new();
             std::mem::swap(&mut tmp_vec, &mut self.next_rows);
             self.row_iter = tmp_vec.into_iter();
-            self.row_iter.next()
+            let o_row = self.row_iter.next();
+            self.hand_out(o_row)
         }
     }
 
@@ -281,14 +534,18 @@ impl RsState {
     }
 
     #[cfg(feature = "sync")]
-    fn fetch_next_sync(&mut self, a_rsmd: &Arc<ResultSetMetadata>) -> HdbResult<()> {
+    pub(crate) fn fetch_next_sync(&mut self, a_rsmd: &Arc<ResultSetMetadata>) -> HdbResult<()> {
         trace!("ResultSet::fetch_next()");
         let (am_conn_core, result_set_id) = {
             let rs_core = self.rs_core_sync()?;
             let am_conn_core = rs_core.am_conn_core().clone();
             (am_conn_core, rs_core.result_set_id())
         };
-        let fetch_size = { am_conn_core.lock_sync()?.configuration().fetch_size() };
+        let fetch_size = match self.o_fetch_size {
+            Some(fetch_size) => fetch_size,
+            None => am_conn_core.lock_sync()?.configuration().fetch_size(),
+        };
+        let fetch_size = self.capped_fetch_size(fetch_size);
 
         // build the request, provide result set id and fetch-size
         debug!("ResultSet::fetch_next() with fetch_size = {}", fetch_size);
@@ -317,8 +574,15 @@ impl RsState {
             if let Some(ref am_rscore) = self.o_am_rscore {
                 let rs_core = am_rscore.lock_async().await;
                 let am_conn_core = rs_core.am_conn_core().clone();
-                let fetch_size = { am_conn_core.lock_async().await.configuration().fetch_size() };
-                (am_conn_core, rs_core.result_set_id(), fetch_size)
+                let fetch_size = match self.o_fetch_size {
+                    Some(fetch_size) => fetch_size,
+                    None => am_conn_core.lock_async().await.configuration().fetch_size(),
+                };
+                (
+                    am_conn_core,
+                    rs_core.result_set_id(),
+                    self.capped_fetch_size(fetch_size),
+                )
             } else {
                 return Err(impl_err!("Fetch no more possible"));
             }
@@ -348,7 +612,9 @@ impl RsState {
 
     #[cfg(feature = "sync")]
     pub(crate) fn is_complete_sync(&self) -> HdbResult<bool> {
-        if let Some(ref am_rscore) = self.o_am_rscore {
+        if self.is_limit_reached() {
+            Ok(true)
+        } else if let Some(ref am_rscore) = self.o_am_rscore {
             let rs_core = am_rscore.lock_sync()?;
             let attributes = rs_core.attributes();
             if (!attributes.is_last_packet())
@@ -366,7 +632,9 @@ impl RsState {
     }
     #[cfg(feature = "async")]
     pub async fn is_complete_async(&self) -> HdbResult<bool> {
-        if let Some(ref am_rscore) = self.o_am_rscore {
+        if self.is_limit_reached() {
+            Ok(true)
+        } else if let Some(ref am_rscore) = self.o_am_rscore {
             let rs_core = am_rscore.lock_async().await;
             if (!rs_core.attributes().is_last_packet())
                 && (rs_core.attributes().row_not_found()
@@ -546,8 +814,21 @@ impl RsState {
             let am_conn_core: &AmConnCore = rs_core.am_conn_core();
             let o_am_rscore = Some(am_rscore.clone());
             for i in 0..no_of_rows {
-                let row = Row::parse_sync(Arc::clone(metadata), &o_am_rscore, am_conn_core, rdr)?;
+                let mut row =
+                    Row::parse_sync(Arc::clone(metadata), &o_am_rscore, am_conn_core, rdr)?;
+                // rows beyond the limit are discarded
+                if self.o_max_rows.is_some_and(|max| self.received_rows >= max) {
+                    self.discarded_rows = true;
+                    continue;
+                }
+                self.received_rows += 1;
+                if self.trim_char_padding {
+                    row.trim_char_padding();
+                }
                 trace!("parse_rows(): Found row #{i}: {row:?}");
+                let heap_size = row.heap_size();
+                self.buffered_bytes += heap_size;
+                self.received_bytes += heap_size;
                 self.next_rows.push(row);
             }
         }
@@ -569,9 +850,21 @@ impl RsState {
             let am_conn_core: &AmConnCore = rs_core.am_conn_core();
             let o_am_rscore = Some(am_rscore.clone());
             for i in 0..no_of_rows {
-                let row =
+                let mut row =
                     Row::parse_async(Arc::clone(metadata), &o_am_rscore, am_conn_core, rdr).await?;
+                // rows beyond the limit are discarded
+                if self.o_max_rows.is_some_and(|max| self.received_rows >= max) {
+                    self.discarded_rows = true;
+                    continue;
+                }
+                self.received_rows += 1;
+                if self.trim_char_padding {
+                    row.trim_char_padding();
+                }
                 trace!("parse_rows(): Found row #{}: {}", i, row);
+                let heap_size = row.heap_size();
+                self.buffered_bytes += heap_size;
+                self.received_bytes += heap_size;
                 self.next_rows.push(row);
             }
         }
@@ -590,3 +883,232 @@ impl std::fmt::Display for RsState {
         Ok(())
     }
 }
+
+#[cfg(all(test, feature = "sync"))]
+mod test {
+    use super::RsState;
+    use crate::{ConnectionConfiguration, HdbValue, QueryOptions, ResultSetBuilder, TypeId};
+
+    #[test]
+    fn test_apply_settings() {
+        let mut builder = ResultSetBuilder::new().column("NAME", TypeId::CHAR);
+        for name in ["a  ", "b  ", "c  "] {
+            builder = builder.row(vec![HdbValue::STRING(name.to_string())]);
+        }
+        let mut config = ConnectionConfiguration::default();
+        config.set_max_rows(Some(1));
+
+        let (_, rows) = builder.clone().into_metadata_and_rows().unwrap();
+        let mut rs_state = RsState::new_detached(rows);
+        rs_state.apply_settings_sync(&config, None).unwrap();
+        assert_eq!(rs_state.len(), 1);
+        let name: String = rs_state.next_row_no_fetch().unwrap().try_into().unwrap();
+        assert_eq!(name, "a  ");
+
+        // the options win over the configuration, which is not modified
+        let options = QueryOptions::new()
+            .with_max_rows(2)
+            .with_trim_char_padding(true)
+            .with_lob_read_length(10);
+        let (_, rows) = builder.into_metadata_and_rows().unwrap();
+        let mut rs_state = RsState::new_detached(rows);
+        rs_state
+            .apply_settings_sync(&config, Some(&options))
+            .unwrap();
+        assert_eq!(rs_state.len(), 2);
+        let name: String = rs_state.next_row_no_fetch().unwrap().try_into().unwrap();
+        assert_eq!(name, "a");
+        assert_eq!(config.max_rows(), Some(1));
+        assert!(!config.is_trim_char_padding());
+    }
+
+    #[test]
+    fn test_set_max_rows() {
+        let mut builder = ResultSetBuilder::new().column("ID", TypeId::INT);
+        for id in 0..5 {
+            builder = builder.row(vec![HdbValue::INT(id)]);
+        }
+
+        // a limit above the number of rows changes nothing
+        let (_, rows) = builder.clone().into_metadata_and_rows().unwrap();
+        let mut rs_state = RsState::new_detached(rows);
+        rs_state.set_max_rows(5);
+        assert_eq!(rs_state.len(), 5);
+        assert_eq!(rs_state.received_rows(), 5);
+        assert!(!rs_state.is_truncated_sync().unwrap());
+
+        // surplus rows are discarded from the end
+        let (_, rows) = builder.into_metadata_and_rows().unwrap();
+        let mut rs_state = RsState::new_detached(rows);
+        rs_state.set_max_rows(3);
+        assert_eq!(rs_state.len(), 3);
+        assert_eq!(rs_state.received_rows(), 3);
+        assert!(rs_state.is_truncated_sync().unwrap());
+        let ids: Vec<i32> = (0..3)
+            .map(|_| rs_state.next_row_no_fetch().unwrap().try_into().unwrap())
+            .collect();
+        assert_eq!(ids, vec![0, 1, 2]);
+        assert!(rs_state.next_row_no_fetch().is_none());
+
+        // a lower limit that is set later truncates further
+        rs_state.set_max_rows(1);
+        assert_eq!(rs_state.received_rows(), 1);
+    }
+
+    #[test]
+    fn test_capped_fetch_size() {
+        let mut rs_state = RsState::new_detached(Vec::new());
+        assert_eq!(rs_state.capped_fetch_size(1000), 1000);
+
+        // one row beyond the limit is fetched, to detect the truncation
+        rs_state.set_max_rows(10);
+        assert_eq!(rs_state.capped_fetch_size(1000), 11);
+        assert_eq!(rs_state.capped_fetch_size(5), 5);
+        rs_state.received_rows = 10;
+        assert!(rs_state.is_limit_reached());
+        assert_eq!(rs_state.capped_fetch_size(1000), 1);
+    }
+
+    #[test]
+    fn test_buffered_bytes() {
+        let mut builder = ResultSetBuilder::new().column("NAME", TypeId::CHAR);
+        for name in ["a".repeat(100), "b".repeat(200), "c".repeat(300)] {
+            builder = builder.row(vec![HdbValue::STRING(name)]);
+        }
+        let (_, rows) = builder.into_metadata_and_rows().unwrap();
+        let mut rs_state = RsState::new_detached(rows);
+        assert_eq!(rs_state.buffered_bytes(), rs_state.count_buffered_bytes());
+        assert!(rs_state.buffered_bytes() >= 600);
+
+        // the limit applies to the bytes that were received, also if they are consumed
+        rs_state.set_max_result_bytes(rs_state.buffered_bytes());
+        assert!(rs_state.is_limit_reached());
+
+        rs_state.set_max_rows(2);
+        assert_eq!(rs_state.buffered_bytes(), rs_state.count_buffered_bytes());
+        rs_state.next_row_no_fetch().unwrap();
+        assert_eq!(rs_state.buffered_bytes(), rs_state.count_buffered_bytes());
+        assert!(rs_state.buffered_bytes() >= 200);
+        rs_state.next_row_no_fetch().unwrap();
+        assert_eq!(rs_state.buffered_bytes(), 0);
+        assert!(rs_state.is_limit_reached());
+    }
+
+    // The ResultSet part (kind 5) with the given ids as rows of a single INT column.
+    #[cfg(all(feature = "mock", feature = "test-utils"))]
+    fn rows_part(ids: &[i32], attributes: u8) -> crate::test_utils::PartBuilder {
+        let mut rows =
+            crate::test_utils::PartBuilder::new(5, ids.len()).with_attributes(attributes);
+        for id in ids {
+            rows = rows.push_u8(1).push_i32(*id);
+        }
+        rows
+    }
+
+    // A reply to a query for a single INT column, with the given ids as rows.
+    #[cfg(all(feature = "mock", feature = "test-utils"))]
+    fn select_reply(ids: &[i32], attributes: u8) -> crate::test_utils::ReplyBuilder {
+        use crate::test_utils::{PartBuilder, ReplyBuilder};
+        ReplyBuilder::new()
+            .with_reply_type(5)
+            .with_part(
+                // ResultSetMetadata: a mandatory INT column named "ID"
+                PartBuilder::new(48, 1)
+                    .push_u8(1)
+                    .push_u8(3)
+                    .push_i16(0)
+                    .push_i16(10)
+                    .push_i16(0)
+                    .push_i32(-1)
+                    .push_i32(-1)
+                    .push_i32(0)
+                    .push_i32(0)
+                    .push_u8(2)
+                    .push_bytes(b"ID"),
+            )
+            .with_part(PartBuilder::new(13, 1).push_u64(17))
+            .with_part(rows_part(ids, attributes))
+    }
+
+    // A reply to a fetch (function code 10).
+    #[cfg(all(feature = "mock", feature = "test-utils"))]
+    fn fetch_reply(ids: &[i32], attributes: u8) -> crate::test_utils::ReplyBuilder {
+        crate::test_utils::ReplyBuilder::new()
+            .with_reply_type(10)
+            .with_part(rows_part(ids, attributes))
+    }
+
+    // The rows beyond the limit are discarded while they are parsed, and no rows
+    // are fetched once the limit is reached.
+    #[cfg(all(feature = "mock", feature = "test-utils"))]
+    #[test]
+    fn test_truncation_while_parsing() {
+        use crate::test_utils::{replay::connection, ReplyBuilder};
+        const FIRST_PACKET: u8 = 0b_0000_0100;
+        const LAST_PACKET: u8 = 0b_0000_0001;
+        const NEXT_PACKET: u8 = 0b_0000_0010;
+        const CLOSED: u8 = 0b_0001_0000;
+
+        let connection = connection([
+            select_reply(&[1, 2, 3], FIRST_PACKET | NEXT_PACKET),
+            // the truncated result set is closed when it is dropped
+            ReplyBuilder::new(),
+            select_reply(&[1, 2], FIRST_PACKET | NEXT_PACKET),
+            fetch_reply(&[3, 4], LAST_PACKET | CLOSED),
+            select_reply(&[1, 2], FIRST_PACKET | LAST_PACKET | CLOSED),
+        ]);
+
+        // the first packet already exceeds the limit
+        let rs = connection
+            .query_with("select ID from T", QueryOptions::new().with_max_rows(2))
+            .unwrap();
+        assert_eq!(rs.received_rows().unwrap(), 2);
+        assert!(rs.is_truncated().unwrap());
+        let ids: Vec<i32> = rs.try_into().unwrap();
+        assert_eq!(ids, vec![1, 2]);
+
+        // the limit is reached with a fetch, which delivers a row too many
+        let rs = connection
+            .query_with("select ID from T", QueryOptions::new().with_max_rows(3))
+            .unwrap();
+        assert!(!rs.is_truncated().unwrap());
+        rs.fetch_all().unwrap();
+        assert_eq!(rs.received_rows().unwrap(), 3);
+        assert!(rs.is_truncated().unwrap());
+        let ids: Vec<i32> = rs.try_into().unwrap();
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        // the limit is not reached
+        let rs = connection
+            .query_with("select ID from T", QueryOptions::new().with_max_rows(2))
+            .unwrap();
+        assert_eq!(rs.received_rows().unwrap(), 2);
+        assert!(!rs.is_truncated().unwrap());
+    }
+
+    // No rows are fetched once the received rows exceed the byte limit.
+    #[cfg(all(feature = "mock", feature = "test-utils"))]
+    #[test]
+    fn test_truncation_by_bytes() {
+        use crate::test_utils::{replay::connection, ReplyBuilder};
+        const FIRST_PACKET: u8 = 0b_0000_0100;
+        const NEXT_PACKET: u8 = 0b_0000_0010;
+
+        let connection = connection([
+            select_reply(&[1, 2], FIRST_PACKET | NEXT_PACKET),
+            // the truncated result set is closed when it is dropped
+            ReplyBuilder::new(),
+        ]);
+
+        let rs = connection
+            .query_with(
+                "select ID from T",
+                QueryOptions::new().with_max_result_bytes(1),
+            )
+            .unwrap();
+        assert!(rs.buffered_bytes().unwrap() > 0);
+        assert!(rs.is_truncated().unwrap());
+        let ids: Vec<i32> = rs.try_into().unwrap();
+        assert_eq!(ids, vec![1, 2]);
+    }
+}
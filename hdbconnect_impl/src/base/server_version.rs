@@ -0,0 +1,117 @@
+use crate::{usage_err, HdbError};
+use std::str::FromStr;
+
+/// The version of the database server, parsed from its full version string
+/// (like "2.00.080.00.1733475234").
+///
+/// Versions can be compared, which allows using SQL features only
+/// with HANA versions that support them:
+///
+/// ```rust
+/// use hdbconnect::ServerVersion;
+/// let version: ServerVersion = "2.00.080.00.1733475234".parse().unwrap();
+/// assert!(version >= ServerVersion::new(2, 0, 59, 0));
+/// assert!(!version.is_cloud());
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct ServerVersion {
+    major: u32,
+    minor: u32,
+    revision: u32,
+    patch: u32,
+}
+
+impl ServerVersion {
+    // HANA Cloud reports versions starting with 4
+    const MIN_CLOUD_MAJOR: u32 = 4;
+
+    /// Creates a version from its components, e.g. for comparisons.
+    #[must_use]
+    pub const fn new(major: u32, minor: u32, revision: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            revision,
+            patch,
+        }
+    }
+
+    /// The major version, e.g. 2 for HANA 2.0.
+    #[must_use]
+    pub fn major(&self) -> u32 {
+        self.major
+    }
+
+    /// The minor version.
+    #[must_use]
+    pub fn minor(&self) -> u32 {
+        self.minor
+    }
+
+    /// The revision, e.g. 80 for HANA 2.0 SPS 08 revision 80.
+    #[must_use]
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    /// The patch level of the revision.
+    #[must_use]
+    pub fn patch(&self) -> u32 {
+        self.patch
+    }
+
+    /// Returns true if the server is an SAP HANA Cloud instance.
+    #[must_use]
+    pub fn is_cloud(&self) -> bool {
+        self.major >= Self::MIN_CLOUD_MAJOR
+    }
+}
+
+impl FromStr for ServerVersion {
+    type Err = HdbError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut numbers = s.trim().split('.').map(|part| {
+            part.parse::<u32>()
+                .map_err(|_| usage_err!("Cannot parse server version from \"{s}\""))
+        });
+        let mut next = || {
+            numbers
+                .next()
+                .unwrap_or_else(|| Err(usage_err!("Incomplete server version \"{s}\"")))
+        };
+        Ok(Self::new(next()?, next()?, next()?, next()?))
+    }
+}
+
+impl std::fmt::Display for ServerVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{:02}.{:03}.{:02}",
+            self.major, self.minor, self.revision, self.patch
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ServerVersion;
+
+    #[test]
+    fn test_parse() {
+        let version: ServerVersion = "2.00.080.00.1733475234".parse().unwrap();
+        assert_eq!(version, ServerVersion::new(2, 0, 80, 0));
+        assert_eq!(version.to_string(), "2.00.080.00");
+        assert!(!version.is_cloud());
+
+        let cloud: ServerVersion = "4.00.000.00.1710841718 (fa/CE2024.2)".parse().unwrap();
+        assert!(cloud.is_cloud());
+        assert!(cloud > version);
+
+        assert!(ServerVersion::new(2, 0, 59, 12) < ServerVersion::new(2, 0, 80, 0));
+        assert!(ServerVersion::new(2, 0, 80, 1) > ServerVersion::new(2, 0, 80, 0));
+
+        assert!("2.00".parse::<ServerVersion>().is_err());
+        assert!("foo".parse::<ServerVersion>().is_err());
+    }
+}
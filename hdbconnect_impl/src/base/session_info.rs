@@ -0,0 +1,92 @@
+/// Information about the own database session, as provided by `Connection::session_info()`.
+///
+/// Besides the ids that are known on the client side,
+/// it contains some data from the monitoring view `M_CONNECTIONS`.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SessionInfo {
+    #[serde(skip)]
+    connection_id: u32,
+    #[serde(skip)]
+    session_id: i64,
+    #[serde(rename = "LOGICAL_CONNECTION_ID")]
+    logical_connection_id: Option<i32>,
+    #[serde(rename = "USER_NAME")]
+    user_name: Option<String>,
+    #[serde(rename = "CURRENT_SCHEMA_NAME")]
+    current_schema_name: Option<String>,
+    #[serde(rename = "CONNECTION_STATUS")]
+    connection_status: Option<String>,
+    #[serde(rename = "MEMORY_SIZE_PER_CONNECTION")]
+    memory_size: Option<i64>,
+    #[serde(rename = "LAST_ACTION")]
+    last_action: Option<String>,
+    #[serde(rename = "CURRENT_STATEMENT_ID")]
+    current_statement_id: Option<String>,
+}
+
+impl SessionInfo {
+    pub(crate) const STATEMENT: &'static str = "SELECT LOGICAL_CONNECTION_ID, USER_NAME, \
+        CURRENT_SCHEMA_NAME, CONNECTION_STATUS, MEMORY_SIZE_PER_CONNECTION, LAST_ACTION, \
+        CURRENT_STATEMENT_ID FROM M_CONNECTIONS WHERE CONNECTION_ID = CURRENT_CONNECTION";
+
+    pub(crate) fn with_ids(mut self, connection_id: u32, session_id: i64) -> Self {
+        self.connection_id = connection_id;
+        self.session_id = session_id;
+        self
+    }
+
+    /// The id of the connection, as used e.g. in `ALTER SYSTEM CANCEL SESSION`.
+    #[must_use]
+    pub fn connection_id(&self) -> u32 {
+        self.connection_id
+    }
+
+    /// The session id that the server assigned to the connection on the protocol level.
+    #[must_use]
+    pub fn session_id(&self) -> i64 {
+        self.session_id
+    }
+
+    /// The logical connection id, which differs from the connection id
+    /// for secondary connections of a distributed system.
+    #[must_use]
+    pub fn logical_connection_id(&self) -> Option<i32> {
+        self.logical_connection_id
+    }
+
+    /// The database user of the session.
+    #[must_use]
+    pub fn user_name(&self) -> Option<&str> {
+        self.user_name.as_deref()
+    }
+
+    /// The current schema of the session.
+    #[must_use]
+    pub fn current_schema_name(&self) -> Option<&str> {
+        self.current_schema_name.as_deref()
+    }
+
+    /// The status of the connection, like `RUNNING` or `IDLE`.
+    #[must_use]
+    pub fn connection_status(&self) -> Option<&str> {
+        self.connection_status.as_deref()
+    }
+
+    /// The memory that is currently allocated by the session, in bytes.
+    #[must_use]
+    pub fn memory_size(&self) -> Option<i64> {
+        self.memory_size
+    }
+
+    /// The last action that was done by the session.
+    #[must_use]
+    pub fn last_action(&self) -> Option<&str> {
+        self.last_action.as_deref()
+    }
+
+    /// The id of the statement that the session executes currently or executed last.
+    #[must_use]
+    pub fn current_statement_id(&self) -> Option<&str> {
+        self.current_statement_id.as_deref()
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{
+    conn::ConnectionConfiguration,
+    protocol::{MessageType, Part, Request},
+};
+
+/// A sequence of statements, commits, and rollbacks, which is composed in the closure
+/// of `Connection::batch()` and executed with a single roundtrip.
+///
+/// Each step is sent as a segment of a single request message, and the server
+/// processes the segments in their order.
+/// A commit that directly follows a statement is sent together with the statement,
+/// by flagging the statement's segment for an immediate commit on the server.
+#[derive(Debug, Default)]
+pub struct StatementBatch {
+    steps: Vec<BatchStep>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum BatchStep {
+    Statement { stmt: String, commit: bool },
+    Commit,
+    Rollback,
+}
+
+impl StatementBatch {
+    /// Adds a statement to the batch.
+    pub fn statement<S: AsRef<str>>(&mut self, stmt: S) -> &mut Self {
+        self.steps.push(BatchStep::Statement {
+            stmt: stmt.as_ref().to_string(),
+            commit: false,
+        });
+        self
+    }
+
+    /// Adds a commit to the batch.
+    pub fn commit(&mut self) -> &mut Self {
+        match self.steps.last_mut() {
+            Some(BatchStep::Statement { commit, .. }) if !*commit => *commit = true,
+            _ => self.steps.push(BatchStep::Commit),
+        }
+        self
+    }
+
+    /// Adds a rollback to the batch.
+    pub fn rollback(&mut self) -> &mut Self {
+        self.steps.push(BatchStep::Rollback);
+        self
+    }
+
+    /// Returns the number of steps, which is the number of segments of the request.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns true if no step was added.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    pub(crate) fn steps(&self) -> &[BatchStep] {
+        &self.steps
+    }
+
+    // Composes a request with a segment for each step.
+    pub(crate) fn request(&self, config: &ConnectionConfiguration) -> Option<Request<'_>> {
+        let mut segments = self.steps.iter().map(|step| {
+            let mut request = Request::new(MessageType::ExecuteDirect, config.command_options());
+            request.push(Part::FetchSize(config.fetch_size()));
+            request.push(Part::Command(step.sql()));
+            if let BatchStep::Statement { commit: true, .. } = step {
+                request.set_commit();
+            }
+            request
+        });
+        let mut request = segments.next()?;
+        for segment in segments {
+            request.append_segment(segment);
+        }
+        Some(request)
+    }
+}
+
+impl BatchStep {
+    pub(crate) fn sql(&self) -> &str {
+        match self {
+            Self::Statement { stmt, .. } => stmt,
+            Self::Commit => "commit",
+            Self::Rollback => "rollback",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BatchStep, StatementBatch};
+    use crate::ConnectionConfiguration;
+
+    #[test]
+    fn test_commit_is_folded_into_statement() {
+        let mut batch = StatementBatch::default();
+        batch
+            .commit()
+            .statement("INSERT INTO T VALUES (1)")
+            .commit()
+            .commit()
+            .statement("DELETE FROM T")
+            .rollback();
+        assert_eq!(batch.len(), 5);
+        assert_eq!(
+            batch.steps(),
+            vec![
+                BatchStep::Commit,
+                BatchStep::Statement {
+                    stmt: "INSERT INTO T VALUES (1)".to_string(),
+                    commit: true
+                },
+                BatchStep::Commit,
+                BatchStep::Statement {
+                    stmt: "DELETE FROM T".to_string(),
+                    commit: false
+                },
+                BatchStep::Rollback,
+            ]
+        );
+        let request = batch.request(&ConnectionConfiguration::default()).unwrap();
+        assert_eq!(request.no_of_segments(), 5);
+
+        assert!(StatementBatch::default()
+            .request(&ConnectionConfiguration::default())
+            .is_none());
+    }
+}
@@ -0,0 +1,103 @@
+// An LRU cache of prepared statements, keyed by their SQL text.
+//
+// The entries belong to the session in which they were prepared.
+// The entries are ordered by their last use, the most recently used entry is at the end.
+// A linear search is sufficient, since lookups happen only when a statement is prepared,
+// which is cheap compared to the roundtrip it saves.
+#[derive(Debug)]
+pub(crate) struct StatementCache<T> {
+    entries: Vec<(String, T)>,
+    session_id: i64,
+}
+
+impl<T> Default for StatementCache<T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            session_id: 0,
+        }
+    }
+}
+
+impl<T> StatementCache<T> {
+    // Removes and returns all entries if they belong to another session than the given one,
+    // e.g. to the one before a reconnect, because their server-side statements are gone.
+    #[must_use]
+    pub(crate) fn invalidate_other_session(&mut self, session_id: i64) -> Vec<T> {
+        if self.session_id == session_id {
+            Vec::new()
+        } else {
+            self.session_id = session_id;
+            self.trim(0)
+        }
+    }
+
+    // Returns the entry for the given SQL, if any, and marks it as most recently used.
+    pub(crate) fn get(&mut self, sql: &str) -> Option<&T> {
+        let pos = self.entries.iter().position(|(s, _)| s == sql)?;
+        let entry = self.entries.remove(pos);
+        self.entries.push(entry);
+        self.entries.last().map(|(_, t)| t)
+    }
+
+    // Adds an entry as most recently used, replacing an entry for the same SQL,
+    // and returns the entries that have to leave the cache to stay within the capacity.
+    //
+    // The returned entries must be dropped only after the cache is released again,
+    // because dropping a prepared statement can lead to a roundtrip.
+    #[must_use]
+    pub(crate) fn insert(&mut self, sql: String, t: T, capacity: usize) -> Vec<T> {
+        let mut evicted = Vec::new();
+        if let Some(pos) = self.entries.iter().position(|(s, _)| *s == sql) {
+            evicted.push(self.entries.remove(pos).1);
+        }
+        self.entries.push((sql, t));
+        evicted.append(&mut self.trim(capacity));
+        evicted
+    }
+
+    // Removes the least recently used entries until at most `capacity` entries are left,
+    // and returns the removed entries.
+    #[must_use]
+    pub(crate) fn trim(&mut self, capacity: usize) -> Vec<T> {
+        let excess = self.entries.len().saturating_sub(capacity);
+        self.entries.drain(..excess).map(|(_, t)| t).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StatementCache;
+
+    #[test]
+    fn test_lru_eviction() {
+        let mut cache = StatementCache::<u32>::default();
+        assert!(cache.insert("A".to_string(), 1, 2).is_empty());
+        assert!(cache.insert("B".to_string(), 2, 2).is_empty());
+        assert_eq!(cache.get("A"), Some(&1));
+
+        // B is the least recently used entry now
+        assert_eq!(cache.insert("C".to_string(), 3, 2), vec![2]);
+        assert_eq!(cache.get("B"), None);
+
+        // replacing an entry returns the old one
+        assert_eq!(cache.insert("A".to_string(), 4, 2), vec![1]);
+        assert_eq!(cache.get("A"), Some(&4));
+
+        assert_eq!(cache.trim(0), vec![3, 4]);
+        assert_eq!(cache.get("C"), None);
+    }
+
+    #[test]
+    fn test_invalidate_other_session() {
+        let mut cache = StatementCache::<u32>::default();
+        assert!(cache.invalidate_other_session(100).is_empty());
+        assert!(cache.insert("A".to_string(), 1, 2).is_empty());
+        assert!(cache.invalidate_other_session(100).is_empty());
+        assert_eq!(cache.get("A"), Some(&1));
+
+        // after a reconnect, the session id differs
+        assert_eq!(cache.invalidate_other_session(200), vec![1]);
+        assert_eq!(cache.get("A"), None);
+    }
+}
@@ -2,32 +2,60 @@
 
 mod am_conn_core;
 mod authentication;
+mod batch_lock;
+#[cfg(feature = "async")]
+mod cleanup_queue;
 mod command_options;
 mod connection_configuration;
 mod connection_core;
 mod connection_statistics;
+mod disconnect_statistics;
 mod initial_request;
+mod lob_buffer_pool;
+#[cfg(feature = "otel")]
+pub(crate) mod otel;
 mod params;
+#[cfg(feature = "mock")]
+mod protocol_recording;
+mod query_options;
 mod session_state;
+mod statement_hook;
 mod tcp_client;
+mod watchdog;
 
 pub mod url;
 
+#[cfg(feature = "async")]
+pub(crate) use cleanup_queue::{Cleanup, CleanupQueue};
+#[cfg(feature = "mock")]
+pub use protocol_recording::{ProtocolRecording, RecordedRoundtrip};
 pub(crate) use {
-    am_conn_core::AmConnCore, command_options::CommandOptions, connection_core::ConnectionCore,
-    params::Compression, tcp_client::TcpClient,
+    am_conn_core::AmConnCore,
+    batch_lock::{BatchLock, BatchLockGuard},
+    command_options::CommandOptions,
+    connection_core::{set_schema_statement, ConnectionCore, CURRENT_IDENTITY_VALUE},
+    lob_buffer_pool::LobBufferPool,
+    params::Compression,
+    statement_hook::{check_statement, StatementHook},
+    tcp_client::TcpClient,
+    watchdog::{watch, WatchdogGuard, WatchdogHandler},
 };
 pub use {
     command_options::CursorHoldability,
     connection_configuration::ConnectionConfiguration,
     connection_statistics::ConnectionStatistics,
+    disconnect_statistics::DisconnectStatistics,
     params::{
+        client_info::ClientInfo,
         connect_params::{ConnectParams, ServerCerts},
         connect_params_builder::ConnectParamsBuilder,
         into_connect_params::IntoConnectParams,
         into_connect_params_builder::IntoConnectParamsBuilder,
+        ClientDistributionMode,
     },
+    query_options::QueryOptions,
+    watchdog::SlowRequest,
 };
 
 use authentication::AuthenticationResult;
-use session_state::SessionState;
+use session_state::{SessionState, TransactionState};
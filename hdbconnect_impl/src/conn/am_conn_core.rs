@@ -9,9 +9,31 @@ use crate::{
 };
 use std::{sync::Arc, time::Instant};
 
+#[cfg(feature = "async")]
+use crate::conn::{Cleanup, CleanupQueue};
+#[cfg(feature = "async")]
+use std::sync::Mutex;
+
+#[cfg(feature = "mock")]
+use crate::conn::ProtocolRecording;
+
 #[derive(Clone, Debug)]
-pub(crate) struct AmConnCore(AM<ConnectionCore>);
+pub(crate) struct AmConnCore {
+    am: AM<ConnectionCore>,
+    // close messages of dropped result sets and prepared statements (async connections only)
+    #[cfg(feature = "async")]
+    o_am_cleanup_queue: Option<Arc<Mutex<CleanupQueue>>>,
+}
 impl AmConnCore {
+    #[cfg(feature = "sync")]
+    fn new_sync(conn_core: ConnectionCore) -> Self {
+        Self {
+            am: crate::base::new_am_sync(conn_core),
+            #[cfg(feature = "async")]
+            o_am_cleanup_queue: None,
+        }
+    }
+
     #[cfg(feature = "sync")]
     pub fn try_new_sync(
         params: ConnectParams,
@@ -30,7 +52,13 @@ impl AmConnCore {
                 conn_core.connect_options().get_full_version_string()
             );
         }
-        Ok(Self(crate::base::new_am_sync(conn_core)))
+        Ok(Self::new_sync(conn_core))
+    }
+    // Creates a connection that serves the recorded replies, without a server.
+    #[cfg(feature = "mock")]
+    pub fn new_for_replay(recording: &ProtocolRecording) -> HdbResult<Self> {
+        let conn_core = ConnectionCore::new_for_replay(recording)?;
+        Ok(Self::new_sync(conn_core))
     }
     #[cfg(feature = "async")]
     pub async fn try_new_async(
@@ -49,16 +77,20 @@ impl AmConnCore {
             conn_core.connect_options().get_system_id(),
             conn_core.connect_options().get_full_version_string()
         );
-        Ok(Self(crate::base::new_am_async(conn_core)))
+        let cleanup_queue = CleanupQueue::new(conn_core.configuration().cleanup_queue_size());
+        Ok(Self {
+            am: crate::base::new_am_async(conn_core),
+            o_am_cleanup_queue: Some(Arc::new(Mutex::new(cleanup_queue))),
+        })
     }
 
     #[cfg(feature = "sync")]
     pub fn lock_sync(&self) -> std::sync::LockResult<std::sync::MutexGuard<ConnectionCore>> {
-        self.0.lock_sync()
+        self.am.lock_sync()
     }
     #[cfg(feature = "async")]
     pub async fn lock_async(&self) -> tokio::sync::MutexGuard<ConnectionCore> {
-        self.0.lock_async().await
+        self.am.lock_async().await
     }
 
     #[cfg(feature = "sync")]
@@ -134,6 +166,8 @@ impl AmConnCore {
         );
         let start = Instant::now();
         let mut conn_core = self.lock_async().await;
+        self.process_cleanups_async(&mut conn_core, request.message_type().executes_statement())
+            .await;
         conn_core.augment_request(&mut request);
 
         let reply = conn_core
@@ -175,6 +209,88 @@ impl AmConnCore {
     }
 }
 
+#[cfg(feature = "async")]
+impl AmConnCore {
+    // Used in Drop implementations: enqueues the cleanup and, if a tokio runtime is available,
+    // spawns a task that processes the queue; the task only holds a weak reference to the
+    // connection, so it does not keep the connection alive.
+    // Without a runtime, the queue is processed before the next request on the connection.
+    pub(crate) fn schedule_cleanup(&self, cleanup: Cleanup) {
+        let Some(ref am_cleanup_queue) = self.o_am_cleanup_queue else {
+            return;
+        };
+        let enqueued = match am_cleanup_queue.lock() {
+            Ok(mut cleanup_queue) => cleanup_queue.push(cleanup),
+            Err(_) => false,
+        };
+        if !enqueued {
+            warn!("cleanup queue is full, discarding {cleanup:?}");
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            debug!("no tokio runtime available, deferring {cleanup:?}");
+            return;
+        };
+        let w_conn_core = Arc::downgrade(&self.am);
+        let o_am_cleanup_queue = Some(Arc::clone(am_cleanup_queue));
+        runtime.spawn(async move {
+            if let Some(am) = w_conn_core.upgrade() {
+                let am_conn_core = Self {
+                    am,
+                    o_am_cleanup_queue,
+                };
+                let mut conn_core = am_conn_core.lock_async().await;
+                am_conn_core
+                    .process_cleanups_async(&mut conn_core, false)
+                    .await;
+            }
+        });
+    }
+
+    // Used for statements that need not be dropped immediately: enqueues the cleanup,
+    // which is processed with the next request on the connection.
+    pub(crate) fn defer_cleanup(&self, cleanup: Cleanup) {
+        if let Some(ref am_cleanup_queue) = self.o_am_cleanup_queue {
+            if !am_cleanup_queue
+                .lock()
+                .is_ok_and(|mut cleanup_queue| cleanup_queue.push(cleanup))
+            {
+                warn!("cleanup queue is full, discarding {cleanup:?}");
+            }
+        }
+    }
+
+    // Sends the pending close messages, ignores all errors.
+    // With `piggyback`, statements are dropped with the request that is sent next.
+    async fn process_cleanups_async(&self, conn_core: &mut ConnectionCore, piggyback: bool) {
+        let cleanups = match self.o_am_cleanup_queue.as_ref().map(|am| am.lock()) {
+            Some(Ok(mut cleanup_queue)) => cleanup_queue.take_all(),
+            Some(Err(_)) | None => return,
+        };
+        for cleanup in cleanups {
+            if !cleanup.belongs_to(conn_core.session_id()) {
+                trace!("skipping {cleanup:?} of an earlier session");
+                continue;
+            }
+            trace!("processing {cleanup:?}");
+            if let (Cleanup::DropStatement { statement_id, .. }, true) = (cleanup, piggyback) {
+                conn_core.defer_statement_drop(statement_id);
+                continue;
+            }
+            let mut request = cleanup.request();
+            conn_core.augment_request(&mut request);
+            if let Ok(mut reply) = conn_core
+                .roundtrip_async(&request, Some(self), None, None, &mut None)
+                .await
+            {
+                reply
+                    .parts
+                    .pop_if_kind(crate::protocol::PartKind::StatementContext);
+            }
+        }
+    }
+}
+
 fn can_be_timeout(kind: std::io::ErrorKind) -> bool {
     matches!(
         kind,
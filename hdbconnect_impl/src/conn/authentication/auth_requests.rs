@@ -145,9 +145,7 @@ fn evaluate_second_response(
         match part {
             Part::TopologyInformation(topology) => conn_core.set_topology(topology),
             Part::ConnectOptions(received_co) => {
-                conn_core
-                    .connect_options_mut()
-                    .digest_server_connect_options(received_co)?;
+                conn_core.digest_server_connect_options(received_co)?;
             }
             Part::Auth(mut af) => match (af.pop(), af.pop(), af.pop()) {
                 (Some(server_proof), Some(method), None) => {
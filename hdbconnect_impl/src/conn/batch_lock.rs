@@ -0,0 +1,158 @@
+use crate::{usage_err, HdbResult};
+use std::sync::{Mutex, PoisonError};
+
+// Serializes sequences of calls among the clones of a connection,
+// see Connection::lock_for_batch().
+//
+// The lock is advisory: calls that don't use it are not blocked.
+// Locking it again from the thread or task that holds it would deadlock, and is rejected.
+#[derive(Debug, Default)]
+pub(crate) struct BatchLock {
+    #[cfg(feature = "sync")]
+    sync_mutex: Mutex<()>,
+    #[cfg(feature = "async")]
+    async_mutex: tokio::sync::Mutex<()>,
+    o_owner: Mutex<Option<Owner>>,
+}
+
+// Identifies who holds the lock: the tokio task, or, outside of tasks, the thread.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Owner {
+    #[cfg(feature = "async")]
+    Task(tokio::task::Id),
+    Thread(std::thread::ThreadId),
+}
+impl Owner {
+    fn current() -> Self {
+        #[cfg(feature = "async")]
+        if let Some(task_id) = tokio::task::try_id() {
+            return Self::Task(task_id);
+        }
+        Self::Thread(std::thread::current().id())
+    }
+}
+
+// Releases the lock when it is dropped.
+#[derive(Debug)]
+pub(crate) struct BatchLockGuard<'a, G> {
+    batch_lock: &'a BatchLock,
+    _guard: G,
+}
+impl<G> Drop for BatchLockGuard<'_, G> {
+    // runs before the mutex guard is dropped
+    fn drop(&mut self) {
+        *self.batch_lock.owner() = None;
+    }
+}
+
+impl BatchLock {
+    fn owner(&self) -> std::sync::MutexGuard<'_, Option<Owner>> {
+        self.o_owner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn check_reentry(&self) -> HdbResult<Owner> {
+        let current = Owner::current();
+        if *self.owner() == Some(current) {
+            Err(usage_err!(
+                "lock_for_batch() was called while the lock is already held by the caller"
+            ))
+        } else {
+            Ok(current)
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    pub(crate) fn lock_sync(&self) -> HdbResult<BatchLockGuard<'_, std::sync::MutexGuard<'_, ()>>> {
+        let current = self.check_reentry()?;
+        // a panic while the lock is held does not make the lock unusable
+        let guard = self
+            .sync_mutex
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *self.owner() = Some(current);
+        Ok(BatchLockGuard {
+            batch_lock: self,
+            _guard: guard,
+        })
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) async fn lock_async(
+        &self,
+    ) -> HdbResult<BatchLockGuard<'_, tokio::sync::MutexGuard<'_, ()>>> {
+        let current = self.check_reentry()?;
+        let guard = self.async_mutex.lock().await;
+        *self.owner() = Some(current);
+        Ok(BatchLockGuard {
+            batch_lock: self,
+            _guard: guard,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BatchLock;
+    use crate::HdbError;
+    use std::{sync::Arc, time::Duration};
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_lock_sync() {
+        use std::sync::mpsc;
+
+        let batch_lock = Arc::new(BatchLock::default());
+
+        let guard = batch_lock.lock_sync().unwrap();
+        // re-entry would deadlock
+        assert!(matches!(batch_lock.lock_sync(), Err(HdbError::Usage(_))));
+
+        // other threads wait until the lock is released
+        let (sender, receiver) = mpsc::channel();
+        let batch_lock2 = Arc::clone(&batch_lock);
+        let handle = std::thread::spawn(move || {
+            let _guard = batch_lock2.lock_sync().unwrap();
+            sender.send(()).unwrap();
+        });
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        drop(guard);
+        receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        handle.join().unwrap();
+
+        drop(batch_lock.lock_sync().unwrap());
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_lock_async() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let batch_lock = Arc::new(BatchLock::default());
+
+            let guard = batch_lock.lock_async().await.unwrap();
+            // re-entry would deadlock
+            assert!(matches!(
+                batch_lock.lock_async().await,
+                Err(HdbError::Usage(_))
+            ));
+
+            // other tasks wait until the lock is released
+            let batch_lock2 = Arc::clone(&batch_lock);
+            let task = tokio::spawn(async move {
+                let guard = batch_lock2.lock_async().await.unwrap();
+                // re-entry is also detected within a task
+                assert!(batch_lock2.lock_async().await.is_err());
+                drop(guard);
+            });
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert!(!task.is_finished());
+            drop(guard);
+            task.await.unwrap();
+
+            drop(batch_lock.lock_async().await.unwrap());
+        });
+    }
+}
@@ -0,0 +1,111 @@
+use crate::{
+    conn::CommandOptions,
+    protocol::{MessageType, Part, Request},
+};
+use std::collections::VecDeque;
+
+// A server-side resource that is to be freed after its client-side owner was dropped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Cleanup {
+    CloseResultSet(u64),
+    // A statement id is only valid in the session in which the statement was prepared.
+    DropStatement { statement_id: u64, session_id: i64 },
+}
+impl Cleanup {
+    // Returns false if the resource ended with an earlier session, e.g. before a reconnect.
+    pub(crate) fn belongs_to(self, session_id: i64) -> bool {
+        match self {
+            Self::CloseResultSet(_) => true,
+            Self::DropStatement {
+                session_id: statement_session_id,
+                ..
+            } => statement_session_id == session_id,
+        }
+    }
+
+    pub(crate) fn request(self) -> Request<'static> {
+        match self {
+            Self::CloseResultSet(rs_id) => {
+                let mut request = Request::new(MessageType::CloseResultSet, CommandOptions::EMPTY);
+                request.push(Part::ResultSetId(rs_id));
+                request
+            }
+            Self::DropStatement { statement_id, .. } => {
+                let mut request = Request::new(MessageType::DropStatementId, CommandOptions::EMPTY);
+                request.push(Part::StatementId(statement_id));
+                request
+            }
+        }
+    }
+}
+
+// The close messages of an async connection that are not yet sent.
+//
+// Drop implementations cannot await the connection, so they only enqueue their close message;
+// the queue is processed by a task on the tokio runtime, or before the next request.
+// The queue is bounded; if it is full, further close messages are discarded,
+// and the server frees the respective resources when the session ends.
+#[derive(Debug)]
+pub(crate) struct CleanupQueue {
+    pending: VecDeque<Cleanup>,
+    capacity: usize,
+}
+impl CleanupQueue {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            pending: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    // Returns false if the queue is full and the cleanup was discarded.
+    pub(crate) fn push(&mut self, cleanup: Cleanup) -> bool {
+        if self.pending.len() < self.capacity {
+            self.pending.push_back(cleanup);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn take_all(&mut self) -> Vec<Cleanup> {
+        self.pending.drain(..).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Cleanup, CleanupQueue};
+    use crate::protocol::MessageType;
+
+    #[test]
+    fn test_cleanup_queue() {
+        let drop_statement = Cleanup::DropStatement {
+            statement_id: 4711,
+            session_id: 300_123,
+        };
+        let mut queue = CleanupQueue::new(2);
+        assert!(queue.push(Cleanup::CloseResultSet(17)));
+        assert!(queue.push(drop_statement));
+        assert!(!queue.push(Cleanup::CloseResultSet(18)));
+
+        let cleanups = queue.take_all();
+        assert_eq!(cleanups, vec![Cleanup::CloseResultSet(17), drop_statement]);
+        assert!(queue.take_all().is_empty());
+        assert!(queue.push(Cleanup::CloseResultSet(18)));
+
+        assert!(matches!(
+            cleanups[0].request().message_type(),
+            MessageType::CloseResultSet
+        ));
+        assert!(matches!(
+            cleanups[1].request().message_type(),
+            MessageType::DropStatementId
+        ));
+
+        // after a reconnect, the statement is not dropped in the new session
+        assert!(drop_statement.belongs_to(300_123));
+        assert!(!drop_statement.belongs_to(300_124));
+        assert!(Cleanup::CloseResultSet(17).belongs_to(300_124));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::{usage_err, HdbError};
 use bitflags::bitflags;
 
 bitflags! {
@@ -30,7 +31,8 @@ impl CommandOptions {
     }
 }
 
-#[derive(Debug)]
+// docu is written at re-exports of frontend crates (hdbconnect/lib.rs, hdbconnect_async/lib.rs)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum CursorHoldability {
     /// Cursors are dropped with commit or rollback.
     None,
@@ -41,6 +43,30 @@ pub enum CursorHoldability {
     /// Cursors are kept over commits and rollbacks.
     CommitAndRollback,
 }
+impl CursorHoldability {
+    /// Returns `true` if cursors are kept over a commit.
+    #[must_use]
+    pub fn holds_over_commit(self) -> bool {
+        CommandOptions::from(self).is_hold_cursors_over_commit()
+    }
+    /// Returns `true` if cursors are kept over a rollback.
+    #[must_use]
+    pub fn holds_over_rollback(self) -> bool {
+        CommandOptions::from(self).is_hold_cursors_over_rollback()
+    }
+}
+impl std::str::FromStr for CursorHoldability {
+    type Err = HdbError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(Self::None),
+            "commit" => Ok(Self::Commit),
+            "rollback" => Ok(Self::Rollback),
+            "commit_and_rollback" => Ok(Self::CommitAndRollback),
+            _ => Err(usage_err!("invalid cursor holdability: {s}")),
+        }
+    }
+}
 impl From<CommandOptions> for CursorHoldability {
     fn from(value: CommandOptions) -> Self {
         if value.is_hold_cursors_over_commit() {
@@ -92,6 +118,14 @@ mod test {
             CommandOptions::HOLD_CURSORS_OVER_COMMIT | CommandOptions::HOLD_CURSORS_OVER_ROLLBACK
         );
 
+        assert!(CursorHoldability::Rollback.holds_over_rollback());
+        assert!(!CursorHoldability::Rollback.holds_over_commit());
+        assert_eq!(
+            "commit_and_rollback".parse::<CursorHoldability>().unwrap(),
+            CursorHoldability::CommitAndRollback
+        );
+        assert!("always".parse::<CursorHoldability>().is_err());
+
         assert_eq!(co3.as_u8(), 0b0100_1000);
         assert_eq!(co3.as_u8(), 72);
         let s: String = serde_json::to_string(&co3).unwrap();
@@ -1,8 +1,10 @@
 use super::command_options::{CommandOptions, CursorHoldability};
+use crate::IdentifierCase;
 use std::time::Duration;
 
 // docu is written at re-exports of frontend crates (hdbconnect/lib.rs, hdbconnect_async/lib.rs)
 #[derive(Debug, Clone, Deserialize)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ConnectionConfiguration {
     auto_commit: bool,
     command_options: CommandOptions,
@@ -10,8 +12,40 @@ pub struct ConnectionConfiguration {
     lob_read_length: u32,
     lob_write_length: u32,
     max_buffer_size: usize,
+    #[serde(default = "ConnectionConfiguration::default_max_reply_size")]
+    max_reply_size: usize,
+    #[serde(default = "ConnectionConfiguration::default_max_request_size")]
+    max_request_size: usize,
     min_compression_size: usize,
     read_timeout: Option<Duration>,
+    #[serde(default = "ConnectionConfiguration::default_tls_handshake_timeout")]
+    tls_handshake_timeout: Option<Duration>,
+    #[serde(default)]
+    sql_in_errors: bool,
+    #[serde(default)]
+    sql_in_traces: bool,
+    #[serde(default)]
+    trim_char_padding: bool,
+    #[serde(default)]
+    max_rows: Option<usize>,
+    #[serde(default)]
+    max_result_bytes: Option<usize>,
+    #[serde(default)]
+    statement_memory_limit: Option<u32>,
+    #[serde(default)]
+    statement_thread_limit: Option<u32>,
+    #[serde(default)]
+    statement_cache_size: usize,
+    #[serde(default)]
+    identifier_case: IdentifierCase,
+    #[serde(default = "ConnectionConfiguration::default_cleanup_queue_size")]
+    cleanup_queue_size: usize,
+    #[serde(default)]
+    watchdog_threshold: Option<Duration>,
+    #[serde(default)]
+    sql_in_watchdog: bool,
+    #[serde(default)]
+    statement_timeout: Option<Duration>,
 }
 
 impl Default for ConnectionConfiguration {
@@ -25,8 +59,24 @@ impl Default for ConnectionConfiguration {
             lob_read_length: Self::DEFAULT_LOB_READ_LENGTH,
             lob_write_length: Self::DEFAULT_LOB_WRITE_LENGTH,
             max_buffer_size: Self::DEFAULT_MAX_BUFFER_SIZE,
+            max_reply_size: Self::DEFAULT_MAX_REPLY_SIZE,
+            max_request_size: Self::DEFAULT_MAX_REQUEST_SIZE,
             min_compression_size: Self::DEFAULT_MIN_COMPRESSION_SIZE,
             read_timeout: Self::DEFAULT_READ_TIMEOUT,
+            tls_handshake_timeout: Self::DEFAULT_TLS_HANDSHAKE_TIMEOUT,
+            sql_in_errors: false,
+            sql_in_traces: false,
+            trim_char_padding: false,
+            max_rows: None,
+            max_result_bytes: None,
+            statement_memory_limit: None,
+            statement_thread_limit: None,
+            statement_cache_size: Self::DEFAULT_STATEMENT_CACHE_SIZE,
+            identifier_case: IdentifierCase::default(),
+            cleanup_queue_size: Self::DEFAULT_CLEANUP_QUEUE_SIZE,
+            watchdog_threshold: None,
+            sql_in_watchdog: false,
+            statement_timeout: None,
         }
     }
 }
@@ -66,6 +116,39 @@ impl ConnectionConfiguration {
     /// The value can be changed at runtime with `Connection::set_max_buffer_size()`.
     pub const DEFAULT_MAX_BUFFER_SIZE: usize = 20 * Self::MIN_BUFFER_SIZE;
 
+    /// Default value for the maximum size of a reply from the server (1 GiB).
+    ///
+    /// Replies that claim to be larger, e.g. because they were corrupted on the way,
+    /// are rejected with an error before any memory is allocated for them.
+    ///
+    /// The value can be changed at runtime with `Connection::set_max_reply_size()`.
+    pub const DEFAULT_MAX_REPLY_SIZE: usize = 1 << 30;
+
+    /// Default value for the maximum size of a request to the server (1 GiB).
+    ///
+    /// The server does not announce its limit for the size of requests in the connect options;
+    /// requests that exceed it make the server drop the connection.
+    /// Larger requests, typically batches with many or large rows, are thus rejected
+    /// with `HdbError::RequestTooLarge` before they are sent.
+    ///
+    /// The value can be changed at runtime with `Connection::set_max_request_size()`.
+    pub const DEFAULT_MAX_REQUEST_SIZE: usize = 1 << 30;
+
+    /// Default value for the number of prepared statements that `Connection::prepare()`
+    /// keeps for reuse (0, i.e., no statements are cached).
+    ///
+    /// With a positive value, preparing an SQL string that was prepared before on the same
+    /// connection reuses the server-side statement and saves the roundtrip.
+    /// The cache is shared by all clones of a connection, as they share its session.
+    /// When the cache is full, the least recently used statement is evicted;
+    /// once no `PreparedStatement` uses it anymore, it is dropped on the server together
+    /// with the next request, without a roundtrip of its own.
+    /// The cache is cleared when the connection reconnects or changes its current schema.
+    /// The cache activity is counted in the [`ConnectionStatistics`](crate::ConnectionStatistics).
+    ///
+    /// The value can be changed at runtime with `Connection::set_statement_cache_size()`.
+    pub const DEFAULT_STATEMENT_CACHE_SIZE: usize = 0;
+
     /// Default value for the threshold size above which requests will be compressed.
     pub const DEFAULT_MIN_COMPRESSION_SIZE: usize = 5 * 1024;
 
@@ -78,6 +161,44 @@ impl ConnectionConfiguration {
     /// and a new connection will be needed to continue working.
     pub const DEFAULT_READ_TIMEOUT: Option<std::time::Duration> = None;
 
+    /// Default value for the time within which the TLS handshake must complete (30 seconds).
+    ///
+    /// The handshake is done after the TCP connection to a server was established,
+    /// if TLS is used. If it stalls, e.g. due to interference of a middlebox,
+    /// establishing the connection fails with `HdbError::TlsHandshakeTimeout`.
+    /// The timeout is independent of the read timeout and does not include
+    /// the establishment of the TCP connection.
+    pub const DEFAULT_TLS_HANDSHAKE_TIMEOUT: Option<std::time::Duration> =
+        Some(Duration::from_secs(30));
+
+    /// Default value for the number of close messages an async connection can queue up (100).
+    ///
+    /// Dropping a `ResultSet` or a `PreparedStatement` of an async connection
+    /// cannot wait for the server; the respective close message is thus put into
+    /// the connection's cleanup queue, which is processed by a task on the current
+    /// tokio runtime, or, if no runtime is available, before the next request on the connection.
+    /// If the queue is full, further close messages are discarded with a warning;
+    /// the server then frees the respective resources when the session ends.
+    ///
+    /// Synchronous connections send their close messages directly and do not use the queue.
+    pub const DEFAULT_CLEANUP_QUEUE_SIZE: usize = 100;
+
+    fn default_max_reply_size() -> usize {
+        Self::DEFAULT_MAX_REPLY_SIZE
+    }
+
+    fn default_max_request_size() -> usize {
+        Self::DEFAULT_MAX_REQUEST_SIZE
+    }
+
+    fn default_tls_handshake_timeout() -> Option<Duration> {
+        Self::DEFAULT_TLS_HANDSHAKE_TIMEOUT
+    }
+
+    fn default_cleanup_queue_size() -> usize {
+        Self::DEFAULT_CLEANUP_QUEUE_SIZE
+    }
+
     /// Returns whether the connection uses auto-commit.
     #[must_use]
     pub fn is_auto_commit(&self) -> bool {
@@ -186,6 +307,72 @@ impl ConnectionConfiguration {
         self
     }
 
+    /// Returns the connection's max reply size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_MAX_REPLY_SIZE`].
+    #[must_use]
+    pub fn max_reply_size(&self) -> usize {
+        self.max_reply_size
+    }
+    /// Sets the connection's max reply size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_MAX_REPLY_SIZE`].
+    pub fn set_max_reply_size(&mut self, max_reply_size: usize) {
+        self.max_reply_size = max_reply_size;
+    }
+    /// Builder-method for setting the connection's max reply size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_MAX_REPLY_SIZE`].
+    #[must_use]
+    pub fn with_max_reply_size(mut self, max_reply_size: usize) -> Self {
+        self.max_reply_size = max_reply_size;
+        self
+    }
+
+    /// Returns the connection's max request size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_MAX_REQUEST_SIZE`].
+    #[must_use]
+    pub fn max_request_size(&self) -> usize {
+        self.max_request_size
+    }
+    /// Sets the connection's max request size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_MAX_REQUEST_SIZE`].
+    pub fn set_max_request_size(&mut self, max_request_size: usize) {
+        self.max_request_size = max_request_size;
+    }
+    /// Builder-method for setting the connection's max request size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_MAX_REQUEST_SIZE`].
+    #[must_use]
+    pub fn with_max_request_size(mut self, max_request_size: usize) -> Self {
+        self.max_request_size = max_request_size;
+        self
+    }
+
+    /// Returns the connection's statement cache size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_STATEMENT_CACHE_SIZE`].
+    #[must_use]
+    pub fn statement_cache_size(&self) -> usize {
+        self.statement_cache_size
+    }
+    /// Sets the connection's statement cache size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_STATEMENT_CACHE_SIZE`].
+    pub fn set_statement_cache_size(&mut self, statement_cache_size: usize) {
+        self.statement_cache_size = statement_cache_size;
+    }
+    /// Builder-method for setting the connection's statement cache size.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_STATEMENT_CACHE_SIZE`].
+    #[must_use]
+    pub fn with_statement_cache_size(mut self, statement_cache_size: usize) -> Self {
+        self.statement_cache_size = statement_cache_size;
+        self
+    }
+
     /// Returns the connection's min compression size.
     ///
     /// See [`ConnectionConfiguration::DEFAULT_MIN_COMPRESSION_SIZE`].
@@ -225,4 +412,314 @@ impl ConnectionConfiguration {
         self.read_timeout = read_timeout;
         self
     }
+
+    /// Returns the time within which the TLS handshake must complete.
+    #[must_use]
+    pub fn tls_handshake_timeout(&self) -> Option<Duration> {
+        self.tls_handshake_timeout
+    }
+    /// Sets the time within which the TLS handshake must complete;
+    /// with `None`, the handshake is not limited.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_TLS_HANDSHAKE_TIMEOUT`].
+    pub fn set_tls_handshake_timeout(&mut self, tls_handshake_timeout: Option<Duration>) {
+        self.tls_handshake_timeout = tls_handshake_timeout;
+    }
+    /// Builder-method for setting the time within which the TLS handshake must complete.
+    #[must_use]
+    pub fn with_tls_handshake_timeout(mut self, tls_handshake_timeout: Option<Duration>) -> Self {
+        self.tls_handshake_timeout = tls_handshake_timeout;
+        self
+    }
+
+    /// Returns whether errors from statement execution include the statement text.
+    #[must_use]
+    pub fn is_sql_in_errors(&self) -> bool {
+        self.sql_in_errors
+    }
+    /// Defines whether errors from statement execution should include the statement text.
+    ///
+    /// If switched on, errors that occur during the execution of a statement are wrapped into
+    /// [`HdbError::WithStatement`](crate::HdbError::WithStatement), which shows
+    /// the statement text and, for prepared statements, a summary of the parameters.
+    /// Parameter values are never shown, only their number and types.
+    ///
+    /// Is off by default, since statement texts might contain sensitive data.
+    pub fn set_sql_in_errors(&mut self, sql_in_errors: bool) {
+        self.sql_in_errors = sql_in_errors;
+    }
+    /// Builder-method for defining whether errors from statement execution should include
+    /// the statement text.
+    ///
+    /// See [`ConnectionConfiguration::set_sql_in_errors`].
+    #[must_use]
+    pub fn with_sql_in_errors(mut self, sql_in_errors: bool) -> Self {
+        self.sql_in_errors = sql_in_errors;
+        self
+    }
+
+    /// Returns whether the tracing spans of statement executions include the statement text.
+    #[must_use]
+    pub fn is_sql_in_traces(&self) -> bool {
+        self.sql_in_traces
+    }
+    /// Defines whether the tracing spans of statement executions should include
+    /// the statement text, as field `db.statement`.
+    ///
+    /// Is only relevant with the feature `otel`.
+    /// Is off by default, since statement texts might contain sensitive data.
+    pub fn set_sql_in_traces(&mut self, sql_in_traces: bool) {
+        self.sql_in_traces = sql_in_traces;
+    }
+    /// Builder-method for defining whether the tracing spans of statement executions
+    /// should include the statement text.
+    ///
+    /// See [`ConnectionConfiguration::set_sql_in_traces`].
+    #[must_use]
+    pub fn with_sql_in_traces(mut self, sql_in_traces: bool) -> Self {
+        self.sql_in_traces = sql_in_traces;
+        self
+    }
+
+    /// Returns whether the blank padding of CHAR and NCHAR values is removed.
+    #[must_use]
+    pub fn is_trim_char_padding(&self) -> bool {
+        self.trim_char_padding
+    }
+    /// Defines whether the blank padding of CHAR and NCHAR values is removed.
+    ///
+    /// HANA pads the values of fixed-length CHAR and NCHAR columns with blanks
+    /// up to the column length. If switched on, trailing blanks are removed from these values
+    /// when they are read from the server, so they need not be trimmed after deserialization.
+    /// Values of other string types are not affected.
+    ///
+    /// Is off by default.
+    pub fn set_trim_char_padding(&mut self, trim: bool) {
+        self.trim_char_padding = trim;
+    }
+    /// Builder-method for defining whether the blank padding of CHAR and NCHAR values
+    /// is removed.
+    ///
+    /// See [`ConnectionConfiguration::set_trim_char_padding`].
+    #[must_use]
+    pub fn with_trim_char_padding(mut self, trim: bool) -> Self {
+        self.trim_char_padding = trim;
+        self
+    }
+
+    /// Returns the maximum number of rows that are read from a result set.
+    #[must_use]
+    pub fn max_rows(&self) -> Option<usize> {
+        self.max_rows
+    }
+    /// Sets the maximum number of rows that are read from a result set.
+    ///
+    /// Once a result set has delivered this number of rows, no more rows are fetched,
+    /// and the result set is marked as truncated if the server has more rows
+    /// (see `ResultSet::is_truncated()`).
+    /// This is a safety belt for tools that execute arbitrary queries.
+    ///
+    /// Is off by default.
+    pub fn set_max_rows(&mut self, max_rows: Option<usize>) {
+        self.max_rows = max_rows;
+    }
+    /// Builder-method for setting the maximum number of rows that are read from a result set.
+    ///
+    /// See [`ConnectionConfiguration::set_max_rows`].
+    #[must_use]
+    pub fn with_max_rows(mut self, max_rows: Option<usize>) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Returns the approximate maximum number of bytes that are read from a result set.
+    #[must_use]
+    pub fn max_result_bytes(&self) -> Option<usize> {
+        self.max_result_bytes
+    }
+    /// Sets the approximate maximum number of bytes that are read from a result set.
+    ///
+    /// Like with [`ConnectionConfiguration::set_max_rows`], no more rows are fetched
+    /// once the rows that a result set has delivered occupy this number of bytes
+    /// (see `ResultSet::buffered_bytes()` for how it is measured), and the result set
+    /// is marked as truncated if the server has more rows.
+    /// The limit is checked after each fetch, so it can be exceeded by up to one fetch.
+    ///
+    /// Is off by default.
+    pub fn set_max_result_bytes(&mut self, max_result_bytes: Option<usize>) {
+        self.max_result_bytes = max_result_bytes;
+    }
+    /// Builder-method for setting the approximate maximum number of bytes that are read
+    /// from a result set.
+    ///
+    /// See [`ConnectionConfiguration::set_max_result_bytes`].
+    #[must_use]
+    pub fn with_max_result_bytes(mut self, max_result_bytes: Option<usize>) -> Self {
+        self.max_result_bytes = max_result_bytes;
+        self
+    }
+
+    /// Returns the time after which the server cancels the execution of a statement.
+    #[must_use]
+    pub fn statement_timeout(&self) -> Option<Duration> {
+        self.statement_timeout
+    }
+    /// Sets the time after which the server cancels the execution of a statement;
+    /// with `None` (the default), the execution is not limited.
+    ///
+    /// The timeout is sent to the server with each statement execution, in full seconds
+    /// (rounded up), and applies to all statements of the connection; it can be overridden
+    /// for single calls with [`QueryOptions::with_statement_timeout`](crate::QueryOptions::with_statement_timeout).
+    /// When the timeout is exceeded, the server cancels the statement and the execution fails
+    /// with a server error; in contrast to the read timeout, the connection remains usable.
+    /// Fetching rows of a result set is not limited by this timeout.
+    pub fn set_statement_timeout(&mut self, statement_timeout: Option<Duration>) {
+        self.statement_timeout = statement_timeout;
+    }
+    /// Builder-method for setting the time after which the server cancels the execution
+    /// of a statement.
+    ///
+    /// See [`ConnectionConfiguration::set_statement_timeout`].
+    #[must_use]
+    pub fn with_statement_timeout(mut self, statement_timeout: Option<Duration>) -> Self {
+        self.statement_timeout = statement_timeout;
+        self
+    }
+
+    /// Returns the memory limit for single statements of the session, in GB.
+    #[must_use]
+    pub fn statement_memory_limit(&self) -> Option<u32> {
+        self.statement_memory_limit
+    }
+    /// Sets the memory limit for single statements of the session, in GB.
+    ///
+    /// The limit is applied with `SET 'STATEMENT MEMORY LIMIT' = '<limit>'`
+    /// when the connection is established, and again after a reconnect.
+    /// It can only lower the limit that is configured on the server.
+    pub fn set_statement_memory_limit(&mut self, limit: Option<u32>) {
+        self.statement_memory_limit = limit;
+    }
+    /// Builder-method for setting the memory limit for single statements of the session, in GB.
+    ///
+    /// See [`ConnectionConfiguration::set_statement_memory_limit`].
+    #[must_use]
+    pub fn with_statement_memory_limit(mut self, limit: Option<u32>) -> Self {
+        self.statement_memory_limit = limit;
+        self
+    }
+
+    /// Returns the maximum number of threads a single statement of the session can use.
+    #[must_use]
+    pub fn statement_thread_limit(&self) -> Option<u32> {
+        self.statement_thread_limit
+    }
+    /// Sets the maximum number of threads a single statement of the session can use.
+    ///
+    /// The limit is applied with `SET 'STATEMENT THREAD LIMIT' = '<limit>'`
+    /// when the connection is established, and again after a reconnect.
+    /// It can only lower the limit that is configured on the server.
+    pub fn set_statement_thread_limit(&mut self, limit: Option<u32>) {
+        self.statement_thread_limit = limit;
+    }
+    /// Builder-method for setting the maximum number of threads a single statement
+    /// of the session can use.
+    ///
+    /// See [`ConnectionConfiguration::set_statement_thread_limit`].
+    #[must_use]
+    pub fn with_statement_thread_limit(mut self, limit: Option<u32>) -> Self {
+        self.statement_thread_limit = limit;
+        self
+    }
+
+    /// Returns how the table-oriented helpers convert table and column names into identifiers.
+    #[must_use]
+    pub fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
+    }
+    /// Defines how the table-oriented helpers convert table and column names into identifiers.
+    ///
+    /// Use [`IdentifierCase::Verbatim`] for schemas with case-sensitive (quoted) names.
+    pub fn set_identifier_case(&mut self, identifier_case: IdentifierCase) {
+        self.identifier_case = identifier_case;
+    }
+    /// Builder-method for defining how the table-oriented helpers convert table and column
+    /// names into identifiers.
+    ///
+    /// See [`ConnectionConfiguration::set_identifier_case`].
+    #[must_use]
+    pub fn with_identifier_case(mut self, identifier_case: IdentifierCase) -> Self {
+        self.identifier_case = identifier_case;
+        self
+    }
+
+    /// Returns the number of close messages an async connection can queue up.
+    #[must_use]
+    pub fn cleanup_queue_size(&self) -> usize {
+        self.cleanup_queue_size
+    }
+    /// Sets the number of close messages an async connection can queue up.
+    ///
+    /// The value is evaluated when the connection is established.
+    /// See [`ConnectionConfiguration::DEFAULT_CLEANUP_QUEUE_SIZE`].
+    pub fn set_cleanup_queue_size(&mut self, cleanup_queue_size: usize) {
+        self.cleanup_queue_size = cleanup_queue_size;
+    }
+    /// Builder-method for setting the number of close messages an async connection
+    /// can queue up.
+    #[must_use]
+    pub fn with_cleanup_queue_size(mut self, cleanup_queue_size: usize) -> Self {
+        self.cleanup_queue_size = cleanup_queue_size;
+        self
+    }
+
+    /// Returns the duration after which the watchdog reports a request that is still in flight.
+    #[must_use]
+    pub fn watchdog_threshold(&self) -> Option<Duration> {
+        self.watchdog_threshold
+    }
+    /// Sets the duration after which the watchdog reports a request that is still in flight;
+    /// with `None` (the default), no watchdog is used.
+    ///
+    /// A request is reported at most once, while it is still running, with a warning
+    /// in the log, or by calling the handler that was registered with
+    /// `Connection::set_watchdog_handler()`.
+    /// The report contains the request type and, if available,
+    /// a label for the statement (see [`SlowRequest`](crate::SlowRequest)),
+    /// which contains the statement text only with
+    /// [`ConnectionConfiguration::set_sql_in_watchdog`].
+    /// The request itself is not affected; use the read timeout to abort hanging requests.
+    pub fn set_watchdog_threshold(&mut self, watchdog_threshold: Option<Duration>) {
+        self.watchdog_threshold = watchdog_threshold;
+    }
+    /// Builder-method for setting the duration after which the watchdog reports a request
+    /// that is still in flight.
+    ///
+    /// See [`ConnectionConfiguration::set_watchdog_threshold`].
+    #[must_use]
+    pub fn with_watchdog_threshold(mut self, watchdog_threshold: Option<Duration>) -> Self {
+        self.watchdog_threshold = watchdog_threshold;
+        self
+    }
+
+    /// Returns whether the reports of the watchdog include the statement text.
+    #[must_use]
+    pub fn is_sql_in_watchdog(&self) -> bool {
+        self.sql_in_watchdog
+    }
+    /// Defines whether the reports of the watchdog should include the beginning
+    /// of the statement text; otherwise only its first keyword, like `SELECT`, is reported.
+    ///
+    /// Is off by default, since statement texts might contain sensitive data.
+    pub fn set_sql_in_watchdog(&mut self, sql_in_watchdog: bool) {
+        self.sql_in_watchdog = sql_in_watchdog;
+    }
+    /// Builder-method for defining whether the reports of the watchdog should include
+    /// the statement text.
+    ///
+    /// See [`ConnectionConfiguration::set_sql_in_watchdog`].
+    #[must_use]
+    pub fn with_sql_in_watchdog(mut self, sql_in_watchdog: bool) -> Self {
+        self.sql_in_watchdog = sql_in_watchdog;
+        self
+    }
 }
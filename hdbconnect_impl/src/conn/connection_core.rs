@@ -1,22 +1,42 @@
+#[cfg(feature = "mock")]
+use crate::conn::{
+    protocol_recording::{ProtocolRecording, Recorder},
+    tcp_client::SyncReplayClient,
+};
 use crate::{
     base::RsState,
     conn::{
-        authentication, initial_request, AmConnCore, AuthenticationResult, CommandOptions,
-        ConnectParams, ConnectionConfiguration, ConnectionStatistics, SessionState, TcpClient,
+        authentication, initial_request, watch, AmConnCore, AuthenticationResult, CommandOptions,
+        ConnectParams, ConnectionConfiguration, ConnectionStatistics, DisconnectStatistics,
+        LobBufferPool, QueryOptions, SessionState, StatementHook, TcpClient, TransactionState,
+        WatchdogGuard, WatchdogHandler,
     },
+    impl_err,
     protocol::{
+        max_parameter_rows_size,
         parts::{
-            ClientInfo, ConnectOptions, DbConnectInfo, ParameterDescriptors, ResultSetMetadata,
-            ServerError, StatementContext, Topology, TransactionFlags,
+            ClientInfo, ConnectOptions, ConnectOptionsPart, DbConnectInfo, ParameterDescriptors,
+            ResultSetMetadata, ServerError, StatementContext, Topology, TransactionFlags,
         },
-        MessageType, Part, Reply, ReplyType, Request, ServerUsage,
+        MessageType, Part, Reply, ReplyType, Request, ServerUsage, StatementMetrics,
     },
-    HdbError, HdbResult,
+    HdbError, HdbResult, ServerVersion,
 };
 use debug_ignore::DebugIgnore;
-#[cfg(feature = "sync")]
-use std::time::Duration;
-use std::{io::Cursor, io::ErrorKind, mem, sync::Arc};
+use std::{
+    io::{Cursor, ErrorKind},
+    mem,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use time::UtcOffset;
+
+// The session variable that carries the client locale
+const CLIENT_LOCALE_SESSION_VARIABLE: &str = "LOCALE";
+
+// The time that sending the disconnect request may take when a connection is dropped;
+// the socket is closed in any case.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(1);
 
 #[doc(hidden)]
 #[derive(Debug)]
@@ -25,16 +45,27 @@ pub(crate) struct ConnectionCore {
     session_id: i64,
     client_info: ClientInfo,
     client_info_touched: bool,
+    initial_client_info: ClientInfo,
     statistics: ConnectionStatistics,
     server_usage: ServerUsage,
     config: ConnectionConfiguration,
+    initial_config: ConnectionConfiguration,
     session_state: SessionState,
     statement_sequence: Option<i64>, // statement sequence within the transaction
+    deferred_statement_drops: Vec<u64>,
     connect_options: ConnectOptions,
     topology: Option<Topology>,
     pub(crate) warnings: Vec<ServerError>,
+    pub(crate) statement_metrics: StatementMetrics,
+    current_schema: Option<String>,
+    o_server_version: Option<ServerVersion>,
     tcp_client: TcpClient,
     io_buffer: DebugIgnore<Cursor<Vec<u8>>>,
+    lob_buffers: LobBufferPool,
+    o_statement_hook: DebugIgnore<Option<StatementHook>>,
+    o_watchdog_handler: DebugIgnore<Option<WatchdogHandler>>,
+    #[cfg(feature = "mock")]
+    o_recording: Option<ProtocolRecording>,
 }
 
 impl<'a> ConnectionCore {
@@ -43,6 +74,7 @@ impl<'a> ConnectionCore {
         params: ConnectParams,
         config: &ConnectionConfiguration,
     ) -> HdbResult<Self> {
+        let config = &params.effective_configuration(config);
         let o_dbname = params.dbname().map(ToString::to_string);
         let network_group = params.network_group().unwrap_or_default().to_string();
         let mut conn_core = ConnectionCore::try_new_initialized_sync(params, config)?;
@@ -80,7 +112,10 @@ impl<'a> ConnectionCore {
         // here we can encounter an additional implicit redirect, triggered by HANA itself
         loop {
             match authentication::authenticate_sync(&mut conn_core, false)? {
-                AuthenticationResult::Ok => return Ok(conn_core),
+                AuthenticationResult::Ok => {
+                    conn_core.initialize_session_sync()?;
+                    return Ok(conn_core);
+                }
                 AuthenticationResult::Redirect(db_connect_info) => {
                     trace!("Redirect initiated by HANA");
                     let redirect_params = conn_core
@@ -98,6 +133,7 @@ impl<'a> ConnectionCore {
         params: ConnectParams,
         config: &ConnectionConfiguration,
     ) -> HdbResult<Self> {
+        let config = &params.effective_configuration(config);
         let o_dbname = params.dbname().map(ToString::to_string);
         let network_group = params.network_group().unwrap_or_default().to_string();
         let mut conn_core = ConnectionCore::try_new_initialized_async(params, config).await?;
@@ -138,7 +174,10 @@ impl<'a> ConnectionCore {
         // here we can encounter an additional implicit redirect, triggered by HANA itself
         loop {
             match authentication::authenticate_async(&mut conn_core, false).await? {
-                AuthenticationResult::Ok => return Ok(conn_core),
+                AuthenticationResult::Ok => {
+                    conn_core.initialize_session_async().await?;
+                    return Ok(conn_core);
+                }
                 AuthenticationResult::Redirect(db_connect_info) => {
                     trace!("Redirect initiated by HANA");
                     let redirect_params = conn_core
@@ -157,28 +196,13 @@ impl<'a> ConnectionCore {
         params: ConnectParams,
         config: &ConnectionConfiguration,
     ) -> HdbResult<Self> {
-        let connect_options =
-            ConnectOptions::new(params.clientlocale(), &get_os_user(), params.compression());
-        let mut tcp_client = TcpClient::try_new_sync(params, config.read_timeout())?;
+        let mut tcp_client = TcpClient::try_new_sync(
+            params,
+            config.read_timeout(),
+            config.tls_handshake_timeout(),
+        )?;
         initial_request::send_and_receive_sync(&mut tcp_client)?;
-        Ok(Self {
-            authenticated: false,
-            session_id: 0,
-            statistics: ConnectionStatistics::new(),
-            server_usage: ServerUsage::default(),
-            io_buffer: DebugIgnore::from(Cursor::new(Vec::<u8>::with_capacity(
-                ConnectionConfiguration::MIN_BUFFER_SIZE,
-            ))),
-            config: config.clone(),
-            client_info: ClientInfo::default(),
-            client_info_touched: true,
-            session_state: SessionState::default(),
-            statement_sequence: None,
-            connect_options,
-            topology: None,
-            warnings: Vec::<ServerError>::new(),
-            tcp_client,
-        })
+        Ok(Self::new_unauthenticated(config, tcp_client))
     }
 
     #[cfg(feature = "async")]
@@ -186,11 +210,43 @@ impl<'a> ConnectionCore {
         params: ConnectParams,
         config: &ConnectionConfiguration,
     ) -> HdbResult<Self> {
-        let connect_options =
-            ConnectOptions::new(params.clientlocale(), &get_os_user(), params.compression());
-        let mut tcp_client = TcpClient::try_new_async(params).await?;
+        let mut tcp_client =
+            TcpClient::try_new_async(params, config.tls_handshake_timeout()).await?;
         initial_request::send_and_receive_async(&mut tcp_client).await?;
-        Ok(Self {
+        Ok(Self::new_unauthenticated(config, tcp_client))
+    }
+
+    // Creates a connection core that serves the recorded replies, without a server.
+    #[cfg(feature = "mock")]
+    pub(crate) fn new_for_replay(recording: &ProtocolRecording) -> HdbResult<Self> {
+        let params = ConnectParams::builder()
+            .hostname("replay")
+            .port(0)
+            .dbuser("REPLAY")
+            .password("REPLAY")
+            .build()?;
+        let tcp_client = TcpClient::SyncReplay(SyncReplayClient::new(params, recording.replies()));
+        let mut conn_core =
+            Self::new_unauthenticated(&ConnectionConfiguration::default(), tcp_client);
+        conn_core.digest_server_connect_options(recording.connect_options()?)?;
+        conn_core.authenticated = true;
+        conn_core.client_info_touched = false;
+        Ok(conn_core)
+    }
+
+    fn new_unauthenticated(config: &ConnectionConfiguration, tcp_client: TcpClient) -> Self {
+        let params = tcp_client.connect_params();
+        let connect_options = ConnectOptions::new(
+            params.clientlocale(),
+            &get_os_user(),
+            params.compression(),
+            params.data_format_version_2(),
+            params.client_distribution_mode(),
+        );
+        let current_schema = params.default_schema().map(ToString::to_string);
+        let mut client_info = ClientInfo::default();
+        client_info.update(params.client_info());
+        Self {
             authenticated: false,
             session_id: 0,
             statistics: ConnectionStatistics::new(),
@@ -198,16 +254,42 @@ impl<'a> ConnectionCore {
             io_buffer: DebugIgnore::from(Cursor::new(Vec::<u8>::with_capacity(
                 ConnectionConfiguration::MIN_BUFFER_SIZE,
             ))),
+            lob_buffers: LobBufferPool::default(),
+            o_statement_hook: DebugIgnore::from(None),
+            o_watchdog_handler: DebugIgnore::from(None),
             config: config.clone(),
-            client_info: ClientInfo::default(),
+            initial_config: config.clone(),
+            initial_client_info: client_info.clone(),
+            client_info,
             client_info_touched: true,
             session_state: SessionState::default(),
             statement_sequence: None,
+            deferred_statement_drops: Vec::new(),
             connect_options,
             topology: None,
             warnings: Vec::<ServerError>::new(),
+            statement_metrics: StatementMetrics::default(),
+            current_schema,
+            o_server_version: None,
             tcp_client,
-        })
+            #[cfg(feature = "mock")]
+            o_recording: None,
+        }
+    }
+
+    // Starts recording the byte streams of all following roundtrips.
+    #[cfg(feature = "mock")]
+    pub(crate) fn start_protocol_recording(&mut self) -> HdbResult<()> {
+        self.o_recording = Some(ProtocolRecording::new(
+            self.connect_options.server_connect_options(),
+        )?);
+        Ok(())
+    }
+
+    // Stops recording and returns what was recorded.
+    #[cfg(feature = "mock")]
+    pub(crate) fn stop_protocol_recording(&mut self) -> ProtocolRecording {
+        self.o_recording.take().unwrap_or_default()
     }
 
     #[cfg(feature = "sync")]
@@ -215,19 +297,26 @@ impl<'a> ConnectionCore {
         warn!("Trying to reconnect");
         let mut conn_params = self.tcp_client.connect_params().clone();
         loop {
-            let mut tcp_conn =
-                TcpClient::try_new_sync(conn_params.clone(), self.config.read_timeout())?;
+            let mut tcp_conn = TcpClient::try_new_sync(
+                conn_params.clone(),
+                self.config.read_timeout(),
+                self.config.tls_handshake_timeout(),
+            )?;
             initial_request::send_and_receive_sync(&mut tcp_conn)?;
             self.tcp_client = tcp_conn;
             self.authenticated = false;
             self.session_id = 0;
+            // the statements ended with the old session
+            self.deferred_statement_drops.clear();
+            // session variables are sent again with the next request
+            self.client_info_touched = true;
             // fetch_size, lob_read_length, lob_write_length are considered automatically
 
             debug!("Reconnected, not yet authenticated");
             match authentication::authenticate_sync(self, true)? {
                 AuthenticationResult::Ok => {
                     debug!("Re-authenticated");
-                    return Ok(());
+                    return self.initialize_session_sync();
                 }
                 AuthenticationResult::Redirect(db_connect_info) => {
                     debug!("Redirected");
@@ -245,18 +334,24 @@ impl<'a> ConnectionCore {
         debug!("Trying to reconnect");
         let mut conn_params = self.tcp_client.connect_params().clone();
         loop {
-            let mut tcp_client = TcpClient::try_new_async(conn_params.clone()).await?;
+            let mut tcp_client =
+                TcpClient::try_new_async(conn_params.clone(), self.config.tls_handshake_timeout())
+                    .await?;
             initial_request::send_and_receive_async(&mut tcp_client).await?;
             self.tcp_client = tcp_client;
             self.authenticated = false;
             self.session_id = 0;
+            // the statements ended with the old session
+            self.deferred_statement_drops.clear();
+            // session variables are sent again with the next request
+            self.client_info_touched = true;
             // fetch_size, lob_read_length, lob_write_length are considered automatically
 
             debug!("Reconnected, not yet authenticated");
             match authentication::authenticate_async(self, true).await? {
                 AuthenticationResult::Ok => {
                     debug!("Re-authenticated");
-                    return Ok(());
+                    return self.initialize_session_async().await;
                 }
                 AuthenticationResult::Redirect(db_connect_info) => {
                     debug!("Redirected");
@@ -269,12 +364,108 @@ impl<'a> ConnectionCore {
         }
     }
 
+    // The current UTC offset of the server's local time; it is not cached,
+    // because it changes with daylight saving time.
+    pub(crate) const SERVER_UTC_OFFSET_QUERY: &'static str =
+        "SELECT SECONDS_BETWEEN(CURRENT_UTCTIMESTAMP, CURRENT_TIMESTAMP) FROM DUMMY";
+
+    pub(crate) fn to_utc_offset(seconds: i32) -> HdbResult<UtcOffset> {
+        UtcOffset::from_whole_seconds(seconds)
+            .map_err(|e| impl_err!("Server reported invalid UTC offset {seconds}s: {e}"))
+    }
+
+    pub(crate) fn current_schema(&self) -> Option<&str> {
+        self.current_schema.as_deref()
+    }
+
+    pub(crate) fn set_current_schema(&mut self, schema: &str) {
+        self.current_schema = Some(schema.to_string());
+    }
+
+    // Returns true if the current schema differs from the one that was given with the
+    // connect parameters.
+    pub(crate) fn is_current_schema_changed(&self) -> bool {
+        self.current_schema.as_deref() != self.connect_params().default_schema()
+    }
+
+    pub(crate) fn reset_current_schema(&mut self) {
+        self.current_schema = self
+            .connect_params()
+            .default_schema()
+            .map(ToString::to_string);
+    }
+
+    // Restores the configuration, the client info, and the client locale to the values
+    // the connection was opened with, and drops warnings and metrics of former statements.
+    //
+    // Returns the statements that remove the session variables from the server session
+    // that were set only after connect.
+    pub(crate) fn reset_settings(&mut self) -> Vec<String> {
+        let unset_statements = self
+            .client_info
+            .keys_not_in(&self.initial_client_info)
+            .into_iter()
+            .map(|key| format!("UNSET '{}'", key.replace('\'', "''")))
+            .collect();
+        self.client_info = self.initial_client_info.clone();
+        self.client_info_touched = true;
+        let o_client_locale = self
+            .connect_params()
+            .clientlocale()
+            .map(ToString::to_string);
+        self.connect_options.set_client_locale(o_client_locale);
+        self.config = self.initial_config.clone();
+        self.warnings.clear();
+        self.statement_metrics = StatementMetrics::default();
+        unset_statements
+    }
+
+    // The statements that set up a new session: setting the cached current schema,
+    // the resource limits of the configuration,
+    // and then the initial statements of the connect parameters.
+    fn session_setup_statements(&self) -> Vec<String> {
+        self.current_schema
+            .as_deref()
+            .map(set_schema_statement)
+            .into_iter()
+            .chain(resource_limit_statements(&self.config))
+            .chain(self.connect_params().initial_statements().iter().cloned())
+            .collect()
+    }
+
+    // Sets up the session on the server, e.g. after connect or reconnect.
+    #[cfg(feature = "sync")]
+    fn initialize_session_sync(&mut self) -> HdbResult<()> {
+        for stmt in self.session_setup_statements() {
+            debug!("Initializing session with: {stmt}");
+            let mut request = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+            request.push(Part::Command(&stmt));
+            self.roundtrip_sync(&request, None, None, None, &mut None)?;
+        }
+        Ok(())
+    }
+
+    // Sets up the session on the server, e.g. after connect or reconnect.
+    #[cfg(feature = "async")]
+    async fn initialize_session_async(&mut self) -> HdbResult<()> {
+        for stmt in self.session_setup_statements() {
+            debug!("Initializing session with: {stmt}");
+            let mut request = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+            request.push(Part::Command(&stmt));
+            self.roundtrip_async(&request, None, None, None, &mut None)
+                .await?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn connect_params(&self) -> &ConnectParams {
         match self.tcp_client {
             #[cfg(feature = "sync")]
             TcpClient::SyncPlain(ref cl) => cl.connect_params(),
             #[cfg(feature = "sync")]
             TcpClient::SyncTls(ref cl) => cl.connect_params(),
+            #[cfg(feature = "mock")]
+            TcpClient::SyncReplay(ref cl) => cl.connect_params(),
             #[cfg(feature = "async")]
             TcpClient::AsyncPlain(ref cl) => cl.connect_params(),
             #[cfg(feature = "async")]
@@ -315,6 +506,23 @@ impl<'a> ConnectionCore {
         self.client_info_touched = true;
     }
 
+    pub(crate) fn set_client_info(&mut self, client_info: &crate::ClientInfo) {
+        self.client_info.update(client_info);
+        self.client_info_touched = true;
+    }
+
+    pub(crate) fn set_session_variable(&mut self, key: &str, value: &str) {
+        self.client_info.set_session_variable(key, value);
+        self.client_info_touched = true;
+    }
+
+    // The locale is sent as session variable immediately, and as connect option with a reconnect.
+    pub(crate) fn set_client_locale(&mut self, locale: &str) {
+        self.set_session_variable(CLIENT_LOCALE_SESSION_VARIABLE, locale);
+        self.connect_options
+            .set_client_locale(Some(locale.to_string()));
+    }
+
     pub(crate) fn is_client_info_touched(&self) -> bool {
         self.client_info_touched
     }
@@ -330,6 +538,7 @@ impl<'a> ConnectionCore {
             stmt_ctx.statement_sequence_info()
         );
         self.set_statement_sequence(stmt_ctx.statement_sequence_info());
+        self.statement_metrics = StatementMetrics::from_statement_context(stmt_ctx);
         self.server_usage.update(
             stmt_ctx.server_processing_time(),
             stmt_ctx.server_cpu_time(),
@@ -354,6 +563,32 @@ impl<'a> ConnectionCore {
         &mut self.config
     }
 
+    pub(crate) fn statement_hook(&self) -> Option<StatementHook> {
+        (*self.o_statement_hook).clone()
+    }
+
+    pub(crate) fn set_statement_hook(&mut self, o_hook: Option<StatementHook>) {
+        *self.o_statement_hook = o_hook;
+    }
+
+    pub(crate) fn set_watchdog_handler(&mut self, o_handler: Option<WatchdogHandler>) {
+        *self.o_watchdog_handler = o_handler;
+    }
+
+    // Registers the request with the watchdog, if a watchdog threshold is configured;
+    // the request is unregistered when the returned guard is dropped.
+    fn watch(&self, request: &Request, session_id: i64) -> Option<WatchdogGuard> {
+        self.config.watchdog_threshold().map(|threshold| {
+            watch(
+                threshold,
+                session_id,
+                format!("{:?}", request.message_type()),
+                request.label(self.config.is_sql_in_watchdog()),
+                (*self.o_watchdog_handler).clone(),
+            )
+        })
+    }
+
     pub(crate) fn set_session_id(&mut self, session_id: i64) {
         if session_id != self.session_id {
             debug!(
@@ -398,10 +633,21 @@ impl<'a> ConnectionCore {
     pub(crate) fn statistics(&self) -> &ConnectionStatistics {
         &self.statistics
     }
+    pub(crate) fn statistics_mut(&mut self) -> &mut ConnectionStatistics {
+        &mut self.statistics
+    }
     pub(crate) fn reset_statistics(&mut self) {
         self.statistics.reset();
     }
 
+    // Gives a buffer that was obtained with a READ LOB reply back for reuse.
+    pub(crate) fn recycle_lob_buffer(&mut self, buffer: Vec<u8>) {
+        self.lob_buffers
+            .give_back(buffer, self.config.max_buffer_size());
+        self.statistics
+            .update_lob_buffer_peak_size(self.lob_buffers.pooled_size());
+    }
+
     pub(crate) fn evaluate_ta_flags(&mut self, ta_flags: TransactionFlags) -> HdbResult<()> {
         self.session_state.update(ta_flags);
         if self.session_state.dead {
@@ -421,26 +667,59 @@ impl<'a> ConnectionCore {
         }
     }
 
+    // Takes over the connect options that the server sent during authentication.
+    pub(crate) fn digest_server_connect_options(
+        &mut self,
+        server_connect_options: ConnectOptionsPart,
+    ) -> HdbResult<()> {
+        self.connect_options
+            .digest_server_connect_options(server_connect_options)?;
+        self.o_server_version = self.connect_options.get_full_version_string().parse().ok();
+        Ok(())
+    }
+
     pub(crate) fn connect_options(&self) -> &ConnectOptions {
         &self.connect_options
     }
 
-    pub(crate) fn connect_options_mut(&mut self) -> &mut ConnectOptions {
-        &mut self.connect_options
+    // The maximum number of parameter rows, and their maximum size,
+    // that can be sent with a single execute request.
+    pub(crate) fn batch_limits(&self) -> (usize, usize) {
+        (
+            self.connect_options.get_max_rows_per_request(),
+            max_parameter_rows_size(&self.config),
+        )
     }
 
     pub(crate) fn augment_request(&mut self, request: &mut Request<'a>) {
         if self.authenticated {
-            if let Some(ssi_value) = self.statement_sequence() {
-                request.add_statement_context(*ssi_value);
-            }
+            let o_query_timeout = if request.message_type().executes_statement() {
+                match request.options().and_then(QueryOptions::statement_timeout) {
+                    Some(statement_timeout) => statement_timeout,
+                    None => self.config.statement_timeout(),
+                }
+            } else {
+                None
+            };
+            request.add_statement_context(self.statement_sequence().copied(), o_query_timeout);
             if self.is_client_info_touched() {
                 request.push(Part::ClientInfo(self.get_client_info_for_sending()));
             }
+            if request.message_type().executes_statement() {
+                request.append_statement_drops(std::mem::take(&mut self.deferred_statement_drops));
+            }
         }
     }
 
+    // Lets the statement be dropped with further segments of the next request
+    // that executes a statement.
+    // If that request fails, the server frees the statement when the session ends.
+    pub(crate) fn defer_statement_drop(&mut self, statement_id: u64) {
+        self.deferred_statement_drops.push(statement_id);
+    }
+
     #[cfg(feature = "sync")]
+    #[allow(clippy::too_many_lines)]
     pub(crate) fn roundtrip_sync(
         &mut self,
         request: &'a Request<'a>,
@@ -449,34 +728,66 @@ impl<'a> ConnectionCore {
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
         o_rs: &mut Option<&mut RsState>,
     ) -> HdbResult<Reply> {
+        let compress = self.connect_options().use_compression();
+        request.check_size(o_a_descriptors, &self.config, compress)?;
+        if self.authenticated {
+            request.check_dataformat_version2(
+                o_a_descriptors,
+                self.connect_options.get_dataformat_version2(),
+                self.o_server_version.as_ref(),
+            )?;
+        }
         let (session_id, nsn, default_error_handling) =
             if let MessageType::Authenticate = request.message_type() {
                 (0, 1, false)
             } else {
                 (self.session_id, self.next_sequence_number(), true)
             };
-        let compress = self.connect_options().use_compression();
+        let _o_watchdog_guard = self.watch(request, session_id);
+        let config = QueryOptions::applied_to(request.options(), &self.config);
 
         let w: &mut dyn std::io::Write = match self.tcp_client {
             TcpClient::SyncPlain(ref mut cl) => cl.writer(),
             TcpClient::SyncTls(ref mut cl) => cl.writer(),
+            #[cfg(feature = "mock")]
+            TcpClient::SyncReplay(ref mut cl) => cl.writer(),
             TcpClient::Dead { .. } => return Err(HdbError::ConnectionBroken { source: None }),
             #[cfg(feature = "async")]
             _ => unreachable!("Async connections not supported here"),
         };
 
+        #[cfg(feature = "mock")]
+        let (mut recorded_request, mut recorded_reply) = (Vec::<u8>::new(), Vec::<u8>::new());
+        #[cfg(feature = "mock")]
+        let mut request_recorder;
+        #[cfg(feature = "mock")]
+        let w: &mut dyn std::io::Write = if self.o_recording.is_some() {
+            request_recorder = Recorder::new(w, &mut recorded_request);
+            &mut request_recorder
+        } else {
+            w
+        };
+
         let start = request
             .emit_sync(
                 session_id,
                 nsn,
-                &self.config,
+                &config,
                 compress,
                 o_a_descriptors,
+                self.o_server_version.as_ref(),
                 &mut self.statistics,
                 &mut self.io_buffer,
                 w,
             )
             .map_err(|e| {
+                if let HdbError::RequestTooLarge { .. } = e {
+                    // the request was rejected before anything was sent
+                    if default_error_handling {
+                        self.statistics.revert_sequence_number();
+                    }
+                    return e;
+                }
                 info!(
                     "roundtrip_sync(): TCP connection discarded because write failed with \"{e}\""
                 );
@@ -486,43 +797,80 @@ impl<'a> ConnectionCore {
                 }
             })?;
 
+        // a read timeout of the request is only used for reading its reply
+        let o_overridden_read_timeout = request.options().and_then(QueryOptions::read_timeout);
+        if let Some(read_timeout) = o_overridden_read_timeout {
+            self.tcp_client.set_read_timeout_sync(read_timeout)?;
+        }
         let rdr: &mut dyn std::io::Read = match self.tcp_client {
             TcpClient::SyncPlain(ref mut cl) => cl.reader(),
             TcpClient::SyncTls(ref mut cl) => cl.reader(),
+            #[cfg(feature = "mock")]
+            TcpClient::SyncReplay(ref mut cl) => cl.reader(),
             TcpClient::Dead { .. } => return Err(HdbError::ConnectionBroken { source: None }),
             #[cfg(feature = "async")]
             _ => unreachable!("Async connections not supported here"),
         };
-        let mut reply = match Reply::parse_sync(
+        #[cfg(feature = "mock")]
+        let mut reply_recorder;
+        #[cfg(feature = "mock")]
+        let rdr: &mut dyn std::io::Read = if self.o_recording.is_some() {
+            reply_recorder = Recorder::new(rdr, &mut recorded_reply);
+            &mut reply_recorder
+        } else {
+            rdr
+        };
+        let parse_result = Reply::parse_sync(
             o_a_rsmd,
             o_a_descriptors,
             o_rs,
             o_am_conn_core,
             &mut self.statistics,
+            &mut self.lob_buffers,
             start,
+            config.max_reply_size(),
             &mut self.io_buffer,
             rdr,
-        ) {
+        );
+        let restore_result = match o_overridden_read_timeout {
+            Some(_) if !self.tcp_client.has_died() => self
+                .tcp_client
+                .set_read_timeout_sync(self.config.read_timeout()),
+            _ => Ok(()),
+        };
+        let mut reply = match parse_result {
             Ok(reply) => reply,
             Err(e) => {
                 info!("roundtrip_sync(): TCP connection discarded after \"{e}\"");
                 self.tcp_client.die();
-                return Err(connection_broken(e, self.config.read_timeout()));
+                return Err(connection_broken(e, config.read_timeout()));
             }
         };
+        restore_result?;
+
+        #[cfg(feature = "mock")]
+        if let Some(ref mut recording) = self.o_recording {
+            recording.push(recorded_request, recorded_reply);
+        }
 
         if self.io_buffer.get_ref().capacity() > self.config.max_buffer_size() {
             *(self.io_buffer.get_mut()) = Vec::with_capacity(self.config.max_buffer_size());
             self.statistics.add_buffer_shrinking();
         }
 
-        if default_error_handling {
+        // the replies to the dropped statements are not handed out
+        reply.truncate_following(request.no_of_segments() - request.no_of_statement_drops() - 1);
+
+        // the replies to a request with several segments are checked by the caller
+        if default_error_handling && request.no_of_segments() == 1 + request.no_of_statement_drops()
+        {
             reply.handle_db_error(self)?;
         }
         Ok(reply)
     }
 
     #[cfg(feature = "async")]
+    #[allow(clippy::too_many_lines)]
     pub(crate) async fn roundtrip_async(
         &mut self,
         request: &'a Request<'a>,
@@ -531,13 +879,23 @@ impl<'a> ConnectionCore {
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
         o_rs: &mut Option<&mut RsState>,
     ) -> HdbResult<Reply> {
+        let compress = self.connect_options().use_compression();
+        request.check_size(o_a_descriptors, &self.config, compress)?;
+        if self.authenticated {
+            request.check_dataformat_version2(
+                o_a_descriptors,
+                self.connect_options.get_dataformat_version2(),
+                self.o_server_version.as_ref(),
+            )?;
+        }
         let (session_id, nsn, default_error_handling) =
             if let MessageType::Authenticate = request.message_type() {
                 (0, 1, false)
             } else {
                 (self.session_id(), self.next_sequence_number(), true)
             };
-        let compress = self.connect_options().use_compression();
+        let _o_watchdog_guard = self.watch(request, session_id);
+        let config = QueryOptions::applied_to(request.options(), &self.config);
 
         let start = match self.tcp_client {
             TcpClient::AsyncPlain(ref mut cl) => {
@@ -545,9 +903,10 @@ impl<'a> ConnectionCore {
                     .emit_async(
                         session_id,
                         nsn,
-                        &self.config,
+                        &config,
                         compress,
                         o_a_descriptors,
+                        self.o_server_version.as_ref(),
                         &mut self.statistics,
                         &mut self.io_buffer,
                         cl.writer(),
@@ -559,9 +918,10 @@ impl<'a> ConnectionCore {
                     .emit_async(
                         session_id,
                         nsn,
-                        &self.config,
+                        &config,
                         compress,
                         o_a_descriptors,
+                        self.o_server_version.as_ref(),
                         &mut self.statistics,
                         &mut self.io_buffer,
                         cl.writer(),
@@ -573,6 +933,13 @@ impl<'a> ConnectionCore {
             _ => unreachable!("Sync connections not supported here"),
         }
         .map_err(|e| {
+            if let HdbError::RequestTooLarge { .. } = e {
+                // the request was rejected before anything was sent
+                if default_error_handling {
+                    self.statistics.revert_sequence_number();
+                }
+                return e;
+            }
             info!("roundtrip_async(): TCP connection discarded because write failed with \"{e}\"");
             self.tcp_client.die();
             HdbError::ConnectionBroken {
@@ -580,7 +947,7 @@ impl<'a> ConnectionCore {
             }
         })?;
 
-        let mut reply = if let Some(timeout) = self.config.read_timeout() {
+        let mut reply = if let Some(timeout) = config.read_timeout() {
             match tokio::time::timeout(
                 timeout,
                 Reply::parse_async(
@@ -590,6 +957,8 @@ impl<'a> ConnectionCore {
                     o_am_conn_core,
                     start,
                     &mut self.statistics,
+                    &mut self.lob_buffers,
+                    config.max_reply_size(),
                     &mut self.io_buffer,
                     &mut self.tcp_client,
                 ),
@@ -612,6 +981,8 @@ impl<'a> ConnectionCore {
                 o_am_conn_core,
                 start,
                 &mut self.statistics,
+                &mut self.lob_buffers,
+                config.max_reply_size(),
                 &mut self.io_buffer,
                 &mut self.tcp_client,
             )
@@ -619,7 +990,7 @@ impl<'a> ConnectionCore {
         }.map_err(|e|{
             info!("roundtrip_async(): TCP connection discarded after \"{e}\"");
             self.tcp_client.die();
-            connection_broken(e, self.config.read_timeout())
+            connection_broken(e, config.read_timeout())
         })?;
 
         if self.io_buffer.get_ref().capacity() > self.config.max_buffer_size() {
@@ -627,7 +998,12 @@ impl<'a> ConnectionCore {
             self.statistics.add_buffer_shrinking();
         }
 
-        if default_error_handling {
+        // the replies to the dropped statements are not handed out
+        reply.truncate_following(request.no_of_segments() - request.no_of_statement_drops() - 1);
+
+        // the replies to a request with several segments are checked by the caller
+        if default_error_handling && request.no_of_segments() == 1 + request.no_of_statement_drops()
+        {
             reply.handle_db_error(self)?;
         }
         Ok(reply)
@@ -636,95 +1012,156 @@ impl<'a> ConnectionCore {
     pub(crate) fn is_broken(&self) -> bool {
         self.tcp_client.has_died()
     }
+
+    // True if the server reported that a write transaction was started,
+    // and it was not yet committed or rolled back.
+    pub(crate) fn has_pending_write_transaction(&self) -> bool {
+        matches!(
+            self.session_state.ta_state,
+            TransactionState::WriteTransaction
+        )
+    }
 }
 
 impl Drop for ConnectionCore {
-    // try to send a disconnect to the database, ignore all errors
+    // try to send a disconnect to the database, within DISCONNECT_TIMEOUT, ignore all errors;
+    // the socket is closed when the TcpClient is dropped
     fn drop(&mut self) {
         debug!("Drop of ConnectionCore, session_id = {}", self.session_id);
         #[cfg(any(feature = "sync", feature = "async"))]
-        if self.authenticated {
+        if self.authenticated && !matches!(self.tcp_client, TcpClient::Dead { .. }) {
             let request = Request::new_for_disconnect();
-            let session_id = self.session_id();
-            let nsn = self.next_sequence_number();
+            let start = Instant::now();
             #[cfg(feature = "sync")]
-            {
-                let w: &mut dyn std::io::Write = match self.tcp_client {
-                    TcpClient::SyncPlain(ref mut cl) => cl.writer() as &mut dyn std::io::Write,
-                    TcpClient::SyncTls(ref mut cl) => cl.writer() as &mut dyn std::io::Write,
-                    TcpClient::Dead { .. } => return,
-                    #[cfg(feature = "async")]
-                    _ => unreachable!("Async connections not supported here"),
-                };
-                request
-                    .emit_sync(
-                        session_id,
-                        nsn,
-                        &self.config,
-                        false,
-                        None,
-                        &mut self.statistics,
-                        &mut self.io_buffer,
-                        w,
-                    )
-                    .map_err(|e| {
-                        warn!("Disconnect request failed with {:?}", e);
-                        e
-                    })
-                    .ok();
-            }
-            #[cfg(feature = "async")]
-            {
-                let mut tcp_client = TcpClient::Dead {
-                    params: self.tcp_client.connect_params().clone(),
-                };
-                std::mem::swap(&mut tcp_client, &mut self.tcp_client);
-                let mut io_buffer = Cursor::new(Vec::<u8>::with_capacity(200));
-                let config = self.configuration().clone();
-                // see https://www.reddit.com/r/rust/comments/vckd9h/async_drop/
-                tokio::spawn(async move {
-                    match tcp_client {
-                        TcpClient::AsyncPlain(ref mut cl) => {
-                            request
-                                .emit_async(
-                                    session_id,
-                                    nsn,
-                                    &config,
-                                    false,
-                                    None,
-                                    &mut ConnectionStatistics::new(),
-                                    &mut io_buffer,
-                                    cl.writer(),
-                                )
-                                .await
-                                .ok();
-                        }
-                        TcpClient::AsyncTls(ref mut cl) => {
-                            request
-                                .emit_async(
-                                    session_id,
-                                    nsn,
-                                    &config,
-                                    false,
-                                    None,
-                                    &mut ConnectionStatistics::new(),
-                                    &mut io_buffer,
-                                    cl.writer(),
-                                )
-                                .await
-                                .ok();
-                        }
-                        TcpClient::Dead { .. } => {}
-                        #[cfg(feature = "sync")]
-                        _ => unreachable!("Sync connections not supported here"),
-                    }
-                    trace!("Disconnect: request successfully sent");
-                });
+            let is_sync = self.tcp_client.is_sync();
+            #[cfg(not(feature = "sync"))]
+            let is_sync = false;
+            if is_sync {
+                #[cfg(feature = "sync")]
+                self.send_disconnect_sync(&request, start);
+            } else {
+                #[cfg(feature = "async")]
+                self.spawn_disconnect_async(request, start);
             }
         }
     }
 }
 
+impl ConnectionCore {
+    #[cfg(feature = "sync")]
+    fn send_disconnect_sync(&mut self, request: &Request<'_>, start: Instant) {
+        let session_id = self.session_id();
+        let nsn = self.next_sequence_number();
+        if let Err(e) = self
+            .tcp_client
+            .set_write_timeout_sync(Some(DISCONNECT_TIMEOUT))
+        {
+            record_disconnect(&Err(e), start);
+            return;
+        }
+        let w: &mut dyn std::io::Write = match self.tcp_client {
+            TcpClient::SyncPlain(ref mut cl) => cl.writer() as &mut dyn std::io::Write,
+            TcpClient::SyncTls(ref mut cl) => cl.writer() as &mut dyn std::io::Write,
+            #[cfg(feature = "mock")]
+            TcpClient::SyncReplay(ref mut cl) => cl.writer() as &mut dyn std::io::Write,
+            _ => return,
+        };
+        let result = request
+            .emit_sync(
+                session_id,
+                nsn,
+                &self.config,
+                false,
+                None,
+                None,
+                &mut self.statistics,
+                &mut self.io_buffer,
+                w,
+            )
+            .map(|_| ());
+        record_disconnect(&result, start);
+    }
+
+    #[cfg(feature = "async")]
+    fn spawn_disconnect_async(&mut self, request: Request<'static>, start: Instant) {
+        let session_id = self.session_id();
+        let nsn = self.next_sequence_number();
+        let mut tcp_client = TcpClient::Dead {
+            params: self.tcp_client.connect_params().clone(),
+        };
+        std::mem::swap(&mut tcp_client, &mut self.tcp_client);
+        let mut io_buffer = Cursor::new(Vec::<u8>::with_capacity(200));
+        let config = self.configuration().clone();
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            record_disconnect(
+                &Err(impl_err!(
+                    "no tokio runtime available, closing the socket only"
+                )),
+                start,
+            );
+            return;
+        };
+        // see https://www.reddit.com/r/rust/comments/vckd9h/async_drop/
+        runtime.spawn(async move {
+            let disconnect = async {
+                match tcp_client {
+                    TcpClient::AsyncPlain(ref mut cl) => request
+                        .emit_async(
+                            session_id,
+                            nsn,
+                            &config,
+                            false,
+                            None,
+                            None,
+                            &mut ConnectionStatistics::new(),
+                            &mut io_buffer,
+                            cl.writer(),
+                        )
+                        .await
+                        .map(|_| ()),
+                    TcpClient::AsyncTls(ref mut cl) => request
+                        .emit_async(
+                            session_id,
+                            nsn,
+                            &config,
+                            false,
+                            None,
+                            None,
+                            &mut ConnectionStatistics::new(),
+                            &mut io_buffer,
+                            cl.writer(),
+                        )
+                        .await
+                        .map(|_| ()),
+                    TcpClient::Dead { .. } => Ok(()),
+                    #[cfg(feature = "sync")]
+                    _ => unreachable!("Sync connections are not disconnected here"),
+                }
+            };
+            let result = tokio::time::timeout(DISCONNECT_TIMEOUT, disconnect)
+                .await
+                .unwrap_or_else(|_| {
+                    Err(HdbError::Io {
+                        source: std::io::Error::from(ErrorKind::TimedOut),
+                    })
+                });
+            record_disconnect(&result, start);
+        });
+    }
+}
+
+// Logs and counts the outcome and the duration of sending the disconnect request.
+#[cfg(any(feature = "sync", feature = "async"))]
+fn record_disconnect(result: &HdbResult<()>, start: Instant) {
+    let duration = Instant::now().duration_since(start);
+    DisconnectStatistics::record(result, duration);
+    let micros = duration.as_micros();
+    match result {
+        Ok(()) => debug!("Disconnect: request sent ({micros} µs)"),
+        Err(e) => warn!("Disconnect: request failed after {micros} µs with {e:?}"),
+    }
+}
+
 fn get_os_user() -> String {
     let os_user = username::get_user_name().unwrap_or_default();
     trace!("OS user: {}", os_user);
@@ -755,3 +1192,102 @@ fn connection_broken(mut e: HdbError, o_timeout: Option<std::time::Duration>) ->
         source: Some(Box::new(e)),
     }
 }
+
+// Returns the last value that was generated for an IDENTITY column in the session.
+pub(crate) const CURRENT_IDENTITY_VALUE: &str = "SELECT CURRENT_IDENTITY_VALUE() FROM DUMMY";
+
+// The schema name is used as-is, i.e., it is case-sensitive.
+pub(crate) fn set_schema_statement(schema: &str) -> String {
+    format!("SET SCHEMA {}", crate::base::delimit(schema))
+}
+
+// The server ignores these limits if they are sent as client info,
+// so they are set with explicit statements.
+fn resource_limit_statements(config: &ConnectionConfiguration) -> Vec<String> {
+    [
+        ("STATEMENT MEMORY LIMIT", config.statement_memory_limit()),
+        ("STATEMENT THREAD LIMIT", config.statement_thread_limit()),
+    ]
+    .into_iter()
+    .filter_map(|(key, o_limit)| o_limit.map(|limit| format!("SET '{key}' = '{limit}'")))
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::resource_limit_statements;
+    use crate::ConnectionConfiguration;
+
+    #[test]
+    fn test_resource_limit_statements() {
+        assert!(resource_limit_statements(&ConnectionConfiguration::default()).is_empty());
+
+        assert_eq!(
+            resource_limit_statements(
+                &ConnectionConfiguration::default()
+                    .with_statement_memory_limit(Some(20))
+                    .with_statement_thread_limit(Some(4)),
+            ),
+            vec![
+                "SET 'STATEMENT MEMORY LIMIT' = '20'".to_string(),
+                "SET 'STATEMENT THREAD LIMIT' = '4'".to_string(),
+            ]
+        );
+    }
+
+    // Async connections apply the read timeout of the configuration with each roundtrip,
+    // so restoring the configuration restores the read timeout.
+    #[cfg(feature = "mock")]
+    #[test]
+    fn test_reset_settings() {
+        use super::ConnectionCore;
+        use crate::{
+            protocol::parts::{ConnectOptionsPart, ServerConnectOptions},
+            ProtocolRecording,
+        };
+        use std::time::Duration;
+
+        let recording =
+            ProtocolRecording::new(&ServerConnectOptions::new(ConnectOptionsPart::default()))
+                .unwrap();
+        let mut conn_core = ConnectionCore::new_for_replay(&recording).unwrap();
+        let initial_config = conn_core.configuration().clone();
+
+        conn_core
+            .configuration_mut()
+            .set_read_timeout(Some(Duration::from_secs(5)));
+        conn_core.configuration_mut().set_auto_commit(false);
+        conn_core.configuration_mut().set_statement_cache_size(10);
+        assert!(conn_core.reset_settings().is_empty());
+
+        assert_eq!(
+            conn_core.configuration().read_timeout(),
+            initial_config.read_timeout()
+        );
+        assert!(conn_core.configuration().is_auto_commit());
+        assert_eq!(conn_core.configuration().statement_cache_size(), 0);
+    }
+
+    // Dropping an authenticated connection sends the disconnect request and counts it;
+    // the statistics are process-wide, so other tests can increase them concurrently.
+    #[cfg(all(feature = "mock", feature = "sync"))]
+    #[test]
+    fn test_drop_counts_disconnect() {
+        use super::ConnectionCore;
+        use crate::{
+            protocol::parts::{ConnectOptionsPart, ServerConnectOptions},
+            DisconnectStatistics, ProtocolRecording,
+        };
+
+        let recording =
+            ProtocolRecording::new(&ServerConnectOptions::new(ConnectOptionsPart::default()))
+                .unwrap();
+        let conn_core = ConnectionCore::new_for_replay(&recording).unwrap();
+        let before = DisconnectStatistics::snapshot();
+        drop(conn_core);
+        let after = DisconnectStatistics::snapshot();
+
+        assert!(after.sent_count() > before.sent_count());
+        assert!(after.total_time() >= before.total_time());
+    }
+}
@@ -10,9 +10,14 @@ pub struct ConnectionStatistics {
     compressed_replies_uncompressed_size: u64,
     compressed_replies_count: u32,
     shrinked_oversized_buffer_count: u32,
+    statement_cache_hits: u32,
+    statement_cache_misses: u32,
+    statement_cache_evictions: u32,
+    lob_buffer_peak_size: usize,
     created_at: time::OffsetDateTime,
     last_reset_at: time::OffsetDateTime,
     wait_time: std::time::Duration,
+    server_processing_time: std::time::Duration,
 }
 impl Default for ConnectionStatistics {
     fn default() -> Self {
@@ -29,7 +34,12 @@ impl Default for ConnectionStatistics {
             compressed_replies_compressed_size: 0,
             compressed_replies_uncompressed_size: 0,
             shrinked_oversized_buffer_count: 0,
+            statement_cache_hits: 0,
+            statement_cache_misses: 0,
+            statement_cache_evictions: 0,
+            lob_buffer_peak_size: 0,
             wait_time: std::time::Duration::default(),
+            server_processing_time: std::time::Duration::default(),
         }
     }
 }
@@ -50,6 +60,11 @@ impl ConnectionStatistics {
         self.sequence_number
     }
 
+    // Gives back the sequence number of a request that was not sent.
+    pub(crate) fn revert_sequence_number(&mut self) {
+        self.sequence_number -= 1;
+    }
+
     pub(crate) fn add_compressed_request(
         &mut self,
         compressed_size: usize,
@@ -74,9 +89,29 @@ impl ConnectionStatistics {
     pub(crate) fn add_wait_time(&mut self, wait_time: std::time::Duration) {
         self.wait_time += wait_time;
     }
+    pub(crate) fn add_server_processing_time(
+        &mut self,
+        o_server_processing_time: Option<std::time::Duration>,
+    ) {
+        if let Some(server_processing_time) = o_server_processing_time {
+            self.server_processing_time += server_processing_time;
+        }
+    }
     pub(crate) fn add_buffer_shrinking(&mut self) {
         self.shrinked_oversized_buffer_count += 1;
     }
+    pub(crate) fn add_statement_cache_hit(&mut self) {
+        self.statement_cache_hits += 1;
+    }
+    pub(crate) fn add_statement_cache_miss(&mut self) {
+        self.statement_cache_misses += 1;
+    }
+    pub(crate) fn add_statement_cache_evictions(&mut self, count: usize) {
+        self.statement_cache_evictions += u32::try_from(count).unwrap_or(u32::MAX);
+    }
+    pub(crate) fn update_lob_buffer_peak_size(&mut self, size: usize) {
+        self.lob_buffer_peak_size = std::cmp::max(self.lob_buffer_peak_size, size);
+    }
 
     /// Returns the number of roundtrips to the database that were done through this connection
     /// since the last reset.
@@ -93,6 +128,27 @@ impl ConnectionStatistics {
         self.wait_time
     }
 
+    /// Returns the part of the accumulated wait time that the server reported
+    /// as its processing time.
+    ///
+    /// The server reports its processing time for most, but not all kinds of requests,
+    /// so this value is a lower bound.
+    #[must_use]
+    pub fn accumulated_server_processing_time(&self) -> std::time::Duration {
+        self.server_processing_time
+    }
+
+    /// Returns the part of the accumulated wait time that was not reported as processing
+    /// time by the server, i.e., the time spent on serializing requests, on the network,
+    /// and in the server's communication layer.
+    ///
+    /// If this value dominates the wait time, slowness is more likely caused by the client
+    /// or the network than by the database.
+    #[must_use]
+    pub fn accumulated_network_time(&self) -> std::time::Duration {
+        self.wait_time.saturating_sub(self.server_processing_time)
+    }
+
     /// Returns the number of outgoing requests that were compressed.
     #[must_use]
     pub fn compressed_requests_count(&self) -> u32 {
@@ -128,6 +184,37 @@ impl ConnectionStatistics {
     pub fn compressed_replies_uncompressed_size(&self) -> u64 {
         self.compressed_replies_uncompressed_size
     }
+
+    /// Returns the number of prepared statements that were taken from the statement cache.
+    ///
+    /// See [`ConnectionConfiguration::DEFAULT_STATEMENT_CACHE_SIZE`](crate::ConnectionConfiguration::DEFAULT_STATEMENT_CACHE_SIZE).
+    #[must_use]
+    pub fn statement_cache_hits(&self) -> u32 {
+        self.statement_cache_hits
+    }
+
+    /// Returns the number of prepared statements that were not found in the statement cache,
+    /// and thus had to be prepared on the server.
+    #[must_use]
+    pub fn statement_cache_misses(&self) -> u32 {
+        self.statement_cache_misses
+    }
+
+    /// Returns the number of prepared statements that were evicted from the statement cache.
+    #[must_use]
+    pub fn statement_cache_evictions(&self) -> u32 {
+        self.statement_cache_evictions
+    }
+
+    /// Returns the maximal number of bytes that were held by the connection's pool of
+    /// buffers for reading LOBs.
+    ///
+    /// The chunks of a LOB are read into buffers that are reused for subsequent roundtrips;
+    /// the number of pooled buffers is bounded.
+    #[must_use]
+    pub fn lob_buffer_peak_size(&self) -> usize {
+        self.lob_buffer_peak_size
+    }
 }
 
 impl std::fmt::Display for ConnectionStatistics {
@@ -138,11 +225,38 @@ impl std::fmt::Display for ConnectionStatistics {
         writeln!(f, "Last reset at:  {}", self.last_reset_at)?;
         writeln!(f, "Total number of requests: {}", self.sequence_number)?;
         writeln!(f, "Total wait time:          {:?}", self.wait_time)?;
+        writeln!(
+            f,
+            "  - server processing:    {:?}",
+            self.server_processing_time
+        )?;
+        writeln!(
+            f,
+            "  - network and client:   {:?}",
+            self.accumulated_network_time()
+        )?;
         writeln!(
             f,
             "Buffer was shrinked:      {:?}",
             self.shrinked_oversized_buffer_count
         )?;
+        if self.statement_cache_hits + self.statement_cache_misses > 0 {
+            writeln!(f, "Statement cache")?;
+            writeln!(f, "  - hits:                 {}", self.statement_cache_hits)?;
+            writeln!(
+                f,
+                "  - misses:               {}",
+                self.statement_cache_misses
+            )?;
+            writeln!(
+                f,
+                "  - evictions:            {}",
+                self.statement_cache_evictions
+            )?;
+        }
+        if self.lob_buffer_peak_size > 0 {
+            writeln!(f, "LOB buffer peak size:     {}", self.lob_buffer_peak_size)?;
+        }
         writeln!(f, "Compressed requests",)?;
         writeln!(
             f,
@@ -187,6 +301,24 @@ mod test {
         stat.add_buffer_shrinking();
         stat.add_compressed_reply(100, 800);
         stat.add_compressed_request(200, 777);
+        stat.add_statement_cache_miss();
+        stat.add_statement_cache_evictions(2);
+        stat.add_statement_cache_hit();
+        assert_eq!(stat.statement_cache_evictions(), 2);
+        stat.update_lob_buffer_peak_size(200);
+        stat.update_lob_buffer_peak_size(100);
+        assert_eq!(stat.lob_buffer_peak_size(), 200);
+        stat.add_wait_time(std::time::Duration::from_millis(50));
+        stat.add_server_processing_time(Some(std::time::Duration::from_millis(30)));
+        stat.add_server_processing_time(None);
+        assert_eq!(
+            stat.accumulated_server_processing_time(),
+            std::time::Duration::from_millis(30)
+        );
+        assert_eq!(
+            stat.accumulated_network_time(),
+            std::time::Duration::from_millis(20)
+        );
         println!("{stat}");
 
         std::thread::sleep(std::time::Duration::from_millis(100));
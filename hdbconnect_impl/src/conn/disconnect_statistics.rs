@@ -0,0 +1,123 @@
+use crate::{HdbError, HdbResult};
+use std::{
+    io::ErrorKind,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+// The outcomes of all disconnect requests of the process
+static SENT: AtomicU64 = AtomicU64::new(0);
+static FAILED: AtomicU64 = AtomicU64::new(0);
+static TIMED_OUT: AtomicU64 = AtomicU64::new(0);
+static TOTAL_MICROS: AtomicU64 = AtomicU64::new(0);
+
+// docu is written at re-exports of frontend crates (hdbconnect/lib.rs, hdbconnect_async/lib.rs)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DisconnectStatistics {
+    sent: u64,
+    failed: u64,
+    timed_out: u64,
+    total_time: Duration,
+}
+
+impl DisconnectStatistics {
+    /// Returns a snapshot of the process-wide statistics.
+    #[must_use]
+    pub fn snapshot() -> Self {
+        Self {
+            sent: SENT.load(Ordering::Relaxed),
+            failed: FAILED.load(Ordering::Relaxed),
+            timed_out: TIMED_OUT.load(Ordering::Relaxed),
+            total_time: Duration::from_micros(TOTAL_MICROS.load(Ordering::Relaxed)),
+        }
+    }
+
+    /// Returns the number of disconnect requests that were sent successfully.
+    #[must_use]
+    pub fn sent_count(&self) -> u64 {
+        self.sent
+    }
+
+    /// Returns the number of disconnect requests that failed, e.g. because the connection
+    /// was broken, but did not time out.
+    #[must_use]
+    pub fn failed_count(&self) -> u64 {
+        self.failed
+    }
+
+    /// Returns the number of disconnect requests that were given up because they took too long.
+    #[must_use]
+    pub fn timed_out_count(&self) -> u64 {
+        self.timed_out
+    }
+
+    /// Returns the accumulated time that was spent with sending disconnect requests.
+    #[must_use]
+    pub fn total_time(&self) -> Duration {
+        self.total_time
+    }
+
+    // Counts the outcome of a disconnect request.
+    pub(crate) fn record(result: &HdbResult<()>, duration: Duration) {
+        let counter = match result {
+            Ok(()) => &SENT,
+            Err(e) if is_timeout(e) => &TIMED_OUT,
+            Err(_) => &FAILED,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        TOTAL_MICROS.fetch_add(
+            u64::try_from(duration.as_micros()).unwrap_or(u64::MAX),
+            Ordering::Relaxed,
+        );
+    }
+}
+
+// Sync sockets report an expired write timeout, depending on the platform,
+// with one of these kinds
+fn is_timeout(e: &HdbError) -> bool {
+    matches!(
+        e,
+        HdbError::Io { source } if matches!(source.kind(), ErrorKind::TimedOut | ErrorKind::WouldBlock)
+    )
+}
+
+impl std::fmt::Display for DisconnectStatistics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Disconnects: {} sent, {} failed, {} timed out, total time {:?}",
+            self.sent, self.failed, self.timed_out, self.total_time
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DisconnectStatistics;
+    use crate::HdbError;
+    use std::{io::ErrorKind, time::Duration};
+
+    // The statistics are process-wide, so other tests can increase them concurrently
+    #[test]
+    fn test_record() {
+        let before = DisconnectStatistics::snapshot();
+        DisconnectStatistics::record(&Ok(()), Duration::from_millis(2));
+        DisconnectStatistics::record(
+            &Err(HdbError::Io {
+                source: std::io::Error::from(ErrorKind::TimedOut),
+            }),
+            Duration::from_secs(1),
+        );
+        DisconnectStatistics::record(
+            &Err(HdbError::ConnectionBroken { source: None }),
+            Duration::from_millis(1),
+        );
+        let after = DisconnectStatistics::snapshot();
+
+        assert!(after.sent_count() > before.sent_count());
+        assert!(after.timed_out_count() > before.timed_out_count());
+        assert!(after.failed_count() > before.failed_count());
+        assert!(after.total_time() >= before.total_time() + Duration::from_millis(1_003));
+        assert!(after.to_string().starts_with("Disconnects: "));
+    }
+}
@@ -23,6 +23,8 @@ pub(crate) fn send_and_receive_sync(sync_tcp_connection: &mut TcpClient) -> HdbR
             })?;
         }
         TcpClient::Dead { .. } => unreachable!(),
+        #[cfg(feature = "mock")]
+        TcpClient::SyncReplay(_) => unreachable!("Replay connections are not initialized"),
         #[cfg(feature = "async")]
         _ => unreachable!("Async connections not supported here"),
     }
@@ -41,6 +43,8 @@ pub(crate) fn send_and_receive_sync(sync_tcp_connection: &mut TcpClient) -> HdbR
             })
         }
         TcpClient::Dead { .. } => unreachable!(),
+        #[cfg(feature = "mock")]
+        TcpClient::SyncReplay(_) => unreachable!("Replay connections are not initialized"),
         #[cfg(feature = "async")]
         _ => unreachable!("Async connections not supported here"),
     }
@@ -0,0 +1,64 @@
+// A small pool of buffers for the data of READ LOB replies.
+//
+// Reading a LOB needs a buffer for each chunk, which is only used until its content
+// is appended to the LOB handle. The pool allows reusing these buffers, rather than
+// allocating a fresh one for each roundtrip.
+// The number of pooled buffers is bounded, so the pool cannot grow beyond a few chunks.
+#[derive(Debug, Default)]
+pub(crate) struct LobBufferPool {
+    buffers: Vec<Vec<u8>>,
+}
+
+impl LobBufferPool {
+    const MAX_POOLED_BUFFERS: usize = 4;
+
+    // Returns an empty buffer, preferably a pooled one.
+    pub(crate) fn take(&mut self) -> Vec<u8> {
+        self.buffers.pop().unwrap_or_default()
+    }
+
+    // Returns the buffer to the pool, if it is not yet full and the buffer does not exceed
+    // the given size limit.
+    pub(crate) fn give_back(&mut self, mut buffer: Vec<u8>, max_buffer_size: usize) {
+        if self.buffers.len() < Self::MAX_POOLED_BUFFERS && buffer.capacity() <= max_buffer_size {
+            buffer.clear();
+            self.buffers.push(buffer);
+        }
+    }
+
+    // The number of bytes that are held by the pooled buffers.
+    pub(crate) fn pooled_size(&self) -> usize {
+        self.buffers.iter().map(Vec::capacity).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LobBufferPool;
+
+    #[test]
+    fn test_lob_buffer_pool() {
+        let mut pool = LobBufferPool::default();
+        let mut buffer = pool.take();
+        buffer.extend_from_slice(&[1_u8; 100]);
+        let capacity = buffer.capacity();
+        pool.give_back(buffer, 1000);
+        assert_eq!(pool.pooled_size(), capacity);
+
+        // the pooled buffer is reused, and it is empty
+        let buffer = pool.take();
+        assert!(buffer.is_empty());
+        assert_eq!(buffer.capacity(), capacity);
+        assert_eq!(pool.pooled_size(), 0);
+
+        // oversized buffers are not pooled
+        pool.give_back(buffer, 10);
+        assert_eq!(pool.pooled_size(), 0);
+
+        // the number of pooled buffers is bounded
+        for _ in 0..10 {
+            pool.give_back(Vec::with_capacity(10), 1000);
+        }
+        assert_eq!(pool.pooled_size(), LobBufferPool::MAX_POOLED_BUFFERS * 10);
+    }
+}
@@ -0,0 +1,107 @@
+// Spans for statement executions that follow the OpenTelemetry semantic conventions
+// for database client calls (https://opentelemetry.io/docs/specs/semconv/database/).
+//
+// The spans are emitted with `tracing`; a subscriber like `tracing-opentelemetry`
+// translates the `otel.*` fields into the span's name, kind and status.
+use crate::{conn::ConnectionCore, AffectedRowCount, ConnectionConfiguration, HdbResult};
+use tracing::{field::Empty, Span};
+
+// Creates the span for the execution of the given statement.
+pub(crate) fn statement_span(stmt: &str, conn_core: &ConnectionCore) -> Span {
+    let params = conn_core.connect_params();
+    let o_port = params
+        .addr()
+        .rsplit_once(':')
+        .and_then(|(_, port)| port.parse::<u16>().ok());
+    tracing::info_span!(
+        target: "hdbconnect",
+        "db.statement",
+        otel.name = operation(stmt),
+        otel.kind = "client",
+        otel.status_code = Empty,
+        db.system = "hana",
+        db.statement = traced_statement(stmt, conn_core.configuration()),
+        db.user = params.dbuser(),
+        db.rows_affected = Empty,
+        net.peer.name = params.host(),
+        net.peer.port = o_port,
+        error.message = Empty,
+    )
+}
+
+// Records the outcome of the execution in the span;
+// `rows` provides the number of affected rows, if the execution returned such.
+pub(crate) fn record_outcome<T>(
+    span: &Span,
+    result: &HdbResult<T>,
+    rows: impl FnOnce(&T) -> Option<AffectedRowCount>,
+) {
+    match result {
+        Ok(t) => {
+            if let Some(count) = rows(t).and_then(AffectedRowCount::count) {
+                span.record("db.rows_affected", count);
+            }
+        }
+        Err(e) => {
+            span.record("otel.status_code", "ERROR");
+            span.record("error.message", tracing::field::display(e));
+        }
+    }
+}
+
+// The statement text is only added to the span if this is configured,
+// since statement texts might contain sensitive data.
+fn traced_statement<'a>(stmt: &'a str, config: &ConnectionConfiguration) -> Option<&'a str> {
+    config.is_sql_in_traces().then_some(stmt)
+}
+
+// The first keyword of the statement, like "SELECT" or "INSERT".
+fn operation(stmt: &str) -> String {
+    stmt.split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_uppercase()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{operation, traced_statement};
+    use crate::ConnectionConfiguration;
+
+    #[test]
+    fn test_operation() {
+        assert_eq!(operation("  select * from dummy"), "SELECT");
+        assert_eq!(operation("INSERT INTO T VALUES(?)"), "INSERT");
+        assert_eq!(operation(""), "");
+    }
+
+    #[test]
+    fn test_traced_statement() {
+        let stmt = "select * from USERS where PASSWORD = 'secret'";
+        assert_eq!(
+            traced_statement(stmt, &ConnectionConfiguration::default()),
+            None
+        );
+        assert_eq!(
+            traced_statement(
+                stmt,
+                &ConnectionConfiguration::default().with_sql_in_traces(true)
+            ),
+            Some(stmt)
+        );
+    }
+
+    // The spans are not entered across await points, so the futures remain Send
+    // and can be spawned on a multi-threaded runtime.
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_async_futures_are_send() {
+        fn assert_send<T: Send>(_: T) {}
+        let _ = |connection: &crate::a_sync::Connection| {
+            assert_send(connection.batch(|b| {
+                b.statement("select * from dummy");
+            }));
+            assert_send(connection.execute_all(&["select * from dummy"]));
+        };
+    }
+}
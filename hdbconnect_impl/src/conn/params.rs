@@ -1,10 +1,40 @@
+pub mod client_info;
 pub mod connect_params;
 pub mod connect_params_builder;
+pub(crate) mod cp_hdbsql;
 pub mod cp_url;
 pub mod into_connect_params;
 pub mod into_connect_params_builder;
 pub(crate) mod tls;
 
+/// The client distribution mode, which determines how a client uses
+/// the hosts of a scale-out system.
+///
+/// The server may reduce the requested mode, depending on its own configuration;
+/// the effective mode can be seen with
+/// [`ServerConnectOptions::client_distribution_mode`](crate::ServerConnectOptions::client_distribution_mode).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+pub enum ClientDistributionMode {
+    /// No routing and no distributed transaction handling.
+    Off,
+    /// Connections can be opened to any host of the system.
+    Connection,
+    /// The server returns which host is preferred for executing a statement.
+    Statement,
+    /// Combination of `Connection` and `Statement`.
+    StatementConnection,
+}
+impl ClientDistributionMode {
+    pub(crate) fn code(self) -> i32 {
+        match self {
+            Self::Off => 0,
+            Self::Connection => 1,
+            Self::Statement => 2,
+            Self::StatementConnection => 3,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Copy, Eq, PartialEq, Deserialize)]
 pub(crate) enum Compression {
     Off,
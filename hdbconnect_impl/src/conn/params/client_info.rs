@@ -0,0 +1,97 @@
+/// Information about the client application that is transmitted to the server.
+///
+/// The values are visible on the server, e.g. in the monitoring view `M_SESSION_CONTEXT`,
+/// and help to attribute sessions to applications and end users.
+///
+/// A `ClientInfo` can be given to the
+/// [`ConnectParamsBuilder`](crate::ConnectParamsBuilder::client_info) to be applied on connect,
+/// and it can be applied to an open connection with `Connection::set_client_info()`.
+/// Only the fields that are set are transmitted; other values remain unchanged.
+/// The values are transmitted with the next request to the server,
+/// and again after the connection was re-established.
+/// The application name can also be given in the connect URL, with the option `application`.
+///
+/// ```rust
+/// use hdbconnect::ClientInfo;
+/// let client_info = ClientInfo::new()
+///     .with_application("MyApp")
+///     .with_application_version("1.2.3")
+///     .with_application_user("K2209657");
+/// ```
+///
+/// If the application name is set to the name of the service that owns the connection,
+/// operators can find out which service owns which session, e.g. with
+///
+/// ```sql
+/// SELECT C.CONNECTION_ID, C.CLIENT_HOST, C.USER_NAME, S.VALUE AS APPLICATION
+///   FROM M_CONNECTIONS C JOIN M_SESSION_CONTEXT S
+///     ON C.HOST = S.HOST AND C.PORT = S.PORT AND C.CONNECTION_ID = S.CONNECTION_ID
+///  WHERE S.KEY = 'APPLICATION'
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ClientInfo {
+    application: Option<String>,
+    application_version: Option<String>,
+    application_user: Option<String>,
+    application_source: Option<String>,
+}
+
+impl ClientInfo {
+    /// Creates a new, empty instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the name of the application (`APPLICATION`).
+    #[must_use]
+    pub fn with_application<S: AsRef<str>>(mut self, application: S) -> Self {
+        self.application = Some(application.as_ref().to_string());
+        self
+    }
+
+    /// Sets the version of the application (`APPLICATIONVERSION`).
+    #[must_use]
+    pub fn with_application_version<S: AsRef<str>>(mut self, version: S) -> Self {
+        self.application_version = Some(version.as_ref().to_string());
+        self
+    }
+
+    /// Sets the end user of the application (`APPLICATIONUSER`).
+    #[must_use]
+    pub fn with_application_user<S: AsRef<str>>(mut self, user: S) -> Self {
+        self.application_user = Some(user.as_ref().to_string());
+        self
+    }
+
+    /// Sets the source code location in the application (`APPLICATIONSOURCE`).
+    #[must_use]
+    pub fn with_application_source<S: AsRef<str>>(mut self, source: S) -> Self {
+        self.application_source = Some(source.as_ref().to_string());
+        self
+    }
+
+    /// Returns the name of the application.
+    #[must_use]
+    pub fn application(&self) -> Option<&str> {
+        self.application.as_deref()
+    }
+
+    /// Returns the version of the application.
+    #[must_use]
+    pub fn application_version(&self) -> Option<&str> {
+        self.application_version.as_deref()
+    }
+
+    /// Returns the end user of the application.
+    #[must_use]
+    pub fn application_user(&self) -> Option<&str> {
+        self.application_user.as_deref()
+    }
+
+    /// Returns the source code location in the application.
+    #[must_use]
+    pub fn application_source(&self) -> Option<&str> {
+        self.application_source.as_deref()
+    }
+}
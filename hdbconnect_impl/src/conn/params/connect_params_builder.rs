@@ -1,6 +1,12 @@
-use super::{cp_url::format_as_url, tls::Tls};
+use super::{
+    client_info::ClientInfo,
+    cp_url::{format_as_url, ConfigOption},
+    tls::Tls,
+};
 use crate::{
-    conn::Compression, usage_err, ConnectParams, HdbResult, IntoConnectParamsBuilder, ServerCerts,
+    conn::{ClientDistributionMode, Compression},
+    protocol::parts::ConnectOptions,
+    usage_err, ConnectParams, HdbResult, IntoConnectParamsBuilder, ServerCerts,
 };
 use secstr::SecUtf8;
 
@@ -46,6 +52,16 @@ pub struct ConnectParamsBuilder {
     dbname: Option<String>,
     network_group: Option<String>,
     clientlocale: Option<String>,
+    default_schema: Option<String>,
+    #[serde(skip)]
+    initial_statements: Vec<String>,
+    #[serde(skip)]
+    client_info: ClientInfo,
+    #[serde(skip)]
+    data_format_version_2: Option<u8>,
+    #[serde(skip)]
+    o_client_distribution_mode: Option<ClientDistributionMode>,
+    config_options: Vec<ConfigOption>,
     compression: Compression,
     tls: Tls,
 }
@@ -137,15 +153,77 @@ impl ConnectParamsBuilder {
         self
     }
 
-    /// Sets the client locale from the value of the environment variable LANG
+    /// Sets the client locale from the process environment.
+    ///
+    /// The environment variables `LC_ALL`, `LC_MESSAGES`, and `LANG` are evaluated
+    /// in this order; encoding and modifier (like in `de_DE.UTF-8@euro`) are removed,
+    /// and the values `C` and `POSIX` are ignored.
     pub fn clientlocale_from_env_lang(&mut self) -> &mut Self {
-        self.clientlocale = match std::env::var("LANG") {
-            Ok(l) => Some(l),
-            Err(_) => None,
-        };
+        self.clientlocale = locale_from_env();
+        self
+    }
+
+    /// Sets the schema that is set as current schema after connect and after reconnect.
+    ///
+    /// The name is used as-is, i.e., it is case-sensitive.
+    pub fn default_schema<S: AsRef<str>>(&mut self, schema: S) -> &mut Self {
+        self.default_schema = Some(schema.as_ref().to_owned());
+        self
+    }
+
+    /// Adds a statement that is executed after connect and after reconnect,
+    /// before the connection is used.
+    ///
+    /// This can be used for session settings, like `SET 'MY_VARIABLE' = 'my value'`.
+    /// The statements are executed in the order in which they were added,
+    /// after the default schema was set.
+    /// If one of them fails, the connect fails.
+    ///
+    /// The initial statements are not part of the URL representation of the builder.
+    pub fn initial_statement<S: AsRef<str>>(&mut self, stmt: S) -> &mut Self {
+        self.initial_statements.push(stmt.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the information about the client application that is transmitted on connect.
+    ///
+    /// The client information is not part of the URL representation of the builder.
+    pub fn client_info(&mut self, client_info: ClientInfo) -> &mut Self {
+        self.client_info = client_info;
         self
     }
 
+    /// Pins the data format version that is requested from the server to a value
+    /// lower than the one that is used by default.
+    ///
+    /// This is intended for testing the compatibility with older HANA revisions;
+    /// values below 4 are not supported.
+    /// The server can still reply with an even lower version,
+    /// see `Connection::data_format_version_2()`.
+    ///
+    /// The data format version is not part of the URL representation of the builder.
+    pub fn data_format_version_2(&mut self, version: u8) -> &mut Self {
+        self.data_format_version_2 = Some(version);
+        self
+    }
+
+    /// Requests the given client distribution mode from the server, rather than
+    /// relying on the defaults.
+    ///
+    /// On scale-out systems this allows e.g. switching statement routing explicitly on or off.
+    /// The distribution mode is not part of the URL representation of the builder.
+    pub fn client_distribution_mode(&mut self, mode: ClientDistributionMode) -> &mut Self {
+        self.o_client_distribution_mode = Some(mode);
+        self
+    }
+
+    // Adds an override for the ConnectionConfiguration, replacing an earlier one of the same kind.
+    pub(crate) fn add_config_option(&mut self, config_option: ConfigOption) {
+        self.config_options
+            .retain(|co| std::mem::discriminant(co) != std::mem::discriminant(&config_option));
+        self.config_options.push(config_option);
+    }
+
     /// Switch off compression (for debugging purposes?)
     ///
     /// By default, compression is supported, like with `always_uncompressed(false)`
@@ -219,6 +297,19 @@ impl ConnectParamsBuilder {
             .clone()
             .ok_or_else(|| usage_err!("password is missing"))?;
 
+        if let Some(version) = self.data_format_version_2 {
+            if !(ConnectOptions::MIN_DATAFORMAT_VERSION2..=ConnectOptions::DATAFORMAT_VERSION2)
+                .contains(&version)
+            {
+                return Err(usage_err!(
+                    "data format version {version} is not supported, \
+                     use a value between {} and {}",
+                    ConnectOptions::MIN_DATAFORMAT_VERSION2,
+                    ConnectOptions::DATAFORMAT_VERSION2
+                ));
+            }
+        }
+
         Ok(ConnectParams::new(
             host,
             port,
@@ -227,6 +318,12 @@ impl ConnectParamsBuilder {
             self.dbname.clone(),
             self.network_group.clone(),
             self.clientlocale.clone(),
+            self.default_schema.clone(),
+            self.initial_statements.clone(),
+            self.client_info.clone(),
+            self.data_format_version_2,
+            self.o_client_distribution_mode,
+            self.config_options.clone(),
             self.compression,
             self.tls.clone(),
         ))
@@ -261,6 +358,9 @@ impl ConnectParamsBuilder {
             self.network_group.as_deref(),
             &self.tls,
             self.clientlocale.as_deref(),
+            self.default_schema.as_deref(),
+            self.client_info.application(),
+            &self.config_options,
             self.compression,
         )
     }
@@ -295,6 +395,36 @@ impl ConnectParamsBuilder {
         self.clientlocale.as_deref()
     }
 
+    /// Returns the configured default schema.
+    #[must_use]
+    pub fn get_default_schema(&self) -> Option<&str> {
+        self.default_schema.as_deref()
+    }
+
+    /// Returns the configured initial statements.
+    #[must_use]
+    pub fn get_initial_statements(&self) -> &[String] {
+        &self.initial_statements
+    }
+
+    /// Returns the configured client information.
+    #[must_use]
+    pub fn get_client_info(&self) -> &ClientInfo {
+        &self.client_info
+    }
+
+    /// Returns the pinned data format version.
+    #[must_use]
+    pub fn get_data_format_version_2(&self) -> Option<u8> {
+        self.data_format_version_2
+    }
+
+    /// Returns the requested client distribution mode.
+    #[must_use]
+    pub fn get_client_distribution_mode(&self) -> Option<ClientDistributionMode> {
+        self.o_client_distribution_mode
+    }
+
     /// Returns the configured database name.
     #[must_use]
     pub fn get_dbname(&self) -> Option<&str> {
@@ -365,17 +495,38 @@ impl std::fmt::Display for ConnectParamsBuilder {
                 self.network_group.as_deref(),
                 &self.tls,
                 self.clientlocale.as_deref(),
+                self.default_schema.as_deref(),
+                self.client_info.application(),
+                &self.config_options,
                 self.compression,
             )
         )
     }
 }
 
+// Determines the locale from the usual environment variables.
+pub(super) fn locale_from_env() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find_map(|value| normalize_locale(&value))
+}
+
+// Removes encoding and modifier from a POSIX locale; ignores the POSIX default locale.
+fn normalize_locale(value: &str) -> Option<String> {
+    let locale = value.split(['.', '@']).next().unwrap_or_default().trim();
+    if locale.is_empty() || locale == "C" || locale == "POSIX" {
+        None
+    } else {
+        Some(locale.to_string())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::super::into_connect_params_builder::IntoConnectParamsBuilder;
-    use super::ConnectParamsBuilder;
     use super::ServerCerts;
+    use super::{normalize_locale, ConnectParamsBuilder};
 
     #[test]
     fn test_connect_params_builder() {
@@ -401,7 +552,10 @@ mod test {
                 .port(2222)
                 .dbuser("MEIER")
                 .password("schLau")
-                .clientlocale("de_DE");
+                .clientlocale("de_DE")
+                .default_schema("MY_SCHEMA")
+                .initial_statement("SET 'A' = '1'")
+                .initial_statement("SET 'B' = '2'");
             builder.tls_with(crate::ServerCerts::Directory("TCD".to_string()));
             builder.tls_with(crate::ServerCerts::RootCertificates);
 
@@ -409,6 +563,11 @@ mod test {
             assert_eq!("MEIER", params.dbuser());
             assert_eq!("schLau", params.password().unsecure());
             assert_eq!(Some("de_DE"), params.clientlocale());
+            assert_eq!(Some("MY_SCHEMA"), params.default_schema());
+            assert_eq!(
+                ["SET 'A' = '1'", "SET 'B' = '2'"].as_slice(),
+                params.initial_statements()
+            );
             assert_eq!(
                 ServerCerts::Directory("TCD".to_string()),
                 *params.server_certs().unwrap().first().unwrap()
@@ -432,6 +591,55 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_normalize_locale() {
+        assert_eq!(
+            Some("de_DE".to_string()),
+            normalize_locale("de_DE.UTF-8@euro")
+        );
+        assert_eq!(Some("en_US".to_string()), normalize_locale("en_US"));
+        assert_eq!(None, normalize_locale("C.UTF-8"));
+        assert_eq!(None, normalize_locale("POSIX"));
+        assert_eq!(None, normalize_locale(""));
+    }
+
+    #[test]
+    fn test_data_format_version_2() {
+        let mut builder = ConnectParamsBuilder::new();
+        builder
+            .hostname("abcd123")
+            .port(2222)
+            .dbuser("MEIER")
+            .password("schLau");
+        assert_eq!(None, builder.build().unwrap().data_format_version_2());
+
+        builder.data_format_version_2(6);
+        assert_eq!(Some(6), builder.get_data_format_version_2());
+        assert_eq!(Some(6), builder.build().unwrap().data_format_version_2());
+
+        builder.data_format_version_2(3);
+        assert!(builder.build().is_err());
+        builder.data_format_version_2(9);
+        assert!(builder.build().is_err());
+    }
+
+    #[test]
+    fn test_default_schema_in_url() {
+        let mut builder = ConnectParamsBuilder::new();
+        builder
+            .hostname("abcd123")
+            .port(2222)
+            .dbuser("MEIER")
+            .default_schema("MY SCHEMA&x=1");
+        let url = builder.to_url_without_password();
+        assert_eq!(
+            url,
+            "hdbsql://MEIER@abcd123:2222?default_schema=MY+SCHEMA%26x%3D1"
+        );
+        let parsed = url.into_connect_params_builder().unwrap();
+        assert_eq!(parsed.get_default_schema(), Some("MY SCHEMA&x=1"));
+    }
+
     #[test]
     fn serde_test() {
         #[derive(Serialize, Deserialize, Debug)]
@@ -448,12 +656,13 @@ mod test {
             .dbuser("MEIER")
             .password("schLau")
             .clientlocale("de_DE")
+            .default_schema("MY_SCHEMA")
             .tls_with(crate::ServerCerts::Directory("TCD".to_string()))
             .tls_with(crate::ServerCerts::RootCertificates);
 
         let serialized = serde_json::to_string(&data).unwrap();
         assert_eq!(
-            r#"{"x":"hdbsqls://MEIER@abcd123:2222?tls_certificate_dir=TCD&use_mozillas_root_certificates&client_locale=de_DE"}"#,
+            r#"{"x":"hdbsqls://MEIER@abcd123:2222?tls_certificate_dir=TCD&use_mozillas_root_certificates&client_locale=de_DE&default_schema=MY_SCHEMA"}"#,
             serialized
         );
 
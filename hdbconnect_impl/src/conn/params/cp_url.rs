@@ -2,11 +2,13 @@ use std::io::{Cursor, Write};
 
 use super::{tls::Tls, Compression};
 use crate::{
+    conn::CommandOptions,
     url::{self, HDBSQL, HDBSQLS},
-    ServerCerts,
+    usage_err, ConnectionConfiguration, CursorHoldability, HdbResult, ServerCerts,
 };
+use std::time::Duration;
 
-#[allow(clippy::too_many_arguments)]
+#[allow(clippy::too_many_arguments, clippy::too_many_lines)]
 pub(crate) fn format_as_url(
     addr: &str,
     dbuser: &str,
@@ -14,6 +16,9 @@ pub(crate) fn format_as_url(
     network_group: Option<&str>,
     tls: &Tls,
     clientlocale: Option<&str>,
+    default_schema: Option<&str>,
+    application: Option<&str>,
+    config_options: &[ConfigOption],
     compression: Compression,
 ) -> String {
     let mut buf = Cursor::new(Vec::<u8>::with_capacity(200));
@@ -33,6 +38,9 @@ pub(crate) fn format_as_url(
         && network_group.is_none()
         && matches!(tls, Tls::Off)
         && clientlocale.is_none()
+        && default_schema.is_none()
+        && application.is_none()
+        && config_options.is_empty()
     {
     } else {
         // write URL options
@@ -107,6 +115,32 @@ pub(crate) fn format_as_url(
             .ok();
         }
 
+        if let Some(schema) = default_schema {
+            write!(
+                &mut buf,
+                "{}{}={}",
+                sep.next().unwrap(),
+                UrlOpt::DefaultSchema,
+                encode(schema)
+            )
+            .ok();
+        }
+
+        if let Some(application) = application {
+            write!(
+                &mut buf,
+                "{}{}={}",
+                sep.next().unwrap(),
+                UrlOpt::Application,
+                encode(application)
+            )
+            .ok();
+        }
+
+        for config_option in config_options {
+            write!(&mut buf, "{}{config_option}", sep.next().unwrap()).ok();
+        }
+
         match compression {
             Compression::Always => {}
             // Compression::Remote => {}
@@ -119,6 +153,11 @@ pub(crate) fn format_as_url(
     String::from_utf8_lossy(&buf.into_inner()).to_string()
 }
 
+// Free-text values must survive the decoding of the query pairs when the URL is parsed.
+fn encode(value: &str) -> String {
+    ::url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
 pub(crate) enum UrlOpt {
     TlsCertificateDir,
     TlsCertificateEnv,
@@ -130,6 +169,13 @@ pub(crate) enum UrlOpt {
     Database,
     NetworkGroup,
     NoCompression,
+    DefaultSchema,
+    Application,
+    AutoCommit,
+    CursorHoldability,
+    FetchSize,
+    LobReadLength,
+    ReadTimeout,
 }
 impl UrlOpt {
     pub fn from(s: &str) -> Option<Self> {
@@ -143,6 +189,13 @@ impl UrlOpt {
             url::DATABASE => Some(UrlOpt::Database),
             url::NETWORK_GROUP => Some(UrlOpt::NetworkGroup),
             url::NO_COMPRESSION => Some(UrlOpt::NoCompression),
+            url::DEFAULT_SCHEMA => Some(UrlOpt::DefaultSchema),
+            url::APPLICATION => Some(UrlOpt::Application),
+            url::AUTO_COMMIT => Some(UrlOpt::AutoCommit),
+            url::CURSOR_HOLDABILITY => Some(UrlOpt::CursorHoldability),
+            url::FETCH_SIZE => Some(UrlOpt::FetchSize),
+            url::LOB_READ_LENGTH => Some(UrlOpt::LobReadLength),
+            url::READ_TIMEOUT => Some(UrlOpt::ReadTimeout),
             _ => None,
         }
     }
@@ -162,7 +215,82 @@ impl std::fmt::Display for UrlOpt {
                 UrlOpt::Database => url::DATABASE,
                 UrlOpt::NetworkGroup => url::NETWORK_GROUP,
                 UrlOpt::NoCompression => url::NO_COMPRESSION,
+                UrlOpt::DefaultSchema => url::DEFAULT_SCHEMA,
+                UrlOpt::Application => url::APPLICATION,
+                UrlOpt::AutoCommit => url::AUTO_COMMIT,
+                UrlOpt::CursorHoldability => url::CURSOR_HOLDABILITY,
+                UrlOpt::FetchSize => url::FETCH_SIZE,
+                UrlOpt::LobReadLength => url::LOB_READ_LENGTH,
+                UrlOpt::ReadTimeout => url::READ_TIMEOUT,
             }
         )
     }
 }
+
+// A url option that overrides a value of the `ConnectionConfiguration`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub(crate) enum ConfigOption {
+    AutoCommit(bool),
+    CursorHoldability(CommandOptions),
+    FetchSize(u32),
+    LobReadLength(u32),
+    ReadTimeout(u64), // in seconds, 0 means no timeout
+}
+impl ConfigOption {
+    pub(crate) fn parse(url_opt: &UrlOpt, value: &str) -> HdbResult<Self> {
+        let invalid = || usage_err!("invalid value '{value}' for url-option '{url_opt}'");
+        Ok(match url_opt {
+            UrlOpt::AutoCommit => Self::AutoCommit(value.parse().map_err(|_| invalid())?),
+            UrlOpt::CursorHoldability => Self::CursorHoldability(
+                value
+                    .parse::<CursorHoldability>()
+                    .map_err(|_| invalid())?
+                    .into(),
+            ),
+            UrlOpt::FetchSize => Self::FetchSize(value.parse().map_err(|_| invalid())?),
+            UrlOpt::LobReadLength => Self::LobReadLength(value.parse().map_err(|_| invalid())?),
+            UrlOpt::ReadTimeout => Self::ReadTimeout(value.parse().map_err(|_| invalid())?),
+            _ => {
+                return Err(usage_err!(
+                    "url-option '{url_opt}' is not a configuration option"
+                ))
+            }
+        })
+    }
+
+    pub(crate) fn apply(&self, config: &mut ConnectionConfiguration) {
+        match *self {
+            Self::AutoCommit(ac) => config.set_auto_commit(ac),
+            Self::CursorHoldability(command_options) => {
+                config.set_cursor_holdability(command_options.into());
+            }
+            Self::FetchSize(fetch_size) => config.set_fetch_size(fetch_size),
+            Self::LobReadLength(lob_read_length) => config.set_lob_read_length(lob_read_length),
+            Self::ReadTimeout(0) => config.set_read_timeout(None),
+            Self::ReadTimeout(secs) => config.set_read_timeout(Some(Duration::from_secs(secs))),
+        }
+    }
+}
+impl std::fmt::Display for ConfigOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::AutoCommit(ac) => write!(f, "{}={ac}", UrlOpt::AutoCommit),
+            Self::CursorHoldability(command_options) => write!(
+                f,
+                "{}={}",
+                UrlOpt::CursorHoldability,
+                match CursorHoldability::from(command_options) {
+                    CursorHoldability::None => "none",
+                    CursorHoldability::Commit => "commit",
+                    CursorHoldability::Rollback => "rollback",
+                    CursorHoldability::CommitAndRollback => "commit_and_rollback",
+                }
+            ),
+            Self::FetchSize(fetch_size) => write!(f, "{}={fetch_size}", UrlOpt::FetchSize),
+            Self::LobReadLength(lob_read_length) => {
+                write!(f, "{}={lob_read_length}", UrlOpt::LobReadLength)
+            }
+            Self::ReadTimeout(secs) => write!(f, "{}={secs}", UrlOpt::ReadTimeout),
+        }
+    }
+}
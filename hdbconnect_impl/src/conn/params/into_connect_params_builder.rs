@@ -1,4 +1,7 @@
-use super::cp_url::UrlOpt;
+use super::{
+    cp_hdbsql::parse_hdbsql,
+    cp_url::{ConfigOption, UrlOpt},
+};
 use crate::{
     url::{HDBSQL, HDBSQLS},
     usage_err, ConnectParamsBuilder, HdbError, HdbResult, ServerCerts,
@@ -7,6 +10,11 @@ use url::Url;
 
 /// A trait implemented by types that can be converted into a `ConnectParamsBuilder`.
 ///
+/// Strings are parsed as URLs (see [`url`](crate::url)); for the convenience of
+/// command line tools, the connection arguments of `hdbsql`
+/// (`-n <host>[:<port>] [-i <instance>] [-d <database>] -u <user> -p <password> [-e [-ssltrustcert]]`)
+/// and the shorthand `<user>[:<password>]@<host>:<port>` are accepted as well.
+///
 /// # Example
 /// ```rust
 ///     use hdbconnect::IntoConnectParamsBuilder;
@@ -33,6 +41,9 @@ impl IntoConnectParamsBuilder for ConnectParamsBuilder {
 
 impl IntoConnectParamsBuilder for &str {
     fn into_connect_params_builder(self) -> HdbResult<ConnectParamsBuilder> {
+        if let Some(result) = parse_hdbsql(self) {
+            return result;
+        }
         Url::parse(self)
             .map_err(|e| HdbError::conn_params(Box::new(e)))?
             .into_connect_params_builder()
@@ -46,6 +57,7 @@ impl IntoConnectParamsBuilder for String {
 }
 
 impl IntoConnectParamsBuilder for Url {
+    #[allow(clippy::too_many_lines)]
     fn into_connect_params_builder(self) -> HdbResult<ConnectParamsBuilder> {
         let mut builder = ConnectParamsBuilder::new();
         self.host_str().map(|host| builder.hostname(host));
@@ -78,9 +90,13 @@ impl IntoConnectParamsBuilder for Url {
                     builder.clientlocale(&value);
                 }
                 Some(UrlOpt::ClientLocaleFromEnv) => {
-                    std::env::var(value.to_string())
-                        .ok()
-                        .map(|s| builder.clientlocale(s));
+                    if value.is_empty() {
+                        builder.clientlocale_from_env_lang();
+                    } else {
+                        std::env::var(value.to_string())
+                            .ok()
+                            .map(|s| builder.clientlocale(s));
+                    }
                 }
                 Some(UrlOpt::TlsCertificateDir) => {
                     server_certs.push(ServerCerts::Directory(value.to_string()));
@@ -103,6 +119,22 @@ impl IntoConnectParamsBuilder for Url {
                 Some(UrlOpt::NoCompression) => {
                     builder.always_uncompressed(true);
                 }
+                Some(UrlOpt::DefaultSchema) => {
+                    builder.default_schema(&value);
+                }
+                Some(UrlOpt::Application) => {
+                    let client_info = builder.get_client_info().clone().with_application(&value);
+                    builder.client_info(client_info);
+                }
+                Some(
+                    ref url_opt @ (UrlOpt::AutoCommit
+                    | UrlOpt::CursorHoldability
+                    | UrlOpt::FetchSize
+                    | UrlOpt::LobReadLength
+                    | UrlOpt::ReadTimeout),
+                ) => {
+                    builder.add_config_option(ConfigOption::parse(url_opt, &value)?);
+                }
                 None => {
                     return Err(usage_err!("option '{name}' not supported"));
                 }
@@ -0,0 +1,309 @@
+use crate::{
+    protocol::parts::{ConnectOptionsPart, ServerConnectOptions},
+    usage_err, HdbResult,
+};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::{Read, Write};
+
+/// The byte streams of a single roundtrip, as they were sent to and received from the server.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RecordedRoundtrip {
+    request: Vec<u8>,
+    reply: Vec<u8>,
+}
+
+impl RecordedRoundtrip {
+    /// The bytes of the request.
+    #[must_use]
+    pub fn request(&self) -> &[u8] {
+        &self.request
+    }
+
+    /// The bytes of the reply.
+    #[must_use]
+    pub fn reply(&self) -> &[u8] {
+        &self.reply
+    }
+}
+
+/// The recorded roundtrips of a live session,
+/// together with the connect options that the server had returned.
+///
+/// A recording is produced by `Connection::start_protocol_recording()` and
+/// `Connection::stop_protocol_recording()`, and can be persisted with
+/// [`ProtocolRecording::write_to`] and [`ProtocolRecording::read_from`].
+///
+/// With `Connection::replay()`, the recorded replies are served to a connection
+/// that does not talk to a server, so that problems with parsing the replies
+/// can be reproduced offline and deterministically,
+/// by executing the same calls in the same order as during the recording.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProtocolRecording {
+    no_of_connect_options: u32,
+    connect_options: Vec<u8>,
+    roundtrips: Vec<RecordedRoundtrip>,
+}
+
+impl ProtocolRecording {
+    // Identifies the persisted format
+    const MAGIC: &'static [u8; 8] = b"HDBREC01";
+
+    pub(crate) fn new(server_connect_options: &ServerConnectOptions) -> HdbResult<Self> {
+        let part = server_connect_options.to_part();
+        let mut connect_options = Vec::<u8>::new();
+        part.emit(&mut connect_options)?;
+        Ok(Self {
+            no_of_connect_options: len_u32(part.len())?,
+            connect_options,
+            roundtrips: Vec::new(),
+        })
+    }
+
+    /// The recorded roundtrips, in their order.
+    #[must_use]
+    pub fn roundtrips(&self) -> &[RecordedRoundtrip] {
+        &self.roundtrips
+    }
+
+    /// Writes the recording in a simple binary format.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Io` if writing fails,
+    /// `HdbError::Usage` if the recording is too big.
+    pub fn write_to<W: Write>(&self, w: &mut W) -> HdbResult<()> {
+        w.write_all(Self::MAGIC)?;
+        w.write_u32::<LittleEndian>(self.no_of_connect_options)?;
+        w.write_u32::<LittleEndian>(len_u32(self.connect_options.len())?)?;
+        w.write_all(&self.connect_options)?;
+        w.write_u32::<LittleEndian>(len_u32(self.roundtrips.len())?)?;
+        for roundtrip in &self.roundtrips {
+            for bytes in [&roundtrip.request, &roundtrip.reply] {
+                w.write_u32::<LittleEndian>(len_u32(bytes.len())?)?;
+                w.write_all(bytes)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a recording that was written with [`ProtocolRecording::write_to`].
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Io` if reading fails,
+    /// `HdbError::Usage` if the data are not a protocol recording.
+    pub fn read_from<R: Read>(r: &mut R) -> HdbResult<Self> {
+        let mut magic = [0_u8; 8];
+        r.read_exact(&mut magic)?;
+        if &magic != Self::MAGIC {
+            return Err(usage_err!("Not a protocol recording"));
+        }
+        let no_of_connect_options = r.read_u32::<LittleEndian>()?;
+        let connect_options = read_bytes(r)?;
+        let count = r.read_u32::<LittleEndian>()?;
+        // the count is not trusted for preallocating
+        let mut roundtrips = Vec::new();
+        for _ in 0..count {
+            let request = read_bytes(r)?;
+            let reply = read_bytes(r)?;
+            roundtrips.push(RecordedRoundtrip { request, reply });
+        }
+        Ok(Self {
+            no_of_connect_options,
+            connect_options,
+            roundtrips,
+        })
+    }
+
+    pub(crate) fn push(&mut self, request: Vec<u8>, reply: Vec<u8>) {
+        self.roundtrips.push(RecordedRoundtrip { request, reply });
+    }
+
+    // The connect options that the server had returned.
+    pub(crate) fn connect_options(&self) -> HdbResult<ConnectOptionsPart> {
+        ConnectOptionsPart::parse(
+            self.no_of_connect_options as usize,
+            &mut self.connect_options.as_slice(),
+        )
+    }
+
+    // All replies, as a single stream.
+    pub(crate) fn replies(&self) -> Vec<u8> {
+        self.roundtrips
+            .iter()
+            .flat_map(|roundtrip| roundtrip.reply.iter().copied())
+            .collect()
+    }
+}
+
+fn len_u32(len: usize) -> HdbResult<u32> {
+    u32::try_from(len).map_err(|_| usage_err!("Protocol recording is too big"))
+}
+
+// Like util_sync::parse_bytes(), the preallocation is capped, so that a corrupt length
+// cannot exhaust the memory.
+fn read_bytes<R: Read>(r: &mut R) -> HdbResult<Vec<u8>> {
+    const MAX_PREALLOCATION: usize = 64 * 1024;
+    let len = r.read_u32::<LittleEndian>()?;
+    let mut bytes = Vec::<u8>::with_capacity((len as usize).min(MAX_PREALLOCATION));
+    r.take(u64::from(len)).read_to_end(&mut bytes)?;
+    if bytes.len() == len as usize {
+        Ok(bytes)
+    } else {
+        Err(usage_err!(
+            "Corrupt protocol recording: expected {len} bytes, but only {} are available",
+            bytes.len()
+        ))
+    }
+}
+
+// Passes the bytes through and keeps a copy of them.
+pub(crate) struct Recorder<'a, T: ?Sized> {
+    inner: &'a mut T,
+    bytes: &'a mut Vec<u8>,
+}
+impl<'a, T: ?Sized> Recorder<'a, T> {
+    pub(crate) fn new(inner: &'a mut T, bytes: &'a mut Vec<u8>) -> Self {
+        Self { inner, bytes }
+    }
+}
+impl<T: Read + ?Sized> Read for Recorder<'_, T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.bytes.extend_from_slice(&buf[0..n]);
+        Ok(n)
+    }
+}
+impl<T: Write + ?Sized> Write for Recorder<'_, T> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.bytes.extend_from_slice(&buf[0..n]);
+        Ok(n)
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ProtocolRecording;
+    use crate::protocol::parts::{
+        ConnOptId, ConnectOptionsPart, OptionValue, ServerConnectOptions,
+    };
+
+    #[test]
+    fn test_write_and_read() {
+        let mut part = ConnectOptionsPart::default();
+        part.insert(ConnOptId::DataFormatVersion2, OptionValue::INT(8));
+        part.insert(
+            ConnOptId::FullVersionString,
+            OptionValue::STRING("2.00.080.00.1234567890".to_string()),
+        );
+        let mut recording = ProtocolRecording::new(&ServerConnectOptions::new(part)).unwrap();
+        recording.push(vec![1, 2, 3], vec![4, 5]);
+        recording.push(vec![6], vec![7, 8, 9]);
+
+        let mut buffer = Vec::<u8>::new();
+        recording.write_to(&mut buffer).unwrap();
+        let read = ProtocolRecording::read_from(&mut buffer.as_slice()).unwrap();
+        assert_eq!(read, recording);
+        assert_eq!(read.replies(), vec![4, 5, 7, 8, 9]);
+        let options = ServerConnectOptions::new(read.connect_options().unwrap());
+        assert_eq!(options.data_format_version_2(), Some(8));
+        assert_eq!(
+            options.full_version_string(),
+            Some("2.00.080.00.1234567890")
+        );
+
+        assert!(ProtocolRecording::read_from(&mut &b"something else"[..]).is_err());
+    }
+
+    // Lengths and counts in the header are not trusted for allocating memory.
+    #[test]
+    fn test_read_corrupt() {
+        use crate::HdbError;
+
+        // a header that announces far more data than it contains
+        let mut oversized = ProtocolRecording::MAGIC.to_vec();
+        oversized.extend_from_slice(&0_u32.to_le_bytes());
+        oversized.extend_from_slice(&0_u32.to_le_bytes());
+        oversized.extend_from_slice(&u32::MAX.to_le_bytes());
+        assert!(ProtocolRecording::read_from(&mut oversized.as_slice()).is_err());
+
+        let mut oversized = ProtocolRecording::MAGIC.to_vec();
+        oversized.extend_from_slice(&0_u32.to_le_bytes());
+        oversized.extend_from_slice(&u32::MAX.to_le_bytes());
+        oversized.extend_from_slice(&[1, 2, 3]);
+        assert!(matches!(
+            ProtocolRecording::read_from(&mut oversized.as_slice()),
+            Err(HdbError::Usage(_))
+        ));
+
+        // a truncated recording
+        let mut recording =
+            ProtocolRecording::new(&ServerConnectOptions::new(ConnectOptionsPart::default()))
+                .unwrap();
+        recording.push(vec![1, 2, 3], vec![4, 5]);
+        let mut buffer = Vec::<u8>::new();
+        recording.write_to(&mut buffer).unwrap();
+        assert!(matches!(
+            ProtocolRecording::read_from(&mut &buffer[..buffer.len() - 1]),
+            Err(HdbError::Usage(_))
+        ));
+    }
+
+    // Replays recorded replies through a connection, as it would be done offline
+    // to reproduce an issue with parsing the replies of a real session.
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_replay() {
+        use crate::{
+            sync::Connection,
+            test_utils::{PartBuilder, ReplyBuilder},
+        };
+
+        let mut part = ConnectOptionsPart::default();
+        part.insert(ConnOptId::DataFormatVersion2, OptionValue::INT(8));
+        let mut recording = ProtocolRecording::new(&ServerConnectOptions::new(part)).unwrap();
+        // an INSERT (function code 2) with an ExecutionResults part (kind 12)
+        recording.push(
+            vec![],
+            ReplyBuilder::new()
+                .with_reply_type(2)
+                .with_part(PartBuilder::new(12, 1).push_i32(3))
+                .build(),
+        );
+        // an error segment with an Error part (kind 6)
+        recording.push(
+            vec![],
+            ReplyBuilder::new()
+                .as_error()
+                .with_part(PartBuilder::error(259, "HY000", "boom!"))
+                .build(),
+        );
+
+        // the persisted recording is replayed
+        let mut buffer = Vec::<u8>::new();
+        recording.write_to(&mut buffer).unwrap();
+        let recording = ProtocolRecording::read_from(&mut buffer.as_slice()).unwrap();
+        let connection = Connection::replay(&recording).unwrap();
+        assert_eq!(connection.data_format_version_2().unwrap(), 8);
+
+        assert_eq!(
+            connection
+                .dml("insert into T select * from S")
+                .unwrap()
+                .count(),
+            Some(3)
+        );
+
+        let err = connection.exec("select * from NOT_EXISTING").unwrap_err();
+        let server_error = err.server_error().unwrap();
+        assert_eq!(server_error.code(), 259);
+        assert_eq!(server_error.text(), "boom!");
+
+        // no more replies were recorded
+        assert!(connection.exec("commit").is_err());
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{HdbError, HdbResult};
+use std::sync::Arc;
+
+// A hook that is called before statements are executed, with the statement text
+// and the number of input parameters; an error vetoes the execution.
+// See Connection::set_statement_hook().
+pub(crate) type StatementHook = Arc<dyn Fn(&str, usize) -> Result<(), String> + Send + Sync>;
+
+// Lets the hook, if any, decide whether the statement can be executed.
+pub(crate) fn check_statement(
+    o_hook: Option<StatementHook>,
+    stmt: &str,
+    parameter_count: usize,
+) -> HdbResult<()> {
+    match o_hook {
+        Some(hook) => hook(stmt, parameter_count).map_err(|reason| HdbError::Vetoed { reason }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_statement, StatementHook};
+    use crate::HdbError;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_check_statement() {
+        assert!(check_statement(None, "DROP TABLE ORDERS", 0).is_ok());
+
+        let hook: StatementHook = Arc::new(|stmt, parameter_count| {
+            if stmt.trim_start().to_uppercase().starts_with("DROP") {
+                Err(format!(
+                    "DROP is not allowed ({parameter_count} parameters)"
+                ))
+            } else {
+                Ok(())
+            }
+        });
+        assert!(check_statement(Some(hook.clone()), "select * from dummy", 0).is_ok());
+        match check_statement(Some(hook), " drop table ORDERS", 0) {
+            Err(HdbError::Vetoed { reason }) => {
+                assert_eq!(reason, "DROP is not allowed (0 parameters)");
+            }
+            _ => panic!("statement was not vetoed"),
+        }
+    }
+}
@@ -4,9 +4,13 @@ mod async_plain_tcp_client;
 mod async_tls_tcp_client;
 #[cfg(feature = "sync")]
 mod sync_plain_tcp_client;
+#[cfg(feature = "mock")]
+mod sync_replay_client;
 #[cfg(feature = "sync")]
 mod sync_tls_tcp_client;
 
+#[cfg(all(feature = "sync", feature = "async"))]
+use crate::impl_err;
 #[cfg(feature = "sync")]
 use crate::HdbError;
 use crate::{ConnectParams, HdbResult};
@@ -19,6 +23,8 @@ use std::time::Duration;
 use std::time::Instant;
 #[cfg(feature = "sync")]
 use sync_plain_tcp_client::SyncPlainTcpClient;
+#[cfg(feature = "mock")]
+pub(crate) use sync_replay_client::SyncReplayClient;
 #[cfg(feature = "sync")]
 use sync_tls_tcp_client::SyncTlsTcpClient;
 
@@ -34,6 +40,10 @@ pub(crate) enum TcpClient {
     #[cfg(feature = "sync")]
     SyncTls(SyncTlsTcpClient),
 
+    // Serves recorded replies, without a server.
+    #[cfg(feature = "mock")]
+    SyncReplay(SyncReplayClient),
+
     // A buffered async tcp connection without TLS.
     #[cfg(feature = "async")]
     AsyncPlain(AsyncPlainTcpClient),
@@ -55,12 +65,13 @@ impl TcpClient {
     pub fn try_new_sync(
         params: ConnectParams,
         o_timeout: Option<std::time::Duration>,
+        o_handshake_timeout: Option<std::time::Duration>,
     ) -> HdbResult<Self> {
         let start = Instant::now();
         trace!("TcpClient: Connecting to {:?})", params.addr());
 
         let mut tcp_conn = if params.is_tls() {
-            Self::SyncTls(SyncTlsTcpClient::try_new(params)?)
+            Self::SyncTls(SyncTlsTcpClient::try_new(params, o_handshake_timeout)?)
         } else {
             Self::SyncPlain(SyncPlainTcpClient::try_new(params)?)
         };
@@ -76,12 +87,15 @@ impl TcpClient {
     // Constructs a buffered tcp connection, with or without TLS,
     // depending on the given connection parameters.
     #[cfg(feature = "async")]
-    pub async fn try_new_async(params: ConnectParams) -> HdbResult<Self> {
+    pub async fn try_new_async(
+        params: ConnectParams,
+        o_handshake_timeout: Option<std::time::Duration>,
+    ) -> HdbResult<Self> {
         let start = Instant::now();
         trace!("TcpClient: Connecting to {:?})", params.addr());
 
         let tcp_conn = if params.is_tls() {
-            Self::AsyncTls(AsyncTlsTcpClient::try_new(params).await?)
+            Self::AsyncTls(AsyncTlsTcpClient::try_new(params, o_handshake_timeout).await?)
         } else {
             Self::AsyncPlain(AsyncPlainTcpClient::try_new(params).await?)
         };
@@ -101,6 +115,8 @@ impl TcpClient {
             Self::SyncPlain(_) => "Sync Plain TCP",
             #[cfg(feature = "sync")]
             Self::SyncTls(_) => "Sync TLS TCP",
+            #[cfg(feature = "mock")]
+            Self::SyncReplay(_) => "Sync Replay",
             #[cfg(feature = "async")]
             Self::AsyncPlain(_) => "Async Plain TCP",
             #[cfg(feature = "async")]
@@ -115,6 +131,8 @@ impl TcpClient {
             Self::SyncPlain(cl) => cl.connect_params(),
             #[cfg(feature = "sync")]
             Self::SyncTls(cl) => cl.connect_params(),
+            #[cfg(feature = "mock")]
+            Self::SyncReplay(cl) => cl.connect_params(),
             #[cfg(feature = "async")]
             Self::AsyncPlain(cl) => cl.connect_params(),
             #[cfg(feature = "async")]
@@ -131,9 +149,41 @@ impl TcpClient {
         match self {
             Self::SyncPlain(cl) => Ok(cl.set_read_timeout(client_timeout)?),
             Self::SyncTls(cl) => Ok(cl.set_read_timeout(client_timeout)?),
+            #[cfg(feature = "mock")]
+            Self::SyncReplay(_) => Ok(()),
             Self::Dead { .. } => Err(HdbError::ConnectionBroken { source: None }),
             #[cfg(feature = "async")]
-            _ => unimplemented!(),
+            _ => Err(impl_err!(
+                "Socket timeouts of async connections cannot be set"
+            )),
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    pub(crate) fn set_write_timeout_sync(
+        &mut self,
+        client_timeout: Option<Duration>,
+    ) -> HdbResult<()> {
+        match self {
+            Self::SyncPlain(cl) => Ok(cl.set_write_timeout(client_timeout)?),
+            Self::SyncTls(cl) => Ok(cl.set_write_timeout(client_timeout)?),
+            #[cfg(feature = "mock")]
+            Self::SyncReplay(_) => Ok(()),
+            Self::Dead { .. } => Err(HdbError::ConnectionBroken { source: None }),
+            #[cfg(feature = "async")]
+            _ => Err(impl_err!(
+                "Socket timeouts of async connections cannot be set"
+            )),
+        }
+    }
+
+    #[cfg(feature = "sync")]
+    pub(crate) fn is_sync(&self) -> bool {
+        match self {
+            Self::SyncPlain(_) | Self::SyncTls(_) => true,
+            #[cfg(feature = "mock")]
+            Self::SyncReplay(_) => true,
+            _ => false,
         }
     }
 
@@ -153,3 +203,36 @@ impl Drop for TcpClient {
         trace!("Drop of TcpClient");
     }
 }
+
+#[cfg(all(test, feature = "sync", feature = "async"))]
+mod test {
+    use super::TcpClient;
+    use crate::ConnectParams;
+    use std::time::Duration;
+
+    // Socket timeouts of async connections are applied per roundtrip,
+    // so the sync setters must fail rather than panic
+    #[test]
+    fn test_sync_timeouts_of_async_client() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let params = ConnectParams::builder()
+                .hostname("127.0.0.1")
+                .port(listener.local_addr().unwrap().port())
+                .dbuser("USER")
+                .password("PASSWORD")
+                .build()
+                .unwrap();
+
+            let mut tcp_client = TcpClient::try_new_async(params, None).await.unwrap();
+            assert!(!tcp_client.is_sync());
+            let timeout = Some(Duration::from_secs(1));
+            assert!(tcp_client.set_read_timeout_sync(timeout).is_err());
+            assert!(tcp_client.set_write_timeout_sync(timeout).is_err());
+        });
+    }
+}
@@ -1,6 +1,6 @@
 use crate::{ConnectParams, HdbError, HdbResult};
 use rustls::pki_types::ServerName;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::net::TcpStream;
 use tokio_rustls::{client::TlsStream, TlsConnector};
 
@@ -11,19 +11,26 @@ pub(crate) struct AsyncTlsTcpClient {
 }
 
 impl AsyncTlsTcpClient {
-    pub async fn try_new(params: ConnectParams) -> HdbResult<Self> {
+    pub async fn try_new(
+        params: ConnectParams,
+        o_handshake_timeout: Option<Duration>,
+    ) -> HdbResult<Self> {
         let a_client_config = Arc::new(params.rustls_clientconfig()?.0);
         let server_name = ServerName::try_from(params.host().to_owned())?;
 
         let tls_connector = TlsConnector::from(a_client_config);
 
         let tcp_stream = TcpStream::connect(params.addr()).await?;
-        let tls_stream = tls_connector
-            .connect(server_name, tcp_stream)
-            .await
-            .map_err(|e| HdbError::TlsInit {
-                source: Box::new(e),
-            })?;
+        let handshake = tls_connector.connect(server_name, tcp_stream);
+        let tls_stream = match o_handshake_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, handshake)
+                .await
+                .map_err(|_| HdbError::TlsHandshakeTimeout { timeout })?,
+            None => handshake.await,
+        }
+        .map_err(|e| HdbError::TlsInit {
+            source: Box::new(e),
+        })?;
         Ok(AsyncTlsTcpClient { params, tls_stream })
     }
 
@@ -39,3 +46,43 @@ impl AsyncTlsTcpClient {
         &mut self.tls_stream
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::AsyncTlsTcpClient;
+    use crate::{ConnectParams, HdbError};
+    use std::time::{Duration, Instant};
+    use tokio::net::TcpListener;
+
+    // The listener accepts the connection, but never answers the TLS handshake.
+    #[test]
+    fn test_handshake_timeout() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let params = ConnectParams::builder()
+                .hostname("127.0.0.1")
+                .port(listener.local_addr().unwrap().port())
+                .dbuser("USER")
+                .password("PASSWORD")
+                .tls_without_server_verification()
+                .build()
+                .unwrap();
+
+            let timeout = Duration::from_millis(200);
+            let start = Instant::now();
+            let err = AsyncTlsTcpClient::try_new(params, Some(timeout))
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, HdbError::TlsHandshakeTimeout { timeout: t } if t == timeout),
+                "{err:?}"
+            );
+            assert!(start.elapsed() >= timeout);
+            assert!(start.elapsed() < Duration::from_secs(5));
+        });
+    }
+}
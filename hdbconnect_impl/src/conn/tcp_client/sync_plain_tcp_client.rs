@@ -24,6 +24,13 @@ impl SyncPlainTcpClient {
         self.tcp_stream.set_read_timeout(o_duration)
     }
 
+    pub(crate) fn set_write_timeout(
+        &mut self,
+        o_duration: Option<Duration>,
+    ) -> std::io::Result<()> {
+        self.tcp_stream.set_write_timeout(o_duration)
+    }
+
     pub fn writer(&mut self) -> &mut TcpStream {
         &mut self.tcp_stream
     }
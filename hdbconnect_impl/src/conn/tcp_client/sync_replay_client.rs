@@ -0,0 +1,32 @@
+use crate::ConnectParams;
+use std::io::{Cursor, Sink};
+
+// Serves recorded replies instead of talking to a server; requests are discarded.
+#[derive(Debug)]
+pub(crate) struct SyncReplayClient {
+    params: ConnectParams,
+    replies: Cursor<Vec<u8>>,
+    requests: Sink,
+}
+
+impl SyncReplayClient {
+    pub fn new(params: ConnectParams, replies: Vec<u8>) -> Self {
+        Self {
+            params,
+            replies: Cursor::new(replies),
+            requests: std::io::sink(),
+        }
+    }
+
+    pub fn connect_params(&self) -> &ConnectParams {
+        &self.params
+    }
+
+    pub fn writer(&mut self) -> &mut Sink {
+        &mut self.requests
+    }
+
+    pub fn reader(&mut self) -> &mut Cursor<Vec<u8>> {
+        &mut self.replies
+    }
+}
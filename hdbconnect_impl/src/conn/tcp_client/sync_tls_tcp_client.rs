@@ -1,10 +1,15 @@
-use crate::{ConnectParams, HdbResult};
+use crate::{ConnectParams, HdbError, HdbResult};
 use rustls::{pki_types::ServerName, ClientConnection, StreamOwned};
-use std::{sync::Arc, time::Duration};
+use std::{
+    io::ErrorKind,
+    net::TcpStream,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 pub(crate) struct SyncTlsTcpClient {
     params: ConnectParams,
-    tls_stream: StreamOwned<ClientConnection, std::net::TcpStream>,
+    tls_stream: StreamOwned<ClientConnection, TcpStream>,
 }
 impl std::fmt::Debug for SyncTlsTcpClient {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
@@ -12,9 +17,12 @@ impl std::fmt::Debug for SyncTlsTcpClient {
     }
 }
 impl SyncTlsTcpClient {
-    pub fn try_new(params: ConnectParams) -> HdbResult<Self> {
+    pub fn try_new(
+        params: ConnectParams,
+        o_handshake_timeout: Option<Duration>,
+    ) -> HdbResult<Self> {
         Ok(Self {
-            tls_stream: try_new_tls_stream(&params)?,
+            tls_stream: try_new_tls_stream(&params, o_handshake_timeout)?,
             params,
         })
     }
@@ -27,6 +35,13 @@ impl SyncTlsTcpClient {
         self.tls_stream.sock.set_read_timeout(o_duration)
     }
 
+    pub(crate) fn set_write_timeout(
+        &mut self,
+        o_duration: Option<Duration>,
+    ) -> std::io::Result<()> {
+        self.tls_stream.sock.set_write_timeout(o_duration)
+    }
+
     pub(crate) fn writer(&mut self) -> &mut dyn std::io::Write {
         &mut self.tls_stream
     }
@@ -38,15 +53,84 @@ impl SyncTlsTcpClient {
 
 fn try_new_tls_stream(
     params: &ConnectParams,
-) -> HdbResult<StreamOwned<ClientConnection, std::net::TcpStream>> {
+    o_handshake_timeout: Option<Duration>,
+) -> HdbResult<StreamOwned<ClientConnection, TcpStream>> {
     let a_client_config = Arc::new(params.rustls_clientconfig()?.0);
     let server_name = ServerName::try_from(params.host().to_owned())?;
-    let client_connection = ClientConnection::new(a_client_config, server_name)?;
+    let mut client_connection = ClientConnection::new(a_client_config, server_name)?;
     debug!("ClientConnection: {client_connection:?}");
 
     debug!("Connecting to {:?}", params.addr());
-    let tcpstream = std::net::TcpStream::connect(params.addr())?;
+    let mut tcpstream = TcpStream::connect(params.addr())?;
     trace!("tcpstream working");
 
+    // without timeout, the handshake is done implicitly with the first request
+    if let Some(timeout) = o_handshake_timeout {
+        handshake(&mut client_connection, &mut tcpstream, timeout)?;
+        trace!("TLS handshake done");
+    }
+
     Ok(StreamOwned::new(client_connection, tcpstream))
 }
+
+// Completes the TLS handshake, using socket timeouts to give up when the time is over.
+fn handshake(
+    client_connection: &mut ClientConnection,
+    tcpstream: &mut TcpStream,
+    timeout: Duration,
+) -> HdbResult<()> {
+    let deadline = Instant::now() + timeout;
+    while client_connection.is_handshaking() {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(HdbError::TlsHandshakeTimeout { timeout });
+        }
+        tcpstream.set_read_timeout(Some(remaining))?;
+        tcpstream.set_write_timeout(Some(remaining))?;
+        if let Err(e) = client_connection.complete_io(tcpstream) {
+            return Err(match e.kind() {
+                ErrorKind::WouldBlock | ErrorKind::TimedOut => {
+                    HdbError::TlsHandshakeTimeout { timeout }
+                }
+                _ => HdbError::from(e),
+            });
+        }
+    }
+    tcpstream.set_read_timeout(None)?;
+    tcpstream.set_write_timeout(None)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::SyncTlsTcpClient;
+    use crate::{ConnectParams, HdbError};
+    use std::{
+        net::TcpListener,
+        time::{Duration, Instant},
+    };
+
+    // The listener accepts the connection, but never answers the TLS handshake.
+    #[test]
+    fn test_handshake_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let params = ConnectParams::builder()
+            .hostname("127.0.0.1")
+            .port(listener.local_addr().unwrap().port())
+            .dbuser("USER")
+            .password("PASSWORD")
+            .tls_without_server_verification()
+            .build()
+            .unwrap();
+
+        let timeout = Duration::from_millis(200);
+        let start = Instant::now();
+        let err = SyncTlsTcpClient::try_new(params, Some(timeout)).unwrap_err();
+        assert!(
+            matches!(err, HdbError::TlsHandshakeTimeout { timeout: t } if t == timeout),
+            "{err:?}"
+        );
+        assert!(start.elapsed() >= timeout);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
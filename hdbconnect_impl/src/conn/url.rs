@@ -23,9 +23,19 @@
 //! - `client_locale=<value>` is used in language-dependent handling within the
 //!   SAP HANA database calculation engine
 //! - `client_locale_from_env` (no value) lets the driver read the client's locale from the
-//!   environment variabe LANG
+//!   environment variables `LC_ALL`, `LC_MESSAGES`, or `LANG`
 //! - `<networkgroup>` = a network group
 //! - `no_compression` disables the support for compression
+//! - `default_schema=<schema>` sets the current schema after connect and after reconnect
+//! - `application=<name>` sets the application name that identifies the session on the server,
+//!   see [`ClientInfo`](crate::ClientInfo)
+//! - options that override the respective values of the
+//!   [`ConnectionConfiguration`](crate::ConnectionConfiguration):
+//!   - `auto_commit=<true|false>`
+//!   - `cursor_holdability=<none|commit|rollback|commit_and_rollback>`
+//!   - `fetch_size=<number of rows>`
+//!   - `lob_read_length=<number of bytes or chars>`
+//!   - `read_timeout=<seconds>`, where 0 means no timeout
 //! - the [TLS](https://en.wikipedia.org/wiki/Transport_Layer_Security) options:
 // FIXME not only pem files!!
 //!   - `tls_certificate_dir=<value>` points to a folder with pem files that contain
@@ -98,3 +108,24 @@ pub const NETWORK_GROUP: &str = "network_group";
 
 /// Option-key for switching off compression
 pub const NO_COMPRESSION: &str = "no_compression";
+
+/// Option-key for denoting the schema that is set as current schema after connect.
+pub const DEFAULT_SCHEMA: &str = "default_schema";
+
+/// Option-key for denoting the application name that is transmitted as client info.
+pub const APPLICATION: &str = "application";
+
+/// Option-key for overriding the auto-commit behavior of the `ConnectionConfiguration`.
+pub const AUTO_COMMIT: &str = "auto_commit";
+
+/// Option-key for overriding the cursor holdability of the `ConnectionConfiguration`.
+pub const CURSOR_HOLDABILITY: &str = "cursor_holdability";
+
+/// Option-key for overriding the fetch size of the `ConnectionConfiguration`.
+pub const FETCH_SIZE: &str = "fetch_size";
+
+/// Option-key for overriding the lob read length of the `ConnectionConfiguration`.
+pub const LOB_READ_LENGTH: &str = "lob_read_length";
+
+/// Option-key for overriding the read timeout (in seconds) of the `ConnectionConfiguration`.
+pub const READ_TIMEOUT: &str = "read_timeout";
@@ -0,0 +1,232 @@
+use std::{
+    sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Describes a request that has been in flight longer than the configured watchdog threshold.
+///
+/// See `ConnectionConfiguration::with_watchdog_threshold` and
+/// `Connection::set_watchdog_handler`.
+#[derive(Clone, Debug)]
+pub struct SlowRequest {
+    session_id: i64,
+    request_type: String,
+    o_label: Option<String>,
+    elapsed: Duration,
+}
+impl SlowRequest {
+    /// Returns the session id of the connection that sent the request.
+    #[must_use]
+    pub fn session_id(&self) -> i64 {
+        self.session_id
+    }
+    /// Returns the type of the request, like `ExecuteDirect` or `FetchNext`.
+    #[must_use]
+    pub fn request_type(&self) -> &str {
+        &self.request_type
+    }
+    /// Returns a label for the statement that is processed by the request, if available:
+    /// the first keyword of the statement, or, with
+    /// `ConnectionConfiguration::with_sql_in_watchdog(true)`, the beginning of the statement text;
+    /// or the id of the prepared statement or result set.
+    #[must_use]
+    pub fn label(&self) -> Option<&str> {
+        self.o_label.as_deref()
+    }
+    /// Returns how long the request was in flight when the watchdog reported it.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+impl std::fmt::Display for SlowRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "request {} of session {} in flight for {} ms",
+            self.request_type,
+            self.session_id,
+            self.elapsed.as_millis()
+        )?;
+        if let Some(ref label) = self.o_label {
+            write!(f, " ({label})")?;
+        }
+        Ok(())
+    }
+}
+
+// Is called by the watchdog for each request that exceeds the threshold;
+// see Connection::set_watchdog_handler().
+pub(crate) type WatchdogHandler = Arc<dyn Fn(&SlowRequest) + Send + Sync>;
+
+// A request that is registered with the watchdog.
+struct InFlight {
+    id: u64,
+    since: Instant,
+    threshold: Duration,
+    slow_request: SlowRequest,
+    o_handler: Option<WatchdogHandler>,
+    reported: bool,
+}
+
+// Keeps track of the requests in flight of all connections that use a watchdog threshold.
+// A single background thread is started with the first registration;
+// it sleeps until the next registered request becomes due, and reports each request at most once.
+#[derive(Default)]
+struct Watchdog {
+    state: Mutex<WatchdogState>,
+    condvar: Condvar,
+}
+#[derive(Default)]
+struct WatchdogState {
+    next_id: u64,
+    in_flight: Vec<InFlight>,
+}
+
+// Unregisters the request when it is dropped, i.e., when the roundtrip is finished.
+pub(crate) struct WatchdogGuard {
+    id: u64,
+}
+impl Drop for WatchdogGuard {
+    fn drop(&mut self) {
+        watchdog().lock().in_flight.retain(|r| r.id != self.id);
+    }
+}
+
+// Registers a request with the watchdog.
+pub(crate) fn watch(
+    threshold: Duration,
+    session_id: i64,
+    request_type: String,
+    o_label: Option<String>,
+    o_handler: Option<WatchdogHandler>,
+) -> WatchdogGuard {
+    let watchdog = watchdog();
+    let mut state = watchdog.lock();
+    state.next_id += 1;
+    let id = state.next_id;
+    state.in_flight.push(InFlight {
+        id,
+        since: Instant::now(),
+        threshold,
+        slow_request: SlowRequest {
+            session_id,
+            request_type,
+            o_label,
+            elapsed: Duration::ZERO,
+        },
+        o_handler,
+        reported: false,
+    });
+    watchdog.condvar.notify_one();
+    WatchdogGuard { id }
+}
+
+fn watchdog() -> &'static Watchdog {
+    static WATCHDOG: OnceLock<Watchdog> = OnceLock::new();
+    WATCHDOG.get_or_init(|| {
+        if let Err(e) = std::thread::Builder::new()
+            .name("hdbconnect-watchdog".to_string())
+            .spawn(|| watchdog().run())
+        {
+            warn!("watchdog thread could not be started: {e}");
+        }
+        Watchdog::default()
+    })
+}
+
+impl Watchdog {
+    fn lock(&self) -> MutexGuard<'_, WatchdogState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn run(&self) {
+        let mut state = self.lock();
+        loop {
+            let now = Instant::now();
+            let mut due = Vec::new();
+            let mut o_next_due: Option<Instant> = None;
+            for in_flight in state.in_flight.iter_mut().filter(|r| !r.reported) {
+                let due_at = in_flight.since + in_flight.threshold;
+                if due_at <= now {
+                    in_flight.reported = true;
+                    let mut slow_request = in_flight.slow_request.clone();
+                    slow_request.elapsed = now.duration_since(in_flight.since);
+                    due.push((slow_request, in_flight.o_handler.clone()));
+                } else {
+                    o_next_due = Some(o_next_due.map_or(due_at, |next| next.min(due_at)));
+                }
+            }
+
+            // the handlers are called without holding the lock
+            if !due.is_empty() {
+                drop(state);
+                for (slow_request, o_handler) in due {
+                    match o_handler {
+                        Some(handler) => handler(&slow_request),
+                        None => warn!("watchdog: {slow_request}"),
+                    }
+                }
+                state = self.lock();
+                continue;
+            }
+
+            state = match o_next_due {
+                Some(next_due) => {
+                    self.condvar
+                        .wait_timeout(state, next_due.saturating_duration_since(now))
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .condvar
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{watch, SlowRequest, WatchdogHandler};
+    use std::{
+        sync::{mpsc, Arc, Mutex},
+        time::Duration,
+    };
+
+    #[test]
+    fn test_watchdog() {
+        let (sender, receiver) = mpsc::channel::<SlowRequest>();
+        let sender = Mutex::new(sender);
+        let handler: WatchdogHandler = Arc::new(move |slow_request| {
+            sender.lock().unwrap().send(slow_request.clone()).ok();
+        });
+
+        // a request that finishes in time is not reported
+        let guard = watch(
+            Duration::from_secs(60),
+            1,
+            "ExecuteDirect".to_string(),
+            Some("select * from dummy".to_string()),
+            Some(Arc::clone(&handler)),
+        );
+        drop(guard);
+
+        // a slow request is reported exactly once
+        let guard = watch(
+            Duration::from_millis(20),
+            2,
+            "FetchNext".to_string(),
+            Some("result set id 17".to_string()),
+            Some(handler),
+        );
+        let slow_request = receiver.recv_timeout(Duration::from_secs(10)).unwrap();
+        assert_eq!(slow_request.session_id(), 2);
+        assert_eq!(slow_request.request_type(), "FetchNext");
+        assert_eq!(slow_request.label(), Some("result set id 17"));
+        assert!(slow_request.elapsed() >= Duration::from_millis(20));
+        assert!(receiver.recv_timeout(Duration::from_millis(100)).is_err());
+        drop(guard);
+    }
+}
@@ -23,6 +23,8 @@ extern crate serde;
 
 mod base;
 mod conn;
+#[doc(hidden)]
+pub mod macro_support;
 mod protocol;
 mod serde_db_impl;
 mod types_impl;
@@ -35,30 +37,62 @@ pub mod a_sync;
 pub mod sync;
 
 pub use crate::{
-    base::{HdbError, HdbResult, Row, Rows},
+    base::{
+        quote_ident, quote_qualified, BulkLoadReport, ExplainPlan, FailedBatch, GenericConnection,
+        GenericPreparedStatement, HdbError, HdbResult, HintedStatement, IdentifierCase,
+        ParamsBuilder, PlanNode, ResultSetBuilder, Row, Rows, ServerVersion, SessionInfo,
+        StatementBatch,
+    },
     conn::{
-        url, ConnectParams, ConnectParamsBuilder, ConnectionConfiguration, ConnectionStatistics,
-        CursorHoldability, IntoConnectParams, IntoConnectParamsBuilder, ServerCerts,
+        url, ClientDistributionMode, ClientInfo, ConnectParams, ConnectParamsBuilder,
+        ConnectionConfiguration, ConnectionStatistics, CursorHoldability, DisconnectStatistics,
+        IntoConnectParams, IntoConnectParamsBuilder, QueryOptions, ServerCerts, SlowRequest,
     },
     protocol::parts::{
-        ExecutionResult, ExecutionResults, FieldMetadata, HdbValue, OutputParameters,
-        ParameterBinding, ParameterDescriptor, ParameterDescriptors, ParameterDirection,
-        ResultSetMetadata, ServerError, Severity, TypeId,
+        AffectedRowCount, ExecutionResult, ExecutionResults, FieldMetadata, HdbValue,
+        OutputParameters, ParameterBinding, ParameterDescriptor, ParameterDescriptors,
+        ParameterDirection, ResultSetMetadata, ServerConnectOptions, ServerError, Severity, TypeId,
     },
-    protocol::ServerUsage,
+    protocol::{ServerUsage, StatementMetrics},
     serde_db_impl::{time, ToHana},
 };
 
+#[cfg_attr(docsrs, doc(cfg(feature = "mock")))]
+#[cfg(feature = "mock")]
+pub use crate::conn::{ProtocolRecording, RecordedRoundtrip};
+
+#[cfg_attr(docsrs, doc(cfg(feature = "test-utils")))]
+#[cfg(feature = "test-utils")]
+pub use crate::protocol::test_utils;
+
 pub use serde_db::{de::DeserializationError, ser::SerializationError};
 
+#[cfg_attr(docsrs, doc(cfg(feature = "migrations")))]
+#[cfg(feature = "migrations")]
+pub use crate::base::{Migration, Migrations};
+
 /// Non-standard types that are used to represent database values.
 ///
 /// A `ResultSet` contains a sequence of `Row`s, each row is a sequence of `HdbValue`s.
 /// Some  variants of `HdbValue` are implemented using plain rust types,
 /// others are based on the types in this module.
+///
+/// # Timestamps and time zones
+///
+/// HANA stores timestamps without time zone; values that were written with
+/// `CURRENT_TIMESTAMP` are in the server's local time, so a naive conversion
+/// misinterprets them if the server does not run in UTC.
+/// [`LongDate::to_offset_date_time`](crate::types::LongDate::to_offset_date_time) and
+/// [`SecondDate::to_offset_date_time`](crate::types::SecondDate::to_offset_date_time)
+/// therefore take the UTC offset of the server, which can be obtained with
+/// `Connection::server_utc_offset()`; for values that were written in UTC,
+/// use `UtcOffset::UTC`.
+/// The given offset is applied as is, so with a server time zone that has daylight
+/// saving time, the conversion is only correct for values from the period
+/// in which the server had this offset.
 pub mod types {
     pub use crate::types_impl::{
         daydate::DayDate, lob::CharLobSlice, longdate::LongDate, seconddate::SecondDate,
-        secondtime::SecondTime,
+        secondtime::SecondTime, typed_null::TypedNull, wkb::Wkb,
     };
 }
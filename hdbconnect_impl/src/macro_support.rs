@@ -0,0 +1,128 @@
+// Support for the code that is generated by `hdbconnect::hana_query!`.
+//
+// The macro maps each column of the query's result set to one of the kinds below
+// and lets the compiler verify that the type of the corresponding struct field
+// can be deserialized from a column of that kind.
+
+/// Columns of type `TINYINT`, `SMALLINT`, `INT`, `BIGINT`.
+#[derive(Debug)]
+pub struct Integer;
+/// Columns of type `DECIMAL` and `SMALLDECIMAL`.
+#[derive(Debug)]
+pub struct Decimal;
+/// Columns of type `REAL` and `DOUBLE`.
+#[derive(Debug)]
+pub struct Float;
+/// Columns of type `BOOLEAN`.
+#[derive(Debug)]
+pub struct Boolean;
+/// Columns with character data, including `CLOB` and `NCLOB`.
+#[derive(Debug)]
+pub struct Text;
+/// Columns with binary data, including `BLOB` and the spatial types.
+#[derive(Debug)]
+pub struct Binary;
+/// Columns of the date and time types.
+#[derive(Debug)]
+pub struct Temporal;
+
+/// Marks the types into which values of a column kind can be deserialized.
+#[diagnostic::on_unimplemented(
+    message = "a field of type `{Self}` cannot take the values of a `{K}` column",
+    label = "the result set column does not fit to this field"
+)]
+pub trait FromColumn<K> {}
+
+/// Marks the types into which values of a nullable column can be deserialized.
+#[diagnostic::on_unimplemented(
+    message = "a field of type `{Self}` cannot take the values of a nullable `{K}` column",
+    label = "the result set column is nullable, use an `Option`"
+)]
+pub trait FromNullableColumn<K> {}
+
+impl<K, T: FromColumn<K>> FromColumn<K> for Option<T> {}
+impl<K, T: FromColumn<K>> FromNullableColumn<K> for Option<T> {}
+
+macro_rules! from_column {
+    ($kind:ty: $($t:ty),+) => {
+        $(impl FromColumn<$kind> for $t {})+
+    };
+}
+
+from_column!(Integer: i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+from_column!(Decimal: bigdecimal::BigDecimal, f32, f64, String);
+from_column!(Float: f32, f64);
+from_column!(Boolean: bool);
+from_column!(Text: String);
+from_column!(Binary: Vec<u8>);
+from_column!(
+    Temporal: String,
+    crate::time::HanaDate,
+    crate::time::HanaTime,
+    crate::time::HanaPrimitiveDateTime,
+    crate::time::HanaOffsetDateTime,
+    time::Date,
+    time::Time,
+    time::PrimitiveDateTime,
+    time::OffsetDateTime
+);
+
+/// Compiles only if the field can take the values of a non-nullable column of kind `K`.
+pub fn column<K, T: FromColumn<K>>(_field: &T) {}
+
+/// Compiles only if the field can take the values of a nullable column of kind `K`.
+pub fn nullable_column<K, T: FromNullableColumn<K>>(_field: &T) {}
+
+/// Compiles only if each of the required columns is among the columns of the result set.
+///
+/// # Panics
+///
+/// At compile time, if a required column is missing.
+pub const fn assert_columns(required: &[&str], columns: &[&str]) {
+    let mut i = 0;
+    while i < required.len() {
+        let mut found = false;
+        let mut j = 0;
+        while j < columns.len() {
+            if str_eq(required[i], columns[j]) {
+                found = true;
+            }
+            j += 1;
+        }
+        assert!(
+            found,
+            "hana_query!: a field of the struct that has no default has no column in the query"
+        );
+        i += 1;
+    }
+}
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::{assert_columns, str_eq};
+
+    #[test]
+    fn test_assert_columns() {
+        assert!(str_eq("ID", "ID"));
+        assert!(!str_eq("ID", "IDS"));
+        assert!(!str_eq("ID", "Id"));
+        assert_columns(&["ID"], &["NAME", "ID"]);
+        assert_columns(&[], &["NAME"]);
+        assert!(std::panic::catch_unwind(|| assert_columns(&["ID"], &["NAME"])).is_err());
+    }
+}
@@ -12,6 +12,9 @@ mod reply_type;
 mod request;
 
 mod server_usage;
+mod statement_metrics;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 pub(crate) mod util;
 
 #[cfg(feature = "async")]
@@ -20,11 +23,16 @@ pub(crate) mod util_async;
 pub(crate) mod util_sync;
 
 pub(crate) use self::{
-    message_type::MessageType, part::Part, part_attributes::PartAttributes, partkind::PartKind,
-    reply::Reply, reply_type::ReplyType, request::Request,
+    message_type::MessageType,
+    part::Part,
+    part_attributes::PartAttributes,
+    partkind::PartKind,
+    reply::Reply,
+    reply_type::ReplyType,
+    request::{max_parameter_rows_size, Request, MAX_SEGMENTS},
 };
 
-pub use self::server_usage::ServerUsage;
+pub use self::{server_usage::ServerUsage, statement_metrics::StatementMetrics};
 
 const MESSAGE_AND_SEGMENT_HEADER_SIZE: usize = 32 + 24;
 const SEGMENT_HEADER_SIZE: u32 = 24;
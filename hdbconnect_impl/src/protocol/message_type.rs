@@ -48,4 +48,9 @@ impl MessageType {
             Self::ExecuteDirect | Self::Prepare | Self::DbConnectInfo
         )
     }
+
+    // requests that let the server execute a statement
+    pub(crate) fn executes_statement(self) -> bool {
+        matches!(self, Self::ExecuteDirect | Self::Execute)
+    }
 }
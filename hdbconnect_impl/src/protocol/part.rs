@@ -2,7 +2,7 @@
 use crate::protocol::parts::XatOptions;
 use crate::{
     base::RsState,
-    conn::AmConnCore,
+    conn::{AmConnCore, LobBufferPool},
     impl_err,
     protocol::{
         parts::{
@@ -13,7 +13,7 @@ use crate::{
         },
         util, util_sync, PartAttributes, PartKind,
     },
-    ExecutionResults, HdbResult,
+    ExecutionResults, HdbResult, ServerVersion,
 };
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::{cmp::max, io::Write, sync::Arc};
@@ -181,6 +181,7 @@ impl<'a> Part<'a> {
         &self,
         mut remaining_bufsize: u32,
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        o_server_version: Option<&ServerVersion>,
         w: &mut std::io::Cursor<Vec<u8>>,
     ) -> HdbResult<u32> {
         debug!("Serializing part of kind {:?}", self.kind());
@@ -224,7 +225,7 @@ impl<'a> Part<'a> {
             Part::ParameterRows(ref parameters) => {
                 o_a_descriptors
                     .ok_or_else(|| impl_err!("Part::Parameters::emit(): No metadata"))
-                    .and_then(|descriptors| parameters.emit(descriptors, w))?;
+                    .and_then(|descriptors| parameters.emit(descriptors, o_server_version, w))?;
             }
             Part::ReadLobRequest(ref r) => r.emit(w)?,
             Part::ResultSetId(rs_id) => {
@@ -261,22 +262,30 @@ impl<'a> Part<'a> {
     }
 
     #[cfg(feature = "sync")]
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn parse_sync(
         already_received_parts: &mut Parts,
         o_am_conn_core: Option<&AmConnCore>,
         o_a_rsmd: Option<&Arc<ResultSetMetadata>>,
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
         o_rs: &mut Option<&mut RsState>,
+        lob_buffers: &mut LobBufferPool,
         last: bool,
         rdr: &mut std::io::Cursor<Vec<u8>>,
-    ) -> HdbResult<Part<'static>> {
+    ) -> HdbResult<Option<Part<'static>>> {
         trace!("parse()");
-        let (kind, attributes, arg_size, no_of_args) = parse_header(rdr)?;
+        let (raw_kind, attributes, arg_size, no_of_args) = parse_header(rdr)?;
         debug!(
             "parse() found part of kind {:?} with attributes {:?}, arg_size {} and no_of_args {}",
-            kind, attributes, arg_size, no_of_args
+            raw_kind, attributes, arg_size, no_of_args
         );
-        let arg = Part::parse_body_sync(
+        check_part_size(arg_size, no_of_args, rdr)?;
+        let Ok(kind) = PartKind::from_i8(raw_kind) else {
+            skip_part(raw_kind, arg_size, last, rdr);
+            return Ok(None);
+        };
+        let start = rdr.position();
+        let Some(arg) = Part::parse_body_sync(
             kind,
             attributes,
             no_of_args,
@@ -285,8 +294,14 @@ impl<'a> Part<'a> {
             o_a_rsmd,
             o_a_descriptors,
             o_rs,
+            lob_buffers,
             rdr,
-        )?;
+        )?
+        else {
+            rdr.set_position(start);
+            skip_part(raw_kind, arg_size, last, rdr);
+            return Ok(None);
+        };
 
         let padsize = 7 - (arg_size + 7) % 8;
         match (kind, last) {
@@ -298,26 +313,34 @@ impl<'a> Part<'a> {
             }
         }
 
-        Ok(arg)
+        Ok(Some(arg))
     }
 
     #[cfg(feature = "async")]
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn parse_async(
         already_received_parts: &mut Parts<'_>,
         o_am_conn_core: Option<&AmConnCore>,
         o_a_rsmd: Option<&Arc<ResultSetMetadata>>,
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
         o_rs: &mut Option<&mut RsState>,
+        lob_buffers: &mut LobBufferPool,
         last: bool,
         rdr: &mut std::io::Cursor<Vec<u8>>,
-    ) -> HdbResult<Part<'static>> {
+    ) -> HdbResult<Option<Part<'static>>> {
         trace!("parse()");
-        let (kind, attributes, arg_size, no_of_args) = parse_header(rdr)?;
+        let (raw_kind, attributes, arg_size, no_of_args) = parse_header(rdr)?;
         debug!(
             "parse() found part of kind {:?} with attributes {:?}, arg_size {} and no_of_args {}",
-            kind, attributes, arg_size, no_of_args
+            raw_kind, attributes, arg_size, no_of_args
         );
-        let arg = Part::parse_body_async(
+        check_part_size(arg_size, no_of_args, rdr)?;
+        let Ok(kind) = PartKind::from_i8(raw_kind) else {
+            skip_part(raw_kind, arg_size, last, rdr);
+            return Ok(None);
+        };
+        let start = rdr.position();
+        let Some(arg) = Part::parse_body_async(
             kind,
             attributes,
             no_of_args,
@@ -326,9 +349,15 @@ impl<'a> Part<'a> {
             o_a_rsmd,
             o_a_descriptors,
             o_rs,
+            lob_buffers,
             rdr,
         )
-        .await?;
+        .await?
+        else {
+            rdr.set_position(start);
+            skip_part(raw_kind, arg_size, last, rdr);
+            return Ok(None);
+        };
 
         let padsize = 7 - (arg_size + 7) % 8;
         match (kind, last) {
@@ -340,7 +369,7 @@ impl<'a> Part<'a> {
             }
         }
 
-        Ok(arg)
+        Ok(Some(arg))
     }
 
     #[cfg(feature = "sync")]
@@ -354,8 +383,9 @@ impl<'a> Part<'a> {
         o_a_rsmd: Option<&Arc<ResultSetMetadata>>,
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
         o_rs: &mut Option<&mut RsState>,
+        lob_buffers: &mut LobBufferPool,
         rdr: &mut std::io::Cursor<Vec<u8>>,
-    ) -> HdbResult<Part<'a>> {
+    ) -> HdbResult<Option<Part<'a>>> {
         trace!("parse(no_of_args={}, kind={:?})", no_of_args, kind);
 
         let arg = match kind {
@@ -375,7 +405,7 @@ impl<'a> Part<'a> {
             PartKind::ParameterMetadata => {
                 Part::ParameterMetadata(ParameterDescriptors::parse(no_of_args, rdr)?)
             }
-            PartKind::ReadLobReply => Part::ReadLobReply(ReadLobReply::parse(rdr)?),
+            PartKind::ReadLobReply => Part::ReadLobReply(ReadLobReply::parse(lob_buffers, rdr)?),
             PartKind::WriteLobReply => Part::WriteLobReply(WriteLobReply::parse(no_of_args, rdr)?),
             PartKind::ResultSet => {
                 let rs_state_and_a_rsmd = RsState::parse_sync(
@@ -422,14 +452,10 @@ impl<'a> Part<'a> {
             }
             #[cfg(feature = "dist_tx")]
             PartKind::XatOptions => Part::XatOptions(XatOptions::parse(no_of_args, rdr)?),
-            _ => {
-                return Err(impl_err!(
-                    "No handling implemented for received partkind {kind:?}",
-                ));
-            }
+            _ => return Ok(None),
         };
 
-        Ok(arg)
+        Ok(Some(arg))
     }
 
     #[cfg(feature = "async")]
@@ -443,8 +469,9 @@ impl<'a> Part<'a> {
         o_a_rsmd: Option<&Arc<ResultSetMetadata>>,
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
         o_rs: &mut Option<&mut RsState>,
+        lob_buffers: &mut LobBufferPool,
         rdr: &mut std::io::Cursor<Vec<u8>>,
-    ) -> HdbResult<Part<'a>> {
+    ) -> HdbResult<Option<Part<'a>>> {
         trace!("parse(no_of_args={}, kind={:?})", no_of_args, kind);
 
         let arg = match kind {
@@ -467,7 +494,7 @@ impl<'a> Part<'a> {
             PartKind::ParameterMetadata => {
                 Part::ParameterMetadata(ParameterDescriptors::parse(no_of_args, rdr)?)
             }
-            PartKind::ReadLobReply => Part::ReadLobReply(ReadLobReply::parse(rdr)?),
+            PartKind::ReadLobReply => Part::ReadLobReply(ReadLobReply::parse(lob_buffers, rdr)?),
             PartKind::WriteLobReply => Part::WriteLobReply(WriteLobReply::parse(no_of_args, rdr)?),
             PartKind::ResultSet => {
                 let rs_state_and_a_rsmd = RsState::parse_async(
@@ -515,23 +542,43 @@ impl<'a> Part<'a> {
             }
             #[cfg(feature = "dist_tx")]
             PartKind::XatOptions => Part::XatOptions(XatOptions::parse(no_of_args, rdr)?),
-            _ => {
-                return Err(impl_err!(
-                    "No handling implemented for received partkind {kind:?}",
-                ));
-            }
+            _ => return Ok(None),
         };
 
-        Ok(arg)
+        Ok(Some(arg))
+    }
+}
+
+// Parts that this driver does not know or does not handle are skipped, so that newer server
+// versions do not break the communication.
+fn skip_part(raw_kind: i8, arg_size: usize, last: bool, rdr: &mut std::io::Cursor<Vec<u8>>) {
+    warn!("skipping received part of unknown or unhandled kind {raw_kind} ({arg_size} bytes)");
+    let padsize = if last { 0 } else { padsize(arg_size) };
+    rdr.set_position(rdr.position() + (arg_size + padsize) as u64);
+}
+
+// Protects against corrupt part headers: a part cannot be larger than the rest of the reply,
+// and each of its arguments occupies at least one byte.
+fn check_part_size(
+    arg_size: usize,
+    no_of_args: usize,
+    rdr: &std::io::Cursor<Vec<u8>>,
+) -> HdbResult<()> {
+    let remaining = (rdr.get_ref().len() as u64).saturating_sub(rdr.position());
+    if arg_size as u64 > remaining || no_of_args as u64 > remaining {
+        Err(impl_err!(
+            "corrupt reply: part with arg_size {arg_size} and no_of_args {no_of_args} \
+             exceeds the remaining {remaining} bytes"
+        ))
+    } else {
+        Ok(())
     }
 }
 
 #[allow(clippy::cast_sign_loss)]
-fn parse_header(
-    rdr: &mut dyn std::io::Read,
-) -> HdbResult<(PartKind, PartAttributes, usize, usize)> {
+fn parse_header(rdr: &mut dyn std::io::Read) -> HdbResult<(i8, PartAttributes, usize, usize)> {
     // PART HEADER: 16 bytes
-    let kind = PartKind::from_i8(rdr.read_i8()?)?; // I1
+    let kind = rdr.read_i8()?; // I1
     let attributes = PartAttributes::new(rdr.read_u8()?); // U1 (documented as I1)
     let no_of_argsi16 = rdr.read_i16::<LittleEndian>()?; // I2
     let no_of_argsi32 = rdr.read_i32::<LittleEndian>()?; // I4
@@ -539,6 +586,11 @@ fn parse_header(
     rdr.read_i32::<LittleEndian>()?; // I4 remaining_packet_size
 
     let no_of_args = max(i32::from(no_of_argsi16), no_of_argsi32);
+    if arg_size < 0 || no_of_args < 0 {
+        return Err(impl_err!(
+            "corrupt reply: negative arg_size {arg_size} or no_of_args {no_of_args}"
+        ));
+    }
     Ok((kind, attributes, arg_size as usize, no_of_args as usize))
 }
 
@@ -548,3 +600,39 @@ fn padsize(size: usize) -> usize {
         _ => 7 - (size - 1) % 8,
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "sync")]
+mod test {
+    use super::Part;
+    use crate::{conn::LobBufferPool, protocol::parts::Parts};
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    #[test]
+    fn test_skip_unknown_part() {
+        let mut buf = Vec::<u8>::new();
+        buf.write_i8(99).unwrap(); // unknown part kind
+        buf.write_u8(0).unwrap();
+        buf.write_i16::<LittleEndian>(1).unwrap();
+        buf.write_i32::<LittleEndian>(0).unwrap();
+        buf.write_i32::<LittleEndian>(3).unwrap();
+        buf.write_i32::<LittleEndian>(0).unwrap();
+        buf.extend_from_slice(&[1, 2, 3, 0, 0, 0, 0, 0]); // body and padding
+        buf.extend_from_slice(&[42]);
+
+        let mut rdr = std::io::Cursor::new(buf);
+        let o_part = Part::parse_sync(
+            &mut Parts::default(),
+            None,
+            None,
+            None,
+            &mut None,
+            &mut LobBufferPool::default(),
+            false,
+            &mut rdr,
+        )
+        .unwrap();
+        assert!(o_part.is_none());
+        assert_eq!(rdr.position(), 24);
+    }
+}
@@ -9,9 +9,10 @@ mod execution_result;
 mod fetch_options;
 mod field_metadata;
 mod hdb_value;
-mod length_indicator;
+pub(crate) mod length_indicator;
 mod lob_flags;
 mod multiline_option_part;
+mod null_emit_rules;
 mod option_part;
 mod option_value;
 mod output_parameters;
@@ -22,6 +23,7 @@ mod read_lob_reply;
 mod read_lob_request;
 
 mod result_set_metadata;
+mod server_connect_options;
 mod server_error;
 mod session_context;
 mod statement_context;
@@ -44,7 +46,7 @@ pub(crate) use self::{
     db_connect_info::DbConnectInfo,
     lob_flags::LobFlags,
     option_value::OptionValue,
-    parameter_rows::ParameterRows,
+    parameter_rows::{check_values, to_hdb_values, ParameterRows},
     read_lob_reply::ReadLobReply,
     read_lob_request::ReadLobRequest,
     session_context::SessionContext,
@@ -57,7 +59,7 @@ pub(crate) use self::{
 
 pub(crate) use self::partition_information::PartitionInformation;
 pub use self::{
-    execution_result::{ExecutionResult, ExecutionResults},
+    execution_result::{AffectedRowCount, ExecutionResult, ExecutionResults},
     field_metadata::FieldMetadata,
     hdb_value::HdbValue,
     output_parameters::OutputParameters,
@@ -65,13 +67,14 @@ pub use self::{
         ParameterBinding, ParameterDescriptor, ParameterDescriptors, ParameterDirection,
     },
     result_set_metadata::ResultSetMetadata,
+    server_connect_options::ServerConnectOptions,
     server_error::{ServerError, Severity},
     type_id::TypeId,
 };
 
 use crate::{
     base::{InternalReturnValue, RsState},
-    conn::AmConnCore,
+    conn::{AmConnCore, QueryOptions},
     impl_err,
     protocol::{part_attributes::FIRST_PACKET, Part, PartAttributes, PartKind, ServerUsage},
     HdbResult,
@@ -146,6 +149,7 @@ impl Parts<'static> {
         self,
         am_conn_core: &AmConnCore,
         mut o_additional_server_usage: Option<&mut ServerUsage>,
+        o_options: Option<&QueryOptions>,
     ) -> HdbResult<Vec<InternalReturnValue>> {
         let mut conn_core = am_conn_core.lock_sync()?;
         let mut int_return_values = Vec::<InternalReturnValue>::new();
@@ -173,17 +177,19 @@ impl Parts<'static> {
                 Part::ParameterMetadata(pm) => {
                     int_return_values.push(InternalReturnValue::ParameterMetadata(Arc::new(pm)));
                 }
-                Part::RsState(Some(rs_state_and_a_rsmd)) => {
-                    int_return_values.push(InternalReturnValue::RsState(rs_state_and_a_rsmd));
+                Part::RsState(Some((mut rs_state, a_rsmd))) => {
+                    rs_state.apply_settings_sync(conn_core.configuration(), o_options)?;
+                    int_return_values.push(InternalReturnValue::RsState((rs_state, a_rsmd)));
                 }
                 Part::ResultSetMetadata(rsmd) => {
                     if let Some(Part::ResultSetId(rs_id)) = parts.next() {
-                        let rs = RsState::new_sync(
+                        let mut rs = RsState::new_sync(
                             None,
                             am_conn_core,
                             PartAttributes::new(FIRST_PACKET),
                             rs_id,
                         );
+                        rs.apply_settings_sync(conn_core.configuration(), o_options)?;
                         int_return_values.push(InternalReturnValue::RsState((rs, Arc::new(rsmd))));
                     } else {
                         return Err(impl_err!("Missing required part ResultSetID"));
@@ -209,6 +215,7 @@ impl Parts<'static> {
         self,
         am_conn_core: &AmConnCore,
         mut o_additional_server_usage: Option<&mut ServerUsage>,
+        o_options: Option<&QueryOptions>,
     ) -> HdbResult<Vec<InternalReturnValue>> {
         let mut conn_core = am_conn_core.lock_async().await;
         let mut int_return_values = Vec::<InternalReturnValue>::new();
@@ -236,17 +243,22 @@ impl Parts<'static> {
                 Part::ParameterMetadata(pm) => {
                     int_return_values.push(InternalReturnValue::ParameterMetadata(Arc::new(pm)));
                 }
-                Part::RsState(Some(rs_state_and_a_rsmd)) => {
-                    int_return_values.push(InternalReturnValue::RsState(rs_state_and_a_rsmd));
+                Part::RsState(Some((mut rs_state, a_rsmd))) => {
+                    rs_state
+                        .apply_settings_async(conn_core.configuration(), o_options)
+                        .await;
+                    int_return_values.push(InternalReturnValue::RsState((rs_state, a_rsmd)));
                 }
                 Part::ResultSetMetadata(rsmd) => {
                     if let Some(Part::ResultSetId(rs_id)) = parts.next() {
-                        let rs = RsState::new_async(
+                        let mut rs = RsState::new_async(
                             None,
                             am_conn_core,
                             PartAttributes::new(FIRST_PACKET),
                             rs_id,
                         );
+                        rs.apply_settings_async(conn_core.configuration(), o_options)
+                            .await;
                         int_return_values.push(InternalReturnValue::RsState((rs, Arc::new(rsmd))));
                     } else {
                         return Err(impl_err!("Missing required part ResultSetID"));
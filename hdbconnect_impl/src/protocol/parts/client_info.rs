@@ -55,6 +55,35 @@ impl ClientInfo {
     pub fn set_application_user(&mut self, application_user: &str) {
         self.set(ClientInfoKey::ApplicationUser, application_user);
     }
+    pub fn update(&mut self, client_info: &crate::ClientInfo) {
+        if let Some(application) = client_info.application() {
+            self.set_application(application);
+        }
+        if let Some(version) = client_info.application_version() {
+            self.set_application_version(version);
+        }
+        if let Some(user) = client_info.application_user() {
+            self.set_application_user(user);
+        }
+        if let Some(source) = client_info.application_source() {
+            self.set_application_source(source);
+        }
+    }
+    pub fn set_session_variable(&mut self, key: &str, value: &str) {
+        self.set(ClientInfoKey::from(key), value);
+    }
+    // Returns the keys that are set here, but not in the other instance.
+    pub fn keys_not_in(&self, other: &Self) -> Vec<String> {
+        self.0
+            .keys()
+            .filter(|key| !other.0.contains_key(*key))
+            .map(|key| key.as_ref().to_string())
+            .collect()
+    }
+    #[cfg(test)]
+    pub fn session_variable(&self, key: &str) -> Option<&str> {
+        self.0.get(&ClientInfoKey::from(key)).map(String::as_str)
+    }
     fn set_driver(&mut self, driver: &str) {
         self.set(ClientInfoKey::Driver, driver);
     }
@@ -99,6 +128,21 @@ enum ClientInfoKey {
     Driver,
     DriverInfo,
     DriverVersion,
+    Other(String),
+}
+impl From<&str> for ClientInfoKey {
+    fn from(key: &str) -> Self {
+        match key {
+            "APPLICATION" => Self::Application,
+            "APPLICATIONVERSION" => Self::ApplicationVersion,
+            "APPLICATIONSOURCE" => Self::ApplicationSource,
+            "APPLICATIONUSER" => Self::ApplicationUser,
+            "DRIVER" => Self::Driver,
+            "DRIVERINFO" => Self::DriverInfo,
+            "DRIVERVERSION" => Self::DriverVersion,
+            _ => Self::Other(key.to_string()),
+        }
+    }
 }
 impl AsRef<str> for ClientInfoKey {
     fn as_ref(&self) -> &str {
@@ -110,6 +154,45 @@ impl AsRef<str> for ClientInfoKey {
             Self::Driver => "DRIVER",
             Self::DriverInfo => "DRIVERINFO",
             Self::DriverVersion => "DRIVERVERSION",
+            Self::Other(key) => key,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::ClientInfo;
+
+    #[test]
+    fn test_session_variables() {
+        let mut ci = ClientInfo::default();
+        ci.set_session_variable("APPLICATIONUSER", "K2209657");
+        ci.set_session_variable("MY_VAR", "42");
+        assert_eq!(ci.session_variable("APPLICATIONUSER"), Some("K2209657"));
+        assert_eq!(ci.session_variable("MY_VAR"), Some("42"));
+        assert_eq!(ci.session_variable("OTHER_VAR"), None);
+
+        let mut buf = Vec::new();
+        ci.emit(&mut buf).unwrap();
+        assert_eq!(buf.len(), ci.size());
+
+        let mut new_keys = ci.keys_not_in(&ClientInfo::default());
+        new_keys.sort();
+        assert_eq!(new_keys, vec!["APPLICATIONUSER", "MY_VAR"]);
+    }
+
+    #[test]
+    fn test_update() {
+        let mut ci = ClientInfo::default();
+        ci.set_session_variable("APPLICATIONSOURCE", "main.rs");
+        ci.update(
+            &crate::ClientInfo::new()
+                .with_application("MyApp")
+                .with_application_user("K2209657"),
+        );
+        assert_eq!(ci.session_variable("APPLICATION"), Some("MyApp"));
+        assert_eq!(ci.session_variable("APPLICATIONUSER"), Some("K2209657"));
+        assert_eq!(ci.session_variable("APPLICATIONSOURCE"), Some("main.rs"));
+        assert_eq!(ci.session_variable("APPLICATIONVERSION"), None);
+    }
+}
@@ -1,15 +1,19 @@
 use crate::{
-    conn::Compression,
+    conn::{ClientDistributionMode, Compression},
     protocol::parts::{
         option_part::{OptionId, OptionPart},
         option_value::OptionValue,
+        ServerConnectOptions,
     },
-    HdbResult,
+    HdbError, HdbResult,
 };
 
 //const USE_COMPRESSION_REMOTE: u32 = 0x0000_0300; // LZ4Supported (100) & LZ4Enabled (200)
 const USE_COMPRESSION_ALWAYS: u32 = 0x0000_0700; // LZ4Supported (100) & LZ4Enabled (200) & ForceLocal (400)
 
+// Servers that do not support large bulk operations accept at most this number of rows per request.
+const MAX_ROWS_WITHOUT_LARGE_BULK_OPERATIONS: usize = 32_767;
+
 // ConnectOptions are influenced by the application (`ConnectOptionsEnum::Initial`),
 // augmented by the implementation and sent to the server (`ConnectOptionsEnum::for_server()`),
 // and finalized based on the response from the server
@@ -25,11 +29,15 @@ pub(crate) enum ConnectOptions {
         os_user: String,
         o_client_locale: Option<String>,
         compression: Compression,
+        requested_dataformat_version2: u8,
+        o_client_distribution_mode: Option<ClientDistributionMode>,
     },
     Final {
         os_user: String,
         o_client_locale: Option<String>,
         compression: Compression,
+        requested_dataformat_version2: u8,
+        o_client_distribution_mode: Option<ClientDistributionMode>,
 
         client_reconnect_wait_timeout: std::time::Duration,
         dataformat_version2: u8,
@@ -43,12 +51,15 @@ pub(crate) enum ConnectOptions {
         database_name: String,
         full_version: String,
         implicit_lob_streaming: bool,
+        supports_large_bulk_operations: bool,
+        server_connect_options: ServerConnectOptions,
     },
 }
 impl ConnectOptions {
     // Hard-coded defaults
     const CLIENT_RECONNECT_WAIT_TIMEOUT_IN_SECONDS: u32 = 600; // server does not allow more
-    const DATAFORMAT_VERSION2: u8 = 8;
+    pub(crate) const DATAFORMAT_VERSION2: u8 = 8;
+    pub(crate) const MIN_DATAFORMAT_VERSION2: u8 = 4;
     const ENABLE_ARRAY_TYPE: bool = true;
     #[cfg(feature = "alpha_routing")]
     #[allow(dead_code)]
@@ -63,29 +74,60 @@ impl ConnectOptions {
         o_client_locale: Option<&str>,
         os_user: &str,
         compression: Compression,
+        o_dataformat_version2: Option<u8>,
+        o_client_distribution_mode: Option<ClientDistributionMode>,
     ) -> Self {
         ConnectOptions::Initial {
             o_client_locale: o_client_locale.map(ToString::to_string),
             os_user: os_user.to_string(),
             compression,
+            requested_dataformat_version2: o_dataformat_version2
+                .unwrap_or(Self::DATAFORMAT_VERSION2),
+            o_client_distribution_mode,
         }
     }
 
+    #[allow(clippy::too_many_lines)]
     pub(crate) fn for_server(&self) -> ConnectOptionsPart {
         // read user input from initial state
-        let (o_client_locale, os_user, compression, o_connection_id) = match self {
+        let (
+            o_client_locale,
+            os_user,
+            compression,
+            dataformat_version2,
+            o_client_distribution_mode,
+            o_connection_id,
+        ) = match self {
             ConnectOptions::Initial {
                 ref o_client_locale,
                 ref os_user,
                 ref compression,
-            } => (o_client_locale, os_user, compression, None),
+                requested_dataformat_version2,
+                o_client_distribution_mode,
+            } => (
+                o_client_locale,
+                os_user,
+                compression,
+                *requested_dataformat_version2,
+                *o_client_distribution_mode,
+                None,
+            ),
             ConnectOptions::Final {
                 ref o_client_locale,
                 ref os_user,
                 ref compression,
+                requested_dataformat_version2,
+                o_client_distribution_mode,
                 ref connection_id,
                 ..
-            } => (o_client_locale, os_user, compression, Some(connection_id)),
+            } => (
+                o_client_locale,
+                os_user,
+                compression,
+                *requested_dataformat_version2,
+                *o_client_distribution_mode,
+                Some(connection_id),
+            ),
         };
 
         let mut connopts_part = ConnectOptionsPart::default();
@@ -113,9 +155,14 @@ impl ConnectOptions {
             ConnOptId::EnableArrayType,
             OptionValue::BOOLEAN(Self::ENABLE_ARRAY_TYPE),
         );
+        // the server confirms the support, otherwise requests are limited to 32K rows
+        set_opt(
+            ConnOptId::SupportsLargeBulkOperations,
+            OptionValue::BOOLEAN(true),
+        );
         set_opt(
             ConnOptId::DataFormatVersion2,
-            OptionValue::INT(From::from(Self::DATAFORMAT_VERSION2)),
+            OptionValue::INT(From::from(dataformat_version2)),
         );
         set_opt(ConnOptId::OSUser, OptionValue::STRING(os_user.clone()));
 
@@ -142,7 +189,20 @@ impl ConnectOptions {
             Compression::Off => {}
         }
 
-        if cfg!(feature = "alpha_routing") {
+        if let Some(mode) = o_client_distribution_mode {
+            let enabled = mode != ClientDistributionMode::Off;
+            set_opt(
+                ConnOptId::DistributionEnabled,
+                OptionValue::BOOLEAN(enabled),
+            );
+            set_opt(
+                ConnOptId::ClientDistributionMode,
+                OptionValue::INT(mode.code()),
+            );
+            if enabled {
+                set_opt(ConnOptId::DistributionProtocolVersion, OptionValue::INT(1));
+            }
+        } else if cfg!(feature = "alpha_routing") {
             warn!("Feature alpha_routing is active!");
             set_opt(ConnOptId::DistributionEnabled, OptionValue::BOOLEAN(true));
             set_opt(ConnOptId::ClientDistributionMode, OptionValue::INT(0));
@@ -154,28 +214,45 @@ impl ConnectOptions {
         connopts_part
     }
 
+    #[allow(clippy::too_many_lines)]
     pub(crate) fn digest_server_connect_options(
         &mut self,
         incoming: ConnectOptionsPart,
     ) -> HdbResult<()> {
-        let (o_client_locale, os_user, compression) = match *self {
+        let (
+            o_client_locale,
+            os_user,
+            compression,
+            requested_dataformat_version2,
+            o_client_distribution_mode,
+        ) = match *self {
             ConnectOptions::Initial {
                 ref o_client_locale,
                 ref os_user,
                 ref mut compression,
+                requested_dataformat_version2,
+                o_client_distribution_mode,
             }
             | ConnectOptions::Final {
                 // necessary for reconnects
                 ref o_client_locale,
                 ref os_user,
                 ref mut compression,
+                requested_dataformat_version2,
+                o_client_distribution_mode,
                 ..
-            } => (o_client_locale, os_user, compression),
+            } => (
+                o_client_locale,
+                os_user,
+                compression,
+                requested_dataformat_version2,
+                o_client_distribution_mode,
+            ),
         };
         let mut client_reconnect_wait_timeout = std::time::Duration::from_secs(u64::from(
             Self::CLIENT_RECONNECT_WAIT_TIMEOUT_IN_SECONDS,
         ));
-        let mut dataformat_version2 = Self::DATAFORMAT_VERSION2;
+        let mut dataformat_version2 = requested_dataformat_version2;
         let enable_array_type = true;
         #[cfg(feature = "alpha_routing")]
         let alpha_routing = false;
@@ -186,6 +263,8 @@ impl ConnectOptions {
         let mut database_name = String::default();
         let mut full_version = String::default();
         let mut implicit_lob_streaming = false;
+        let mut supports_large_bulk_operations = false;
+        let server_connect_options = ServerConnectOptions::new(incoming.clone());
 
         for (k, v) in incoming {
             match k {
@@ -213,6 +292,9 @@ impl ConnectOptions {
                 ConnOptId::ImplicitLobStreaming => {
                     implicit_lob_streaming = v.get_bool()?;
                 }
+                ConnOptId::SupportsLargeBulkOperations => {
+                    supports_large_bulk_operations = v.get_bool()?;
+                }
                 ConnOptId::CompressionLevelAndFlags => {
                     *compression = {
                         if (v.get_int_as_u32()? & USE_COMPRESSION_ALWAYS) == 0 {
@@ -228,7 +310,6 @@ impl ConnectOptions {
                 | ConnOptId::EngineDataFormatVersion
                 | ConnOptId::DataFormatVersion
                 | ConnOptId::NonTransactionalPrepare
-                | ConnOptId::SupportsLargeBulkOperations
                 | ConnOptId::ActiveActiveProtocolVersion
                 | ConnOptId::CompleteArrayExecution
                 | ConnOptId::QueryTimeoutOK
@@ -248,10 +329,14 @@ impl ConnectOptions {
             };
         }
 
+        Self::check_dataformat_version2(dataformat_version2)?;
+
         *self = ConnectOptions::Final {
             os_user: os_user.clone(),
             o_client_locale: o_client_locale.clone(),
             compression: *compression,
+            requested_dataformat_version2,
+            o_client_distribution_mode,
             client_reconnect_wait_timeout,
             dataformat_version2,
             enable_array_type,
@@ -262,10 +347,47 @@ impl ConnectOptions {
             database_name,
             full_version,
             implicit_lob_streaming,
+            supports_large_bulk_operations,
+            server_connect_options,
         };
         Ok(())
     }
 
+    // Changes the client locale that is sent to the server with a reconnect.
+    pub(crate) fn set_client_locale(&mut self, client_locale: Option<String>) {
+        match self {
+            ConnectOptions::Initial {
+                o_client_locale, ..
+            }
+            | ConnectOptions::Final {
+                o_client_locale, ..
+            } => *o_client_locale = client_locale,
+        }
+    }
+
+    pub(crate) fn get_client_locale(&self) -> Option<&str> {
+        match self {
+            ConnectOptions::Initial {
+                o_client_locale, ..
+            }
+            | ConnectOptions::Final {
+                o_client_locale, ..
+            } => o_client_locale.as_deref(),
+        }
+    }
+
+    fn check_dataformat_version2(dataformat_version2: u8) -> HdbResult<()> {
+        if dataformat_version2 < Self::MIN_DATAFORMAT_VERSION2 {
+            Err(HdbError::DataFormatVersion {
+                feature: "Connecting".to_string(),
+                required: Self::MIN_DATAFORMAT_VERSION2,
+                negotiated: dataformat_version2,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
     // The connection ID is filled by the server when the connection is established.
     // It can be used in DISCONNECT/KILL commands for command or session
     // cancellation.
@@ -300,6 +422,17 @@ impl ConnectOptions {
         }
     }
 
+    // All connect options that were returned by the server.
+    pub(crate) fn server_connect_options(&self) -> &ServerConnectOptions {
+        match &self {
+            ConnectOptions::Initial { .. } => panic_not_final(),
+            ConnectOptions::Final {
+                server_connect_options,
+                ..
+            } => server_connect_options,
+        }
+    }
+
     // Full version string.
     pub(crate) fn get_full_version_string(&self) -> String {
         match &self {
@@ -322,6 +455,8 @@ impl ConnectOptions {
     //   (Support for ALPHANUM, TEXT, SHORTTEXT, LONGDATE, SECONDDATE, DAYDATE, and
     //   SECONDTIME.)
     // 6 Send data type BINTEXT to client.
+    // 7 Support for BOOLEAN.
+    // 8 Support for FIXED8, FIXED12, and FIXED16.
     //
     pub(crate) fn get_dataformat_version2(&self) -> u8 {
         match &self {
@@ -345,6 +480,23 @@ impl ConnectOptions {
         }
     }
 
+    // The maximum number of parameter rows the server accepts with a single request.
+    pub(crate) fn get_max_rows_per_request(&self) -> usize {
+        match &self {
+            ConnectOptions::Initial { .. } => panic_not_final(),
+            ConnectOptions::Final {
+                supports_large_bulk_operations,
+                ..
+            } => {
+                if *supports_large_bulk_operations {
+                    i32::MAX as usize
+                } else {
+                    MAX_ROWS_WITHOUT_LARGE_BULK_OPERATIONS
+                }
+            }
+        }
+    }
+
     // Compression
     pub(crate) fn use_compression(&self) -> bool {
         matches!(
@@ -757,8 +909,10 @@ impl OptionId<ConnOptId> for ConnOptId {
 
 #[cfg(test)]
 mod test {
-    use crate::protocol::parts::connect_options::ConnOptId;
+    use crate::conn::{ClientDistributionMode, Compression};
+    use crate::protocol::parts::connect_options::{ConnOptId, ConnectOptions, ConnectOptionsPart};
     use crate::protocol::parts::option_part::OptionId;
+    use crate::protocol::parts::option_value::OptionValue;
 
     #[test]
     fn test_display() {
@@ -768,4 +922,52 @@ mod test {
             assert_eq!(i, i2);
         }
     }
+
+    #[test]
+    fn test_client_distribution_mode() {
+        let options =
+            |mode| ConnectOptions::new(None, "me", Compression::Off, None, mode).for_server();
+
+        let part = options(Some(ClientDistributionMode::Statement));
+        assert_eq!(
+            part.get(&ConnOptId::ClientDistributionMode).unwrap(),
+            &OptionValue::INT(2)
+        );
+        assert_eq!(
+            part.get(&ConnOptId::DistributionEnabled).unwrap(),
+            &OptionValue::BOOLEAN(true)
+        );
+
+        let part = options(Some(ClientDistributionMode::Off));
+        assert_eq!(
+            part.get(&ConnOptId::DistributionEnabled).unwrap(),
+            &OptionValue::BOOLEAN(false)
+        );
+        assert!(part.get(&ConnOptId::DistributionProtocolVersion).is_err());
+    }
+
+    #[test]
+    fn test_max_rows_per_request() {
+        let negotiated = |o_supported: Option<bool>| {
+            let mut options = ConnectOptions::new(None, "me", Compression::Off, None, None);
+            let part = options.for_server();
+            assert_eq!(
+                part.get(&ConnOptId::SupportsLargeBulkOperations).unwrap(),
+                &OptionValue::BOOLEAN(true)
+            );
+            let mut incoming = ConnectOptionsPart::default();
+            if let Some(supported) = o_supported {
+                incoming.insert(
+                    ConnOptId::SupportsLargeBulkOperations,
+                    OptionValue::BOOLEAN(supported),
+                );
+            }
+            options.digest_server_connect_options(incoming).unwrap();
+            options.get_max_rows_per_request()
+        };
+
+        assert_eq!(negotiated(Some(true)), i32::MAX as usize);
+        assert_eq!(negotiated(Some(false)), 32_767);
+        assert_eq!(negotiated(None), 32_767);
+    }
 }
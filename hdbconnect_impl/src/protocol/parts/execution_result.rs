@@ -1,13 +1,13 @@
-use std::vec::IntoIter;
+use std::{collections::BTreeMap, vec::IntoIter};
 
-use crate::{HdbResult, ServerError};
+use crate::{impl_err, HdbResult, ServerError};
 use byteorder::{LittleEndian, ReadBytesExt};
 
 /// Describes the success of a command.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ExecutionResult {
     /// Number of rows that were affected by the successful execution.
-    RowsAffected(usize),
+    RowsAffected(u64),
     /// Command was successful.
     SuccessNoInfo, // -2
     /// Execution failed with given `ServerError`.
@@ -26,6 +26,61 @@ impl ExecutionResult {
     pub fn is_rows_affected(&self) -> bool {
         matches!(self, Self::RowsAffected(_))
     }
+    /// Returns the number of affected rows, if the server has reported it.
+    ///
+    /// Returns `None` for failures and for `Self::SuccessNoInfo`, where the server
+    /// does not know the number of affected rows.
+    #[must_use]
+    pub fn rows_affected(&self) -> Option<u64> {
+        match self {
+            Self::RowsAffected(count) => Some(*count),
+            _ => None,
+        }
+    }
+}
+
+/// The number of rows that were affected by a DML statement.
+///
+/// The server reports for some statements only that they were executed successfully,
+/// without the number of affected rows (on the wire, this is the code -2).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AffectedRowCount {
+    /// Number of rows that were affected.
+    Count(u64),
+    /// Command was successful, but the server did not report the number of affected rows.
+    SuccessNoInfo,
+}
+impl AffectedRowCount {
+    /// Returns the number of affected rows, if the server has reported it.
+    #[must_use]
+    pub fn count(self) -> Option<u64> {
+        match self {
+            Self::Count(count) => Some(count),
+            Self::SuccessNoInfo => None,
+        }
+    }
+}
+impl PartialEq<u64> for AffectedRowCount {
+    fn eq(&self, other: &u64) -> bool {
+        self.count() == Some(*other)
+    }
+}
+// The sum is only known if all summands are known.
+impl std::iter::Sum for AffectedRowCount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::Count(0), |sum, count| match (sum, count) {
+            (Self::Count(sum), Self::Count(count)) => Self::Count(sum.saturating_add(count)),
+            _ => Self::SuccessNoInfo,
+        })
+    }
+}
+impl std::fmt::Display for AffectedRowCount {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Count(count) => write!(fmt, "{count}"),
+            Self::SuccessNoInfo => write!(fmt, "unknown"),
+        }
+    }
 }
 
 impl std::fmt::Display for ExecutionResult {
@@ -64,14 +119,94 @@ impl std::fmt::Display for ExecutionResults {
     }
 }
 impl ExecutionResults {
+    /// Returns an iterator over the contained execution results.
+    pub fn iter(&self) -> std::slice::Iter<'_, ExecutionResult> {
+        self.0.iter()
+    }
+
+    /// Returns the number of contained execution results.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if no execution result is contained.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the sum of all reported numbers of affected rows.
+    ///
+    /// Failed executions do not contribute to the sum. If the server did not report
+    /// the number of affected rows for a successful execution
+    /// (`ExecutionResult::SuccessNoInfo`), the sum is not known either, and
+    /// `AffectedRowCount::SuccessNoInfo` is returned.
+    #[must_use]
+    pub fn total_affected_rows(&self) -> AffectedRowCount {
+        self.0
+            .iter()
+            .filter_map(|er| match er {
+                ExecutionResult::RowsAffected(count) => Some(AffectedRowCount::Count(*count)),
+                ExecutionResult::SuccessNoInfo => Some(AffectedRowCount::SuccessNoInfo),
+                _ => None,
+            })
+            .sum()
+    }
+
+    /// True if at least one execution has failed.
+    #[must_use]
+    pub fn has_failures(&self) -> bool {
+        self.0.iter().any(|er| {
+            matches!(
+                er,
+                ExecutionResult::Failure(_) | ExecutionResult::ExtraFailure(_)
+            )
+        })
+    }
+
+    /// Returns the indexes of the failed executions, together with the reported errors.
+    ///
+    /// For batch executions, the index is the index of the row in the batch.
+    pub fn failures(&self) -> impl Iterator<Item = (usize, Option<&ServerError>)> {
+        self.0.iter().enumerate().filter_map(|(idx, er)| match er {
+            ExecutionResult::Failure(o_server_error) => Some((idx, o_server_error.as_ref())),
+            ExecutionResult::ExtraFailure(server_error) => Some((idx, Some(server_error))),
+            _ => None,
+        })
+    }
+
+    /// Maps the indexes of the failed input rows of a batch execution to the reported errors.
+    ///
+    /// Rows that failed without a reported error, and errors that the server reported
+    /// in addition (`ExecutionResult::ExtraFailure`), are not contained.
+    /// This allows e.g. retrying or logging just the failed rows.
+    #[must_use]
+    pub fn row_errors(&self) -> BTreeMap<usize, &ServerError> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, er)| match er {
+                ExecutionResult::Failure(Some(server_error)) => Some((idx, server_error)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn new(execution_results: Vec<ExecutionResult>) -> Self {
+        Self(execution_results)
+    }
+
     pub(crate) fn parse(count: usize, rdr: &mut dyn std::io::Read) -> HdbResult<Self> {
         let mut vec = Vec::<ExecutionResult>::with_capacity(count);
         for _ in 0..count {
             match rdr.read_i32::<LittleEndian>()? {
                 -2 => vec.push(ExecutionResult::SuccessNoInfo),
                 -3 => vec.push(ExecutionResult::Failure(None)),
-                #[allow(clippy::cast_sign_loss)]
-                i => vec.push(ExecutionResult::RowsAffected(i as usize)),
+                i => vec
+                    .push(ExecutionResult::RowsAffected(u64::try_from(i).map_err(
+                        |_| impl_err!("Unexpected execution result code {i}"),
+                    )?)),
             }
         }
         Ok(Self(vec))
@@ -88,7 +223,7 @@ impl ExecutionResults {
                 "Reply::handle_db_error(): \
                  found more server_errors than instances of ExecutionResult::Failure"
             );
-            self.0.push(ExecutionResult::Failure(Some(e)));
+            self.0.push(ExecutionResult::ExtraFailure(e));
         }
     }
 }
@@ -100,9 +235,110 @@ impl std::iter::IntoIterator for ExecutionResults {
         self.0.into_iter()
     }
 }
+impl<'a> std::iter::IntoIterator for &'a ExecutionResults {
+    type Item = &'a ExecutionResult;
+    type IntoIter = std::slice::Iter<'a, ExecutionResult>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
 impl<I: std::slice::SliceIndex<[ExecutionResult]>> std::ops::Index<I> for ExecutionResults {
     type Output = I::Output;
     fn index(&self, index: I) -> &Self::Output {
         &self.0[index]
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{AffectedRowCount, ExecutionResult, ExecutionResults};
+    use crate::{ServerError, Severity};
+
+    #[test]
+    fn test_execution_results() {
+        let mut ers = ExecutionResults(vec![
+            ExecutionResult::RowsAffected(2),
+            ExecutionResult::Failure(None),
+            ExecutionResult::SuccessNoInfo,
+            ExecutionResult::RowsAffected(3),
+        ]);
+        ers.mix_in_server_errors(
+            vec![ServerError::new(
+                301,
+                0,
+                Severity::Error,
+                b"23000".to_vec(),
+                "unique constraint violated".to_string(),
+            )]
+            .into_iter(),
+        );
+
+        assert_eq!(ers.len(), 4);
+        // the number of rows of the SuccessNoInfo is not known
+        assert_eq!(ers.total_affected_rows(), AffectedRowCount::SuccessNoInfo);
+        assert!(ers.has_failures());
+        let failures: Vec<(usize, i32)> = ers
+            .failures()
+            .map(|(idx, o_se)| (idx, o_se.unwrap().code()))
+            .collect();
+        assert_eq!(failures, vec![(1, 301)]);
+        let row_errors = ers.row_errors();
+        assert_eq!(row_errors.len(), 1);
+        assert_eq!(row_errors[&1].code(), 301);
+        assert_eq!(ers.iter().filter(|er| er.is_rows_affected()).count(), 2);
+        assert_eq!(ers[2].rows_affected(), None);
+
+        let ers = ExecutionResults(vec![
+            ExecutionResult::RowsAffected(2),
+            ExecutionResult::Failure(None),
+            ExecutionResult::RowsAffected(3),
+        ]);
+        assert_eq!(ers.total_affected_rows(), 5);
+        assert_eq!(ExecutionResults(vec![]).total_affected_rows(), 0);
+    }
+
+    #[test]
+    fn test_row_errors() {
+        let server_error = |code| {
+            ServerError::new(
+                code,
+                0,
+                Severity::Error,
+                b"HY000".to_vec(),
+                "failed".to_string(),
+            )
+        };
+        let mut ers = ExecutionResults(vec![
+            ExecutionResult::Failure(None),
+            ExecutionResult::RowsAffected(1),
+            ExecutionResult::Failure(None),
+        ]);
+        ers.mix_in_server_errors(
+            vec![server_error(1), server_error(2), server_error(3)].into_iter(),
+        );
+
+        assert_eq!(ers.len(), 4);
+        assert_eq!(ers[3], ExecutionResult::ExtraFailure(server_error(3)));
+        let row_errors: Vec<(usize, i32)> = ers
+            .row_errors()
+            .into_iter()
+            .map(|(idx, se)| (idx, se.code()))
+            .collect();
+        assert_eq!(row_errors, vec![(0, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_parse() {
+        let bytes: Vec<u8> = [5_i32, -2, -3]
+            .iter()
+            .flat_map(|i| i.to_le_bytes())
+            .collect();
+        let ers = ExecutionResults::parse(3, &mut std::io::Cursor::new(bytes)).unwrap();
+        assert_eq!(ers[0], ExecutionResult::RowsAffected(5));
+        assert_eq!(ers[1], ExecutionResult::SuccessNoInfo);
+        assert_eq!(ers[2], ExecutionResult::Failure(None));
+
+        let bytes = (-7_i32).to_le_bytes().to_vec();
+        assert!(ExecutionResults::parse(1, &mut std::io::Cursor::new(bytes)).is_err());
+    }
+}
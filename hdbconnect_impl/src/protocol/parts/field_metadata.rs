@@ -61,10 +61,85 @@ impl InnerFieldMetadata {
 }
 
 impl FieldMetadata {
+    // Bits of column_options
+    const MANDATORY: u8 = 0b_0000_0001;
+    const OPTIONAL: u8 = 0b_0000_0010;
+
     pub(crate) fn new(inner: InnerFieldMetadata, names: Arc<VecMap<String>>) -> Self {
         Self { inner, names }
     }
 
+    /// Creates the metadata of a nullable column with the given name and type,
+    /// e.g. for fabricating result sets without a database.
+    ///
+    /// The display name is set to the column name; schema and table name are empty.
+    #[must_use]
+    pub fn new_column<S: AsRef<str>>(columnname: S, type_id: TypeId) -> Self {
+        let mut names = VecMap::new();
+        names.insert(0, columnname.as_ref().to_string());
+        Self::new(
+            InnerFieldMetadata::new(u32::MAX, u32::MAX, 0, 0, Self::OPTIONAL, type_id, 0, 0),
+            Arc::new(names),
+        )
+    }
+
+    /// Sets the schema name.
+    #[must_use]
+    pub fn with_schemaname<S: AsRef<str>>(mut self, schemaname: S) -> Self {
+        self.inner.schemaname_idx = self.add_name(schemaname.as_ref());
+        self
+    }
+
+    /// Sets the table name.
+    #[must_use]
+    pub fn with_tablename<S: AsRef<str>>(mut self, tablename: S) -> Self {
+        self.inner.tablename_idx = self.add_name(tablename.as_ref());
+        self
+    }
+
+    /// Sets the display name.
+    #[must_use]
+    pub fn with_displayname<S: AsRef<str>>(mut self, displayname: S) -> Self {
+        self.inner.displayname_idx = self.add_name(displayname.as_ref());
+        self
+    }
+
+    /// Sets whether the column can contain NULL values.
+    #[must_use]
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.inner.column_options &= !(Self::MANDATORY | Self::OPTIONAL);
+        self.inner.column_options |= if nullable {
+            Self::OPTIONAL
+        } else {
+            Self::MANDATORY
+        };
+        self
+    }
+
+    /// Sets the length or the precision of the value.
+    #[must_use]
+    pub fn with_precision(mut self, precision: i16) -> Self {
+        self.inner.precision = precision;
+        self
+    }
+
+    /// Sets the scale of the value.
+    #[must_use]
+    pub fn with_scale(mut self, scale: i16) -> Self {
+        self.inner.scale = scale;
+        self
+    }
+
+    // Adds a name and returns its index.
+    fn add_name(&mut self, name: &str) -> u32 {
+        let names = Arc::make_mut(&mut self.names);
+        let idx = names.keys().max().map_or(0, |max| max + 1);
+        names.insert(idx, name.to_string());
+        #[allow(clippy::cast_possible_truncation)]
+        let idx = idx as u32;
+        idx
+    }
+
     /// Database schema of the field.
     pub fn schemaname(&self) -> &str {
         self.names
@@ -99,18 +174,19 @@ impl FieldMetadata {
         self.inner.type_id
     }
 
-    // Returns true for BLOB, CLOB, and NCLOB, and false otherwise.
+    // Returns true for BLOB, CLOB, and NCLOB, and for BINTEXT, which is read as BLOB;
+    // false otherwise.
     pub(crate) fn is_lob(&self) -> bool {
         matches!(
             self.inner.type_id,
-            TypeId::BLOB | TypeId::CLOB | TypeId::NCLOB
+            TypeId::BLOB | TypeId::CLOB | TypeId::NCLOB | TypeId::BINTEXT
         )
     }
 
     /// True if column can contain NULL values.
     #[must_use]
     pub fn is_nullable(&self) -> bool {
-        (self.inner.column_options & 0b_0000_0010_u8) != 0
+        (self.inner.column_options & Self::OPTIONAL) != 0
     }
 
     /// The length or the precision of the value.
@@ -153,3 +229,67 @@ impl FieldMetadata {
         (self.inner.column_options & 0b_0100_0000_u8) != 0
     }
 }
+
+// Compares the described columns, not the way the names are stored.
+impl PartialEq for FieldMetadata {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner.column_options == other.inner.column_options
+            && self.inner.type_id == other.inner.type_id
+            && self.inner.scale == other.inner.scale
+            && self.inner.precision == other.inner.precision
+            && self.schemaname() == other.schemaname()
+            && self.tablename() == other.tablename()
+            && self.columnname() == other.columnname()
+            && self.displayname() == other.displayname()
+    }
+}
+impl Eq for FieldMetadata {}
+
+#[cfg(test)]
+mod test {
+    use super::FieldMetadata;
+    use crate::{HdbValue, ResultSetMetadata, Row, TypeId};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_new_column() {
+        let fmd = FieldMetadata::new_column("ID", TypeId::DECIMAL)
+            .with_schemaname("S")
+            .with_tablename("T")
+            .with_displayname("Identifier")
+            .with_nullable(false)
+            .with_precision(10)
+            .with_scale(2);
+        assert_eq!(fmd.columnname(), "ID");
+        assert_eq!(fmd.schemaname(), "S");
+        assert_eq!(fmd.tablename(), "T");
+        assert_eq!(fmd.displayname(), "Identifier");
+        assert!(!fmd.is_nullable());
+        assert_eq!((fmd.precision(), fmd.scale()), (10, 2));
+
+        let plain = FieldMetadata::new_column("NAME", TypeId::NVARCHAR);
+        assert_eq!(plain.displayname(), "NAME");
+        assert_eq!(plain.tablename(), "");
+        assert!(plain.is_nullable());
+
+        let metadata = Arc::new(ResultSetMetadata::new(vec![fmd, plain]));
+        assert!(Row::try_new(Arc::clone(&metadata), vec![HdbValue::NULL]).is_err());
+        let row = Row::try_new(metadata, vec![HdbValue::INT(1), HdbValue::NULL]).unwrap();
+        assert_eq!(row.len(), 2);
+    }
+
+    #[test]
+    fn test_eq() {
+        let fmd = FieldMetadata::new_column("ID", TypeId::INT).with_tablename("T");
+        assert_eq!(
+            fmd,
+            FieldMetadata::new_column("ID", TypeId::INT).with_tablename("T")
+        );
+        assert_ne!(fmd, FieldMetadata::new_column("ID", TypeId::INT));
+        assert_ne!(
+            fmd,
+            FieldMetadata::new_column("ID", TypeId::BIGINT).with_tablename("T")
+        );
+        assert_ne!(fmd, fmd.clone().with_nullable(false));
+    }
+}
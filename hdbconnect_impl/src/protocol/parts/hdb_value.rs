@@ -3,7 +3,7 @@ use crate::{
     conn::AmConnCore,
     impl_err,
     protocol::{
-        parts::{length_indicator, ParameterDescriptor, TypeId},
+        parts::{length_indicator, null_emit_rules::type_id_for_null, ParameterDescriptor, TypeId},
         util, util_sync,
     },
     types::{DayDate, LongDate, SecondDate, SecondTime},
@@ -11,11 +11,12 @@ use crate::{
         daydate::parse_daydate, decimal, lob, longdate::parse_longdate,
         seconddate::parse_seconddate, secondtime::parse_secondtime,
     },
-    usage_err, HdbError, HdbResult,
+    usage_err, HdbError, HdbResult, ServerVersion,
 };
 use bigdecimal::BigDecimal;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use serde_db::de::DeserializationError;
+use std::fmt::Write;
 
 const ALPHANUM_PURELY_NUMERIC: u8 = 0b_1000_0000_u8;
 const ALPHANUM_LENGTH_MASK: u8 = 0b_0111_1111_u8;
@@ -26,6 +27,15 @@ const ALPHANUM_LENGTH_MASK: u8 = 0b_0111_1111_u8;
 pub enum HdbValue<'a> {
     /// Representation of a database NULL value.
     NULL,
+    /// A database NULL value that is sent with the given type,
+    /// rather than with the type of the parameter it is bound to.
+    ///
+    /// This is useful where the parameter type is ambiguous, e.g. when the
+    /// server describes a parameter of an expression like `COALESCE(?, 0)` with a type
+    /// that does not match the intended one.
+    /// Like plain NULL values, a typed NULL of type `SECONDTIME` is sent as `SECONDDATE`,
+    /// because the server does not accept the former.
+    TYPED_NULL(TypeId),
     /// Stores an 8-bit unsigned integer.
     /// The minimum value is 0. The maximum value is 255.
     TINYINT(u8),
@@ -100,6 +110,15 @@ pub enum HdbValue<'a> {
     /// [`PreparedStatement::execute_row()`](crate::PreparedStatement::execute_row)).
     STR(&'a str),
 
+    /// Can be used for avoiding copying when sending large binary values to the database,
+    /// analogously to `STR`; is sent like `BINARY`.
+    ///
+    /// Any type that dereferences to `[u8]`, like `bytes::Bytes`, can be bound
+    /// with e.g. `HdbValue::BYTES(&bytes)`.
+    /// Note that values that are serialized with serde are always copied,
+    /// because serde does not hand out borrowed byte slices.
+    BYTES(&'a [u8]),
+
     /// Timestamp with 10^-7 seconds precision, uses eight bytes.
     LONGDATE(LongDate),
     /// TIMESTAMP with second precision.
@@ -119,13 +138,44 @@ pub enum HdbValue<'a> {
 }
 
 impl HdbValue<'_> {
-    pub(crate) fn type_id_for_emit(&self, requested_type_id: TypeId) -> HdbResult<TypeId> {
+    // Returns the approximate number of bytes that the value occupies on the heap,
+    // in addition to its inline size; the buffers of LOBs are included.
+    pub(crate) fn heap_size(&self) -> usize {
+        match self {
+            Self::STRING(s) => s.capacity(),
+            Self::BINARY(v) | Self::DBSTRING(v) | Self::GEOMETRY(v) | Self::POINT(v) => {
+                v.capacity()
+            }
+            // a decimal digit needs a bit less than half a byte
+            Self::DECIMAL(bd) => usize::try_from(bd.digits() / 2).unwrap_or(0),
+            #[cfg(feature = "sync")]
+            Self::SYNC_BLOB(blob) => blob.cur_buf_len(),
+            #[cfg(feature = "sync")]
+            Self::SYNC_CLOB(clob) => clob.cur_buf_len(),
+            #[cfg(feature = "sync")]
+            Self::SYNC_NCLOB(nclob) => nclob.cur_buf_len(),
+            #[cfg(feature = "async")]
+            Self::ASYNC_BLOB(blob) => blob.cur_buf_len(),
+            #[cfg(feature = "async")]
+            Self::ASYNC_CLOB(clob) => clob.cur_buf_len(),
+            #[cfg(feature = "async")]
+            Self::ASYNC_NCLOB(nclob) => nclob.cur_buf_len(),
+            Self::ARRAY(values) => values
+                .iter()
+                .map(|value| std::mem::size_of::<Self>() + value.heap_size())
+                .sum(),
+            _ => 0,
+        }
+    }
+
+    pub(crate) fn type_id_for_emit(
+        &self,
+        requested_type_id: TypeId,
+        o_server_version: Option<&ServerVersion>,
+    ) -> HdbResult<TypeId> {
         Ok(match *self {
-            HdbValue::NULL => match requested_type_id {
-                // work around a bug in HANA: it doesn't accept NULL SECONDTIME values
-                TypeId::SECONDTIME => TypeId::SECONDDATE,
-                tid => tid,
-            },
+            HdbValue::NULL => type_id_for_null(requested_type_id, o_server_version),
+            HdbValue::TYPED_NULL(type_id) => type_id_for_null(type_id, o_server_version),
 
             HdbValue::TINYINT(_) => TypeId::TINYINT,
             HdbValue::SMALLINT(_) => TypeId::SMALLINT,
@@ -157,7 +207,8 @@ impl HdbValue<'_> {
             HdbValue::SECONDTIME(_) => TypeId::SECONDTIME,
             HdbValue::GEOMETRY(_) | // TypeId::GEOMETRY,
             HdbValue::POINT(_) |    // TypeId::POINT,
-            HdbValue::BINARY(_) => TypeId::BINARY,
+            HdbValue::BINARY(_) |
+            HdbValue::BYTES(_) => TypeId::BINARY,
             HdbValue::DBSTRING(_) => unimplemented!("Can't send DBSTRINGs to the database"),
             HdbValue::ARRAY(_) => unimplemented!("Can't send array type to DB; not yet supported"),
         })
@@ -166,18 +217,93 @@ impl HdbValue<'_> {
     /// Returns true if the value is a NULL value.
     #[must_use]
     pub fn is_null(&self) -> bool {
-        matches!(*self, HdbValue::NULL)
+        matches!(*self, HdbValue::NULL | HdbValue::TYPED_NULL(_))
+    }
+
+    /// Renders the value as SQL literal, e.g. for logging a parameterized execution
+    /// in a form that can be replayed in an SQL console.
+    ///
+    /// Strings are rendered as Unicode literals (`N'...'`) with embedded quotes doubled,
+    /// binary values as hex literals (`X'...'`), timestamps, dates, and times as typed
+    /// literals (`TIMESTAMP'...'` etc.), and arrays with the `ARRAY()` constructor.
+    ///
+    /// Caveats: the type of a numeric literal is derived from its notation,
+    /// so the server may infer a different type than that of the parameter,
+    /// e.g. `DOUBLE` values are rendered in exponent notation;
+    /// `SECONDDATE` values are rendered as `TIMESTAMP` literals;
+    /// spatial values are rendered with `ST_GeomFromWKB()`, without SRID.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` for LOBs and LOB streams, whose content is not available,
+    /// and for non-finite floating point values, which HANA does not support.
+    pub fn to_sql_literal(&self) -> HdbResult<String> {
+        Ok(match *self {
+            HdbValue::NULL | HdbValue::TYPED_NULL(_) => "NULL".to_string(),
+            HdbValue::TINYINT(value) => value.to_string(),
+            HdbValue::SMALLINT(value) => value.to_string(),
+            HdbValue::INT(value) => value.to_string(),
+            HdbValue::BIGINT(value) => value.to_string(),
+            HdbValue::DECIMAL(ref value) => value.to_plain_string(),
+            HdbValue::REAL(value) if value.is_finite() => format!("{value:e}"),
+            HdbValue::DOUBLE(value) if value.is_finite() => format!("{value:e}"),
+            HdbValue::REAL(_) | HdbValue::DOUBLE(_) => {
+                return Err(usage_err!("{self} cannot be rendered as SQL literal"));
+            }
+            HdbValue::BOOLEAN(value) => if value { "TRUE" } else { "FALSE" }.to_string(),
+            HdbValue::STRING(ref value) => string_literal(value),
+            HdbValue::STR(value) => string_literal(value),
+            HdbValue::DBSTRING(ref bytes) => {
+                string_literal(&util::string_from_cesu8(bytes.clone())?)
+            }
+            HdbValue::BINARY(ref bytes) => hex_literal(bytes),
+            HdbValue::BYTES(bytes) => hex_literal(bytes),
+            HdbValue::LONGDATE(ref value) => {
+                format!("TIMESTAMP'{}'", value.to_string().replace('T', " "))
+            }
+            HdbValue::SECONDDATE(ref value) => {
+                format!("TIMESTAMP'{}'", value.to_string().replace('T', " "))
+            }
+            HdbValue::DAYDATE(ref value) => format!("DATE'{value}'"),
+            HdbValue::SECONDTIME(ref value) => format!("TIME'{value}'"),
+            HdbValue::GEOMETRY(ref bytes) | HdbValue::POINT(ref bytes) => {
+                format!("ST_GeomFromWKB({})", hex_literal(bytes))
+            }
+            HdbValue::ARRAY(ref values) => format!(
+                "ARRAY({})",
+                values
+                    .iter()
+                    .map(HdbValue::to_sql_literal)
+                    .collect::<HdbResult<Vec<_>>>()?
+                    .join(", ")
+            ),
+            #[cfg(feature = "sync")]
+            HdbValue::SYNC_CLOB(_)
+            | HdbValue::SYNC_NCLOB(_)
+            | HdbValue::SYNC_BLOB(_)
+            | HdbValue::SYNC_LOBSTREAM(_) => {
+                return Err(usage_err!("{self} cannot be rendered as SQL literal"));
+            }
+            #[cfg(feature = "async")]
+            HdbValue::ASYNC_CLOB(_)
+            | HdbValue::ASYNC_NCLOB(_)
+            | HdbValue::ASYNC_BLOB(_)
+            | HdbValue::ASYNC_LOBSTREAM(_) => {
+                return Err(usage_err!("{self} cannot be rendered as SQL literal"));
+            }
+        })
     }
 
     pub(crate) fn emit(
         &self,
         data_pos: &mut i32,
         descriptor: &ParameterDescriptor,
+        o_server_version: Option<&ServerVersion>,
         w: &mut dyn std::io::Write,
     ) -> HdbResult<()> {
-        if !self.emit_type_id(descriptor.type_id(), w)? {
+        if !self.emit_type_id(descriptor.type_id(), o_server_version, w)? {
             match *self {
-                HdbValue::NULL => {}
+                HdbValue::NULL | HdbValue::TYPED_NULL(_) => {}
                 HdbValue::TINYINT(u) => w.write_u8(u)?,
                 HdbValue::SMALLINT(i) => w.write_i16::<LittleEndian>(i)?,
                 HdbValue::INT(i) => w.write_i32::<LittleEndian>(i)?,
@@ -206,6 +332,7 @@ impl HdbValue<'_> {
                 HdbValue::BINARY(ref v) | HdbValue::GEOMETRY(ref v) | HdbValue::POINT(ref v) => {
                     emit_length_and_bytes(v, w)?;
                 }
+                HdbValue::BYTES(v) => emit_length_and_bytes(v, w)?,
                 _ => {
                     return Err(impl_err!("HdbValue::{self} cannot be sent to the database",));
                 }
@@ -218,11 +345,12 @@ impl HdbValue<'_> {
     fn emit_type_id(
         &self,
         requested_type_id: TypeId,
+        o_server_version: Option<&ServerVersion>,
         w: &mut dyn std::io::Write,
     ) -> HdbResult<bool> {
         let is_null = self.is_null();
         let type_code = self
-            .type_id_for_emit(requested_type_id)
+            .type_id_for_emit(requested_type_id, o_server_version)
             .map_err(|e| impl_err!("{}", e))?
             .type_code(is_null);
         w.write_u8(type_code)?;
@@ -232,7 +360,7 @@ impl HdbValue<'_> {
     // is used to calculate the part size (in emit())
     pub(crate) fn size(&self, type_id: TypeId) -> HdbResult<usize> {
         Ok(1 + match self {
-            HdbValue::NULL => 0,
+            HdbValue::NULL | HdbValue::TYPED_NULL(_) => 0,
             HdbValue::BOOLEAN(_) | HdbValue::TINYINT(_) => 1,
             HdbValue::SMALLINT(_) => 2,
             HdbValue::DECIMAL(_) => match type_id {
@@ -265,6 +393,7 @@ impl HdbValue<'_> {
             HdbValue::BINARY(ref v) | HdbValue::GEOMETRY(ref v) | HdbValue::POINT(ref v) => {
                 binary_length(v.len())
             }
+            HdbValue::BYTES(v) => binary_length(v.len()),
 
             #[cfg(feature = "sync")]
             HdbValue::SYNC_BLOB(_)
@@ -317,6 +446,32 @@ impl HdbValue<'static> {
         })
     }
 
+    /// Wraps a reader that produces the content of a LOB parameter,
+    /// for streaming it to the database with
+    /// [`PreparedStatement::execute_row()`](crate::sync::PreparedStatement::execute_row).
+    ///
+    /// For `CLOB` and `NCLOB` parameters, the reader must produce UTF-8;
+    /// the conversion to CESU-8 is done piecewise, so the content is never buffered completely.
+    #[cfg(feature = "sync")]
+    pub fn sync_lobstream<R: std::io::Read + Send + 'static>(reader: R) -> Self {
+        HdbValue::SYNC_LOBSTREAM(Some(std::sync::Arc::new(std::sync::Mutex::new(reader))))
+    }
+
+    /// Wraps an asynchronous reader that produces the content of a LOB parameter,
+    /// for streaming it to the database with
+    /// [`PreparedStatement::execute_row()`](crate::a_sync::PreparedStatement::execute_row).
+    ///
+    /// For `CLOB` and `NCLOB` parameters, the reader must produce UTF-8;
+    /// the conversion to CESU-8 is done piecewise, so the content is never buffered completely.
+    /// This allows e.g. inserting multi-GB documents directly from object storage.
+    #[cfg(feature = "async")]
+    pub fn async_lobstream<R>(reader: R) -> Self
+    where
+        R: tokio::io::AsyncRead + Send + Unpin + 'static,
+    {
+        HdbValue::ASYNC_LOBSTREAM(Some(std::sync::Arc::new(tokio::sync::Mutex::new(reader))))
+    }
+
     /// Convert into `BLob`.
     ///
     /// # Errors
@@ -830,10 +985,22 @@ fn emit_length_and_bytes(v: &[u8], w: &mut dyn std::io::Write) -> HdbResult<()>
     Ok(())
 }
 
+// Unicode string literal; quotes are the only characters that need escaping.
+fn string_literal(value: &str) -> String {
+    format!("N'{}'", value.replace('\'', "''"))
+}
+
+fn hex_literal(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::from("X'"), |mut literal, b| {
+        write!(literal, "{b:02X}").ok();
+        literal
+    }) + "'"
+}
+
 impl std::fmt::Display for HdbValue<'_> {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
-            HdbValue::NULL => write!(fmt, "<NULL>"),
+            HdbValue::NULL | HdbValue::TYPED_NULL(_) => write!(fmt, "<NULL>"),
             HdbValue::TINYINT(value) => write!(fmt, "{value}"),
             HdbValue::SMALLINT(value) => write!(fmt, "{value}"),
             HdbValue::INT(value) => write!(fmt, "{value}"),
@@ -865,6 +1032,7 @@ impl std::fmt::Display for HdbValue<'_> {
                 }
             }
             HdbValue::BINARY(ref vec) => write!(fmt, "<BINARY length = {}>", vec.len()),
+            HdbValue::BYTES(bytes) => write!(fmt, "<BINARY length = {}>", bytes.len()),
 
             #[cfg(feature = "sync")]
             HdbValue::SYNC_CLOB(ref clob) => {
@@ -921,6 +1089,7 @@ impl std::fmt::Debug for HdbValue<'_> {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         match *self {
             HdbValue::NULL => write!(fmt, "<NULL>"),
+            HdbValue::TYPED_NULL(type_id) => write!(fmt, "<NULL>:{type_id}"),
             HdbValue::TINYINT(value) => write!(fmt, "{value}:TINYINT"),
             HdbValue::SMALLINT(value) => write!(fmt, "{value}:SMALLINT"),
             HdbValue::INT(value) => write!(fmt, "{value}:INT"),
@@ -952,6 +1121,7 @@ impl std::fmt::Debug for HdbValue<'_> {
                 }
             }
             HdbValue::BINARY(ref vec) => write!(fmt, "<BINARY length = {}>", vec.len()),
+            HdbValue::BYTES(bytes) => write!(fmt, "<BINARY length = {}>", bytes.len()),
 
             #[cfg(feature = "sync")]
             HdbValue::SYNC_CLOB(ref clob) => {
@@ -1028,7 +1198,7 @@ impl std::cmp::PartialEq<&str> for HdbValue<'_> {
 #[cfg(test)]
 mod test {
     use crate::types::{DayDate, LongDate, SecondDate, SecondTime};
-    use crate::HdbValue;
+    use crate::{HdbValue, ServerVersion, TypeId};
     use bigdecimal::BigDecimal;
     use num::bigint::BigInt;
     use num::FromPrimitive;
@@ -1054,14 +1224,117 @@ mod test {
             // HdbValue::BLOB(_),
             // HdbValue::LOBSTREAM(_),
             HdbValue::BOOLEAN(true),
-            HdbValue::LONGDATE(LongDate::new(100_i64)),
-            HdbValue::SECONDDATE(SecondDate::new(100_i64)),
-            HdbValue::DAYDATE(DayDate::new(100_i32)),
-            HdbValue::SECONDTIME(SecondTime::new(100_i32)),
+            HdbValue::LONGDATE(LongDate::checked_new(100_i64).unwrap()),
+            HdbValue::SECONDDATE(SecondDate::checked_new(100_i64).unwrap()),
+            HdbValue::DAYDATE(DayDate::checked_new(100_i32).unwrap()),
+            HdbValue::SECONDTIME(SecondTime::checked_new(100_i32).unwrap()),
             // HdbValue::GEOMETRY(ref vec),
             // HdbValue::POINT(ref vec),
         ] {
             let _s = value.to_string();
         }
     }
+
+    #[test]
+    fn test_to_sql_literal() {
+        let literal = |value: HdbValue| value.to_sql_literal().unwrap();
+        assert_eq!(literal(HdbValue::NULL), "NULL");
+        assert_eq!(literal(HdbValue::BIGINT(-42)), "-42");
+        assert_eq!(
+            literal(HdbValue::DECIMAL(BigDecimal::new(BigInt::from(12_345), -3))),
+            "12345000"
+        );
+        assert_eq!(literal(HdbValue::DOUBLE(0.25)), "2.5e-1");
+        assert_eq!(literal(HdbValue::BOOLEAN(false)), "FALSE");
+        assert_eq!(literal(HdbValue::STR("it's")), "N'it''s'");
+        assert_eq!(literal(HdbValue::BINARY(vec![0, 0xAB])), "X'00AB'");
+        assert_eq!(
+            literal(HdbValue::LONGDATE(
+                LongDate::from_unix_timestamp(0).unwrap()
+            )),
+            "TIMESTAMP'1970-01-01 00:00:00.0000000'"
+        );
+        assert_eq!(
+            literal(HdbValue::DAYDATE(DayDate::from_unix_timestamp(0).unwrap())),
+            "DATE'1970-01-01'"
+        );
+        assert_eq!(
+            literal(HdbValue::ARRAY(vec![HdbValue::INT(1), HdbValue::NULL])),
+            "ARRAY(1, NULL)"
+        );
+        assert!(HdbValue::DOUBLE(f64::NAN).to_sql_literal().is_err());
+    }
+
+    #[test]
+    fn test_bytes_like_binary() {
+        let data = vec![0_u8, 0xAB, 42];
+        let bytes = HdbValue::BYTES(&data);
+        let binary = HdbValue::BINARY(data.clone());
+
+        assert_eq!(
+            bytes.size(TypeId::VARBINARY).unwrap(),
+            binary.size(TypeId::VARBINARY).unwrap()
+        );
+        let (mut buf1, mut buf2) = (Vec::new(), Vec::new());
+        bytes
+            .emit_type_id(TypeId::VARBINARY, None, &mut buf1)
+            .unwrap();
+        binary
+            .emit_type_id(TypeId::VARBINARY, None, &mut buf2)
+            .unwrap();
+        assert_eq!(buf1, buf2);
+        assert_eq!(bytes.to_sql_literal().unwrap(), "X'00AB2A'");
+        assert_eq!(bytes.to_string(), binary.to_string());
+    }
+
+    #[test]
+    fn test_null_emit_of_date_and_time_types() {
+        let server_version = ServerVersion::new(2, 0, 80, 0);
+        for o_server_version in [None, Some(&server_version)] {
+            for (requested, expected) in [
+                (TypeId::DAYDATE, TypeId::DAYDATE),
+                (TypeId::SECONDDATE, TypeId::SECONDDATE),
+                (TypeId::LONGDATE, TypeId::LONGDATE),
+                (TypeId::SECONDTIME, TypeId::SECONDDATE),
+            ] {
+                let mut buf = Vec::new();
+                assert!(HdbValue::NULL
+                    .emit_type_id(requested, o_server_version, &mut buf)
+                    .unwrap());
+                assert_eq!(buf, vec![expected.type_code(true)]);
+
+                // typed NULLs are sent with their own type, and follow the same rules
+                let mut buf = Vec::new();
+                assert!(HdbValue::TYPED_NULL(requested)
+                    .emit_type_id(TypeId::NVARCHAR, o_server_version, &mut buf)
+                    .unwrap());
+                assert_eq!(buf, vec![expected.type_code(true)]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_typed_null() {
+        assert_eq!(
+            HdbValue::NULL
+                .type_id_for_emit(TypeId::SECONDTIME, None)
+                .unwrap(),
+            TypeId::SECONDDATE
+        );
+        let typed_null = HdbValue::TYPED_NULL(TypeId::FIXED8);
+        assert!(typed_null.is_null());
+        assert_eq!(typed_null.size(TypeId::DECIMAL).unwrap(), 1);
+        assert_eq!(
+            typed_null.type_id_for_emit(TypeId::DECIMAL, None).unwrap(),
+            TypeId::FIXED8
+        );
+        assert_eq!(
+            HdbValue::TYPED_NULL(TypeId::SECONDTIME)
+                .type_id_for_emit(TypeId::DECIMAL, None)
+                .unwrap(),
+            TypeId::SECONDDATE
+        );
+        assert_eq!(typed_null.to_sql_literal().unwrap(), "NULL");
+        assert_eq!(format!("{typed_null:?}"), "<NULL>:FIXED8");
+    }
 }
@@ -0,0 +1,107 @@
+use crate::{protocol::parts::TypeId, ServerVersion};
+
+// Compatibility rules for sending NULL values as parameters.
+//
+// A NULL is sent with the null-variant of its type code: an untyped NULL with the type
+// that the server requested for the parameter, a typed NULL (`HdbValue::TYPED_NULL`)
+// with its own type. Some servers do not accept this for certain types, but they accept
+// the NULL of a substitute type, which they convert.
+//
+// A rule applies to all server versions before the version in which the server was fixed;
+// if this version is not known, the rule applies to all server versions.
+#[derive(Debug)]
+struct NullEmitRule {
+    type_id: TypeId,
+    substitute: TypeId,
+    o_fixed_in: Option<ServerVersion>,
+}
+
+impl NullEmitRule {
+    // The rule is applied also if the server version is not known.
+    fn applies_to(&self, o_server_version: Option<&ServerVersion>) -> bool {
+        match (&self.o_fixed_in, o_server_version) {
+            (Some(fixed_in), Some(server_version)) => server_version < fixed_in,
+            (_, _) => true,
+        }
+    }
+}
+
+const NULL_EMIT_RULES: [NullEmitRule; 1] = [
+    // HANA does not accept NULL values of type SECONDTIME
+    NullEmitRule {
+        type_id: TypeId::SECONDTIME,
+        substitute: TypeId::SECONDDATE,
+        o_fixed_in: None,
+    },
+];
+
+// Returns the type id with which a NULL of the given type is sent.
+pub(crate) fn type_id_for_null(
+    type_id: TypeId,
+    o_server_version: Option<&ServerVersion>,
+) -> TypeId {
+    apply_rules(&NULL_EMIT_RULES, type_id, o_server_version)
+}
+
+fn apply_rules(
+    rules: &[NullEmitRule],
+    type_id: TypeId,
+    o_server_version: Option<&ServerVersion>,
+) -> TypeId {
+    rules
+        .iter()
+        .find(|rule| rule.type_id == type_id && rule.applies_to(o_server_version))
+        .map_or(type_id, |rule| rule.substitute)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{apply_rules, type_id_for_null, NullEmitRule};
+    use crate::{ServerVersion, TypeId};
+
+    #[test]
+    fn test_version_dependency() {
+        let rules = [NullEmitRule {
+            type_id: TypeId::SECONDTIME,
+            substitute: TypeId::SECONDDATE,
+            o_fixed_in: Some(ServerVersion::new(2, 0, 80, 0)),
+        }];
+        for (o_server_version, expected) in [
+            (None, TypeId::SECONDDATE),
+            (Some(ServerVersion::new(2, 0, 59, 12)), TypeId::SECONDDATE),
+            (Some(ServerVersion::new(2, 0, 80, 0)), TypeId::SECONDTIME),
+            (Some(ServerVersion::new(4, 0, 0, 0)), TypeId::SECONDTIME),
+        ] {
+            assert_eq!(
+                apply_rules(&rules, TypeId::SECONDTIME, o_server_version.as_ref()),
+                expected
+            );
+            // other types are not affected
+            assert_eq!(
+                apply_rules(&rules, TypeId::SECONDDATE, o_server_version.as_ref()),
+                TypeId::SECONDDATE
+            );
+        }
+    }
+
+    #[test]
+    fn test_date_and_time_types() {
+        for o_server_version in [
+            None,
+            Some(ServerVersion::new(2, 0, 59, 12)),
+            Some(ServerVersion::new(4, 0, 0, 0)),
+        ] {
+            for (type_id, expected) in [
+                (TypeId::DAYDATE, TypeId::DAYDATE),
+                (TypeId::SECONDDATE, TypeId::SECONDDATE),
+                (TypeId::LONGDATE, TypeId::LONGDATE),
+                (TypeId::SECONDTIME, TypeId::SECONDDATE),
+            ] {
+                assert_eq!(
+                    type_id_for_null(type_id, o_server_version.as_ref()),
+                    expected
+                );
+            }
+        }
+    }
+}
@@ -8,7 +8,7 @@ use crate::{
         util,
     },
     serde_db_impl::de::DeserializableOutputParameters,
-    HdbResult,
+    usage_err, HdbResult,
 };
 use serde_db::de::DeserializableRow;
 
@@ -39,13 +39,50 @@ impl OutputParameters {
         )?)
     }
 
+    /// Converts the value with the given index into a plain rust value.
+    ///
+    /// This is handy e.g. for procedures with a single output parameter, or if only
+    /// some of the output parameters are of interest.
+    ///
+    /// ```rust,no_run
+    /// # use hdbconnect::{Connection, HdbResult};
+    /// # fn foo() -> HdbResult<()> {
+    /// # let connection = Connection::new("...")?;
+    /// let output_parameters = connection
+    ///     .statement("CALL GET_ORDER_COUNT(?)")?
+    ///     .into_output_parameters()?;
+    /// let count: u32 = output_parameters.try_get(0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if there is no value with the given index,
+    /// `HdbError::Deserialization` if the value cannot be converted into the target type.
+    pub fn try_get<'de, T>(&self, idx: usize) -> HdbResult<T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        self.values
+            .get(idx)
+            .ok_or_else(|| {
+                usage_err!(
+                    "Output parameter index {idx} is out of range, there are {} output parameters",
+                    self.values.len()
+                )
+            })?
+            .clone()
+            .try_into()
+    }
+
     /// Returns the descriptors.
     #[must_use]
     pub fn descriptors(&self) -> &Vec<ParameterDescriptor> {
         &(self.descriptors)
     }
 
-    /// Converts into an iterator of the contained values.
+    /// Converts into a vec of the contained values.
     #[must_use]
     pub fn into_values(self) -> Vec<HdbValue<'static>> {
         self.values
@@ -152,3 +189,22 @@ impl std::fmt::Display for OutputParameters {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::OutputParameters;
+    use crate::HdbValue;
+
+    #[test]
+    fn test_try_get() {
+        let output_parameters = OutputParameters {
+            descriptors: Vec::new(),
+            values: vec![HdbValue::INT(42), HdbValue::STRING("abc".to_string())],
+        };
+        assert_eq!(output_parameters.try_get::<u64>(0).unwrap(), 42);
+        assert_eq!(output_parameters.try_get::<String>(1).unwrap(), "abc");
+        assert!(output_parameters.try_get::<u64>(1).is_err());
+        assert!(output_parameters.try_get::<u64>(2).is_err());
+        assert_eq!(output_parameters.into_values().len(), 2);
+    }
+}
@@ -40,6 +40,12 @@ impl ParameterDescriptors {
         self.0.is_empty()
     }
 
+    // Describes the given number of parameter rows without revealing any values.
+    pub(crate) fn redacted_summary(&self, row_count: usize) -> String {
+        let types: Vec<String> = self.iter_in().map(|d| d.type_id.to_string()).collect();
+        format!("{row_count} row(s) of [{}]", types.join(", "))
+    }
+
     pub(crate) fn parse(count: usize, rdr: &mut dyn std::io::Read) -> HdbResult<Self> {
         let mut vec_pd = Vec::<ParameterDescriptor>::new();
         let mut name_offsets = Vec::<u32>::new();
@@ -256,3 +262,22 @@ pub enum ParameterDirection {
     /// output parameter
     OUT,
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ParameterDescriptor, ParameterDirection};
+    use crate::{HdbValue, TypeId};
+
+    #[test]
+    fn test_bintext_parameter() {
+        let descriptor =
+            ParameterDescriptor::try_new(2, TypeId::BINTEXT as u8, ParameterDirection::IN, -1, 0)
+                .unwrap();
+        assert!(matches!(
+            descriptor.parse_value("ein Text").unwrap(),
+            HdbValue::STRING(ref s) if s == "ein Text"
+        ));
+        assert!(TypeId::BINTEXT.matches_value_type(TypeId::STRING).is_ok());
+        assert!(TypeId::BINTEXT.matches_value_type(TypeId::BINARY).is_ok());
+    }
+}
@@ -1,5 +1,8 @@
-use crate::{impl_err, protocol::parts::ParameterDescriptors, HdbResult, HdbValue};
-use serde_db::ser::to_params;
+use crate::{
+    impl_err, protocol::parts::ParameterDescriptors, types_impl::decimal, usage_err, HdbError,
+    HdbResult, HdbValue, ServerVersion,
+};
+use serde_db::ser::{to_params, SerializationError};
 
 // Implementation of the PARAMETERS part.
 //
@@ -29,10 +32,11 @@ impl<'a> ParameterRows<'a> {
     pub(crate) fn emit(
         &self,
         descriptors: &ParameterDescriptors,
+        o_server_version: Option<&ServerVersion>,
         w: &mut dyn std::io::Write,
     ) -> HdbResult<()> {
         for row in &self.0 {
-            row.emit(descriptors, w)?;
+            row.emit(descriptors, o_server_version, w)?;
         }
         Ok(())
     }
@@ -41,6 +45,30 @@ impl<'a> ParameterRows<'a> {
         self.0.len()
     }
 
+    // Fails if a value would be sent with a type code that the negotiated
+    // data format version does not support.
+    pub(crate) fn check_dataformat_version2(
+        &self,
+        descriptors: &ParameterDescriptors,
+        dataformat_version2: u8,
+        o_server_version: Option<&ServerVersion>,
+    ) -> HdbResult<()> {
+        for row in &self.0 {
+            for (value, descriptor) in row.0.iter().zip(descriptors.iter_in()) {
+                let type_id = value.type_id_for_emit(descriptor.type_id(), o_server_version)?;
+                let required = type_id.min_dataformat_version2();
+                if required > dataformat_version2 {
+                    return Err(HdbError::DataFormatVersion {
+                        feature: format!("Sending a {type_id} value"),
+                        required,
+                        negotiated: dataformat_version2,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn size(&self, descriptors: &ParameterDescriptors) -> HdbResult<usize> {
         let mut size = 0;
         for row in &self.0 {
@@ -48,6 +76,30 @@ impl<'a> ParameterRows<'a> {
         }
         Ok(size)
     }
+
+    // Splits the rows into chunks that do not exceed the given number of rows and size,
+    // so that each chunk can be sent with a request of its own.
+    // A row that alone exceeds the size makes up a chunk of its own.
+    pub(crate) fn split(
+        self,
+        max_rows: usize,
+        max_size: usize,
+        descriptors: &ParameterDescriptors,
+    ) -> HdbResult<Vec<ParameterRows<'a>>> {
+        let mut chunks = vec![ParameterRows::new()];
+        let mut chunk_size = 0;
+        for row in self.0 {
+            let row_size = row.size(descriptors)?;
+            let chunk_count = chunks.last().map_or(0, ParameterRows::count);
+            if chunk_count > 0 && (chunk_count >= max_rows || chunk_size + row_size > max_size) {
+                chunks.push(ParameterRows::new());
+                chunk_size = 0;
+            }
+            chunk_size += row_size;
+            chunks.last_mut().unwrap(/*OK*/).0.push(row);
+        }
+        Ok(chunks)
+    }
 }
 
 impl ParameterRows<'static> {
@@ -56,14 +108,67 @@ impl ParameterRows<'static> {
         input: &T,
         descriptors: &ParameterDescriptors,
     ) -> HdbResult<()> {
-        self.0.push(ParameterRow::new(
-            to_params(input, &mut descriptors.iter_in())?,
-            descriptors,
-        )?);
+        let hdb_parameters = to_hdb_values(input, descriptors)?;
+        self.0.push(ParameterRow::new(hdb_parameters, descriptors)?);
         Ok(())
     }
 }
 
+// Converts the input into the values of a parameter row.
+pub(crate) fn to_hdb_values<T: serde::ser::Serialize>(
+    input: &T,
+    descriptors: &ParameterDescriptors,
+) -> HdbResult<Vec<HdbValue<'static>>> {
+    let mut in_descriptors = descriptors.iter_in();
+    to_params(input, &mut in_descriptors).map_err(|e| match e {
+        // the serializer asked for a descriptor after the last one
+        SerializationError::StructuralMismatch(_) if in_descriptors.next().is_none() => {
+            usage_err!(
+                "Too many parameter values: the statement has only {} input parameters",
+                descriptors.iter_in().count()
+            )
+        }
+        e => HdbError::from(e),
+    })
+}
+
+// Fails if the values do not match the in-descriptors in number, type, or, for decimals,
+// precision and scale.
+pub(crate) fn check_values(
+    hdb_parameters: &[HdbValue],
+    descriptors: &ParameterDescriptors,
+) -> HdbResult<()> {
+    let expected = descriptors.iter_in().count();
+    if hdb_parameters.len() != expected {
+        return Err(usage_err!(
+            "Wrong number of parameter values: the statement has {expected} input parameters, \
+             but {} values were provided",
+            hdb_parameters.len()
+        ));
+    }
+    let mut in_descriptors = descriptors.iter_in();
+    for hdb_value in hdb_parameters {
+        if let Some(descriptor) = in_descriptors.next() {
+            if !hdb_value.is_null() {
+                descriptor
+                    .type_id()
+                    .matches_value_type(hdb_value.type_id_for_emit(descriptor.type_id(), None)?)?;
+            }
+            if let HdbValue::DECIMAL(ref big_decimal) = hdb_value {
+                decimal::check_fits(
+                    big_decimal,
+                    descriptor.precision(),
+                    descriptor.scale(),
+                    descriptor.name(),
+                )?;
+            }
+        } else {
+            return Err(impl_err!("check_values(): Not enough metadata"));
+        }
+    }
+    Ok(())
+}
+
 // A single row of parameters.
 #[derive(Clone, Default, Debug)]
 pub struct ParameterRow<'a>(Vec<HdbValue<'a>>);
@@ -74,18 +179,7 @@ impl<'a> ParameterRow<'a> {
         hdb_parameters: Vec<HdbValue<'a>>,
         descriptors: &ParameterDescriptors,
     ) -> HdbResult<ParameterRow<'a>> {
-        let mut in_descriptors = descriptors.iter_in();
-        for hdb_value in &hdb_parameters {
-            if let Some(descriptor) = in_descriptors.next() {
-                if !hdb_value.is_null() {
-                    descriptor
-                        .type_id()
-                        .matches_value_type(hdb_value.type_id_for_emit(descriptor.type_id())?)?;
-                }
-            } else {
-                return Err(impl_err!("ParameterRow::new(): Not enough metadata"));
-            }
-        }
+        check_values(&hdb_parameters, descriptors)?;
         Ok(ParameterRow(hdb_parameters))
     }
 
@@ -106,6 +200,7 @@ impl<'a> ParameterRow<'a> {
     fn emit(
         &self,
         descriptors: &ParameterDescriptors,
+        o_server_version: Option<&ServerVersion>,
         w: &mut dyn std::io::Write,
     ) -> HdbResult<()> {
         let mut data_pos = 0_i32;
@@ -113,7 +208,7 @@ impl<'a> ParameterRow<'a> {
         for value in &(self.0) {
             // emit the value
             if let Some(descriptor) = in_descriptors.next() {
-                value.emit(&mut data_pos, descriptor, w)?;
+                value.emit(&mut data_pos, descriptor, o_server_version, w)?;
             } else {
                 return Err(impl_err!("ParameterRow::emit(): Not enough metadata"));
             }
@@ -121,3 +216,135 @@ impl<'a> ParameterRow<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::ParameterRows;
+    use crate::{protocol::parts::ParameterDescriptors, HdbError, HdbValue, TypeId};
+
+    #[derive(serde::Serialize)]
+    enum Shape {
+        Square { side: u8 },
+    }
+
+    #[test]
+    fn test_check_dataformat_version2() {
+        // a single nullable IN parameter of type TINYINT, without name
+        let descriptors = ParameterDescriptors::parse(
+            1,
+            &mut std::io::Cursor::new(vec![2, 1, 1, 0, 255, 255, 255, 255, 3, 0, 0, 0, 0, 0, 0, 0]),
+        )
+        .unwrap();
+
+        let mut rows = ParameterRows::new();
+        rows.push_hdb_values(vec![HdbValue::TINYINT(1)], &descriptors)
+            .unwrap();
+        assert!(rows
+            .check_dataformat_version2(&descriptors, 4, None)
+            .is_ok());
+
+        // booleans are sent with their own type code, which needs version 7
+        rows.push_hdb_values(vec![HdbValue::BOOLEAN(true)], &descriptors)
+            .unwrap();
+        assert!(rows
+            .check_dataformat_version2(&descriptors, 7, None)
+            .is_ok());
+        match rows.check_dataformat_version2(&descriptors, 6, None) {
+            Err(HdbError::DataFormatVersion {
+                required,
+                negotiated,
+                ..
+            }) => {
+                assert_eq!(required, 7);
+                assert_eq!(negotiated, 6);
+            }
+            _ => panic!("BOOLEAN should need data format version 7"),
+        }
+    }
+
+    #[test]
+    fn test_wrong_number_of_values() {
+        // a single nullable IN parameter of type TINYINT, without name
+        let descriptors = ParameterDescriptors::parse(
+            1,
+            &mut std::io::Cursor::new(vec![2, 1, 1, 0, 255, 255, 255, 255, 3, 0, 0, 0, 0, 0, 0, 0]),
+        )
+        .unwrap();
+        let mut rows = ParameterRows::new();
+        rows.push(&(1_u8,), &descriptors).unwrap();
+
+        match rows.push(&(1_u8, 2_u8), &descriptors) {
+            Err(HdbError::Usage(s)) => assert!(s.contains("Too many"), "{s}"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        match rows.push(&Vec::<u8>::new(), &descriptors) {
+            Err(HdbError::Usage(s)) => assert!(s.contains("Wrong number"), "{s}"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        // other structural problems are not reported as too many values
+        assert!(matches!(
+            rows.push(&Shape::Square { side: 1 }, &descriptors),
+            Err(HdbError::Serialization { .. })
+        ));
+        assert_eq!(rows.count(), 1);
+    }
+
+    #[test]
+    fn test_decimal_fits_fixed_types() {
+        for type_id in [TypeId::FIXED8, TypeId::FIXED12, TypeId::FIXED16] {
+            // a single nullable IN parameter of the given type, with precision 5 and scale 2
+            let mut descriptor = [2, 0, 1, 0, 255, 255, 255, 255, 5, 0, 2, 0, 0, 0, 0, 0];
+            descriptor[1] = type_id.type_code(false);
+            let descriptors =
+                ParameterDescriptors::parse(1, &mut std::io::Cursor::new(descriptor)).unwrap();
+            let mut rows = ParameterRows::new();
+            let decimal = |s: &str| HdbValue::DECIMAL(s.parse().unwrap());
+            rows.push_hdb_values(vec![decimal("999.994")], &descriptors)
+                .unwrap();
+            for value in ["999.995", "1000"] {
+                assert!(
+                    matches!(
+                        rows.push_hdb_values(vec![decimal(value)], &descriptors),
+                        Err(HdbError::Usage(_))
+                    ),
+                    "{value} into {type_id:?}"
+                );
+            }
+            assert_eq!(rows.count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_split() {
+        // a single nullable IN parameter of type TINYINT, without name
+        let descriptors = ParameterDescriptors::parse(
+            1,
+            &mut std::io::Cursor::new(vec![2, 1, 1, 0, 255, 255, 255, 255, 3, 0, 0, 0, 0, 0, 0, 0]),
+        )
+        .unwrap();
+        let mut rows = ParameterRows::new();
+        for i in 0..10_u8 {
+            rows.push(&(i,), &descriptors).unwrap();
+        }
+        // each row has a type code and a single byte
+        assert_eq!(rows.size(&descriptors).unwrap(), 20);
+
+        let counts = |max_rows, max_size| {
+            rows.clone()
+                .split(max_rows, max_size, &descriptors)
+                .unwrap()
+                .iter()
+                .map(ParameterRows::count)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(counts(100, 100), vec![10]);
+        assert_eq!(counts(4, 100), vec![4, 4, 2]);
+        assert_eq!(counts(100, 6), vec![3, 3, 3, 1]);
+        assert_eq!(counts(100, 1), vec![1; 10]);
+
+        // an empty batch remains a single, empty chunk
+        let chunks = ParameterRows::new().split(4, 100, &descriptors).unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].is_empty());
+    }
+}
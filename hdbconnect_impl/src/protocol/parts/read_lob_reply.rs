@@ -1,5 +1,6 @@
-use crate::{protocol::util_sync, HdbResult};
+use crate::{conn::LobBufferPool, impl_err, protocol::util_sync, HdbResult};
 use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Read;
 
 #[derive(Debug)]
 pub(crate) struct ReadLobReply {
@@ -17,14 +18,22 @@ impl ReadLobReply {
 }
 
 impl ReadLobReply {
-    pub fn parse(rdr: &mut dyn std::io::Read) -> HdbResult<Self> {
+    pub fn parse(lob_buffers: &mut LobBufferPool, rdr: &mut dyn std::io::Read) -> HdbResult<Self> {
         let locator_id = rdr.read_u64::<LittleEndian>()?; // I8
         let options = rdr.read_u8()?; // I1
         let is_last_data = (options & 0b100_u8) != 0;
         let chunk_length = rdr.read_i32::<LittleEndian>()?; // I4
         util_sync::skip_bytes(3, rdr)?; // B3 (filler)
-        #[allow(clippy::cast_sign_loss)]
-        let data = util_sync::parse_bytes(chunk_length as usize, rdr)?; // B[chunk_length]
+        let chunk_length = usize::try_from(chunk_length)
+            .map_err(|_| impl_err!("corrupt reply: negative LOB chunk length {chunk_length}"))?;
+        let mut data = lob_buffers.take();
+        rdr.take(chunk_length as u64).read_to_end(&mut data)?; // B[chunk_length]
+        if data.len() != chunk_length {
+            return Err(impl_err!(
+                "corrupt reply: LOB chunk has {} instead of {chunk_length} bytes",
+                data.len()
+            ));
+        }
         Ok(Self {
             locator_id,
             is_last_data,
@@ -22,12 +22,15 @@ use vec_map::VecMap;
 /// let type_id_0 = metadata[0].type_id();
 /// let precision_0 = metadata[0].precision();
 ///
-/// // loop over all fields (ResultSetMetadata deref's to a Vec<FieldMetadata>):
-/// for field_md in &**metadata {
+/// // loop over all fields:
+/// for field_md in metadata.iter() {
 ///     println!("{}", field_md.tablename());
 /// }
+///
+/// // access by name:
+/// let o_price_md = metadata.column_index("PRICE").map(|idx| &metadata[idx]);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub struct ResultSetMetadata(Vec<FieldMetadata>);
 impl Deref for ResultSetMetadata {
     type Target = Vec<FieldMetadata>;
@@ -48,6 +51,50 @@ impl std::fmt::Display for ResultSetMetadata {
 }
 
 impl ResultSetMetadata {
+    /// Creates the metadata from the given field metadata,
+    /// e.g. for fabricating result sets without a database.
+    #[must_use]
+    pub fn new(fields: Vec<FieldMetadata>) -> Self {
+        Self(fields)
+    }
+
+    /// Returns an iterator over the metadata of the fields, in the order of the fields.
+    ///
+    /// Each `FieldMetadata` describes one column, with its name, type id, scale, precision,
+    /// nullability, and the names of its schema and table.
+    pub fn iter(&self) -> std::slice::Iter<'_, FieldMetadata> {
+        self.0.iter()
+    }
+
+    /// Returns the index of the first field with the given name, if any.
+    ///
+    /// The name is compared with the display names of the fields (which are the column names
+    /// or the aliases given in the query), like in deserialization.
+    #[must_use]
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.0
+            .iter()
+            .position(|field_md| field_md.displayname() == name)
+    }
+
+    // Returns the display name of the first field that differs from the other metadata,
+    // or None if both are equal.
+    pub(crate) fn first_difference<'a>(&'a self, other: &'a Self) -> Option<&'a str> {
+        if let Some(field_md) = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .find_map(|(field_md, other_md)| (field_md != other_md).then_some(field_md))
+        {
+            return Some(field_md.displayname());
+        }
+        match self.0.len().cmp(&other.0.len()) {
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Less => Some(other.0[self.0.len()].displayname()),
+            std::cmp::Ordering::Greater => Some(self.0[other.0.len()].displayname()),
+        }
+    }
+
     pub(crate) fn parse(count: usize, rdr: &mut dyn std::io::Read) -> HdbResult<Self> {
         let mut inner_fms = Vec::<InnerFieldMetadata>::new();
         let mut names = VecMap::<String>::new();
@@ -101,6 +148,14 @@ impl ResultSetMetadata {
     }
 }
 
+impl<'a> IntoIterator for &'a ResultSetMetadata {
+    type Item = &'a FieldMetadata;
+    type IntoIter = std::slice::Iter<'a, FieldMetadata>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
 fn add_to_names(names: &mut VecMap<String>, offset: u32) {
     if offset != u32::MAX {
         let offset = offset as usize;
@@ -109,3 +164,29 @@ fn add_to_names(names: &mut VecMap<String>, offset: u32) {
         };
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::ResultSetMetadata;
+    use crate::{FieldMetadata, TypeId};
+
+    #[test]
+    fn test_iter_and_column_index() {
+        let metadata = ResultSetMetadata::new(vec![
+            FieldMetadata::new_column("ID", TypeId::INT),
+            FieldMetadata::new_column("PRICE", TypeId::DECIMAL)
+                .with_displayname("P")
+                .with_precision(10)
+                .with_scale(2),
+        ]);
+        let type_ids: Vec<TypeId> = metadata.iter().map(FieldMetadata::type_id).collect();
+        assert_eq!(type_ids, vec![TypeId::INT, TypeId::DECIMAL]);
+        assert_eq!((&metadata).into_iter().count(), 2);
+
+        assert_eq!(metadata.column_index("ID"), Some(0));
+        assert_eq!(metadata.column_index("P"), Some(1));
+        assert_eq!(metadata[1].scale(), 2);
+        assert_eq!(metadata.column_index("PRICE"), None);
+        assert_eq!(metadata.column_index("id"), None);
+    }
+}
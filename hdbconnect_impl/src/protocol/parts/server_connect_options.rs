@@ -0,0 +1,177 @@
+use crate::protocol::parts::{option_part::OptionId, ConnOptId, ConnectOptionsPart, OptionValue};
+
+/// The connect options that the server returned when the connection was established.
+///
+/// Besides some convenience getters for the more commonly used options,
+/// all options can be inspected with [`ServerConnectOptions::iter`].
+#[derive(Clone, Debug, Default)]
+pub struct ServerConnectOptions(Vec<(ConnOptId, OptionValue)>);
+
+impl ServerConnectOptions {
+    pub(crate) fn new(part: ConnectOptionsPart) -> Self {
+        let mut options: Vec<(ConnOptId, OptionValue)> = part.into_iter().collect();
+        options.sort_by_key(|(id, _)| id.to_u8());
+        Self(options)
+    }
+
+    // Converts the options back into the format in which they were received.
+    #[cfg(feature = "mock")]
+    pub(crate) fn to_part(&self) -> ConnectOptionsPart {
+        let mut part = ConnectOptionsPart::default();
+        for (id, value) in &self.0 {
+            part.insert(id.clone(), value.clone());
+        }
+        part
+    }
+
+    fn get(&self, id: &ConnOptId) -> Option<&OptionValue> {
+        self.0
+            .iter()
+            .find_map(|(k, v)| if k == id { Some(v) } else { None })
+    }
+
+    fn get_int(&self, id: &ConnOptId) -> Option<i32> {
+        self.get(id).and_then(|v| v.get_int_as_i32().ok())
+    }
+
+    fn get_bool(&self, id: &ConnOptId) -> Option<bool> {
+        self.get(id).and_then(|v| v.get_bool().ok())
+    }
+
+    fn get_str(&self, id: &ConnOptId) -> Option<&str> {
+        self.get(id)
+            .and_then(|v| v.get_string().ok())
+            .map(String::as_str)
+    }
+
+    /// Returns the names and values of all options, in the order of their ids.
+    pub fn iter(&self) -> impl Iterator<Item = (String, String)> + '_ {
+        self.0
+            .iter()
+            .map(|(id, value)| (format!("{id:?}"), value.to_string()))
+    }
+
+    /// Returns the number of options.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the server did not return any option.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The id of the connection.
+    #[must_use]
+    pub fn connection_id(&self) -> Option<i32> {
+        self.get_int(&ConnOptId::ConnectionID)
+    }
+
+    /// The SID of the database system.
+    #[must_use]
+    pub fn system_id(&self) -> Option<&str> {
+        self.get_str(&ConnOptId::SystemID)
+    }
+
+    /// The name of the (MDC) database.
+    #[must_use]
+    pub fn database_name(&self) -> Option<&str> {
+        self.get_str(&ConnOptId::DatabaseName)
+    }
+
+    /// The full version string of the server.
+    #[must_use]
+    pub fn full_version_string(&self) -> Option<&str> {
+        self.get_str(&ConnOptId::FullVersionString)
+    }
+
+    /// The build platform of the server.
+    #[must_use]
+    pub fn build_platform(&self) -> Option<i32> {
+        self.get_int(&ConnOptId::BuildPlatform)
+    }
+
+    /// The endianness of the server.
+    #[must_use]
+    pub fn endianness(&self) -> Option<i32> {
+        self.get_int(&ConnOptId::Endianness)
+    }
+
+    /// The data format version that was negotiated.
+    #[must_use]
+    pub fn data_format_version_2(&self) -> Option<i32> {
+        self.get_int(&ConnOptId::DataFormatVersion2)
+    }
+
+    /// The highest data format version the server's engine supports.
+    #[must_use]
+    pub fn engine_data_format_version(&self) -> Option<i32> {
+        self.get_int(&ConnOptId::EngineDataFormatVersion)
+    }
+
+    /// The client distribution mode.
+    #[must_use]
+    pub fn client_distribution_mode(&self) -> Option<i32> {
+        self.get_int(&ConnOptId::ClientDistributionMode)
+    }
+
+    /// Whether distribution is enabled.
+    #[must_use]
+    pub fn distribution_enabled(&self) -> Option<bool> {
+        self.get_bool(&ConnOptId::DistributionEnabled)
+    }
+
+    /// The version of the distribution protocol.
+    #[must_use]
+    pub fn distribution_protocol_version(&self) -> Option<i32> {
+        self.get_int(&ConnOptId::DistributionProtocolVersion)
+    }
+
+    /// Whether the server supports implicit LOB streaming.
+    #[must_use]
+    pub fn implicit_lob_streaming(&self) -> Option<bool> {
+        self.get_bool(&ConnOptId::ImplicitLobStreaming)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ServerConnectOptions;
+    use crate::protocol::parts::{ConnOptId, ConnectOptionsPart, OptionValue};
+
+    #[test]
+    fn test_server_connect_options() {
+        let mut part = ConnectOptionsPart::default();
+        part.insert(
+            ConnOptId::FullVersionString,
+            OptionValue::STRING("2.00.080.00.1234567890".to_string()),
+        );
+        part.insert(ConnOptId::Endianness, OptionValue::INT(1));
+        part.insert(ConnOptId::DataFormatVersion2, OptionValue::INT(8));
+        part.insert(ConnOptId::ImplicitLobStreaming, OptionValue::BOOLEAN(true));
+
+        let options = ServerConnectOptions::new(part);
+        assert_eq!(options.len(), 4);
+        assert_eq!(
+            options.full_version_string(),
+            Some("2.00.080.00.1234567890")
+        );
+        assert_eq!(options.endianness(), Some(1));
+        assert_eq!(options.data_format_version_2(), Some(8));
+        assert_eq!(options.implicit_lob_streaming(), Some(true));
+        assert_eq!(options.system_id(), None);
+
+        let names: Vec<String> = options.iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "DataFormatVersion2",
+                "Endianness",
+                "ImplicitLobStreaming",
+                "FullVersionString"
+            ]
+        );
+    }
+}
@@ -63,16 +63,52 @@ pub struct ServerError {
 const BASE_SIZE: i32 = 4 + 4 + 4 + 1 + 5;
 
 impl ServerError {
+    const INSUFFICIENT_PRIVILEGE: i32 = 258;
+
     /// Returns the error code.
     #[must_use]
     pub fn code(&self) -> i32 {
         self.code
     }
-    /// Returns the position in the line where the error occured.
+    /// Returns the position in the statement where the error occured.
+    ///
+    /// The position is the 0-based character offset within the statement text;
+    /// see also [`ServerError::line_and_column`].
     #[must_use]
     pub fn position(&self) -> i32 {
         self.position
     }
+    /// Maps the error position to 1-based line and column numbers within the given
+    /// statement text, e.g. to highlight the offending token in an editor.
+    ///
+    /// Returns `None` if the position does not lie within the statement
+    /// (or directly behind its end).
+    #[must_use]
+    pub fn line_and_column(&self, statement: &str) -> Option<(usize, usize)> {
+        let offset = usize::try_from(self.position).ok()?;
+        let mut line = 1;
+        let mut column = 1;
+        for c in statement.chars().take(offset) {
+            if c == '\n' {
+                line += 1;
+                column = 1;
+            } else {
+                column += 1;
+            }
+        }
+        if statement.chars().count() >= offset {
+            Some((line, column))
+        } else {
+            None
+        }
+    }
+    /// Returns true if the error reports that the user lacks a privilege
+    /// that the statement requires (error code 258),
+    /// e.g. `SESSION ADMIN` for disconnecting or cancelling other sessions.
+    #[must_use]
+    pub fn is_insufficient_privilege(&self) -> bool {
+        self.code == Self::INSUFFICIENT_PRIVILEGE
+    }
     /// Returns the Severity of the error.
     #[must_use]
     pub fn severity(&self) -> &Severity {
@@ -152,3 +188,43 @@ impl std::fmt::Debug for ServerError {
         write!(fmt, "{self}")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ServerError, Severity};
+
+    #[test]
+    fn test_line_and_column() {
+        let statement = "select *\nfrom dummy\nwher x = 1";
+        let server_error = |position| {
+            ServerError::new(
+                257,
+                position,
+                Severity::Error,
+                b"HY000".to_vec(),
+                String::new(),
+            )
+        };
+        assert_eq!(server_error(0).line_and_column(statement), Some((1, 1)));
+        assert_eq!(server_error(7).line_and_column(statement), Some((1, 8)));
+        assert_eq!(server_error(20).line_and_column(statement), Some((3, 1)));
+        assert_eq!(server_error(30).line_and_column(statement), Some((3, 11)));
+        assert_eq!(server_error(100).line_and_column(statement), None);
+        assert_eq!(server_error(-1).line_and_column(statement), None);
+    }
+
+    #[test]
+    fn test_is_insufficient_privilege() {
+        let server_error = |code| {
+            ServerError::new(
+                code,
+                0,
+                Severity::Error,
+                b"HY000".to_vec(),
+                "insufficient privilege: Not authorized".to_string(),
+            )
+        };
+        assert!(server_error(258).is_insufficient_privilege());
+        assert!(!server_error(257).is_insufficient_privilege());
+    }
+}
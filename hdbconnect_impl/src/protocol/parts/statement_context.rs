@@ -23,6 +23,16 @@ impl StatementContext {
         );
     }
 
+    // The server expects the query timeout in seconds; fractions are rounded up,
+    // so that a short timeout does not switch the timeout off.
+    pub fn set_query_timeout(&mut self, timeout: Duration) {
+        let seconds = timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0);
+        self.insert(
+            StatementContextId::QueryTimeout,
+            OptionValue::BIGINT(i64::try_from(seconds).unwrap_or(i64::MAX)),
+        );
+    }
+
     pub fn server_processing_time(&self) -> Option<Duration> {
         match self.get(&StatementContextId::ServerProcessingTime) {
             Ok(&OptionValue::BIGINT(value)) => {
@@ -41,6 +51,16 @@ impl StatementContext {
         }
     }
 
+    // Only sent by servers that measure it, and only if the statement had to wait.
+    pub fn server_lock_wait_time(&self) -> Option<Duration> {
+        match self.get(&StatementContextId::ServerLockWaitTime) {
+            Ok(&OptionValue::BIGINT(value)) => {
+                Some(Duration::from_micros(value.try_into().unwrap_or(0)))
+            }
+            _ => None,
+        }
+    }
+
     pub fn server_memory_usage(&self) -> Option<u64> {
         match self.get(&StatementContextId::ServerMemoryUsage) {
             Ok(&OptionValue::BIGINT(value)) => Some(value.try_into().unwrap_or(0)),
@@ -59,6 +79,7 @@ pub enum StatementContextId {
     ClientReconnectionWaitTimeout, // 6 // INT
     ServerCPUTime,                 // 7 // BIGINT microseconds
     ServerMemoryUsage,             // 8 // BIGINT bytes
+    ServerLockWaitTime,            // 9 // BIGINT microseconds
     __Unexpected__(u8),
 }
 impl OptionId<StatementContextId> for StatementContextId {
@@ -72,6 +93,7 @@ impl OptionId<StatementContextId> for StatementContextId {
             Self::ClientReconnectionWaitTimeout => 6,
             Self::ServerCPUTime => 7,
             Self::ServerMemoryUsage => 8,
+            Self::ServerLockWaitTime => 9,
             Self::__Unexpected__(val) => val,
         }
     }
@@ -86,6 +108,7 @@ impl OptionId<StatementContextId> for StatementContextId {
             6 => Self::ClientReconnectionWaitTimeout,
             7 => Self::ServerCPUTime,
             8 => Self::ServerMemoryUsage,
+            9 => Self::ServerLockWaitTime,
             val => {
                 warn!("Unsupported value for StatementContextId received: {}", val);
                 Self::__Unexpected__(val)
@@ -97,3 +120,62 @@ impl OptionId<StatementContextId> for StatementContextId {
         "StatementContext"
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{StatementContext, StatementContextId};
+    use crate::protocol::{parts::OptionValue, StatementMetrics};
+    use std::time::Duration;
+
+    #[test]
+    fn test_query_timeout() {
+        for (timeout, expected) in [
+            (Duration::from_secs(30), 30),
+            (Duration::from_millis(1_500), 2),
+            (Duration::from_millis(1), 1),
+        ] {
+            let mut stmt_ctx = StatementContext::default();
+            stmt_ctx.set_query_timeout(timeout);
+            assert!(matches!(
+                stmt_ctx.get(&StatementContextId::QueryTimeout),
+                Ok(&OptionValue::BIGINT(seconds)) if seconds == expected
+            ));
+        }
+    }
+
+    #[test]
+    fn test_statement_metrics() {
+        let mut stmt_ctx = StatementContext::default();
+        stmt_ctx.insert(
+            StatementContextId::ServerProcessingTime,
+            OptionValue::BIGINT(1_500),
+        );
+        stmt_ctx.insert(
+            StatementContextId::ServerMemoryUsage,
+            OptionValue::BIGINT(4_096),
+        );
+        stmt_ctx.insert(
+            StatementContextId::ServerLockWaitTime,
+            OptionValue::BIGINT(250),
+        );
+        let metrics = StatementMetrics::from_statement_context(&stmt_ctx);
+        assert_eq!(
+            metrics.server_processing_time,
+            Some(Duration::from_micros(1_500))
+        );
+        assert_eq!(metrics.server_cpu_time, None);
+        assert_eq!(metrics.server_memory_usage, Some(4_096));
+        assert_eq!(
+            metrics.server_lock_wait_time,
+            Some(Duration::from_micros(250))
+        );
+        assert_eq!(
+            metrics.to_string(),
+            "processing time: 1.5ms, memory usage: 4096 bytes, lock wait time: 250µs"
+        );
+
+        let metrics = StatementMetrics::from_statement_context(&StatementContext::default());
+        assert_eq!(metrics.server_lock_wait_time, None);
+        assert_eq!(metrics.to_string(), "");
+    }
+}
@@ -71,8 +71,9 @@ pub enum TypeId {
     /// used with [`HdbValue::STRING`](crate::HdbValue::STRING).
     SHORTTEXT = 52,
     /// For database type BINTEXT;
-    /// Used with [`HdbValue::BINARY`](crate::HdbValue::BINARY) or
-    /// [`HdbValue::BLOB`](crate::HdbValue::BLOB).
+    /// is read as [`HdbValue::BLOB`](crate::HdbValue::BLOB), and can be deserialized
+    /// into `Vec<u8>`; can be written with strings, which are transmitted
+    /// like for TEXT in CESU-8, or with [`HdbValue::BINARY`](crate::HdbValue::BINARY).
     BINTEXT = 53,
     /// For database type ALPHANUM;
     /// used with [`HdbValue::STRING`](crate::HdbValue::STRING).
@@ -163,7 +164,11 @@ impl TypeId {
             81 => Self::FIXED8,
             82 => Self::FIXED12,
             // TypeCode_CIPHERTEXT               = 90,  // TODO only for client-side encryption?
-            tc => return Err(impl_err!("Illegal type code {tc}")),
+            tc => {
+                return Err(impl_err!(
+                    "Unsupported type code {tc} (the server may use a newer protocol version)"
+                ))
+            }
         })
     }
 
@@ -172,6 +177,16 @@ impl TypeId {
         (if nullable { 128 } else { 0 }) + self as u8
     }
 
+    // The lowest data format version with which values of this type can be sent.
+    pub(crate) fn min_dataformat_version2(self) -> u8 {
+        match self {
+            Self::BINTEXT => 6,
+            Self::BOOLEAN => 7,
+            Self::FIXED8 | Self::FIXED12 | Self::FIXED16 => 8,
+            _ => 1,
+        }
+    }
+
     pub(crate) fn matches_value_type(self, value_type: Self) -> HdbResult<()> {
         if value_type == self {
             return Ok(());
@@ -189,7 +204,12 @@ impl TypeId {
 
             (
                 Self::BINARY,
-                Self::BLOB | Self::BLOCATOR | Self::VARBINARY | Self::GEOMETRY | Self::POINT,
+                Self::BLOB
+                | Self::BLOCATOR
+                | Self::VARBINARY
+                | Self::BINTEXT
+                | Self::GEOMETRY
+                | Self::POINT,
             )
             | (Self::DECIMAL, Self::FIXED8 | Self::FIXED12 | Self::FIXED16) => return Ok(()),
 
@@ -246,3 +266,19 @@ impl std::fmt::Display for TypeId {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::TypeId;
+
+    #[test]
+    fn test_unknown_type_code() {
+        assert_eq!(TypeId::try_new(3).unwrap(), TypeId::INT);
+        assert_eq!(TypeId::try_new(53).unwrap(), TypeId::BINTEXT);
+        // the size of values of unknown types is not known, so they cannot be skipped
+        for tc in [14, 50, 90, 127] {
+            let err = TypeId::try_new(tc).unwrap_err();
+            assert!(err.to_string().contains("Unsupported type code"), "{err}");
+        }
+    }
+}
@@ -2,11 +2,11 @@
 use crate::conn::TcpClient;
 use crate::{
     base::{InternalReturnValue, RsState},
-    conn::{AmConnCore, ConnectionCore, ConnectionStatistics},
+    conn::{AmConnCore, ConnectionCore, ConnectionStatistics, LobBufferPool, QueryOptions},
     impl_err,
     protocol::{
         parts::{ParameterDescriptors, Parts, ResultSetMetadata, ServerError, Severity},
-        util_sync, Part, PartKind, ReplyType, ServerUsage,
+        util_sync, Part, PartKind, ReplyType, ServerUsage, StatementMetrics,
     },
     HdbError, HdbResult,
 };
@@ -15,14 +15,15 @@ use std::{io::Cursor, sync::Arc, time::Instant};
 
 use super::{MESSAGE_AND_SEGMENT_HEADER_SIZE, SEGMENT_HEADER_SIZE};
 
-// Since there is obviously no usecase for multiple segments in one request,
-// we model message and segment together.
+// We model message and first segment together; the further segments of a message,
+// which only occur in replies to requests with several segments, are kept separately.
 // But we differentiate explicitly between request messages and reply messages.
 #[derive(Debug)]
 pub(crate) struct Reply {
     session_id: i64,
     pub replytype: ReplyType,
     pub parts: Parts<'static>,
+    following: Vec<Reply>,
 }
 impl Reply {
     fn new(session_id: i64, replytype: ReplyType) -> Self {
@@ -30,6 +31,7 @@ impl Reply {
             session_id,
             replytype,
             parts: Parts::default(),
+            following: Vec::new(),
         }
     }
 
@@ -37,6 +39,16 @@ impl Reply {
         self.session_id
     }
 
+    // Discards the replies in the further segments of the message beyond the given number.
+    pub(crate) fn truncate_following(&mut self, len: usize) {
+        self.following.truncate(len);
+    }
+
+    // Removes and returns the replies in the further segments of the message.
+    pub(crate) fn take_following(&mut self) -> Vec<Reply> {
+        std::mem::take(&mut self.following)
+    }
+
     // Parse a reply from the stream, building a Reply object.
     //
     // * `ResultSetMetadata` needs to be injected for execute calls of prepared statements
@@ -49,7 +61,9 @@ impl Reply {
         o_rs: &mut Option<&mut RsState>,
         o_am_conn_core: Option<&AmConnCore>,
         statistics: &mut ConnectionStatistics,
+        lob_buffers: &mut LobBufferPool,
         start: std::time::Instant,
+        max_reply_size: usize,
         io_buffer: &mut Cursor<Vec<u8>>,
         rdr: &mut dyn std::io::Read,
     ) -> HdbResult<Self> {
@@ -57,7 +71,7 @@ impl Reply {
         let packet_header = {
             read_into_buffer_sync(MESSAGE_AND_SEGMENT_HEADER_SIZE, io_buffer, rdr)?;
             statistics.add_wait_time(Instant::now().duration_since(start));
-            parse_packet_header(io_buffer)?
+            parse_packet_header(io_buffer, max_reply_size)?
         };
 
         // read rest of reply into buffer and decompress if necessary
@@ -72,19 +86,39 @@ impl Reply {
             .transpose()?
             .map(Cursor::new);
 
-        // parse the parts and build the reply object
-        let mut reply = Self::new(packet_header.session_id, packet_header.reply_type);
-        for i in 0..packet_header.no_of_parts {
-            let part = Part::parse_sync(
-                &mut (reply.parts),
-                o_am_conn_core,
-                o_a_rsmd,
-                o_a_descriptors,
-                o_rs,
-                i == packet_header.no_of_parts - 1,
-                o_cursor.as_mut().unwrap_or(io_buffer),
-            )?;
-            reply.push(part);
+        // parse the parts and build the reply object, and one for each further segment
+        let rdr = o_cursor.as_mut().unwrap_or(io_buffer);
+        let mut reply = Self::new(packet_header.session_id, packet_header.first.reply_type);
+        let mut segment_header = packet_header.first;
+        let mut segment_end = segment_header.size;
+        for segment_no in 1..=packet_header.no_of_segs {
+            let mut segment_reply = Self::new(packet_header.session_id, segment_header.reply_type);
+            for i in 0..segment_header.no_of_parts {
+                if let Some(part) = Part::parse_sync(
+                    &mut (segment_reply.parts),
+                    o_am_conn_core,
+                    o_a_rsmd,
+                    o_a_descriptors,
+                    o_rs,
+                    lob_buffers,
+                    i == segment_header.no_of_parts - 1,
+                    rdr,
+                )? {
+                    if let Part::StatementContext(ref stmt_ctx) = part {
+                        statistics.add_server_processing_time(stmt_ctx.server_processing_time());
+                    }
+                    segment_reply.push(part);
+                }
+            }
+            if segment_no == 1 {
+                reply.parts = segment_reply.parts;
+            } else {
+                reply.following.push(segment_reply);
+            }
+            if segment_no < packet_header.no_of_segs {
+                segment_header = next_segment_header(segment_end, rdr)?;
+                segment_end += segment_header.size;
+            }
         }
         Ok(reply)
     }
@@ -104,6 +138,8 @@ impl Reply {
         o_am_conn_core: Option<&AmConnCore>,
         start: std::time::Instant,
         statistics: &mut ConnectionStatistics,
+        lob_buffers: &mut LobBufferPool,
+        max_reply_size: usize,
         io_buffer: &mut Cursor<Vec<u8>>,
         tcp_client: &mut TcpClient,
     ) -> HdbResult<Self> {
@@ -116,6 +152,8 @@ impl Reply {
                     o_am_conn_core,
                     start,
                     statistics,
+                    lob_buffers,
+                    max_reply_size,
                     io_buffer,
                     cl.reader(),
                 )
@@ -129,6 +167,8 @@ impl Reply {
                     o_am_conn_core,
                     start,
                     statistics,
+                    lob_buffers,
+                    max_reply_size,
                     io_buffer,
                     cl.reader(),
                 )
@@ -149,6 +189,8 @@ impl Reply {
         o_am_conn_core: Option<&AmConnCore>,
         start: std::time::Instant,
         statistics: &mut ConnectionStatistics,
+        lob_buffers: &mut LobBufferPool,
+        max_reply_size: usize,
         io_buffer: &mut Cursor<Vec<u8>>,
         rdr: &mut R,
     ) -> HdbResult<Self> {
@@ -156,7 +198,7 @@ impl Reply {
         let packet_header = {
             read_into_buffer_async(MESSAGE_AND_SEGMENT_HEADER_SIZE, io_buffer, rdr).await?;
             statistics.add_wait_time(Instant::now().duration_since(start));
-            parse_packet_header(io_buffer)?
+            parse_packet_header(io_buffer, max_reply_size)?
         };
 
         // read rest of reply into buffer and decompress if necessary
@@ -171,20 +213,41 @@ impl Reply {
             .transpose()?
             .map(Cursor::new);
 
-        // parse the parts and build the reply object
-        let mut reply = Self::new(packet_header.session_id, packet_header.reply_type);
-        for i in 0..packet_header.no_of_parts {
-            let part = Part::parse_async(
-                &mut (reply.parts),
-                o_am_conn_core,
-                o_a_rsmd,
-                o_a_descriptors,
-                o_rs,
-                i == packet_header.no_of_parts - 1,
-                o_cursor.as_mut().unwrap_or(io_buffer),
-            )
-            .await?;
-            reply.push(part);
+        // parse the parts and build the reply object, and one for each further segment
+        let rdr = o_cursor.as_mut().unwrap_or(io_buffer);
+        let mut reply = Self::new(packet_header.session_id, packet_header.first.reply_type);
+        let mut segment_header = packet_header.first;
+        let mut segment_end = segment_header.size;
+        for segment_no in 1..=packet_header.no_of_segs {
+            let mut segment_reply = Self::new(packet_header.session_id, segment_header.reply_type);
+            for i in 0..segment_header.no_of_parts {
+                if let Some(part) = Part::parse_async(
+                    &mut (segment_reply.parts),
+                    o_am_conn_core,
+                    o_a_rsmd,
+                    o_a_descriptors,
+                    o_rs,
+                    lob_buffers,
+                    i == segment_header.no_of_parts - 1,
+                    rdr,
+                )
+                .await?
+                {
+                    if let Part::StatementContext(ref stmt_ctx) = part {
+                        statistics.add_server_processing_time(stmt_ctx.server_processing_time());
+                    }
+                    segment_reply.push(part);
+                }
+            }
+            if segment_no == 1 {
+                reply.parts = segment_reply.parts;
+            } else {
+                reply.following.push(segment_reply);
+            }
+            if segment_no < packet_header.no_of_segs {
+                segment_header = next_segment_header(segment_end, rdr)?;
+                segment_end += segment_header.size;
+            }
         }
         Ok(reply)
     }
@@ -211,10 +274,14 @@ impl Reply {
         self,
         am_conn_core: &AmConnCore,
         o_additional_server_usage: Option<&mut ServerUsage>,
+        o_options: Option<&QueryOptions>,
     ) -> HdbResult<(Vec<InternalReturnValue>, ReplyType)> {
         Ok((
-            self.parts
-                .into_internal_return_values_sync(am_conn_core, o_additional_server_usage)?,
+            self.parts.into_internal_return_values_sync(
+                am_conn_core,
+                o_additional_server_usage,
+                o_options,
+            )?,
             self.replytype,
         ))
     }
@@ -224,10 +291,15 @@ impl Reply {
         self,
         am_conn_core: &AmConnCore,
         o_additional_server_usage: Option<&mut ServerUsage>,
+        o_options: Option<&QueryOptions>,
     ) -> HdbResult<(Vec<InternalReturnValue>, ReplyType)> {
         Ok((
             self.parts
-                .into_internal_return_values_async(am_conn_core, o_additional_server_usage)
+                .into_internal_return_values_async(
+                    am_conn_core,
+                    o_additional_server_usage,
+                    o_options,
+                )
                 .await?,
             self.replytype,
         ))
@@ -235,6 +307,7 @@ impl Reply {
 
     pub(crate) fn handle_db_error(&mut self, conn_core: &mut ConnectionCore) -> HdbResult<()> {
         conn_core.warnings.clear();
+        conn_core.statement_metrics = StatementMetrics::default();
 
         // Retrieve server_errors from returned parts
         let mut server_errors = {
@@ -296,10 +369,12 @@ impl Reply {
     }
 }
 
-fn parse_packet_header(rdr: &mut dyn std::io::Read) -> HdbResult<ReplyPacketHeader> {
+fn parse_packet_header(
+    rdr: &mut dyn std::io::Read,
+    max_reply_size: usize,
+) -> HdbResult<ReplyPacketHeader> {
     // TODO validate session_id against ConnectionCore::session_id
     // TODO session_id and packet_count must be 0 for exactly the first roundtrip
-    // TODO validate assumptions about seg_size, seg_offset, seg_number being always = (varpart_size, 0, 1)
 
     // MESSAGE HEADER: 32 bytes
     let session_id: i64 = rdr.read_i64::<LittleEndian>()?; // I8
@@ -307,14 +382,8 @@ fn parse_packet_header(rdr: &mut dyn std::io::Read) -> HdbResult<ReplyPacketHead
     let parts_and_segment_header_size: u32 = rdr.read_u32::<LittleEndian>()?; // UI4
     let remaining_bufsize: u32 = rdr.read_u32::<LittleEndian>()?; // UI4
     let no_of_segs = rdr.read_i16::<LittleEndian>()?; // I2
-    match no_of_segs {
-        1 => {}
-        0 => return Err(impl_err!("empty response (is ok for drop connection)")),
-        _ => {
-            return Err(impl_err!(
-                "hdbconnect is not prepared for no_of_segs = {no_of_segs} > 1"
-            ))
-        }
+    if no_of_segs < 1 {
+        return Err(impl_err!("empty response (is ok for drop connection)"));
     }
 
     let compressed = match rdr.read_u8()? {
@@ -328,15 +397,8 @@ fn parse_packet_header(rdr: &mut dyn std::io::Read) -> HdbResult<ReplyPacketHead
     let uncompressed_size = rdr.read_u32::<LittleEndian>()?;
     util_sync::skip_bytes(4, rdr)?; // m_filler4byte
 
-    // SEGMENT HEADER: 24 bytes
-    let seg_size = rdr.read_i32::<LittleEndian>()?; // I4 seg_size
-    let seg_offset = rdr.read_i32::<LittleEndian>()?; // I4 seg_offset
-    let no_of_parts: i16 = rdr.read_i16::<LittleEndian>()?; // I2
-    let seg_number = rdr.read_i16::<LittleEndian>()?; // I2 seg_number
-    let seg_kind = Kind::from_i8(rdr.read_i8()?)?; // I1
-
     trace!(
-        "REPLY, message and segment header: {{\
+        "REPLY, message header: {{\
             \n  session_id = {session_id}, \
             \n  packet_seq_number = {packet_seq_number}, \
             \n  parts_and_segment_header_size = {parts_and_segment_header_size}, \
@@ -344,7 +406,52 @@ fn parse_packet_header(rdr: &mut dyn std::io::Read) -> HdbResult<ReplyPacketHead
             \n  no_of_segs = {no_of_segs}, \
             \n  compressed = {compressed}, \
             \n  uncompressed_size = {uncompressed_size}, \
-            \n\n  seg_size = {seg_size}, \
+        }}"
+    );
+
+    let part_buffer_size = parts_and_segment_header_size
+        .checked_sub(SEGMENT_HEADER_SIZE)
+        .ok_or_else(|| {
+            impl_err!("corrupt reply: size {parts_and_segment_header_size} is too small")
+        })? as usize;
+    if part_buffer_size > max_reply_size
+        || (compressed && uncompressed_size as usize > max_reply_size)
+    {
+        return Err(impl_err!(
+            "corrupt reply: size {} exceeds the configured maximum of {max_reply_size} bytes",
+            std::cmp::max(part_buffer_size, uncompressed_size as usize)
+        ));
+    }
+
+    let first = parse_segment_header(rdr)?;
+    debug!(
+        "Reply::parse(): got reply of type {:?} for session_id {}",
+        first.reply_type, session_id
+    );
+    Ok(ReplyPacketHeader {
+        o_uncompressed_size: if compressed {
+            Some(uncompressed_size as usize)
+        } else {
+            None
+        },
+        session_id,
+        part_buffer_size,
+        no_of_segs,
+        first,
+    })
+}
+
+// SEGMENT HEADER: 24 bytes
+fn parse_segment_header(rdr: &mut dyn std::io::Read) -> HdbResult<SegmentHeader> {
+    let seg_size = rdr.read_i32::<LittleEndian>()?; // I4 seg_size
+    let seg_offset = rdr.read_i32::<LittleEndian>()?; // I4 seg_offset
+    let no_of_parts: i16 = rdr.read_i16::<LittleEndian>()?; // I2
+    let seg_number = rdr.read_i16::<LittleEndian>()?; // I2 seg_number
+    let seg_kind = Kind::from_i8(rdr.read_i8()?)?; // I1
+
+    trace!(
+        "REPLY, segment header: {{\
+            \n  seg_size = {seg_size}, \
             \n  seg_offset = {seg_offset}, \
             \n  no_of_parts = {no_of_parts}, \
             \n  seg_number = {seg_number}, \
@@ -352,32 +459,49 @@ fn parse_packet_header(rdr: &mut dyn std::io::Read) -> HdbResult<ReplyPacketHead
         }}"
     );
 
+    let size = usize::try_from(seg_size)
+        .ok()
+        .filter(|size| *size >= SEGMENT_HEADER_SIZE as usize)
+        .ok_or_else(|| impl_err!("corrupt reply: segment size {seg_size}"))?;
+    let offset = usize::try_from(seg_offset)
+        .map_err(|_| impl_err!("corrupt reply: segment offset {seg_offset}"))?;
     match seg_kind {
         Kind::Request => Err(impl_err!("Cannot _parse_ a request")),
         Kind::Reply | Kind::Error => {
             util_sync::skip_bytes(1, rdr)?; // I1
             let reply_type = ReplyType::from_i16(rdr.read_i16::<LittleEndian>()?)?; // I2
             util_sync::skip_bytes(8, rdr)?; // B[8]
-
-            debug!(
-                "Reply::parse(): got reply of type {:?} and seg_kind {:?} for session_id {}",
-                reply_type, seg_kind, session_id
-            );
-            Ok(ReplyPacketHeader {
+            Ok(SegmentHeader {
+                size,
+                offset,
                 no_of_parts,
-                o_uncompressed_size: if compressed {
-                    Some(uncompressed_size as usize)
-                } else {
-                    None
-                },
-                session_id,
-                part_buffer_size: (parts_and_segment_header_size - SEGMENT_HEADER_SIZE) as usize,
                 reply_type,
             })
         }
     }
 }
 
+// Reads the header of the segment that starts at the given offset within the message;
+// the buffer starts after the header of the first segment.
+fn next_segment_header(offset: usize, rdr: &mut Cursor<Vec<u8>>) -> HdbResult<SegmentHeader> {
+    let position = offset - SEGMENT_HEADER_SIZE as usize;
+    if position + SEGMENT_HEADER_SIZE as usize > rdr.get_ref().len() {
+        return Err(impl_err!(
+            "corrupt reply: segment at offset {offset} is missing"
+        ));
+    }
+    rdr.set_position(position as u64);
+    let segment_header = parse_segment_header(rdr)?;
+    if segment_header.offset == offset {
+        Ok(segment_header)
+    } else {
+        Err(impl_err!(
+            "corrupt reply: segment at offset {offset} claims offset {}",
+            segment_header.offset
+        ))
+    }
+}
+
 /// Specifies the layout of the remaining segment header structure
 #[derive(Debug)]
 enum Kind {
@@ -397,11 +521,19 @@ impl Kind {
 }
 
 struct ReplyPacketHeader {
-    reply_type: ReplyType,
     session_id: i64,
     o_uncompressed_size: Option<usize>,
     part_buffer_size: usize,
+    no_of_segs: i16,
+    first: SegmentHeader,
+}
+
+#[derive(Clone, Copy)]
+struct SegmentHeader {
+    size: usize,
+    offset: usize,
     no_of_parts: i16,
+    reply_type: ReplyType,
 }
 
 #[cfg(feature = "sync")]
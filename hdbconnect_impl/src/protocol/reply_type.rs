@@ -5,32 +5,38 @@ use crate::{impl_err, HdbResult};
 // Irrelevant numbers (ABAP stuff, "reserved") are omitted.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub(crate) enum ReplyType {
-    Nil,                       // Nil
-    Ddl,                       // DDL statement
-    Insert,                    // INSERT statement
-    Update,                    // UPDATE statement
-    Delete,                    // DELETE statement
-    Select,                    // SELECT statement
-    SelectForUpdate,           // SELECT … FOR UPDATE statement
-    Explain,                   // EXPLAIN statement
-    DbProcedureCall,           // CALL statement
-    DbProcedureCallWithResult, // CALL statement returning one or more results
-    Fetch,                     // FETCH message
-    Commit,                    // COMMIT message or statement
-    Rollback,                  // ROLLBACK message or statement
-    Connect,                   // CONNECT or AUTHENTICATION message
-    WriteLob,                  // WRITELOB message
-    ReadLob,                   // READLOB message
-    Disconnect,                // DISCONNECT message
-    CloseCursor,               // CLOSECURSOR message
-    FindLob,                   // FINDLOB message
-    XaStart,                   // XA_START message
-    XaJoin,                    // XA_JOIN message
-    XAControl,                 // undocumented
-    XAPrepare,                 // undocumented
-    XARecover,                 // undocumented
+    Nil = 0,                       // Nil
+    Ddl = 1,                       // DDL statement
+    Insert = 2,                    // INSERT statement
+    Update = 3,                    // UPDATE statement
+    Delete = 4,                    // DELETE statement
+    Select = 5,                    // SELECT statement
+    SelectForUpdate = 6,           // SELECT … FOR UPDATE statement
+    Explain = 7,                   // EXPLAIN statement
+    DbProcedureCall = 8,           // CALL statement
+    DbProcedureCallWithResult = 9, // CALL statement returning one or more results
+    Fetch = 10,                    // FETCH message
+    Commit = 11,                   // COMMIT message or statement
+    Rollback = 12,                 // ROLLBACK message or statement
+    Connect = 14,                  // CONNECT or AUTHENTICATION message
+    WriteLob = 15,                 // WRITELOB message
+    ReadLob = 16,                  // READLOB message
+    Disconnect = 18,               // DISCONNECT message
+    CloseCursor = 19,              // CLOSECURSOR message
+    FindLob = 20,                  // FINDLOB message
+    XaStart = 22,                  // XA_START message
+    XaJoin = 23,                   // XA_JOIN message
+    XAControl = 25,                // undocumented
+    XAPrepare = 26,                // undocumented
+    XARecover = 27,                // undocumented
 }
 impl ReplyType {
+    // The function code, as it is sent by the server.
+    #[cfg(feature = "test-utils")]
+    pub fn to_i16(self) -> i16 {
+        self as i16
+    }
+
     pub fn from_i16(val: i16) -> HdbResult<Self> {
         match val {
             0 => Ok(Self::Nil),
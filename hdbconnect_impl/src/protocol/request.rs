@@ -1,17 +1,14 @@
 use crate::{
-    conn::{CommandOptions, ConnectionConfiguration, ConnectionStatistics},
+    conn::{CommandOptions, ConnectionConfiguration, ConnectionStatistics, QueryOptions},
     protocol::{
         parts::{ParameterDescriptors, Parts, StatementContext},
         MessageType, Part, MESSAGE_AND_SEGMENT_HEADER_SIZE, SEGMENT_HEADER_SIZE,
     },
-    HdbResult,
+    HdbError, HdbResult, ServerVersion,
 };
 use byteorder::{LittleEndian, WriteBytesExt};
 use std::{io::Cursor, sync::Arc};
 
-const ONE_AS_NUMBER_OF_SEGMENTS: i16 = 1;
-const ONE_AS_ORDINAL_OF_THIS_SEGMENT: i16 = 1;
-const ZERO_AS_OFFSET: i32 = 0;
 const SEGMENT_KIND_REQUEST: i8 = 1;
 
 const PACKET_OPTION_COMPRESS: u8 = 2;
@@ -21,12 +18,29 @@ const FILLER_4: u32 = 0;
 const FILLER_8: u64 = 0;
 const FILLER_10: [u8; 10] = [0; 10];
 
+// The packet and segment lengths are signed 32-bit integers in the protocol.
+const MAX_PROTOCOL_REQUEST_SIZE: usize = i32::MAX as usize;
+
+// The number of segments of a message is a signed 16-bit integer in the protocol.
+pub(crate) const MAX_SEGMENTS: usize = i16::MAX as usize;
+
+// Room for the parts of an execute request other than the parameter rows,
+// i.e. the statement id, a statement context, and their part headers.
+const EXECUTE_REQUEST_OVERHEAD: usize = 256;
+
+// Statement texts are cut to this number of characters when they are used as label.
+const MAX_LABEL_LENGTH: usize = 100;
+
 // Packets having the same sequence number belong to one request/response pair.
 #[derive(Debug)]
 pub(crate) struct Request<'a> {
     message_type: MessageType,
     command_options: CommandOptions,
+    commit: bool,
+    o_options: Option<QueryOptions>,
     parts: Parts<'a>,
+    following: Vec<Request<'a>>,
+    statement_drops: usize,
 }
 // Methods for defining a request
 impl<'a> Request<'a> {
@@ -34,7 +48,11 @@ impl<'a> Request<'a> {
         Request {
             message_type,
             command_options,
+            commit: false,
+            o_options: None,
             parts: Parts::default(),
+            following: Vec::new(),
+            statement_drops: 0,
         }
     }
 
@@ -44,20 +62,154 @@ impl<'a> Request<'a> {
     pub fn message_type(&self) -> MessageType {
         self.message_type
     }
+    // Lets the server commit immediately after executing the request,
+    // independent of the connection's auto-commit setting.
+    pub fn set_commit(&mut self) {
+        self.commit = true;
+    }
+    // Lets the request use the given options rather than the connection's configuration.
+    pub fn set_options(&mut self, options: QueryOptions) {
+        self.o_options = Some(options);
+    }
+    pub fn options(&self) -> Option<&QueryOptions> {
+        self.o_options.as_ref()
+    }
     pub fn push(&mut self, part: Part<'a>) {
         self.parts.push(part);
     }
+    // Lets the given request be sent as a further segment of the same message;
+    // the server processes the segments in their order and replies with a message
+    // that has a segment for each of them.
+    // The appended requests share the parameter descriptors of this request.
+    pub fn append_segment(&mut self, request: Request<'a>) {
+        self.following.push(request);
+    }
+    pub fn no_of_segments(&self) -> usize {
+        1 + self.following.len()
+    }
+    // Lets the server drop the given statements after processing the request,
+    // with further segments at the end of the message.
+    pub fn append_statement_drops(&mut self, statement_ids: Vec<u64>) {
+        for statement_id in statement_ids {
+            let mut drop = Request::new(MessageType::DropStatementId, CommandOptions::EMPTY);
+            drop.push(Part::StatementId(statement_id));
+            self.following.push(drop);
+            self.statement_drops += 1;
+        }
+    }
+    pub fn no_of_statement_drops(&self) -> usize {
+        self.statement_drops
+    }
+
+    // An auto-commit setting of the request's options applies to this request only.
+    fn is_auto_commit(&self, config: &ConnectionConfiguration) -> bool {
+        self.o_options
+            .and_then(|options| options.auto_commit())
+            .unwrap_or_else(|| config.is_auto_commit())
+    }
+
+    // Describes the statement that is processed by the request, for diagnostic purposes.
+    // The statement text is only used if this is requested, since it might contain
+    // sensitive data; otherwise its first keyword is used.
+    pub fn label(&self, with_sql: bool) -> Option<String> {
+        self.parts.ref_inner().iter().find_map(|part| match part {
+            Part::Command(stmt) if !with_sql => {
+                stmt.split_whitespace().next().map(str::to_uppercase)
+            }
+            Part::Command(stmt) => Some(if stmt.chars().count() > MAX_LABEL_LENGTH {
+                format!(
+                    "{}...",
+                    stmt.chars().take(MAX_LABEL_LENGTH).collect::<String>()
+                )
+            } else {
+                (*stmt).to_string()
+            }),
+            Part::StatementId(statement_id) => Some(format!("statement id {statement_id}")),
+            Part::ResultSetId(rs_id) => Some(format!("result set id {rs_id}")),
+            _ => None,
+        })
+    }
+
+    // Rejects requests that exceed the maximum request size before anything is sent,
+    // so that the server does not drop the connection.
+    // Requests that may be sent in compressed form only need to respect the protocol's limit here;
+    // their compressed size is checked when they are serialized.
+    pub(crate) fn check_size(
+        &self,
+        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        config: &ConnectionConfiguration,
+        compress: bool,
+    ) -> HdbResult<()> {
+        let varpart_size = self.varpart_size(o_a_descriptors);
+        let size = MESSAGE_AND_SEGMENT_HEADER_SIZE + varpart_size;
+        let limit = if compress && varpart_size > config.min_compression_size() {
+            MAX_PROTOCOL_REQUEST_SIZE
+        } else {
+            max_request_size(config)
+        };
+        if size > limit {
+            Err(HdbError::RequestTooLarge { size, limit })
+        } else {
+            Ok(())
+        }
+    }
 
-    pub fn add_statement_context(&mut self, ssi_value: i64) {
+    // Rejects parameter values that the negotiated data format version does not support.
+    pub fn check_dataformat_version2(
+        &self,
+        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        dataformat_version2: u8,
+        o_server_version: Option<&ServerVersion>,
+    ) -> HdbResult<()> {
+        if let Some(descriptors) = o_a_descriptors {
+            let segments = std::iter::once(self).chain(&self.following);
+            for part in segments.flat_map(|segment| segment.parts.ref_inner()) {
+                if let Part::ParameterRows(rows) = part {
+                    rows.check_dataformat_version2(
+                        descriptors,
+                        dataformat_version2,
+                        o_server_version,
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn add_statement_context(
+        &mut self,
+        o_ssi_value: Option<i64>,
+        o_query_timeout: Option<std::time::Duration>,
+    ) {
+        if o_ssi_value.is_none() && o_query_timeout.is_none() {
+            return;
+        }
         let mut stmt_ctx = StatementContext::default();
-        stmt_ctx.set_statement_sequence_info(ssi_value);
+        if let Some(ssi_value) = o_ssi_value {
+            stmt_ctx.set_statement_sequence_info(ssi_value);
+        }
+        if let Some(query_timeout) = o_query_timeout {
+            stmt_ctx.set_query_timeout(query_timeout);
+        }
         trace!(
-            "Sending StatementContext with sequence_info = {:?}",
-            ssi_value
+            "Sending StatementContext with sequence_info = {:?}, query timeout = {:?}",
+            o_ssi_value,
+            o_query_timeout
         );
         self.push(Part::StatementContext(stmt_ctx));
     }
 
+    // The size of what follows the first segment header: the parts of the first segment,
+    // and the further segments with their headers.
+    fn varpart_size(&self, o_a_descriptors: Option<&Arc<ParameterDescriptors>>) -> usize {
+        self.parts.size(o_a_descriptors)
+            + self
+                .following
+                .iter()
+                .map(|segment| SEGMENT_HEADER_SIZE as usize + segment.parts.size(o_a_descriptors))
+                .sum::<usize>()
+    }
+
     #[cfg(feature = "sync")]
     #[allow(clippy::too_many_arguments)]
     pub fn emit_sync(
@@ -67,36 +219,137 @@ impl<'a> Request<'a> {
         config: &ConnectionConfiguration,
         compress: bool,
         o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        o_server_version: Option<&ServerVersion>,
         statistics: &mut ConnectionStatistics,
         io_buffer: &mut Cursor<Vec<u8>>,
         w: &mut dyn std::io::Write,
     ) -> HdbResult<std::time::Instant> {
+        let o_compressed_varpart = self.serialize(
+            session_id,
+            packet_seq_number,
+            config,
+            compress,
+            o_a_descriptors,
+            o_server_version,
+            io_buffer,
+        )?;
+
+        // serialize request to stream
+        let start = std::time::Instant::now();
+        if let Some((compressed_varpart, uncompressed_size)) = o_compressed_varpart {
+            // serialize header to stream
+            w.write_all(&io_buffer.get_ref()[0..MESSAGE_AND_SEGMENT_HEADER_SIZE])?;
+
+            // serialize compressed parts to stream
+            w.write_all(&compressed_varpart)?;
+            statistics.add_compressed_request(compressed_varpart.len(), uncompressed_size);
+        } else {
+            // serialize header and uncompressed parts to stream
+            w.write_all(io_buffer.get_ref())?;
+        }
+        w.flush()?;
+        trace!("Parts are written");
+
         io_buffer.get_mut().clear();
-        let uncompressed_parts_size = self.parts.size(o_a_descriptors);
+        Ok(start)
+    }
+
+    #[cfg(feature = "async")]
+    #[allow(clippy::too_many_arguments)]
+    pub async fn emit_async<W: std::marker::Unpin + tokio::io::AsyncWriteExt>(
+        &self,
+        session_id: i64,
+        packet_sequence_number: u32,
+        config: &ConnectionConfiguration,
+        compress: bool,
+        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        o_server_version: Option<&ServerVersion>,
+        statistics: &mut ConnectionStatistics,
+        io_buffer: &mut Cursor<Vec<u8>>,
+        w: &mut W,
+    ) -> HdbResult<std::time::Instant> {
+        let o_compressed_varpart = self.serialize(
+            session_id,
+            packet_sequence_number,
+            config,
+            compress,
+            o_a_descriptors,
+            o_server_version,
+            io_buffer,
+        )?;
+
+        // serialize request to stream
+        let start = std::time::Instant::now();
+        if let Some((compressed_varpart, uncompressed_size)) = o_compressed_varpart {
+            // serialize header to stream
+            w.write_all(&io_buffer.get_ref()[0..MESSAGE_AND_SEGMENT_HEADER_SIZE])
+                .await?;
+
+            // serialize compressed parts to stream
+            w.write_all(&compressed_varpart).await?;
+            statistics.add_compressed_request(compressed_varpart.len(), uncompressed_size);
+        } else {
+            // serialize header and uncompressed parts to stream
+            w.write_all(io_buffer.get_ref()).await?;
+        }
+        w.flush().await?;
+        trace!("Parts are written");
+
+        io_buffer.get_mut().clear();
+        Ok(start)
+    }
+
+    // Writes the complete message into the buffer, and returns, if the message is to be sent
+    // in compressed form, what follows the first segment header in compressed form
+    // together with its uncompressed size.
+    #[allow(clippy::too_many_arguments)]
+    fn serialize(
+        &self,
+        session_id: i64,
+        packet_sequence_number: u32,
+        config: &ConnectionConfiguration,
+        compress: bool,
+        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+        o_server_version: Option<&ServerVersion>,
+        io_buffer: &mut Cursor<Vec<u8>>,
+    ) -> HdbResult<Option<(Vec<u8>, usize)>> {
+        io_buffer.get_mut().clear();
+        let uncompressed_varpart_size = self.varpart_size(o_a_descriptors);
 
         //  write uncompressed parts to buffer, and leave space for the packet header
         let capa = io_buffer.get_ref().capacity();
-        if capa < MESSAGE_AND_SEGMENT_HEADER_SIZE + uncompressed_parts_size {
+        if capa < MESSAGE_AND_SEGMENT_HEADER_SIZE + uncompressed_varpart_size {
             io_buffer
                 .get_mut()
-                .reserve(MESSAGE_AND_SEGMENT_HEADER_SIZE + uncompressed_parts_size - capa);
+                .reserve(MESSAGE_AND_SEGMENT_HEADER_SIZE + uncompressed_varpart_size - capa);
         }
         io_buffer.set_position(MESSAGE_AND_SEGMENT_HEADER_SIZE as u64);
-        let mut remaining_bufsize = u32::try_from(uncompressed_parts_size).unwrap(/*OK*/);
-        for part in self.parts.ref_inner() {
-            remaining_bufsize = part.emit(remaining_bufsize, o_a_descriptors, io_buffer)?;
+        emit_parts(&self.parts, o_a_descriptors, o_server_version, io_buffer)?;
+        let first_parts_size = self.parts.size(o_a_descriptors);
+        let mut offset = SEGMENT_HEADER_SIZE as usize + first_parts_size;
+        for (i, segment) in self.following.iter().enumerate() {
+            let parts_size = segment.parts.size(o_a_descriptors);
+            segment.emit_segment_header(
+                offset,
+                i + 2,
+                segment.is_auto_commit(config),
+                parts_size,
+                io_buffer,
+            )?;
+            emit_parts(&segment.parts, o_a_descriptors, o_server_version, io_buffer)?;
+            offset += SEGMENT_HEADER_SIZE as usize + parts_size;
         }
 
         // decide if parts should be sent in compressed form, and compress if necessary
-        let o_compressed_parts = if compress
-            && uncompressed_parts_size > config.min_compression_size()
+        let o_compressed_varpart = if compress
+            && uncompressed_varpart_size > config.min_compression_size()
         {
-            io_buffer.set_position(MESSAGE_AND_SEGMENT_HEADER_SIZE as u64);
-            let compressed_parts =
+            let compressed_varpart =
                 lz4_flex::block::compress(&io_buffer.get_ref()[MESSAGE_AND_SEGMENT_HEADER_SIZE..]);
 
-            if shrunk_by_at_least_five_percent(compressed_parts.len(), uncompressed_parts_size) {
-                Some(compressed_parts)
+            if shrunk_by_at_least_five_percent(compressed_varpart.len(), uncompressed_varpart_size)
+            {
+                Some((compressed_varpart, uncompressed_varpart_size))
             } else {
                 None
             }
@@ -104,47 +357,42 @@ impl<'a> Request<'a> {
             None
         };
 
+        // the size that is actually sent must not exceed the limit
+        let size = MESSAGE_AND_SEGMENT_HEADER_SIZE
+            + o_compressed_varpart
+                .as_ref()
+                .map_or(uncompressed_varpart_size, |(data, _)| data.len());
+        let limit = max_request_size(config);
+        if size > limit {
+            io_buffer.get_mut().clear();
+            return Err(HdbError::RequestTooLarge { size, limit });
+        }
+
         // write header to beginning of buffer
         io_buffer.set_position(0);
         self.emit_packet_header(
             session_id,
-            packet_seq_number,
-            config.is_auto_commit(),
-            u32::try_from(uncompressed_parts_size).expect("uncompressed parts too big"),
-            o_compressed_parts.as_ref().map(|data| {
+            packet_sequence_number,
+            self.is_auto_commit(config),
+            first_parts_size,
+            u32::try_from(uncompressed_varpart_size).expect("uncompressed parts too big"),
+            o_compressed_varpart.as_ref().map(|(data, _)| {
                 trace!("sending compressed request");
                 u32::try_from(data.len()).expect("compressed parts too big")
             }),
             io_buffer,
         )?;
-
-        // serialize request to stream
-        let start = std::time::Instant::now();
-        if let Some(compressed_parts) = o_compressed_parts {
-            // serialize header to stream
-            io_buffer.set_position(0);
-            w.write_all(&io_buffer.get_ref()[0..MESSAGE_AND_SEGMENT_HEADER_SIZE])?;
-
-            // serialize compressed parts to stream
-            w.write_all(&compressed_parts)?;
-            statistics.add_compressed_request(compressed_parts.len(), uncompressed_parts_size);
-        } else {
-            // serialize header and uncompressed parts to stream
-            io_buffer.set_position(0);
-            w.write_all(io_buffer.get_ref())?;
-        }
-        w.flush()?;
-        trace!("Parts are written");
-
-        io_buffer.get_mut().clear();
-        Ok(start)
+        io_buffer.set_position(0);
+        Ok(o_compressed_varpart)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn emit_packet_header(
         &self,
         session_id: i64,
         packet_sequence_number: u32,
         auto_commit: bool,
+        first_parts_size: usize,
         uncompressed_size: u32,
         o_compressed_size: Option<u32>,
         w: &mut dyn std::io::Write,
@@ -160,7 +408,9 @@ impl<'a> Request<'a> {
                 \n  packet_seq_number = {packet_sequence_number}, \
                 \n  compressed = {compressed_size}, \
                 \n  uncompressed = {uncompressed_size}, \
-            }}"
+                \n  segments = {}, \
+            }}",
+            self.no_of_segments()
         );
 
         // MESSAGE HEADER
@@ -176,7 +426,7 @@ impl<'a> Request<'a> {
         )?; // UI4
 
         w.write_u32::<LittleEndian>(uncompressed_size + SEGMENT_HEADER_SIZE)?; // UI4
-        w.write_i16::<LittleEndian>(ONE_AS_NUMBER_OF_SEGMENTS)?; // I2
+        w.write_i16::<LittleEndian>(i16::try_from(self.no_of_segments()).unwrap(/*OK*/))?; // I2
 
         if compress {
             w.write_u8(PACKET_OPTION_COMPRESS)?; // I1
@@ -187,105 +437,252 @@ impl<'a> Request<'a> {
             w.write_all(&FILLER_10)?;
         }
 
-        // (first and only) SEGMENT HEADER
-        w.write_u32::<LittleEndian>(uncompressed_size + SEGMENT_HEADER_SIZE)?; // I4
-        w.write_i32::<LittleEndian>(ZERO_AS_OFFSET)?; // I4
+        // FIRST SEGMENT HEADER
+        self.emit_segment_header(0, 1, auto_commit, first_parts_size, w)?;
+
+        trace!("Headers are written");
+        Ok(())
+    }
+
+    fn emit_segment_header(
+        &self,
+        offset: usize,
+        ordinal: usize,
+        auto_commit: bool,
+        parts_size: usize,
+        w: &mut dyn std::io::Write,
+    ) -> HdbResult<()> {
+        w.write_u32::<LittleEndian>(
+            SEGMENT_HEADER_SIZE + u32::try_from(parts_size).unwrap(/*OK*/),
+        )?; // I4
+        w.write_i32::<LittleEndian>(i32::try_from(offset).unwrap(/*OK*/))?; // I4
         w.write_u16::<LittleEndian>(u16::try_from(self.parts.len()).unwrap(/*OK*/))?; // I2 Number of contained parts
-        w.write_i16::<LittleEndian>(ONE_AS_ORDINAL_OF_THIS_SEGMENT)?; // I2
+        w.write_i16::<LittleEndian>(i16::try_from(ordinal).unwrap(/*OK*/))?; // I2
         w.write_i8(SEGMENT_KIND_REQUEST)?; // I1
         w.write_i8(self.message_type as i8)?; // I1
-        w.write_i8(auto_commit.into())?; // I1
+        w.write_i8((auto_commit || self.commit).into())?; // I1
         w.write_u8(self.command_options.as_u8())?; // I1
         w.write_u64::<LittleEndian>(FILLER_8)?; // [B;8]
-
-        trace!("Headers are written");
         Ok(())
     }
+}
 
-    #[cfg(feature = "async")]
-    #[allow(clippy::too_many_arguments)]
-    pub async fn emit_async<W: std::marker::Unpin + tokio::io::AsyncWriteExt>(
-        &self,
-        session_id: i64,
-        packet_sequence_number: u32,
-        config: &ConnectionConfiguration,
-        compress: bool,
-        o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
-        statistics: &mut ConnectionStatistics,
-        io_buffer: &mut Cursor<Vec<u8>>,
-        w: &mut W,
-    ) -> HdbResult<std::time::Instant> {
-        io_buffer.get_mut().clear();
-        let uncompressed_parts_size = self.parts.size(o_a_descriptors);
+fn emit_parts(
+    parts: &Parts,
+    o_a_descriptors: Option<&Arc<ParameterDescriptors>>,
+    o_server_version: Option<&ServerVersion>,
+    io_buffer: &mut Cursor<Vec<u8>>,
+) -> HdbResult<()> {
+    let mut remaining_bufsize = u32::try_from(parts.size(o_a_descriptors)).unwrap(/*OK*/);
+    for part in parts.ref_inner() {
+        remaining_bufsize = part.emit(
+            remaining_bufsize,
+            o_a_descriptors,
+            o_server_version,
+            io_buffer,
+        )?;
+    }
+    Ok(())
+}
 
-        //  write uncompressed parts to buffer, and leave space for the packet header
-        let capa = io_buffer.get_ref().capacity();
-        if capa < MESSAGE_AND_SEGMENT_HEADER_SIZE + uncompressed_parts_size {
-            io_buffer
-                .get_mut()
-                .reserve(MESSAGE_AND_SEGMENT_HEADER_SIZE + uncompressed_parts_size - capa);
-        }
-        io_buffer.set_position(MESSAGE_AND_SEGMENT_HEADER_SIZE as u64);
-        let mut remaining_bufsize = u32::try_from(uncompressed_parts_size).unwrap(/*OK*/);
-        for part in self.parts.ref_inner() {
-            remaining_bufsize = part.emit(remaining_bufsize, o_a_descriptors, io_buffer)?;
-        }
+// The maximum size of a request: the configured one, within the protocol's limit.
+fn max_request_size(config: &ConnectionConfiguration) -> usize {
+    config.max_request_size().min(MAX_PROTOCOL_REQUEST_SIZE)
+}
 
-        // decide if parts should be sent in compressed form, and compress if necessary
-        let o_compressed_parts = if compress
-            && uncompressed_parts_size > config.min_compression_size()
-        {
-            io_buffer.set_position(MESSAGE_AND_SEGMENT_HEADER_SIZE as u64);
-            let compressed_parts =
-                lz4_flex::block::compress(&io_buffer.get_ref()[MESSAGE_AND_SEGMENT_HEADER_SIZE..]);
+// The maximum size of the parameter rows that can be sent with a single execute request.
+pub(crate) fn max_parameter_rows_size(config: &ConnectionConfiguration) -> usize {
+    max_request_size(config)
+        .saturating_sub(MESSAGE_AND_SEGMENT_HEADER_SIZE + EXECUTE_REQUEST_OVERHEAD)
+}
 
-            if shrunk_by_at_least_five_percent(compressed_parts.len(), uncompressed_parts_size) {
-                Some(compressed_parts)
-            } else {
-                None
+fn shrunk_by_at_least_five_percent(c: usize, u: usize) -> bool {
+    c < u && u - c > u / 20
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Request, MAX_LABEL_LENGTH};
+    use crate::{
+        conn::CommandOptions,
+        protocol::{MessageType, Part},
+        ConnectionConfiguration, HdbError,
+    };
+    #[cfg(feature = "sync")]
+    use crate::{conn::ConnectionStatistics, QueryOptions};
+
+    #[test]
+    fn test_check_size() {
+        let mut request = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+        request.push(Part::Command("SELECT * FROM DUMMY"));
+
+        let config = ConnectionConfiguration::default();
+        assert!(request.check_size(None, &config, false).is_ok());
+
+        let config = config.with_max_request_size(64);
+        match request.check_size(None, &config, false) {
+            Err(HdbError::RequestTooLarge { size, limit }) => {
+                assert!(size > 64);
+                assert_eq!(limit, 64);
             }
-        } else {
-            None
-        };
+            _ => panic!("request should be too large"),
+        }
+    }
 
-        // write header to beginning of buffer
-        io_buffer.set_position(0);
-        self.emit_packet_header(
-            session_id,
-            packet_sequence_number,
-            config.is_auto_commit(),
-            u32::try_from(uncompressed_parts_size).expect("uncompressed parts too big"),
-            o_compressed_parts.as_ref().map(|data| {
-                trace!("sending compressed request");
-                u32::try_from(data.len()).expect("compressed parts too big")
-            }),
-            io_buffer,
-        )?;
+    #[test]
+    fn test_label() {
+        let stmt = format!("select '{}' from dummy", "x".repeat(200));
+        let mut request = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+        request.push(Part::Command(&stmt));
+        assert_eq!(request.label(false).as_deref(), Some("SELECT"));
+        let label = request.label(true).unwrap();
+        assert!(label.starts_with("select 'xxx"));
+        assert!(label.ends_with("..."));
+        assert_eq!(label.chars().count(), MAX_LABEL_LENGTH + 3);
 
-        // serialize request to stream
-        let start = std::time::Instant::now();
-        if let Some(compressed_parts) = o_compressed_parts {
-            // serialize header to stream
-            io_buffer.set_position(0);
-            w.write_all(&io_buffer.get_ref()[0..MESSAGE_AND_SEGMENT_HEADER_SIZE])
-                .await?;
+        let mut request = Request::new(MessageType::Execute, CommandOptions::EMPTY);
+        request.push(Part::StatementId(4711));
+        assert_eq!(request.label(false).as_deref(), Some("statement id 4711"));
 
-            // serialize compressed parts to stream
-            w.write_all(&compressed_parts).await?;
-            statistics.add_compressed_request(compressed_parts.len(), uncompressed_parts_size);
-        } else {
-            // serialize header and uncompressed parts to stream
-            io_buffer.set_position(0);
-            w.write_all(io_buffer.get_ref()).await?;
+        let request = Request::new(MessageType::Disconnect, CommandOptions::EMPTY);
+        assert_eq!(request.label(true), None);
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_auto_commit_of_request() {
+        // the commit flag follows the message header and the first 14 bytes of the segment header
+        const COMMIT_FLAG_OFFSET: usize = 46;
+        fn emitted_commit_flag(request: &Request, config: &ConnectionConfiguration) -> u8 {
+            let mut emitted = Vec::<u8>::new();
+            request
+                .emit_sync(
+                    0,
+                    1,
+                    config,
+                    false,
+                    None,
+                    None,
+                    &mut ConnectionStatistics::default(),
+                    &mut std::io::Cursor::new(Vec::new()),
+                    &mut emitted,
+                )
+                .unwrap();
+            emitted[COMMIT_FLAG_OFFSET]
         }
-        w.flush().await?;
-        trace!("Parts are written");
 
-        io_buffer.get_mut().clear();
-        Ok(start)
+        let config = ConnectionConfiguration::default().with_auto_commit(false);
+        let mut request = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+        request.push(Part::Command("UPDATE T SET A = 1"));
+        assert_eq!(emitted_commit_flag(&request, &config), 0);
+
+        request.set_options(QueryOptions::new().with_auto_commit(true));
+        assert_eq!(emitted_commit_flag(&request, &config), 1);
+        assert!(!config.is_auto_commit());
+
+        let config = config.with_auto_commit(true);
+        request.set_options(QueryOptions::new().with_auto_commit(false));
+        assert_eq!(emitted_commit_flag(&request, &config), 0);
     }
-}
 
-fn shrunk_by_at_least_five_percent(c: usize, u: usize) -> bool {
-    c < u && u - c > u / 20
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_emit_segments() {
+        const MESSAGE_HEADER_SIZE: usize = 32;
+        let read_i16 = |b: &[u8], pos: usize| i16::from_le_bytes([b[pos], b[pos + 1]]);
+        let read_i32 =
+            |b: &[u8], pos: usize| i32::from_le_bytes(b[pos..pos + 4].try_into().unwrap());
+
+        let config = ConnectionConfiguration::default().with_auto_commit(false);
+        let mut request = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+        request.push(Part::Command("INSERT INTO T VALUES (1)"));
+        let mut segment = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+        segment.push(Part::Command("UPDATE T SET A = 2"));
+        segment.set_commit();
+        request.append_segment(segment);
+        let mut segment = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+        segment.push(Part::Command("rollback"));
+        request.append_segment(segment);
+        assert_eq!(request.no_of_segments(), 3);
+        assert!(request.check_size(None, &config, false).is_ok());
+
+        let mut emitted = Vec::<u8>::new();
+        request
+            .emit_sync(
+                0,
+                1,
+                &config,
+                false,
+                None,
+                None,
+                &mut ConnectionStatistics::default(),
+                &mut std::io::Cursor::new(Vec::new()),
+                &mut emitted,
+            )
+            .unwrap();
+
+        // the message header announces three segments and their total size
+        assert_eq!(read_i16(&emitted, 20), 3);
+        assert_eq!(
+            usize::try_from(read_i32(&emitted, 12)).unwrap(),
+            emitted.len() - MESSAGE_HEADER_SIZE
+        );
+
+        // each segment header has its offset, its ordinal, and its own commit flag
+        let mut position = MESSAGE_HEADER_SIZE;
+        for (ordinal, commit) in [(1, 0), (2, 1), (3, 0)] {
+            let offset = usize::try_from(read_i32(&emitted, position + 4)).unwrap();
+            assert_eq!(offset, position - MESSAGE_HEADER_SIZE);
+            assert_eq!(read_i16(&emitted, position + 8), 1); // number of parts
+            assert_eq!(read_i16(&emitted, position + 10), ordinal);
+            assert_eq!(emitted[position + 14], commit);
+            position += usize::try_from(read_i32(&emitted, position)).unwrap();
+        }
+        assert_eq!(position, emitted.len());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_check_compressed_size() {
+        let emit = |request: &Request, config: &ConnectionConfiguration, compress: bool| {
+            request.check_size(None, config, compress)?;
+            request.emit_sync(
+                0,
+                1,
+                config,
+                compress,
+                None,
+                None,
+                &mut ConnectionStatistics::default(),
+                &mut std::io::Cursor::new(Vec::new()),
+                &mut Vec::<u8>::new(),
+            )
+        };
+
+        let stmt = format!("SELECT '{}' FROM DUMMY", "a".repeat(10_000));
+        let mut request = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+        request.push(Part::Command(&stmt));
+        let config = ConnectionConfiguration::default()
+            .with_min_compression_size(1024)
+            .with_max_request_size(2_000);
+
+        // uncompressed, the request is too large
+        assert!(matches!(
+            emit(&request, &config, false),
+            Err(HdbError::RequestTooLarge { limit: 2_000, .. })
+        ));
+
+        // compressed, it fits
+        assert!(emit(&request, &config, true).is_ok());
+
+        // unless the compressed size exceeds the limit, too
+        let config = config.with_max_request_size(100);
+        match emit(&request, &config, true) {
+            Err(HdbError::RequestTooLarge { size, limit }) => {
+                assert!(size > 100 && size < 2_000);
+                assert_eq!(limit, 100);
+            }
+            _ => panic!("compressed request should be too large"),
+        }
+    }
 }
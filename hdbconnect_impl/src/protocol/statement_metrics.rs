@@ -0,0 +1,56 @@
+use crate::protocol::parts::StatementContext;
+use std::time::Duration;
+
+/// The resource consumption that the server reported for a single roundtrip.
+///
+/// Complements the accumulated [`ServerUsage`](crate::ServerUsage):
+/// the metrics are not summed up, they describe only the execution that produced them.
+/// Each value is only available if the server sent it.
+///
+/// The metrics of an execution can be retrieved from the `HdbResponse`;
+/// those of the last roundtrip of a connection from the `Connection`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct StatementMetrics {
+    /// The server-side processing time.
+    pub server_processing_time: Option<Duration>,
+    /// The server-side CPU time.
+    pub server_cpu_time: Option<Duration>,
+    /// The server-side memory consumption, in bytes.
+    pub server_memory_usage: Option<u64>,
+    /// The time the execution waited for locks on the server.
+    pub server_lock_wait_time: Option<Duration>,
+}
+
+impl StatementMetrics {
+    pub(crate) fn from_statement_context(stmt_ctx: &StatementContext) -> Self {
+        Self {
+            server_processing_time: stmt_ctx.server_processing_time(),
+            server_cpu_time: stmt_ctx.server_cpu_time(),
+            server_memory_usage: stmt_ctx.server_memory_usage(),
+            server_lock_wait_time: stmt_ctx.server_lock_wait_time(),
+        }
+    }
+}
+
+impl std::fmt::Display for StatementMetrics {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut separator = "";
+        if let Some(processing_time) = self.server_processing_time {
+            write!(f, "processing time: {processing_time:?}")?;
+            separator = ", ";
+        }
+        if let Some(cpu_time) = self.server_cpu_time {
+            write!(f, "{separator}cpu time: {cpu_time:?}")?;
+            separator = ", ";
+        }
+        if let Some(memory_usage) = self.server_memory_usage {
+            write!(f, "{separator}memory usage: {memory_usage} bytes")?;
+            separator = ", ";
+        }
+        if let Some(lock_wait_time) = self.server_lock_wait_time {
+            write!(f, "{separator}lock wait time: {lock_wait_time:?}")?;
+        }
+        Ok(())
+    }
+}
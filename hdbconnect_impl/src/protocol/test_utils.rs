@@ -0,0 +1,590 @@
+//! Helpers for writing parser tests without capturing traffic from a real database.
+//!
+//! [`ReplyBuilder`] and [`PartBuilder`] produce the bytes of a reply, as the server would
+//! send them, with correct message, segment, and part headers, and with the padding
+//! the protocol requires; the bodies of the parts are composed with the typed
+//! `push_*` methods of [`PartBuilder`].
+//! [`parse_reply`] runs the driver's reply parser on such bytes, and returns the
+//! segments and parts it found; [`describe_reply`] returns a debug representation instead.
+//!
+//! ```rust
+//! use hdbconnect::test_utils::{parse_reply, PartBuilder, ReplyBuilder};
+//!
+//! // a reply with a single part of kind 13 (ResultSetId)
+//! let reply = ReplyBuilder::new()
+//!     .with_reply_type(5) // Select
+//!     .with_part(PartBuilder::new(13, 1).push_u64(4711))
+//!     .build();
+//! let parsed = parse_reply(&reply).unwrap();
+//! let segment = &parsed.segments()[0];
+//! assert_eq!(segment.reply_type(), 5);
+//! assert!(segment.part(13).unwrap().description().contains("4711"));
+//! ```
+use super::{parts::length_indicator, Reply, MESSAGE_AND_SEGMENT_HEADER_SIZE, SEGMENT_HEADER_SIZE};
+use crate::{
+    conn::{ConnectionStatistics, LobBufferPool},
+    HdbResult,
+};
+use byteorder::{LittleEndian, WriteBytesExt};
+use std::io::Cursor;
+
+const PART_HEADER_SIZE: usize = 16;
+const SEGMENT_KIND_REPLY: i8 = 2;
+const SEGMENT_KIND_ERROR: i8 = 5;
+const ERROR_BASE_SIZE: usize = 4 + 4 + 4 + 1 + 5;
+
+/// Builds the bytes of a reply.
+///
+/// Numeric codes are used for reply types and part kinds, as they are defined
+/// by the protocol, so that also unknown or unusual values can be produced.
+#[derive(Clone, Debug, Default)]
+pub struct ReplyBuilder {
+    session_id: i64,
+    reply_type: i16,
+    error: bool,
+    parts: Vec<PartBuilder>,
+    following: Vec<ReplyBuilder>,
+}
+
+impl ReplyBuilder {
+    /// Creates a builder for a reply of type `Nil` (0) without parts.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the session id (default: 0).
+    #[must_use]
+    pub fn with_session_id(mut self, session_id: i64) -> Self {
+        self.session_id = session_id;
+        self
+    }
+
+    /// Sets the function code of the reply, like 1 for DDL or 5 for a query (default: 0).
+    #[must_use]
+    pub fn with_reply_type(mut self, reply_type: i16) -> Self {
+        self.reply_type = reply_type;
+        self
+    }
+
+    /// Marks the segment as error segment, as the server does if it returns errors.
+    #[must_use]
+    pub fn as_error(mut self) -> Self {
+        self.error = true;
+        self
+    }
+
+    /// Appends a part.
+    #[must_use]
+    pub fn with_part(mut self, part: PartBuilder) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Appends a further segment, as the server sends it in reply to a request
+    /// with several segments.
+    ///
+    /// Only the reply type, the error flag, and the parts of the given builder are used.
+    #[must_use]
+    pub fn with_segment(mut self, segment: ReplyBuilder) -> Self {
+        self.following.push(segment);
+        self
+    }
+
+    /// Returns the bytes of the reply.
+    ///
+    /// # Panics
+    ///
+    /// If the reply exceeds the size limits of the protocol.
+    #[must_use]
+    pub fn build(&self) -> Vec<u8> {
+        let mut segments = Vec::<u8>::new();
+        for (i, segment) in std::iter::once(self).chain(&self.following).enumerate() {
+            segment.emit_segment(i + 1, &mut segments);
+        }
+        let varpart_size = u32::try_from(segments.len()).unwrap();
+
+        let mut w = Vec::<u8>::with_capacity(
+            MESSAGE_AND_SEGMENT_HEADER_SIZE - SEGMENT_HEADER_SIZE as usize + segments.len(),
+        );
+        // MESSAGE HEADER: 32 bytes
+        w.write_i64::<LittleEndian>(self.session_id).unwrap();
+        w.write_i32::<LittleEndian>(0).unwrap(); // packet sequence number
+        w.write_u32::<LittleEndian>(varpart_size).unwrap();
+        w.write_u32::<LittleEndian>(0).unwrap(); // remaining buffer size
+        w.write_i16::<LittleEndian>(i16::try_from(1 + self.following.len()).unwrap())
+            .unwrap(); // number of segments
+        w.write_u8(0).unwrap(); // not compressed
+        w.write_u8(0).unwrap(); // filler
+        w.write_u32::<LittleEndian>(0).unwrap(); // uncompressed size
+        w.write_u32::<LittleEndian>(0).unwrap(); // filler
+
+        w.extend_from_slice(&segments);
+        w
+    }
+
+    // Appends the segment header and the parts; the offset of a segment is its position
+    // behind the message header.
+    fn emit_segment(&self, segment_number: usize, w: &mut Vec<u8>) {
+        let mut parts = Vec::<u8>::new();
+        for part in &self.parts {
+            part.emit(&mut parts);
+        }
+        let segment_size = u32::try_from(parts.len()).unwrap() + SEGMENT_HEADER_SIZE;
+        let offset = w.len();
+
+        // SEGMENT HEADER: 24 bytes
+        w.write_i32::<LittleEndian>(i32::try_from(segment_size).unwrap())
+            .unwrap();
+        w.write_i32::<LittleEndian>(i32::try_from(offset).unwrap())
+            .unwrap(); // segment offset
+        w.write_i16::<LittleEndian>(i16::try_from(self.parts.len()).unwrap())
+            .unwrap();
+        w.write_i16::<LittleEndian>(i16::try_from(segment_number).unwrap())
+            .unwrap();
+        w.write_i8(if self.error {
+            SEGMENT_KIND_ERROR
+        } else {
+            SEGMENT_KIND_REPLY
+        })
+        .unwrap();
+        w.write_u8(0).unwrap(); // filler
+        w.write_i16::<LittleEndian>(self.reply_type).unwrap();
+        w.extend_from_slice(&[0; 8]); // filler
+
+        w.extend_from_slice(&parts);
+    }
+}
+
+/// Builds a part of a reply.
+///
+/// The body is written with little-endian byte order, like the protocol requires.
+#[derive(Clone, Debug)]
+pub struct PartBuilder {
+    kind: i8,
+    attributes: u8,
+    no_of_args: usize,
+    body: Vec<u8>,
+}
+
+impl PartBuilder {
+    /// Creates a part with the given kind and number of arguments, and an empty body.
+    #[must_use]
+    pub fn new(kind: i8, no_of_args: usize) -> Self {
+        Self {
+            kind,
+            attributes: 0,
+            no_of_args,
+            body: Vec::new(),
+        }
+    }
+
+    /// Creates an `Error` part (kind 6) with a single error of severity `Error`,
+    /// as the server sends it in an error segment.
+    ///
+    /// # Panics
+    ///
+    /// If the SQLSTATE does not consist of five bytes, or if the text is too long.
+    #[must_use]
+    pub fn error(code: i32, sqlstate: &str, text: &str) -> Self {
+        assert_eq!(sqlstate.len(), 5, "a SQLSTATE consists of five bytes");
+        let text = cesu8::to_cesu8(text);
+        // the parser skips the padding behind each error
+        let padding = 8 - (ERROR_BASE_SIZE + text.len()) % 8;
+        Self::new(6, 1)
+            .push_i32(code)
+            .push_i32(0) // position
+            .push_i32(i32::try_from(text.len()).unwrap())
+            .push_u8(1) // severity
+            .push_bytes(sqlstate.as_bytes())
+            .push_bytes(&text)
+            .push_bytes(&[0; 8][..padding])
+    }
+
+    /// Sets the part attributes, like 1 for "last packet" of a result set (default: 0).
+    #[must_use]
+    pub fn with_attributes(mut self, attributes: u8) -> Self {
+        self.attributes = attributes;
+        self
+    }
+
+    /// Appends a single byte.
+    #[must_use]
+    pub fn push_u8(mut self, value: u8) -> Self {
+        self.body.push(value);
+        self
+    }
+
+    /// Appends a two-byte integer.
+    #[must_use]
+    pub fn push_i16(mut self, value: i16) -> Self {
+        self.body.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// Appends a four-byte integer.
+    #[must_use]
+    pub fn push_i32(mut self, value: i32) -> Self {
+        self.body.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// Appends an eight-byte integer.
+    #[must_use]
+    pub fn push_i64(mut self, value: i64) -> Self {
+        self.body.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// Appends an unsigned eight-byte integer, like a statement or result set id.
+    #[must_use]
+    pub fn push_u64(mut self, value: u64) -> Self {
+        self.body.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// Appends an eight-byte floating point number.
+    #[must_use]
+    pub fn push_f64(mut self, value: f64) -> Self {
+        self.body.extend_from_slice(&value.to_le_bytes());
+        self
+    }
+
+    /// Appends the bytes as they are.
+    #[must_use]
+    pub fn push_bytes(mut self, bytes: &[u8]) -> Self {
+        self.body.extend_from_slice(bytes);
+        self
+    }
+
+    /// Appends the bytes, preceded by a length indicator.
+    ///
+    /// # Panics
+    ///
+    /// If the bytes are longer than the protocol allows.
+    #[must_use]
+    pub fn push_length_indicated(mut self, bytes: &[u8]) -> Self {
+        length_indicator::emit(bytes.len(), &mut self.body).unwrap();
+        self.body.extend_from_slice(bytes);
+        self
+    }
+
+    /// Appends the string in CESU-8 encoding, preceded by a length indicator.
+    ///
+    /// # Panics
+    ///
+    /// If the string is longer than the protocol allows.
+    #[must_use]
+    pub fn push_string(self, s: &str) -> Self {
+        self.push_length_indicated(&cesu8::to_cesu8(s))
+    }
+
+    /// Appends the indicator for a NULL value.
+    #[must_use]
+    pub fn push_null(self) -> Self {
+        self.push_u8(length_indicator::LENGTH_INDICATOR_NULL)
+    }
+
+    fn emit(&self, w: &mut Vec<u8>) {
+        // PART HEADER: 16 bytes
+        w.write_i8(self.kind).unwrap();
+        w.write_u8(self.attributes).unwrap();
+        match i16::try_from(self.no_of_args) {
+            Ok(no_of_args) if no_of_args < i16::MAX => {
+                w.write_i16::<LittleEndian>(no_of_args).unwrap();
+                w.write_i32::<LittleEndian>(0).unwrap();
+            }
+            _ => {
+                w.write_i16::<LittleEndian>(-1).unwrap();
+                w.write_i32::<LittleEndian>(i32::try_from(self.no_of_args).unwrap())
+                    .unwrap();
+            }
+        }
+        w.write_i32::<LittleEndian>(i32::try_from(self.body.len()).unwrap())
+            .unwrap();
+        w.write_i32::<LittleEndian>(0).unwrap(); // remaining packet size
+
+        w.extend_from_slice(&self.body);
+        let padded_len = (PART_HEADER_SIZE + self.body.len()).next_multiple_of(8);
+        w.resize(w.len() + padded_len - PART_HEADER_SIZE - self.body.len(), 0);
+    }
+}
+
+/// A reply as the parser of the driver understood it, see [`parse_reply`].
+#[derive(Debug)]
+pub struct ParsedReply {
+    session_id: i64,
+    segments: Vec<ParsedSegment>,
+}
+
+impl ParsedReply {
+    /// Returns the session id from the message header.
+    #[must_use]
+    pub fn session_id(&self) -> i64 {
+        self.session_id
+    }
+
+    /// Returns the segments of the reply, in the order in which they were sent.
+    #[must_use]
+    pub fn segments(&self) -> &[ParsedSegment] {
+        &self.segments
+    }
+}
+
+/// A segment of a [`ParsedReply`].
+#[derive(Debug)]
+pub struct ParsedSegment {
+    reply_type: i16,
+    parts: Vec<ParsedPart>,
+}
+
+impl ParsedSegment {
+    /// Returns the function code of the segment, like 1 for DDL or 5 for a query.
+    #[must_use]
+    pub fn reply_type(&self) -> i16 {
+        self.reply_type
+    }
+
+    /// Returns the parts that the parser kept, in their order.
+    ///
+    /// Some parts are consumed while parsing, like the `ResultSetMetadata` of a query,
+    /// which is combined with the following parts into a result set.
+    #[must_use]
+    pub fn parts(&self) -> &[ParsedPart] {
+        &self.parts
+    }
+
+    /// Returns the first part of the given kind, if any.
+    #[must_use]
+    pub fn part(&self, kind: i8) -> Option<&ParsedPart> {
+        self.parts.iter().find(|part| part.kind == kind)
+    }
+}
+
+/// A part of a [`ParsedSegment`].
+#[derive(Debug)]
+pub struct ParsedPart {
+    kind: i8,
+    description: String,
+}
+
+impl ParsedPart {
+    /// Returns the part kind, as it is defined by the protocol (like 12 for `ExecutionResults`).
+    #[must_use]
+    pub fn kind(&self) -> i8 {
+        self.kind
+    }
+
+    /// Returns a debug representation of the parsed content; its format is not stable.
+    #[must_use]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+/// Parses the bytes of a reply with the parser of the driver, and returns its segments
+/// and parts.
+///
+/// The reply is parsed without a connection, so parts that need one,
+/// like result sets, cannot be parsed.
+///
+/// # Errors
+///
+/// The error that the parser detected.
+pub fn parse_reply(bytes: &[u8]) -> HdbResult<ParsedReply> {
+    let mut reply = parse(bytes)?;
+    let session_id = reply.session_id();
+    let following = reply.take_following();
+    let segments = std::iter::once(reply)
+        .chain(following)
+        .map(|reply| ParsedSegment {
+            reply_type: reply.replytype.to_i16(),
+            parts: reply
+                .parts
+                .into_iter()
+                .map(|part| ParsedPart {
+                    kind: part.kind() as i8,
+                    description: format!("{part:?}"),
+                })
+                .collect(),
+        })
+        .collect();
+    Ok(ParsedReply {
+        session_id,
+        segments,
+    })
+}
+
+/// Parses the bytes of a reply with the parser of the driver, and returns a debug
+/// representation of the parsed reply.
+///
+/// The format of the representation is not stable; use [`parse_reply`]
+/// for checking the parsed content.
+///
+/// # Errors
+///
+/// The error that the parser detected.
+pub fn describe_reply(bytes: &[u8]) -> HdbResult<String> {
+    Ok(format!("{:?}", parse(bytes)?))
+}
+
+fn parse(bytes: &[u8]) -> HdbResult<Reply> {
+    Reply::parse_sync(
+        None,
+        None,
+        &mut None,
+        None,
+        &mut ConnectionStatistics::new(),
+        &mut LobBufferPool::default(),
+        std::time::Instant::now(),
+        usize::MAX,
+        &mut Cursor::new(Vec::new()),
+        &mut Cursor::new(bytes),
+    )
+}
+
+// Replies for tests that replay them through a connection, see `Connection::replay()`.
+#[cfg(all(test, feature = "mock"))]
+pub(crate) mod replay {
+    use super::{PartBuilder, ReplyBuilder};
+    use crate::{
+        conn::ProtocolRecording,
+        protocol::parts::{ConnectOptionsPart, ServerConnectOptions},
+        sync::Connection,
+    };
+
+    // A connection that is served the given replies, in their order.
+    pub(crate) fn connection<I: IntoIterator<Item = ReplyBuilder>>(replies: I) -> Connection {
+        let mut recording =
+            ProtocolRecording::new(&ServerConnectOptions::new(ConnectOptionsPart::default()))
+                .unwrap();
+        for reply in replies {
+            recording.push(Vec::new(), reply.build());
+        }
+        Connection::replay(&recording).unwrap()
+    }
+
+    // A reply with an ExecutionResults part (kind 12), for the given function code,
+    // like 2 for INSERT or 11 for COMMIT.
+    pub(crate) fn execution_reply(reply_type: i16, execution_result: i32) -> ReplyBuilder {
+        ReplyBuilder::new()
+            .with_reply_type(reply_type)
+            .with_part(PartBuilder::new(12, 1).push_i32(execution_result))
+    }
+
+    // An error segment, with an error of the given code.
+    pub(crate) fn error_reply(code: i32) -> ReplyBuilder {
+        ReplyBuilder::new()
+            .as_error()
+            .with_part(PartBuilder::error(code, "HY000", "boom!"))
+    }
+
+    // A reply to preparing a statement, with a StatementId part (kind 10), and
+    // a ParameterMetadata part (kind 47) for a nullable IN parameter of type INT (3).
+    pub(crate) fn prepare_reply(statement_id: u64) -> ReplyBuilder {
+        ReplyBuilder::new()
+            .with_reply_type(2)
+            .with_part(PartBuilder::new(10, 1).push_u64(statement_id))
+            .with_part(
+                PartBuilder::new(47, 1)
+                    .push_u8(0b_0000_0010) // optional
+                    .push_u8(3)
+                    .push_u8(1) // IN
+                    .push_u8(0)
+                    .push_bytes(&u32::MAX.to_le_bytes()) // no name
+                    .push_i16(10) // length
+                    .push_i16(0) // fraction
+                    .push_i32(0),
+            )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        describe_reply, parse_reply, ParsedPart, ParsedSegment, PartBuilder, ReplyBuilder,
+    };
+
+    #[test]
+    fn test_reply_builder() {
+        // a part of kind 10 (StatementId), and one of kind 64 (TransactionFlags)
+        // with an option of id 0 and a boolean value (type code 28)
+        let reply = ReplyBuilder::new()
+            .with_session_id(17)
+            .with_reply_type(1)
+            .with_part(PartBuilder::new(10, 1).push_u64(4711))
+            .with_part(PartBuilder::new(64, 1).push_u8(0).push_u8(28).push_u8(1))
+            .build();
+        assert_eq!(reply.len() % 8, 0);
+        let description = describe_reply(&reply).unwrap();
+        assert!(description.contains("session_id: 17"), "{description}");
+
+        let parsed = parse_reply(&reply).unwrap();
+        assert_eq!(parsed.session_id(), 17);
+        assert_eq!(parsed.segments().len(), 1);
+        let segment = &parsed.segments()[0];
+        assert_eq!(segment.reply_type(), 1);
+        let kinds: Vec<i8> = segment.parts().iter().map(ParsedPart::kind).collect();
+        assert_eq!(kinds, vec![10, 64]);
+        assert!(segment.part(10).unwrap().description().contains("4711"));
+        assert!(segment.part(12).is_none());
+
+        // a corrupt reply
+        assert!(describe_reply(&reply[..reply.len() - 8]).is_err());
+    }
+
+    #[test]
+    fn test_error_part() {
+        // with "boom!!", the error fills a multiple of 8 bytes, and is padded nevertheless
+        for text in ["boom!", "boom!!"] {
+            let reply = ReplyBuilder::new()
+                .as_error()
+                .with_part(PartBuilder::error(259, "HY000", text))
+                .with_part(PartBuilder::new(10, 1).push_u64(4711))
+                .build();
+            let parsed = parse_reply(&reply).unwrap();
+            let segment = &parsed.segments()[0];
+            let description = segment.part(6).unwrap().description();
+            assert!(description.contains("259"), "{description}");
+            assert!(description.contains(text), "{description}");
+            assert!(segment.part(10).unwrap().description().contains("4711"));
+        }
+    }
+
+    #[test]
+    fn test_reply_with_segments() {
+        let reply = ReplyBuilder::new()
+            .with_reply_type(2)
+            .with_part(PartBuilder::new(12, 1).push_i32(3))
+            .with_segment(ReplyBuilder::new().with_reply_type(11))
+            .with_segment(
+                ReplyBuilder::new()
+                    .with_reply_type(3)
+                    .with_part(PartBuilder::new(12, 1).push_i32(4711)),
+            )
+            .build();
+        let parsed = parse_reply(&reply).unwrap();
+        let reply_types: Vec<i16> = parsed
+            .segments()
+            .iter()
+            .map(ParsedSegment::reply_type)
+            .collect();
+        assert_eq!(reply_types, vec![2, 11, 3]);
+        assert!(parsed.segments()[1].parts().is_empty());
+        assert!(parsed.segments()[2]
+            .part(12)
+            .unwrap()
+            .description()
+            .contains("4711"));
+
+        // the last segment is missing
+        let first_segments = ReplyBuilder::new()
+            .with_reply_type(2)
+            .with_part(PartBuilder::new(12, 1).push_i32(3))
+            .with_segment(ReplyBuilder::new().with_reply_type(11))
+            .build();
+        let mut corrupt = reply[..first_segments.len()].to_vec();
+        corrupt[12..16].copy_from_slice(&reply[12..16]);
+        assert!(parse_reply(&corrupt).is_err());
+    }
+}
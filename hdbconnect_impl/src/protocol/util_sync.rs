@@ -1,10 +1,28 @@
 use crate::{impl_err, HdbResult};
+use std::io::Read;
 
 // Read n bytes, return as Vec<u8>
+//
+// Large lengths are not trusted for pre-allocation, since they are read from the wire;
+// the buffer then only grows with the data that are really available.
 pub(crate) fn parse_bytes(len: usize, rdr: &mut dyn std::io::Read) -> HdbResult<Vec<u8>> {
-    let mut buf: Vec<u8> = vec![0; len];
-    rdr.read_exact(&mut buf)?;
-    Ok(buf)
+    const MAX_PREALLOCATION: usize = 64 * 1024;
+    if len <= MAX_PREALLOCATION {
+        let mut buf: Vec<u8> = vec![0; len];
+        rdr.read_exact(&mut buf)?;
+        Ok(buf)
+    } else {
+        let mut buf = Vec::<u8>::with_capacity(MAX_PREALLOCATION);
+        rdr.take(len as u64).read_to_end(&mut buf)?;
+        if buf.len() == len {
+            Ok(buf)
+        } else {
+            Err(impl_err!(
+                "corrupt reply: expected {len} bytes, but only {} are available",
+                buf.len()
+            ))
+        }
+    }
 }
 
 pub(crate) fn skip_bytes(n: usize, rdr: &mut dyn std::io::Read) -> HdbResult<()> {
@@ -16,3 +34,20 @@ pub(crate) fn skip_bytes(n: usize, rdr: &mut dyn std::io::Read) -> HdbResult<()>
         Ok(rdr.read_exact(&mut buffer[0..n])?)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::parse_bytes;
+
+    #[test]
+    fn test_parse_bytes_with_corrupt_length() {
+        let data = vec![7_u8; 100_000];
+        assert_eq!(
+            parse_bytes(100_000, &mut std::io::Cursor::new(&data)).unwrap(),
+            data
+        );
+        assert!(parse_bytes(10, &mut std::io::Cursor::new(&data[0..5])).is_err());
+        // would try to allocate 4 GB if the length were trusted
+        assert!(parse_bytes(u32::MAX as usize, &mut std::io::Cursor::new(&data)).is_err());
+    }
+}
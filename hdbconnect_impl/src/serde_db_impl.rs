@@ -1,6 +1,14 @@
+mod borrowing_de;
 pub(crate) mod de;
+mod field_probe;
 pub(crate) mod ser;
+mod ser_field_probe;
 
 pub mod time;
 mod to_hana;
 pub use to_hana::ToHana;
+
+pub(crate) use {
+    field_probe::check_target_fields,
+    ser_field_probe::{insert_statement, serialized_field_names},
+};
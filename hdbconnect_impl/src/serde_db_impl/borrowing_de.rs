@@ -0,0 +1,458 @@
+use super::de::{structural_error, wrong_type, FromValueRef, Position};
+use crate::{HdbResult, HdbValue, ResultSetMetadata, Row};
+use serde::de::{
+    value::{BorrowedStrDeserializer, SeqDeserializer},
+    DeserializeSeed, Deserializer, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde_db::de::{DbValueInto, DeserializationError, DeserializationResult};
+use std::{cell::Cell, rc::Rc};
+
+impl Row {
+    // Deserializes the row without consuming it, see Row::try_borrow_into().
+    pub(crate) fn deserialize_borrowed<'r, T>(&'r self) -> HdbResult<T>
+    where
+        T: serde::de::Deserialize<'r>,
+    {
+        let position = Rc::new(Cell::new(Position::default()));
+        T::deserialize(BorrowingRowDeserializer::new(
+            self.metadata(),
+            self.values(),
+            Rc::clone(&position),
+        ))
+        .map_err(|e| structural_error::<T>(e, self.metadata(), position.get()))
+    }
+}
+
+// Deserializes the values of a row without consuming them.
+//
+// In contrast to the serde_db based deserialization, which takes the values by value,
+// strings and binary values can be borrowed from the row, so that targets with
+// `&str`, `Cow<str>`, or `&[u8]` fields avoid an allocation per value.
+// All other values are converted with the same conversions as with the serde_db based
+// deserialization, but from a reference; only LOBs are copied, since their content
+// can only be taken by consuming them.
+struct BorrowingRowDeserializer<'r> {
+    metadata: &'r ResultSetMetadata,
+    values: &'r [HdbValue<'static>],
+    position: Rc<Cell<Position>>,
+}
+impl<'r> BorrowingRowDeserializer<'r> {
+    fn new(
+        metadata: &'r ResultSetMetadata,
+        values: &'r [HdbValue<'static>],
+        position: Rc<Cell<Position>>,
+    ) -> Self {
+        Self {
+            metadata,
+            values,
+            position,
+        }
+    }
+
+    // The values of a row are consumed from the front.
+    fn offset(&self) -> usize {
+        self.metadata.len() - self.values.len()
+    }
+
+    fn single_value(&self) -> DeserializationResult<BorrowingValueDeserializer<'r>> {
+        match self.values {
+            [value] => {
+                self.position.set(Position {
+                    o_row: None,
+                    o_column: Some(self.offset()),
+                });
+                Ok(BorrowingValueDeserializer(value))
+            }
+            [] => Err(DeserializationError::Usage("no more value".to_string())),
+            _ => Err(DeserializationError::TrailingCols),
+        }
+    }
+}
+
+macro_rules! delegate_to_single_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+                self.single_value()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'r> Deserializer<'r> for BorrowingRowDeserializer<'r> {
+    type Error = DeserializationError;
+
+    fn deserialize_any<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        if self.values.len() == 1 {
+            self.single_value()?.deserialize_any(visitor)
+        } else {
+            self.deserialize_map(visitor)
+        }
+    }
+
+    delegate_to_single_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_f32 deserialize_f64 deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf deserialize_option deserialize_unit
+        deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'r>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.single_value()?.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'r>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        let offset = self.offset();
+        visitor.visit_seq(BorrowingFields {
+            row: self,
+            idx: 0,
+            offset,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'r>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'r>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        let offset = self.offset();
+        visitor.visit_map(BorrowingFields {
+            row: self,
+            idx: 0,
+            offset,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'r>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'r>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.single_value()?
+            .deserialize_enum(name, variants, visitor)
+    }
+}
+
+// Hands out the values of a row, as sequence or as map with the column names as keys.
+struct BorrowingFields<'r> {
+    row: BorrowingRowDeserializer<'r>,
+    idx: usize,
+    offset: usize,
+}
+impl<'r> BorrowingFields<'r> {
+    fn next_value(&mut self) -> Option<BorrowingValueDeserializer<'r>> {
+        let value = self.row.values.get(self.idx)?;
+        self.row.position.set(Position {
+            o_row: None,
+            o_column: Some(self.offset + self.idx),
+        });
+        self.idx += 1;
+        Some(BorrowingValueDeserializer(value))
+    }
+}
+
+impl<'r> SeqAccess<'r> for BorrowingFields<'r> {
+    type Error = DeserializationError;
+
+    fn next_element_seed<T: DeserializeSeed<'r>>(
+        &mut self,
+        seed: T,
+    ) -> DeserializationResult<Option<T::Value>> {
+        self.next_value()
+            .map(|value| seed.deserialize(value))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.values.len() - self.idx)
+    }
+}
+
+impl<'r> MapAccess<'r> for BorrowingFields<'r> {
+    type Error = DeserializationError;
+
+    fn next_key_seed<K: DeserializeSeed<'r>>(
+        &mut self,
+        seed: K,
+    ) -> DeserializationResult<Option<K::Value>> {
+        if self.idx >= self.row.values.len() {
+            return Ok(None);
+        }
+        let metadata: &'r ResultSetMetadata = self.row.metadata;
+        let name = metadata[self.offset + self.idx].displayname();
+        seed.deserialize(BorrowedStrDeserializer::new(name))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'r>>(
+        &mut self,
+        seed: V,
+    ) -> DeserializationResult<V::Value> {
+        let value = self
+            .next_value()
+            .ok_or_else(|| DeserializationError::Usage("no more value".to_string()))?;
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.row.values.len() - self.idx)
+    }
+}
+
+// Deserializes a single value, borrowing strings and binary values.
+struct BorrowingValueDeserializer<'r>(&'r HdbValue<'static>);
+
+impl BorrowingValueDeserializer<'_> {
+    // Converts the value like the serde_db based deserialization does.
+    fn convert<T: FromValueRef>(&self) -> DeserializationResult<T> {
+        Ok(T::from_value_ref(self.0)?)
+    }
+
+    fn is_lob(&self) -> bool {
+        match self.0 {
+            #[cfg(feature = "sync")]
+            HdbValue::SYNC_BLOB(_) | HdbValue::SYNC_CLOB(_) | HdbValue::SYNC_NCLOB(_) => true,
+            #[cfg(feature = "async")]
+            HdbValue::ASYNC_BLOB(_) | HdbValue::ASYNC_CLOB(_) | HdbValue::ASYNC_NCLOB(_) => true,
+            _ => false,
+        }
+    }
+
+    // The content of a LOB is copied into the target anyway, so consuming a copy
+    // of the LOB does not cost an additional allocation.
+    fn convert_lob<T>(&self) -> DeserializationResult<T>
+    where
+        HdbValue<'static>: DbValueInto<T>,
+    {
+        Ok(DbValueInto::<T>::try_into(self.0.clone())?)
+    }
+
+    fn to_string(&self) -> DeserializationResult<String> {
+        if self.is_lob() {
+            self.convert_lob::<String>()
+        } else {
+            self.convert::<String>()
+        }
+    }
+}
+
+macro_rules! convert_and_visit {
+    ($($method:ident => $visit:ident: $t:ty,)*) => {
+        $(
+            fn $method<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+                visitor.$visit(self.convert::<$t>()?)
+            }
+        )*
+    };
+}
+
+impl<'r> Deserializer<'r> for BorrowingValueDeserializer<'r> {
+    type Error = DeserializationError;
+
+    fn deserialize_any<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        match self.0 {
+            HdbValue::NULL | HdbValue::TYPED_NULL(_) => visitor.visit_none(),
+            HdbValue::BOOLEAN(b) => visitor.visit_bool(*b),
+            HdbValue::TINYINT(i) => visitor.visit_u8(*i),
+            HdbValue::SMALLINT(i) => visitor.visit_i16(*i),
+            HdbValue::INT(i) => visitor.visit_i32(*i),
+            HdbValue::BIGINT(i) => visitor.visit_i64(*i),
+            HdbValue::REAL(f) => visitor.visit_f32(*f),
+            HdbValue::DOUBLE(f) => visitor.visit_f64(*f),
+            HdbValue::BINARY(_)
+            | HdbValue::BYTES(_)
+            | HdbValue::GEOMETRY(_)
+            | HdbValue::POINT(_) => self.deserialize_bytes(visitor),
+            _ => self.deserialize_str(visitor),
+        }
+    }
+
+    convert_and_visit! {
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+    }
+
+    fn deserialize_char<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        match self.0 {
+            HdbValue::STRING(s) => visitor.visit_borrowed_str(s),
+            HdbValue::STR(s) => visitor.visit_borrowed_str(s),
+            _ => visitor.visit_string(self.to_string()?),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        match self.0 {
+            HdbValue::BINARY(bytes) | HdbValue::GEOMETRY(bytes) | HdbValue::POINT(bytes) => {
+                visitor.visit_borrowed_bytes(bytes)
+            }
+            HdbValue::BYTES(bytes) => visitor.visit_borrowed_bytes(bytes),
+            HdbValue::DBSTRING(bytes) => visitor.visit_borrowed_bytes(bytes),
+            HdbValue::STRING(s) => visitor.visit_borrowed_bytes(s.as_bytes()),
+            HdbValue::STR(s) => visitor.visit_borrowed_bytes(s.as_bytes()),
+            _ if self.is_lob() => visitor.visit_byte_buf(self.convert_lob::<Vec<u8>>()?),
+            _ => Err(wrong_type(self.0, "Vec<u8>").into()),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        if self.0.is_null() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    // only NULL values can be deserialized into ()
+    fn deserialize_unit<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        if self.0.is_null() {
+            visitor.visit_unit()
+        } else {
+            Err(wrong_type(self.0, "()").into())
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'r>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'r>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    // Binary values can be deserialized into plain `Vec<u8>`, which serde handles as sequence
+    fn deserialize_seq<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        match self.0 {
+            HdbValue::BINARY(bytes) | HdbValue::GEOMETRY(bytes) | HdbValue::POINT(bytes) => {
+                visitor.visit_seq(SeqDeserializer::new(bytes.iter().copied()))
+            }
+            HdbValue::BYTES(bytes) => {
+                visitor.visit_seq(SeqDeserializer::new(bytes.iter().copied()))
+            }
+            _ => Err(DeserializationError::NotImplemented(
+                "deserialization of a single value into a sequence",
+            )),
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'r>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'r>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'r>>(self, _visitor: V) -> DeserializationResult<V::Value> {
+        Err(DeserializationError::NotImplemented(
+            "deserialization of a single value into a map or struct",
+        ))
+    }
+
+    fn deserialize_struct<V: Visitor<'r>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        self.deserialize_map(visitor)
+    }
+
+    // unit variants can be read from their name
+    fn deserialize_enum<V: Visitor<'r>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        match self.0 {
+            HdbValue::STRING(s) => visitor.visit_enum(s.as_str().into_deserializer()),
+            HdbValue::STR(s) => visitor.visit_enum(s.into_deserializer()),
+            _ => visitor.visit_enum(self.to_string()?.into_deserializer()),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'r>>(self, visitor: V) -> DeserializationResult<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'r>>(
+        self,
+        visitor: V,
+    ) -> DeserializationResult<V::Value> {
+        visitor.visit_unit()
+    }
+}
@@ -1,14 +1,206 @@
-use crate::{HdbError, HdbValue, OutputParameters, ParameterDescriptor, Row, Rows};
+use super::field_probe::{is_mismatch, mismatch_error, target_fields, Projection};
+use crate::{
+    HdbError, HdbResult, HdbValue, OutputParameters, ParameterDescriptor, ResultSetMetadata, Row,
+    Rows,
+};
 use bigdecimal::ToPrimitive;
 use serde_db::de::{
     ConversionError, DbValue, DbValueInto, DeserializableResultSet, DeserializableRow,
     DeserializationError, DeserializationResult,
 };
 use std::{
+    cell::Cell,
     fmt,
     num::{ParseFloatError, ParseIntError},
+    rc::Rc,
+    sync::Arc,
 };
 
+impl Rows {
+    // Deserializes the rows and, if a value cannot be converted, reports where this happened,
+    // or, if the target structure does not fit, how the target and the result look like.
+    pub(crate) fn deserialize_into<'de, T>(self) -> HdbResult<T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let metadata = Arc::clone(&self.metadata);
+        let position = Rc::new(Cell::new(Position::default()));
+        DeserializableResultSet::try_into(TrackingRows {
+            rows: self,
+            row_count: 0,
+            position: Rc::clone(&position),
+        })
+        .map_err(|e| structural_error::<T>(e, &metadata, position.get()))
+    }
+
+    // Like deserialize_into(), but drops the columns that are not used by the target fields
+    // before, and returns the number of dropped columns.
+    pub(crate) fn deserialize_projected<'de, T>(self) -> HdbResult<(T, usize)>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        match Projection::of::<T>(&self.metadata) {
+            Some(projection) => {
+                let rows = Rows {
+                    metadata: Arc::clone(projection.metadata()),
+                    number_of_rows: self.number_of_rows,
+                    row_queue: self
+                        .row_queue
+                        .into_iter()
+                        .map(|row| projection.apply(row))
+                        .collect(),
+                };
+                Ok((rows.deserialize_into()?, projection.ignored()))
+            }
+            None => Ok((self.deserialize_into()?, 0)),
+        }
+    }
+}
+
+impl Row {
+    // Deserializes the row and, if a value cannot be converted, reports where this happened,
+    // or, if the target structure does not fit, how the target and the result look like.
+    pub(crate) fn deserialize_into<'de, T>(self) -> HdbResult<T>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let metadata = Arc::clone(self.metadata_arc());
+        let position = Rc::new(Cell::new(Position::default()));
+        DeserializableRow::try_into(TrackingRow {
+            row: self,
+            o_row_idx: None,
+            position: Rc::clone(&position),
+        })
+        .map_err(|e| structural_error::<T>(e, &metadata, position.get()))
+    }
+
+    // Like deserialize_into(), but drops the values that are not used by the target fields
+    // before, and returns the number of dropped values.
+    pub(crate) fn deserialize_projected<'de, T>(self) -> HdbResult<(T, usize)>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let first = self.metadata().len() - self.len();
+        match Projection::of::<T>(&self.metadata()[first..]) {
+            Some(projection) => Ok((
+                projection.apply(self).deserialize_into()?,
+                projection.ignored(),
+            )),
+            None => Ok((self.deserialize_into()?, 0)),
+        }
+    }
+}
+
+pub(crate) fn structural_error<'de, T>(
+    e: DeserializationError,
+    metadata: &ResultSetMetadata,
+    position: Position,
+) -> HdbError
+where
+    T: serde::de::Deserialize<'de>,
+{
+    if is_mismatch(&e) {
+        if let Some(fields) = target_fields::<T>() {
+            return mismatch_error(e, metadata, fields);
+        }
+    }
+    position.into_error(e, metadata)
+}
+
+// The position of the value that was last handed out for deserialization.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Position {
+    pub(crate) o_row: Option<usize>,
+    pub(crate) o_column: Option<usize>,
+}
+impl Position {
+    pub(crate) fn into_error(
+        self,
+        e: DeserializationError,
+        metadata: &ResultSetMetadata,
+    ) -> HdbError {
+        match self
+            .o_column
+            .and_then(|col| metadata.get(col).map(|md| (col, md)))
+        {
+            Some((column, field_md)) => HdbError::deserialization_at(
+                e,
+                self.o_row,
+                column,
+                field_md.displayname(),
+                field_md.type_id(),
+            ),
+            None => HdbError::Deserialization { source: e },
+        }
+    }
+}
+
+// Wraps Rows to keep track of the position during deserialization.
+struct TrackingRows {
+    rows: Rows,
+    row_count: usize,
+    position: Rc<Cell<Position>>,
+}
+impl DeserializableResultSet for TrackingRows {
+    type Row = TrackingRow;
+    type Error = DeserializationError;
+
+    fn has_multiple_rows(&mut self) -> Result<bool, DeserializationError> {
+        self.rows.has_multiple_rows()
+    }
+
+    fn next(&mut self) -> DeserializationResult<Option<TrackingRow>> {
+        let o_row_idx = Some(self.row_count);
+        self.row_count += 1;
+        Ok(
+            DeserializableResultSet::next(&mut self.rows)?.map(|row| TrackingRow {
+                row,
+                o_row_idx,
+                position: Rc::clone(&self.position),
+            }),
+        )
+    }
+
+    fn number_of_fields(&self) -> usize {
+        DeserializableResultSet::number_of_fields(&self.rows)
+    }
+
+    fn field_name(&self, i: usize) -> Option<&str> {
+        DeserializableResultSet::field_name(&self.rows, i)
+    }
+}
+
+// Wraps a Row to keep track of the position during deserialization.
+struct TrackingRow {
+    row: Row,
+    o_row_idx: Option<usize>,
+    position: Rc<Cell<Position>>,
+}
+impl DeserializableRow for TrackingRow {
+    type Value = HdbValue<'static>;
+    type Error = DeserializationError;
+
+    fn len(&self) -> usize {
+        self.row.len()
+    }
+
+    fn next(&mut self) -> Option<HdbValue<'static>> {
+        self.position.set(Position {
+            o_row: self.o_row_idx,
+            o_column: Some(self.row.metadata().len() - self.row.len()),
+        });
+        self.row.next_value()
+    }
+
+    fn number_of_fields(&self) -> usize {
+        DeserializableRow::number_of_fields(&self.row)
+    }
+
+    fn field_name(&self, field_idx: usize) -> Option<&str> {
+        DeserializableRow::field_name(&self.row, field_idx)
+    }
+}
+
 impl DeserializableResultSet for Rows {
     type Row = Row;
     type Error = DeserializationError;
@@ -18,7 +210,7 @@ impl DeserializableResultSet for Rows {
     }
 
     fn next(&mut self) -> DeserializationResult<Option<Row>> {
-        Ok(self.row_iter.next())
+        Ok(self.row_queue.pop_front())
     }
 
     fn number_of_fields(&self) -> usize {
@@ -90,13 +282,32 @@ impl DeserializableRow for DeserializableOutputParameters {
 
 impl DbValue for HdbValue<'static> {
     fn is_null(&self) -> bool {
-        matches!(*self, HdbValue::NULL)
+        matches!(*self, HdbValue::NULL | HdbValue::TYPED_NULL(_))
     }
 }
 
-impl DbValueInto<bool> for HdbValue<'static> {
-    fn try_into(self) -> Result<bool, ConversionError> {
-        match self {
+// The conversions of scalar values don't need to consume the value,
+// so they are shared with the borrowing deserialization.
+pub(crate) trait FromValueRef: Sized {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError>;
+}
+
+macro_rules! db_value_into_from_value_ref {
+    ($($t:ty)*) => {
+        $(
+            impl DbValueInto<$t> for HdbValue<'static> {
+                fn try_into(self) -> Result<$t, ConversionError> {
+                    <$t>::from_value_ref(&self)
+                }
+            }
+        )*
+    };
+}
+db_value_into_from_value_ref! { bool u8 u16 u32 u64 i8 i16 i32 i64 f32 f64 }
+
+impl FromValueRef for bool {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::BOOLEAN(b) => Ok(b),
             HdbValue::TINYINT(1)
             | HdbValue::SMALLINT(1)
@@ -105,84 +316,84 @@ impl DbValueInto<bool> for HdbValue<'static> {
             HdbValue::STRING(ref s) => match s.as_ref() {
                 "true" | "TRUE" | "True" => Ok(true),
                 "false" | "FALSE" | "False" => Ok(false),
-                _ => Err(wrong_type(&self, "bool")),
+                _ => Err(wrong_type(value, "bool")),
             },
             HdbValue::TINYINT(0)
             | HdbValue::SMALLINT(0)
             | HdbValue::INT(0)
             | HdbValue::BIGINT(0) => Ok(false),
-            value => Err(wrong_type(&value, "bool")),
+            _ => Err(wrong_type(value, "bool")),
         }
     }
 }
 
-impl DbValueInto<u8> for HdbValue<'static> {
-    fn try_into(self) -> Result<u8, ConversionError> {
-        match self {
+impl FromValueRef for u8 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::TINYINT(u) => Ok(u),
             HdbValue::SMALLINT(i) => {
                 Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u8"))?)
             }
             HdbValue::INT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u8"))?),
             HdbValue::BIGINT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i, "u8"))?),
-            HdbValue::DECIMAL(bigdec) => bigdec.to_u8().ok_or_else(|| decimal_range("u8")),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
-            value => Err(wrong_type(&value, "u8")),
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_u8().ok_or_else(|| decimal_range("u8")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
+            _ => Err(wrong_type(value, "u8")),
         }
     }
 }
 
-impl DbValueInto<u16> for HdbValue<'static> {
-    fn try_into(self) -> Result<u16, ConversionError> {
-        match self {
+impl FromValueRef for u16 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::TINYINT(u) => Ok(u16::from(u)),
             HdbValue::SMALLINT(i) => {
                 Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u16"))?)
             }
             HdbValue::INT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u16"))?),
             HdbValue::BIGINT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i, "u16"))?),
-            HdbValue::DECIMAL(bigdec) => bigdec.to_u16().ok_or_else(|| decimal_range("u16")),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
-            value => Err(wrong_type(&value, "u16")),
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_u16().ok_or_else(|| decimal_range("u16")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
+            _ => Err(wrong_type(value, "u16")),
         }
     }
 }
 
-impl DbValueInto<u32> for HdbValue<'static> {
-    fn try_into(self) -> Result<u32, ConversionError> {
-        match self {
+impl FromValueRef for u32 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::TINYINT(u) => Ok(u32::from(u)),
             HdbValue::SMALLINT(i) => {
                 Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u32"))?)
             }
             HdbValue::INT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u32"))?),
             HdbValue::BIGINT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i, "u32"))?),
-            HdbValue::DECIMAL(bigdec) => bigdec.to_u32().ok_or_else(|| decimal_range("u32")),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
-            value => Err(wrong_type(&value, "u32")),
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_u32().ok_or_else(|| decimal_range("u32")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
+            _ => Err(wrong_type(value, "u32")),
         }
     }
 }
 
-impl DbValueInto<u64> for HdbValue<'static> {
-    fn try_into(self) -> Result<u64, ConversionError> {
-        match self {
+impl FromValueRef for u64 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::TINYINT(u) => Ok(u64::from(u)),
             HdbValue::SMALLINT(i) => {
                 Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u64"))?)
             }
             HdbValue::INT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u64"))?),
             HdbValue::BIGINT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i, "u64"))?),
-            HdbValue::DECIMAL(bigdec) => bigdec.to_u64().ok_or_else(|| decimal_range("u64")),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
-            value => Err(wrong_type(&value, "u64")),
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_u64().ok_or_else(|| decimal_range("u64")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
+            _ => Err(wrong_type(value, "u64")),
         }
     }
 }
 
-impl DbValueInto<i8> for HdbValue<'static> {
-    fn try_into(self) -> Result<i8, ConversionError> {
-        match self {
+impl FromValueRef for i8 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::TINYINT(i) => {
                 Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "i8"))?)
             }
@@ -191,75 +402,75 @@ impl DbValueInto<i8> for HdbValue<'static> {
             }
             HdbValue::INT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "i8"))?),
             HdbValue::BIGINT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i, "i8"))?),
-            HdbValue::DECIMAL(bigdec) => bigdec.to_i8().ok_or_else(|| decimal_range("i8")),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
-            value => Err(wrong_type(&value, "i8")),
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_i8().ok_or_else(|| decimal_range("i8")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
+            _ => Err(wrong_type(value, "i8")),
         }
     }
 }
 
-impl DbValueInto<i16> for HdbValue<'static> {
-    fn try_into(self) -> Result<i16, ConversionError> {
-        match self {
+impl FromValueRef for i16 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::TINYINT(u) => Ok(i16::from(u)),
             HdbValue::SMALLINT(i) => Ok(i),
             HdbValue::INT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i64::from(i), "u8"))?),
             HdbValue::BIGINT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i, "u8"))?),
-            HdbValue::DECIMAL(bigdec) => bigdec.to_i16().ok_or_else(|| decimal_range("i16")),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
-            value => Err(wrong_type(&value, "i16")),
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_i16().ok_or_else(|| decimal_range("i16")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
+            _ => Err(wrong_type(value, "i16")),
         }
     }
 }
 
-impl DbValueInto<i32> for HdbValue<'static> {
-    fn try_into(self) -> Result<i32, ConversionError> {
-        match self {
+impl FromValueRef for i32 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::TINYINT(u) => Ok(i32::from(u)),
             HdbValue::SMALLINT(i) => Ok(i32::from(i)),
             HdbValue::INT(i) => Ok(i),
             HdbValue::BIGINT(i) => Ok(num::cast(i).ok_or_else(|| number_range(i, "i32"))?),
-            HdbValue::DECIMAL(bigdec) => bigdec.to_i32().ok_or_else(|| decimal_range("i32")),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
-            value => Err(wrong_type(&value, "i32")),
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_i32().ok_or_else(|| decimal_range("i32")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
+            _ => Err(wrong_type(value, "i32")),
         }
     }
 }
 
-impl DbValueInto<i64> for HdbValue<'static> {
-    fn try_into(self) -> Result<i64, ConversionError> {
-        match self {
+impl FromValueRef for i64 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
             HdbValue::TINYINT(u) => Ok(i64::from(u)),
             HdbValue::SMALLINT(i) => Ok(i64::from(i)),
             HdbValue::INT(i) => Ok(i64::from(i)),
             HdbValue::BIGINT(i) => Ok(i),
-            HdbValue::LONGDATE(ld) => Ok(*ld.ref_raw()),
-            HdbValue::SECONDDATE(sd) => Ok(*sd.ref_raw()),
-            HdbValue::DECIMAL(bigdec) => bigdec.to_i64().ok_or_else(|| decimal_range("i64")),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
-            value => Err(wrong_type(&value, "i64")),
+            HdbValue::LONGDATE(ref ld) => Ok(*ld.ref_raw()),
+            HdbValue::SECONDDATE(ref sd) => Ok(*sd.ref_raw()),
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_i64().ok_or_else(|| decimal_range("i64")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseIntError| parse_int_err(&e)),
+            _ => Err(wrong_type(value, "i64")),
         }
     }
 }
 
-impl DbValueInto<f32> for HdbValue<'static> {
-    fn try_into(self) -> Result<f32, ConversionError> {
-        match self {
-            HdbValue::DECIMAL(bigdec) => bigdec.to_f32().ok_or_else(|| decimal_range("f32")),
+impl FromValueRef for f32 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_f32().ok_or_else(|| decimal_range("f32")),
             HdbValue::REAL(f) => Ok(f),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseFloatError| parse_float_err(&e)),
-            value => Err(wrong_type(&value, "f32")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseFloatError| parse_float_err(&e)),
+            _ => Err(wrong_type(value, "f32")),
         }
     }
 }
 
-impl DbValueInto<f64> for HdbValue<'static> {
-    fn try_into(self) -> Result<f64, ConversionError> {
-        match self {
-            HdbValue::DECIMAL(bigdec) => bigdec.to_f64().ok_or_else(|| decimal_range("f64")),
+impl FromValueRef for f64 {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
+            HdbValue::DECIMAL(ref bigdec) => bigdec.to_f64().ok_or_else(|| decimal_range("f64")),
             HdbValue::DOUBLE(f) => Ok(f),
-            HdbValue::STRING(s) => s.parse().map_err(|e: ParseFloatError| parse_float_err(&e)),
-            value => Err(wrong_type(&value, "f64")),
+            HdbValue::STRING(ref s) => s.parse().map_err(|e: ParseFloatError| parse_float_err(&e)),
+            _ => Err(wrong_type(value, "f64")),
         }
     }
 }
@@ -268,24 +479,12 @@ impl DbValueInto<String> for HdbValue<'static> {
     fn try_into(self) -> Result<String, ConversionError> {
         trace!("try_into -> String");
         match self {
-            HdbValue::NULL => Ok(String::from("<NULL>")), // FIXME intentionally NOT implemented!?
-            HdbValue::TINYINT(i) => Ok(format!("{i}")),
-            HdbValue::SMALLINT(i) => Ok(format!("{i}")),
-            HdbValue::INT(i) => Ok(format!("{i}")),
-            HdbValue::BIGINT(i) => Ok(format!("{i}")),
-            HdbValue::REAL(f) => Ok(format!("{f}")),
-            HdbValue::DOUBLE(f) => Ok(format!("{f}")),
             HdbValue::STRING(s) => Ok(s),
             HdbValue::DBSTRING(bytes) => {
                 Err(ConversionError::Other(Box::new(HdbError::Cesu8AsBytes {
                     bytes,
                 })))
             }
-            HdbValue::LONGDATE(ld) => Ok(str_from(&ld)),
-            HdbValue::SECONDDATE(sd) => Ok(str_from(&sd)),
-            HdbValue::DAYDATE(date) => Ok(str_from(&date)),
-            HdbValue::SECONDTIME(time) => Ok(str_from(&time)),
-            HdbValue::DECIMAL(bigdec) => Ok(format!("{bigdec}")),
 
             #[cfg(feature = "sync")]
             HdbValue::SYNC_CLOB(clob) => Ok(clob
@@ -305,7 +504,35 @@ impl DbValueInto<String> for HdbValue<'static> {
                 .into_string_if_complete()
                 .map_err(|e| ConversionError::Incomplete(e.to_string()))?),
 
-            value => Err(wrong_type(&value, "String")),
+            value => String::from_value_ref(&value),
+        }
+    }
+}
+
+// LOBs are not covered, since their content can only be taken by consuming them.
+impl FromValueRef for String {
+    fn from_value_ref(value: &HdbValue) -> Result<Self, ConversionError> {
+        match *value {
+            HdbValue::NULL => Ok(String::from("<NULL>")), // FIXME intentionally NOT implemented!?
+            HdbValue::TINYINT(i) => Ok(format!("{i}")),
+            HdbValue::SMALLINT(i) => Ok(format!("{i}")),
+            HdbValue::INT(i) => Ok(format!("{i}")),
+            HdbValue::BIGINT(i) => Ok(format!("{i}")),
+            HdbValue::REAL(f) => Ok(format!("{f}")),
+            HdbValue::DOUBLE(f) => Ok(format!("{f}")),
+            HdbValue::STRING(ref s) => Ok(s.clone()),
+            HdbValue::STR(s) => Ok(s.to_string()),
+            HdbValue::DBSTRING(ref bytes) => {
+                Err(ConversionError::Other(Box::new(HdbError::Cesu8AsBytes {
+                    bytes: bytes.clone(),
+                })))
+            }
+            HdbValue::LONGDATE(ref ld) => Ok(str_from(ld)),
+            HdbValue::SECONDDATE(ref sd) => Ok(str_from(sd)),
+            HdbValue::DAYDATE(ref date) => Ok(str_from(date)),
+            HdbValue::SECONDTIME(ref time) => Ok(str_from(time)),
+            HdbValue::DECIMAL(ref bigdec) => Ok(format!("{bigdec}")),
+            _ => Err(wrong_type(value, "String")),
         }
     }
 }
@@ -324,6 +551,7 @@ impl DbValueInto<Vec<u8>> for HdbValue<'static> {
                 .map_err(|e| ConversionError::Incomplete(e.to_string()))?),
 
             HdbValue::BINARY(v) | HdbValue::GEOMETRY(v) | HdbValue::POINT(v) => Ok(v),
+            HdbValue::BYTES(v) => Ok(v.to_vec()),
 
             HdbValue::STRING(s) => Ok(s.into_bytes()),
             HdbValue::DBSTRING(v) => Ok(v),
@@ -333,7 +561,7 @@ impl DbValueInto<Vec<u8>> for HdbValue<'static> {
     }
 }
 
-fn wrong_type(tv: &HdbValue, ovt: &str) -> ConversionError {
+pub(crate) fn wrong_type(tv: &HdbValue, ovt: &str) -> ConversionError {
     ConversionError::ValueType(format!(
         "The value {tv:?} cannot be converted into type {ovt}",
     ))
@@ -370,3 +598,60 @@ impl From<HdbError> for DeserializationError {
         Self::Usage(e.to_string())
     }
 }
+
+#[cfg(all(test, feature = "sync"))]
+mod test {
+    use crate::{sync::ResultSet, HdbError, HdbValue, ResultSetBuilder, TypeId};
+
+    #[test]
+    fn test_deserialization_at() {
+        let builder = ResultSetBuilder::new()
+            .column("ID", TypeId::INT)
+            .column("AMOUNT", TypeId::NVARCHAR)
+            .row(vec![HdbValue::INT(1), HdbValue::STRING("10".to_string())])
+            .row(vec![HdbValue::INT(2), HdbValue::STRING("11".to_string())])
+            .row(vec![HdbValue::INT(3), HdbValue::STRING("many".to_string())]);
+
+        // a complete result set reports row and column
+        let result_set = ResultSet::try_from(builder.clone()).unwrap();
+        let e = result_set.try_into::<Vec<(i32, u32)>>().unwrap_err();
+        match e {
+            HdbError::DeserializationAt {
+                row,
+                column,
+                ref column_name,
+                type_id,
+                ..
+            } => {
+                assert_eq!((row, column), (Some(2), 1));
+                assert_eq!(column_name, "AMOUNT");
+                assert_eq!(type_id, TypeId::NVARCHAR);
+            }
+            e => panic!("unexpected error: {e:?}"),
+        }
+        assert!(e.to_string().contains("row 2, column AMOUNT"), "{e}");
+
+        // a single row reports only the column
+        let mut result_set = ResultSet::try_from(builder).unwrap();
+        for _ in 0..2 {
+            let _: (i32, u32) = result_set.next_row().unwrap().unwrap().try_into().unwrap();
+        }
+        let e = result_set
+            .next_row()
+            .unwrap()
+            .unwrap()
+            .try_into::<(i32, u32)>()
+            .unwrap_err();
+        assert!(
+            matches!(
+                e,
+                HdbError::DeserializationAt {
+                    row: None,
+                    column: 1,
+                    ..
+                }
+            ),
+            "{e:?}"
+        );
+    }
+}
@@ -0,0 +1,625 @@
+use crate::{
+    types::{DayDate, LongDate, SecondDate, SecondTime},
+    FieldMetadata, HdbError, HdbResult, HdbValue, ResultSetMetadata, Row, TypeId,
+};
+use serde::de::{
+    value::StrDeserializer, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor,
+};
+use serde_db::de::{ConversionError, DeserializationError, DeserializationResult};
+use std::{cell::Cell, sync::Arc};
+
+// Returns the field names of the deserialization target, if it is a struct, or a sequence
+// or an option of structs.
+//
+// The names are taken from the target's Deserialize implementation without producing a value.
+pub(crate) fn target_fields<'de, T>() -> Option<&'static [&'static str]>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    let mut o_fields = None;
+    T::deserialize(FieldProbe(&mut o_fields)).ok();
+    o_fields
+}
+
+// Returns true if the error indicates that the target fields do not fit to the result columns.
+pub(crate) fn is_mismatch(e: &DeserializationError) -> bool {
+    match e {
+        DeserializationError::UnknownField(_) => true,
+        DeserializationError::SerdeError(s) => s.starts_with("missing field"),
+        _ => false,
+    }
+}
+
+// Creates an error that lists the result columns and the target fields,
+// and gives hints on names that only differ in case.
+pub(crate) fn mismatch_error(
+    source: DeserializationError,
+    metadata: &ResultSetMetadata,
+    fields: &[&str],
+) -> HdbError {
+    let columns: Vec<String> = metadata
+        .iter()
+        .map(|md| md.displayname().to_string())
+        .collect();
+    let hints = fields
+        .iter()
+        .filter(|field| !columns.iter().any(|c| c == *field))
+        .filter_map(|field| {
+            columns
+                .iter()
+                .find(|c| c.eq_ignore_ascii_case(field))
+                .map(|c| {
+                    format!(
+                        "field \"{field}\" and column \"{c}\" differ only in case, \
+                         consider #[serde(rename = \"{c}\")] or a column alias"
+                    )
+                })
+        })
+        .collect();
+    HdbError::DeserializationMismatch {
+        source,
+        columns,
+        fields: fields.iter().map(ToString::to_string).collect(),
+        hints,
+    }
+}
+
+// Verifies, before any row is fetched, that the result columns fit to the deserialization
+// target: each field that serde requires (i.e., that is no Option, has no default, and is
+// not skipped) must have a column with the same name, and each column that is used by a
+// field must have a type that can be converted into the type of the field.
+//
+// The check runs the target's Deserialize implementation against probe deserializers that
+// hand out dummy values. Where this does not reveal enough, e.g. because the implementation
+// uses `deserialize_any`, the check gives up silently and leaves the decision to the
+// deserialization of the rows.
+pub(crate) fn check_target_fields<'de, T>(metadata: &ResultSetMetadata) -> HdbResult<()>
+where
+    T: serde::de::Deserialize<'de>,
+{
+    let Some(fields) = target_fields::<T>() else {
+        return Ok(());
+    };
+    let used: Vec<usize> = (0..metadata.len())
+        .filter(|i| fields.contains(&metadata[*i].displayname()))
+        .collect();
+
+    // one probe per column, so that an undecidable field does not hide the others
+    for column in &used {
+        let mismatch = Cell::new(None);
+        T::deserialize(RowProbe {
+            metadata,
+            columns: std::slice::from_ref(column),
+            mismatch: &mismatch,
+        })
+        .ok();
+        if let Some(target_type) = mismatch.get() {
+            let field_md = &metadata[*column];
+            return Err(HdbError::deserialization_at(
+                DeserializationError::ConversionError(ConversionError::ValueType(format!(
+                    "values of type {:?} cannot be converted into type {target_type}",
+                    field_md.type_id(),
+                ))),
+                None,
+                *column,
+                field_md.displayname(),
+                field_md.type_id(),
+            ));
+        }
+    }
+
+    // serde reports the required fields that got no value
+    let mismatch = Cell::new(None);
+    match T::deserialize(RowProbe {
+        metadata,
+        columns: &used,
+        mismatch: &mismatch,
+    }) {
+        Err(e) if is_mismatch(&e) => Err(mismatch_error(e, metadata, fields)),
+        _ => Ok(()),
+    }
+}
+
+// Describes which of the given columns are used by the fields of the deserialization target.
+pub(crate) struct Projection {
+    metadata: Arc<ResultSetMetadata>,
+    used: Vec<bool>,
+}
+impl Projection {
+    // Returns None if the target is not a struct (or a sequence or option of structs),
+    // or if it uses all columns.
+    pub(crate) fn of<'de, T>(columns: &[FieldMetadata]) -> Option<Self>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let fields = target_fields::<T>()?;
+        let used: Vec<bool> = columns
+            .iter()
+            .map(|md| fields.contains(&md.displayname()))
+            .collect();
+        if used.iter().all(|u| *u) {
+            None
+        } else {
+            let metadata = Arc::new(ResultSetMetadata::new(
+                columns
+                    .iter()
+                    .zip(&used)
+                    .filter(|(_, u)| **u)
+                    .map(|(md, _)| md.clone())
+                    .collect(),
+            ));
+            Some(Self { metadata, used })
+        }
+    }
+
+    // The number of columns that are not used.
+    pub(crate) fn ignored(&self) -> usize {
+        self.used.iter().filter(|u| !**u).count()
+    }
+
+    pub(crate) fn metadata(&self) -> &Arc<ResultSetMetadata> {
+        &self.metadata
+    }
+
+    // Creates a row with only the used values.
+    pub(crate) fn apply(&self, values: impl Iterator<Item = HdbValue<'static>>) -> Row {
+        Row::new(
+            Arc::clone(&self.metadata),
+            values
+                .zip(&self.used)
+                .filter_map(|(value, u)| u.then_some(value))
+                .collect(),
+        )
+    }
+}
+
+struct FieldProbe<'a>(&'a mut Option<&'static [&'static str]>);
+
+fn probe_done() -> DeserializationError {
+    DeserializationError::Usage("field probe done".to_string())
+}
+
+impl<'de> Deserializer<'de> for FieldProbe<'_> {
+    type Error = DeserializationError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(probe_done())
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = Some(fields);
+        Err(probe_done())
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(FieldProbeSeq(self.0))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct FieldProbeSeq<'a>(&'a mut Option<&'static [&'static str]>);
+
+impl<'de> SeqAccess<'de> for FieldProbeSeq<'_> {
+    type Error = DeserializationError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        seed.deserialize(FieldProbe(self.0)).map(Some)
+    }
+}
+
+// Presents the given columns as the single row of a result set, with dummy values.
+struct RowProbe<'a> {
+    metadata: &'a ResultSetMetadata,
+    columns: &'a [usize],
+    mismatch: &'a Cell<Option<&'static str>>,
+}
+
+impl<'de> Deserializer<'de> for RowProbe<'_> {
+    type Error = DeserializationError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(probe_done())
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_map(ColumnsProbe {
+            metadata: self.metadata,
+            columns: self.columns.iter(),
+            o_current: None,
+            mismatch: self.mismatch,
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_seq(RowProbeSeq(Some(self)))
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+struct RowProbeSeq<'a>(Option<RowProbe<'a>>);
+
+impl<'de> SeqAccess<'de> for RowProbeSeq<'_> {
+    type Error = DeserializationError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, Self::Error> {
+        self.0
+            .take()
+            .map(|probe| seed.deserialize(probe))
+            .transpose()
+    }
+}
+
+struct ColumnsProbe<'a> {
+    metadata: &'a ResultSetMetadata,
+    columns: std::slice::Iter<'a, usize>,
+    o_current: Option<&'a FieldMetadata>,
+    mismatch: &'a Cell<Option<&'static str>>,
+}
+
+impl<'de> MapAccess<'de> for ColumnsProbe<'_> {
+    type Error = DeserializationError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        self.o_current = self.columns.next().map(|i| &self.metadata[*i]);
+        self.o_current
+            .map(|field_md| seed.deserialize(StrDeserializer::new(field_md.displayname())))
+            .transpose()
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<S::Value, Self::Error> {
+        let field_md = self.o_current.ok_or_else(probe_done)?;
+        seed.deserialize(ValueProbe {
+            type_id: field_md.type_id(),
+            mismatch: self.mismatch,
+        })
+    }
+}
+
+// The target types that are converted differently, see the implementations of `DbValueInto`.
+#[derive(Clone, Copy)]
+enum TargetType {
+    Bool,
+    Integer,
+    I64,
+    F32,
+    F64,
+    Text,
+    Bytes,
+}
+impl TargetType {
+    // Returns None if this cannot be decided from the type id.
+    fn accepts(self, type_id: TypeId) -> Option<bool> {
+        let is_integer = matches!(
+            type_id,
+            TypeId::TINYINT | TypeId::SMALLINT | TypeId::INT | TypeId::BIGINT
+        );
+        let is_decimal = matches!(
+            type_id,
+            TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16
+        );
+        let is_string = matches!(
+            type_id,
+            TypeId::CHAR
+                | TypeId::VARCHAR
+                | TypeId::NCHAR
+                | TypeId::NVARCHAR
+                | TypeId::STRING
+                | TypeId::NSTRING
+                | TypeId::SHORTTEXT
+                | TypeId::ALPHANUM
+        );
+        let is_binary = matches!(
+            type_id,
+            TypeId::BINARY
+                | TypeId::VARBINARY
+                | TypeId::BSTRING
+                | TypeId::GEOMETRY
+                | TypeId::POINT
+                | TypeId::BLOB
+                | TypeId::BINTEXT
+        );
+        if type_id == TypeId::BLOCATOR {
+            return None;
+        }
+        Some(match self {
+            Self::Bool => is_integer || is_string || type_id == TypeId::BOOLEAN,
+            Self::Integer => is_integer || is_decimal || is_string,
+            Self::I64 => {
+                is_integer
+                    || is_decimal
+                    || is_string
+                    || matches!(type_id, TypeId::LONGDATE | TypeId::SECONDDATE)
+            }
+            Self::F32 => is_decimal || is_string || type_id == TypeId::REAL,
+            Self::F64 => is_decimal || is_string || type_id == TypeId::DOUBLE,
+            Self::Text => !(is_binary || type_id == TypeId::BOOLEAN),
+            Self::Bytes => is_binary || is_string,
+        })
+    }
+}
+
+// Hands out a dummy value if the column type fits to the requested type,
+// and records a mismatch otherwise.
+struct ValueProbe<'a> {
+    type_id: TypeId,
+    mismatch: &'a Cell<Option<&'static str>>,
+}
+impl ValueProbe<'_> {
+    fn check(&self, target: TargetType, target_type: &'static str) -> DeserializationResult<()> {
+        match target.accepts(self.type_id) {
+            Some(true) => Ok(()),
+            Some(false) => {
+                self.mismatch.set(Some(target_type));
+                Err(probe_done())
+            }
+            None => Err(probe_done()),
+        }
+    }
+
+    // A string like the one the conversion of a real value would produce.
+    fn sample_string(&self) -> String {
+        match self.type_id {
+            TypeId::LONGDATE => LongDate::from_unix_timestamp(0)
+                .map(|ld| ld.to_string())
+                .unwrap_or_default(),
+            TypeId::SECONDDATE => SecondDate::from_unix_timestamp(0)
+                .map(|sd| sd.to_string())
+                .unwrap_or_default(),
+            TypeId::DAYDATE => DayDate::from_unix_timestamp(0)
+                .map(|dd| dd.to_string())
+                .unwrap_or_default(),
+            TypeId::SECONDTIME => SecondTime::from_unix_timestamp(0).to_string(),
+            _ => "0".to_string(),
+        }
+    }
+}
+
+macro_rules! probe_number {
+    ($($method:ident, $visit:ident, $t:ty, $target:expr;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                self.check($target, stringify!($t))?;
+                visitor.$visit(<$t>::default())
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for ValueProbe<'_> {
+    type Error = DeserializationError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(probe_done())
+    }
+
+    probe_number! {
+        deserialize_bool, visit_bool, bool, TargetType::Bool;
+        deserialize_u8, visit_u8, u8, TargetType::Integer;
+        deserialize_u16, visit_u16, u16, TargetType::Integer;
+        deserialize_u32, visit_u32, u32, TargetType::Integer;
+        deserialize_u64, visit_u64, u64, TargetType::Integer;
+        deserialize_i8, visit_i8, i8, TargetType::Integer;
+        deserialize_i16, visit_i16, i16, TargetType::Integer;
+        deserialize_i32, visit_i32, i32, TargetType::Integer;
+        deserialize_i64, visit_i64, i64, TargetType::I64;
+        deserialize_f32, visit_f32, f32, TargetType::F32;
+        deserialize_f64, visit_f64, f64, TargetType::F64;
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.check(TargetType::Text, "String")?;
+        visitor.visit_string(self.sample_string())
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.check(TargetType::Bytes, "Vec<u8>")?;
+        visitor.visit_byte_buf(Vec::new())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 char unit unit_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_target_fields, target_fields};
+    use crate::{FieldMetadata, HdbError, ResultSetMetadata, TypeId};
+
+    #[derive(serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Entity {
+        id: u32,
+        #[serde(rename = "NAME")]
+        name: String,
+    }
+
+    #[test]
+    fn test_target_fields() {
+        let expected: &[&str] = &["id", "NAME"];
+        assert_eq!(target_fields::<Entity>(), Some(expected));
+        assert_eq!(target_fields::<Vec<Entity>>(), Some(expected));
+        assert_eq!(target_fields::<Option<Entity>>(), Some(expected));
+        assert_eq!(target_fields::<Vec<(u32, String)>>(), None);
+        assert_eq!(target_fields::<u32>(), None);
+    }
+
+    #[test]
+    fn test_check_target_fields() {
+        let metadata = ResultSetMetadata::new(vec![
+            FieldMetadata::new_column("id", TypeId::INT),
+            FieldMetadata::new_column("Name", TypeId::NVARCHAR),
+        ]);
+        assert!(check_target_fields::<(u32, String)>(&metadata).is_ok());
+        let e = check_target_fields::<Entity>(&metadata).unwrap_err();
+        assert!(
+            matches!(e, HdbError::DeserializationMismatch { ref hints, .. } if hints.len() == 1)
+        );
+
+        let metadata = ResultSetMetadata::new(vec![
+            FieldMetadata::new_column("id", TypeId::INT),
+            FieldMetadata::new_column("NAME", TypeId::NVARCHAR),
+            FieldMetadata::new_column("OTHER", TypeId::NVARCHAR),
+        ]);
+        assert!(check_target_fields::<Entity>(&metadata).is_ok());
+    }
+
+    #[derive(serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Lenient {
+        id: u32,
+        #[serde(default)]
+        count: i64,
+        note: Option<String>,
+        #[serde(skip)]
+        cache: Vec<u8>,
+        created: String,
+    }
+
+    #[test]
+    fn test_check_target_fields_with_defaults() {
+        // fields with defaults, Option fields, and skipped fields need no column
+        let metadata = ResultSetMetadata::new(vec![
+            FieldMetadata::new_column("id", TypeId::INT),
+            FieldMetadata::new_column("created", TypeId::LONGDATE),
+        ]);
+        assert!(check_target_fields::<Vec<Lenient>>(&metadata).is_ok());
+
+        // a required field without column is still reported
+        let metadata = ResultSetMetadata::new(vec![
+            FieldMetadata::new_column("id", TypeId::INT),
+            FieldMetadata::new_column("count", TypeId::SECONDDATE),
+        ]);
+        let e = check_target_fields::<Vec<Lenient>>(&metadata).unwrap_err();
+        assert!(
+            matches!(e, HdbError::DeserializationMismatch { .. })
+                && format!("{e:?}").contains("missing field `created`"),
+            "{e:?}"
+        );
+    }
+
+    #[derive(serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Untagged {
+        id: Value,
+    }
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    #[allow(dead_code)]
+    enum Value {
+        Number(u32),
+        Text(String),
+    }
+
+    #[test]
+    fn test_check_target_types() {
+        let check = |id_type, note_type| {
+            check_target_fields::<Vec<Lenient>>(&ResultSetMetadata::new(vec![
+                FieldMetadata::new_column("id", id_type),
+                FieldMetadata::new_column("note", note_type),
+                FieldMetadata::new_column("created", TypeId::DAYDATE),
+            ]))
+        };
+        assert!(check(TypeId::DECIMAL, TypeId::CLOB).is_ok());
+        assert!(check(TypeId::NVARCHAR, TypeId::INT).is_ok());
+
+        match check(TypeId::INT, TypeId::BLOB).unwrap_err() {
+            HdbError::DeserializationAt {
+                column,
+                column_name,
+                type_id,
+                ..
+            } => {
+                assert_eq!(column, 1);
+                assert_eq!(column_name, "note");
+                assert_eq!(type_id, TypeId::BLOB);
+            }
+            e => panic!("unexpected error: {e:?}"),
+        }
+        assert!(matches!(
+            check(TypeId::DOUBLE, TypeId::NVARCHAR),
+            Err(HdbError::DeserializationAt { column: 0, .. })
+        ));
+
+        // implementations that use deserialize_any are not checked
+        let metadata = ResultSetMetadata::new(vec![FieldMetadata::new_column("id", TypeId::BLOB)]);
+        assert!(check_target_fields::<Vec<Untagged>>(&metadata).is_ok());
+    }
+}
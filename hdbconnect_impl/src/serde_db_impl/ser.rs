@@ -85,8 +85,14 @@ impl DbvFactory for &ParameterDescriptor {
                     BigDecimal::from_i32(value).ok_or_else(|| decimal_range(input_type))?,
                 )
             }
-            TypeId::DAYDATE => HdbValue::DAYDATE(DayDate::new(value)),
-            TypeId::SECONDTIME => HdbValue::SECONDTIME(SecondTime::new(value)),
+            TypeId::DAYDATE => HdbValue::DAYDATE(
+                DayDate::checked_new(value)
+                    .ok_or_else(|| SerializationError::Range(input_type, self.descriptor()))?,
+            ),
+            TypeId::SECONDTIME => HdbValue::SECONDTIME(
+                SecondTime::checked_new(value)
+                    .ok_or_else(|| SerializationError::Range(input_type, self.descriptor()))?,
+            ),
             TypeId::VARCHAR | TypeId::NVARCHAR | TypeId::TEXT | TypeId::SHORTTEXT => {
                 HdbValue::STRING(format!("{value}"))
             }
@@ -111,8 +117,14 @@ impl DbvFactory for &ParameterDescriptor {
                     .ok_or_else(|| SerializationError::Range(input_type, self.descriptor()))?,
             ),
             TypeId::BIGINT => HdbValue::BIGINT(value),
-            TypeId::LONGDATE => HdbValue::LONGDATE(LongDate::new(value)),
-            TypeId::SECONDDATE => HdbValue::SECONDDATE(SecondDate::new(value)),
+            TypeId::LONGDATE => HdbValue::LONGDATE(
+                LongDate::checked_new(value)
+                    .ok_or_else(|| SerializationError::Range(input_type, self.descriptor()))?,
+            ),
+            TypeId::SECONDDATE => HdbValue::SECONDDATE(
+                SecondDate::checked_new(value)
+                    .ok_or_else(|| SerializationError::Range(input_type, self.descriptor()))?,
+            ),
 
             TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 => {
                 HdbValue::DECIMAL(
@@ -318,10 +330,19 @@ impl DbvFactory for &ParameterDescriptor {
             | TypeId::SHORTTEXT
             | TypeId::CLOB
             | TypeId::NCLOB
-            | TypeId::LONGDATE
-            | TypeId::SECONDDATE
-            | TypeId::DAYDATE
-            | TypeId::SECONDTIME => HdbValue::STRING(String::from(value)),
+            | TypeId::BINTEXT => HdbValue::STRING(String::from(value)),
+
+            // the server parses the string, but we reject obvious garbage upfront
+            TypeId::LONGDATE | TypeId::SECONDDATE | TypeId::DAYDATE | TypeId::SECONDTIME => {
+                if !is_plausible_temporal(value.trim(), tid) {
+                    return Err(parse_error(
+                        value,
+                        format!("{tid:?} (expected format: {})", temporal_format_hint(tid)),
+                        None,
+                    ));
+                }
+                HdbValue::STRING(String::from(value))
+            }
 
             TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 => {
                 HdbValue::DECIMAL(BigDecimal::from_str(value).map_err(map_bd)?)
@@ -331,12 +352,17 @@ impl DbvFactory for &ParameterDescriptor {
         })
     }
 
+    // serde lends the bytes only for the duration of this call, so they cannot be bound
+    // as HdbValue::BYTES and have to be copied; binary values can be bound without copying
+    // by providing HdbValue::BYTES directly, e.g. with PreparedStatement::execute_row().
     fn serialize_bytes(&self, value: &[u8]) -> Result<HdbValue<'static>, SerializationError> {
         let tid = self.type_id();
         Ok(match tid {
-            TypeId::BLOB | TypeId::BLOCATOR | TypeId::BINARY | TypeId::VARBINARY => {
-                HdbValue::BINARY((*value).to_vec())
-            }
+            TypeId::BLOB
+            | TypeId::BLOCATOR
+            | TypeId::BINARY
+            | TypeId::VARBINARY
+            | TypeId::BINTEXT => HdbValue::BINARY((*value).to_vec()),
             TypeId::GEOMETRY => HdbValue::GEOMETRY((*value).to_vec()),
             TypeId::POINT => HdbValue::POINT((*value).to_vec()),
             TypeId::NCLOB => HdbValue::STRING(
@@ -348,8 +374,9 @@ impl DbvFactory for &ParameterDescriptor {
     }
 
     fn serialize_none(&self) -> Result<HdbValue<'static>, SerializationError> {
+        let o_type_id = crate::types_impl::typed_null::take_pending_type();
         if self.is_nullable() {
-            Ok(HdbValue::NULL)
+            Ok(o_type_id.map_or(HdbValue::NULL, HdbValue::TYPED_NULL))
         } else {
             Err(type_mismatch("none", self.descriptor()))
         }
@@ -370,3 +397,99 @@ fn type_mismatch(value_type: &'static str, db_type: String) -> SerializationErro
         db_type,
     }
 }
+
+// Checks if the string has one of the shapes that HANA accepts for temporal values;
+// the ranges of the fields are checked only roughly, the exact check is done by the server.
+fn is_plausible_temporal(value: &str, tid: TypeId) -> bool {
+    if value.is_empty() {
+        return true;
+    }
+    match tid {
+        TypeId::DAYDATE => is_date(value),
+        TypeId::SECONDTIME => is_time(value),
+        _ => {
+            let value = value.strip_suffix('Z').unwrap_or(value);
+            match value.find([' ', 'T']) {
+                Some(pos) => is_date(&value[..pos]) && is_time(value[pos + 1..].trim_start()),
+                None => is_date(value),
+            }
+        }
+    }
+}
+
+// YYYY-MM-DD, YYYY/MM/DD, or YYYYMMDD
+fn is_date(value: &str) -> bool {
+    let fields: Vec<&str> = value.split(['-', '/']).collect();
+    let (year, month, day) = match fields.as_slice() {
+        [year, month, day] if year.len() == 4 && month.len() <= 2 && day.len() <= 2 => {
+            (*year, *month, *day)
+        }
+        [date] if date.len() == 8 && date.is_ascii() => (&date[..4], &date[4..6], &date[6..]),
+        _ => return false,
+    };
+    is_number_in(year, 1..=9999) && is_number_in(month, 1..=12) && is_number_in(day, 1..=31)
+}
+
+// HH:MI, HH:MI:SS, or HH:MI:SS.FFFFFFF
+fn is_time(value: &str) -> bool {
+    let (value, fraction) = value.split_once('.').unwrap_or((value, "0"));
+    let fields: Vec<&str> = value.split(':').collect();
+    let (hour, minute, second) = match fields.as_slice() {
+        [hour, minute] => (*hour, *minute, "0"),
+        [hour, minute, second] => (*hour, *minute, *second),
+        _ => return false,
+    };
+    is_number_in(hour, 0..=24)
+        && is_number_in(minute, 0..=59)
+        && is_number_in(second, 0..=59)
+        && !fraction.is_empty()
+        && fraction.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_number_in(field: &str, range: std::ops::RangeInclusive<u32>) -> bool {
+    !field.is_empty()
+        && field.len() <= 4
+        && field.bytes().all(|b| b.is_ascii_digit())
+        && u32::from_str(field).is_ok_and(|n| range.contains(&n))
+}
+
+fn temporal_format_hint(tid: TypeId) -> &'static str {
+    match tid {
+        TypeId::DAYDATE => "YYYY-MM-DD",
+        TypeId::SECONDTIME => "HH24:MI:SS",
+        TypeId::SECONDDATE => "YYYY-MM-DD HH24:MI:SS",
+        _ => "YYYY-MM-DD HH24:MI:SS.FF7",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::is_plausible_temporal;
+    use crate::TypeId;
+
+    #[test]
+    fn test_plausible_temporal() {
+        for (value, tid) in [
+            ("2024-02-29", TypeId::DAYDATE),
+            ("2024/2/9", TypeId::DAYDATE),
+            ("20240229", TypeId::DAYDATE),
+            ("23:59:59", TypeId::SECONDTIME),
+            ("8:15", TypeId::SECONDTIME),
+            ("2024-02-29 23:59:59", TypeId::SECONDDATE),
+            ("2024-02-29T23:59:59.1234567Z", TypeId::LONGDATE),
+            ("2024-02-29", TypeId::LONGDATE),
+        ] {
+            assert!(is_plausible_temporal(value, tid), "{value} as {tid:?}");
+        }
+        for (value, tid) in [
+            ("29.02.2024", TypeId::DAYDATE),
+            ("2024-13-01", TypeId::DAYDATE),
+            ("tomorrow", TypeId::DAYDATE),
+            ("23:60", TypeId::SECONDTIME),
+            ("12:00:00.", TypeId::SECONDTIME),
+            ("2024-02-29 noon", TypeId::SECONDDATE),
+        ] {
+            assert!(!is_plausible_temporal(value, tid), "{value} as {tid:?}");
+        }
+    }
+}
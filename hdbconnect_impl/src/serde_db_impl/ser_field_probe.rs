@@ -0,0 +1,252 @@
+use crate::{usage_err, HdbResult, IdentifierCase};
+use serde::ser::{Impossible, Serialize, SerializeStruct, Serializer};
+
+type ProbeError = serde::de::value::Error;
+
+// Returns the statement `INSERT INTO "<table>" ("<f1>", ...) VALUES (?, ...)`,
+// with the names of the fields as they are serialized from the given struct.
+//
+// The table and field names are converted into identifiers according to the identifier case;
+// by default, the field names are quoted verbatim, so they have to match the column names
+// exactly, like they have to when reading the table with deserialization.
+pub(crate) fn insert_statement<T: Serialize>(
+    table: &str,
+    row: &T,
+    identifier_case: IdentifierCase,
+) -> HdbResult<String> {
+    let fields = serialized_field_names(row)
+        .ok_or_else(|| usage_err!("The rows to insert into {table} must be structs"))?;
+    if fields.is_empty() {
+        return Err(usage_err!("The rows to insert into {table} have no fields"));
+    }
+    let columns = fields
+        .iter()
+        .map(|field| identifier_case.column(field))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let markers = vec!["?"; fields.len()].join(", ");
+    Ok(format!(
+        "INSERT INTO {} ({columns}) VALUES ({markers})",
+        identifier_case.table(table)
+    ))
+}
+
+// Returns the names of the fields that are serialized from the given value, if it is a struct.
+pub(crate) fn serialized_field_names<T: Serialize>(value: &T) -> Option<Vec<&'static str>> {
+    value.serialize(FieldNameProbe).ok()
+}
+
+struct FieldNameProbe;
+
+fn not_a_struct() -> ProbeError {
+    serde::ser::Error::custom("not a struct")
+}
+
+impl Serializer for FieldNameProbe {
+    type Ok = Vec<&'static str>;
+    type Error = ProbeError;
+    type SerializeSeq = Impossible<Self::Ok, Self::Error>;
+    type SerializeTuple = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleStruct = Impossible<Self::Ok, Self::Error>;
+    type SerializeTupleVariant = Impossible<Self::Ok, Self::Error>;
+    type SerializeMap = Impossible<Self::Ok, Self::Error>;
+    type SerializeStruct = FieldNames;
+    type SerializeStructVariant = Impossible<Self::Ok, Self::Error>;
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(FieldNames(Vec::with_capacity(len)))
+    }
+
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_i8(self, _v: i8) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_i16(self, _v: i16) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_i32(self, _v: i32) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_i64(self, _v: i64) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_u8(self, _v: u8) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_u16(self, _v: u16) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_u32(self, _v: u32) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_u64(self, _v: u64) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_str(self, _v: &str) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, _value: &T) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Err(not_a_struct())
+    }
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Err(not_a_struct())
+    }
+}
+
+struct FieldNames(Vec<&'static str>);
+
+impl SerializeStruct for FieldNames {
+    type Ok = Vec<&'static str>;
+    type Error = ProbeError;
+
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        _value: &T,
+    ) -> Result<(), Self::Error> {
+        self.0.push(key);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::insert_statement;
+    use crate::IdentifierCase;
+
+    #[derive(serde::Serialize)]
+    struct Entity {
+        #[serde(rename = "ID")]
+        id: u32,
+        #[serde(rename = "NAME")]
+        name: Option<String>,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Pair {
+        id: u32,
+        #[serde(rename = "\"name$\"")]
+        name: Option<String>,
+    }
+
+    #[test]
+    fn test_insert_statement() {
+        let entity = Entity { id: 1, name: None };
+        assert_eq!(
+            insert_statement("my_schema.entities", &entity, IdentifierCase::default()).unwrap(),
+            r#"INSERT INTO "MY_SCHEMA"."ENTITIES" ("ID", "NAME") VALUES (?, ?)"#
+        );
+        assert_eq!(
+            insert_statement("my_schema.entities", &entity, IdentifierCase::Verbatim).unwrap(),
+            r#"INSERT INTO "my_schema"."entities" ("ID", "NAME") VALUES (?, ?)"#
+        );
+        assert_eq!(
+            insert_statement(
+                "ENTITIES",
+                &Pair { id: 1, name: None },
+                IdentifierCase::Upper
+            )
+            .unwrap(),
+            r#"INSERT INTO "ENTITIES" ("ID", "name$") VALUES (?, ?)"#
+        );
+        // the table name cannot be used to inject SQL
+        assert_eq!(
+            insert_statement(
+                r#"entities" (ID) SELECT 1 FROM DUMMY; --"#,
+                &entity,
+                IdentifierCase::default()
+            )
+            .unwrap(),
+            r#"INSERT INTO "entities"" (ID) SELECT 1 FROM DUMMY; --" ("ID", "NAME") VALUES (?, ?)"#
+        );
+        assert!(insert_statement("ENTITIES", &(1, "a"), IdentifierCase::default()).is_err());
+        assert!(insert_statement("ENTITIES", &17, IdentifierCase::default()).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+use crate::ToHana;
+use serde::ser::Error as _;
+use std::{str::FromStr, time::Duration};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Wraps a `std::time::Duration`, helps with serializing from and deserializing into
+/// `Duration`, interpreted as time since midnight.
+///
+/// This fits to columns of type `TIME` (`SECONDTIME`), which have second precision;
+/// subseconds are ignored when serializing.
+///
+/// # Example for serialization
+/// ```rust, no_run
+/// use hdbconnect::ToHana;
+/// use std::time::Duration;
+/// # let stmt = "...";
+/// # let mut connection = hdbconnect::Connection::new("...").unwrap();
+/// let since_midnight = Duration::from_secs(8 * 3_600 + 30 * 60);
+/// let response = connection
+///     .prepare_and_execute(stmt, &(since_midnight.to_hana()))
+///     .unwrap();
+/// ```
+///
+/// # Example for deserialization
+///
+/// Deserialize into `HanaDuration`,
+/// then use `deref()` or `into_inner()` to access the contained `Duration`.
+///
+/// ```rust, no_run
+///  use hdbconnect::time::HanaDuration;
+/// # let the_query = "...";
+/// # let mut connection = hdbconnect::Connection::new("...").unwrap();
+///  let times: Vec<HanaDuration> = connection.query(the_query).unwrap().try_into().unwrap();
+///  let seconds = (*times[0]).as_secs();
+/// ```
+#[derive(Debug)]
+pub struct HanaDuration(pub Duration);
+impl HanaDuration {
+    /// Consumes the `HanaDuration`, returning the wrapped `Duration`.
+    #[must_use]
+    pub fn into_inner(self) -> Duration {
+        self.0
+    }
+}
+impl std::ops::Deref for HanaDuration {
+    type Target = Duration;
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// ***********
+// deserialize
+// ***********
+impl<'de> serde::de::Deserialize<'de> for HanaDuration {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(HanaDurationVisitor)
+    }
+}
+impl FromStr for HanaDuration {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // subsecond is optional and ignored
+        let hms = s.split('.').next().unwrap_or_default();
+        let mut seconds = 0;
+        let mut count = 0;
+        for (part, max) in hms.split(':').zip([23, 59, 59]) {
+            let value = part
+                .parse::<u64>()
+                .ok()
+                .filter(|value| *value <= max)
+                .ok_or_else(|| format!("invalid time of day: {s}"))?;
+            seconds = seconds * 60 + value;
+            count += 1;
+        }
+        if count == 3 {
+            Ok(HanaDuration(Duration::from_secs(seconds)))
+        } else {
+            Err(format!("invalid time of day: {s}"))
+        }
+    }
+}
+
+pub(in crate::serde_db_impl) struct HanaDurationVisitor;
+impl serde::de::Visitor<'_> for HanaDurationVisitor {
+    type Value = HanaDuration;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "a String in the form [hour]:[minute]:[second].[subsecond]"
+        )
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<HanaDuration, E>
+    where
+        E: serde::de::Error,
+    {
+        HanaDuration::from_str(value).map_err(E::custom)
+    }
+}
+
+/// Helper method for deserializing database values into values of type `std::time::Duration`,
+/// interpreted as time since midnight.
+///
+/// # Example
+///
+/// Use serde's annotation `serde(deserialize_with = "..")` to refer to this method:
+///
+/// ```rust
+///     use std::time::Duration;
+///     #[derive(serde::Deserialize)]
+///     struct WithTime {
+///         #[serde(deserialize_with = "hdbconnect::time::to_duration")]
+///         opening: Duration,
+///     }
+/// ```
+///
+/// Like with [`to_time`](crate::time::to_time), this cannot be applied if you want to
+/// deserialize into a `Vec<Duration>` or a plain `Duration`;
+/// deserialize then into [`HanaDuration`] and use `deref()` or `into_inner()`.
+#[allow(clippy::missing_errors_doc)]
+pub fn to_duration<'de, D>(input: D) -> Result<Duration, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    input
+        .deserialize_str(HanaDurationVisitor)
+        .map(HanaDuration::into_inner)
+}
+
+//
+// serialize
+//
+
+impl ToHana<HanaDuration> for Duration {
+    fn to_hana(self) -> HanaDuration {
+        HanaDuration(self)
+    }
+}
+
+impl serde::ser::Serialize for HanaDuration {
+    fn serialize<S: serde::ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let seconds = self.0.as_secs();
+        if seconds >= SECONDS_PER_DAY {
+            return Err(S::Error::custom(format!(
+                "duration of {seconds} seconds exceeds a day"
+            )));
+        }
+        serializer.serialize_str(&format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3_600,
+            seconds / 60 % 60,
+            seconds % 60
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::HanaDuration;
+    use std::{str::FromStr, time::Duration};
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!(
+            *HanaDuration::from_str("08:30:15").unwrap(),
+            Duration::from_secs(30_615)
+        );
+        assert_eq!(
+            *HanaDuration::from_str("23:59:59.999").unwrap(),
+            Duration::from_secs(86_399)
+        );
+        assert!(HanaDuration::from_str("24:00:00").is_err());
+        assert!(HanaDuration::from_str("08:30").is_err());
+    }
+}
@@ -1,11 +1,14 @@
-//! Support for serializing from or deserializing into types of the `time` crate.
+//! Support for serializing from or deserializing into types of the `time` crate,
+//! and into `std::time::Duration`.
 
 mod hana_date;
+mod hana_duration;
 mod hana_offset_date_time;
 mod hana_primitive_date_time;
 mod hana_time;
 
 pub use hana_date::{to_date, HanaDate};
+pub use hana_duration::{to_duration, HanaDuration};
 pub use hana_offset_date_time::{to_offset_date_time, HanaOffsetDateTime};
 pub use hana_primitive_date_time::{to_primitive_date_time, HanaPrimitiveDateTime};
 pub use hana_time::{to_time, HanaTime};
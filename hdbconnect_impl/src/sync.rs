@@ -1,17 +1,27 @@
 mod blob;
+mod bulk_loader;
+mod chunked_dml;
 mod clob;
 mod connection;
+mod db_connection;
 mod hdb_response;
 mod hdb_return_value;
+#[cfg(feature = "mock")]
+mod mock;
 mod nclob;
 mod prepared_statement;
 mod result_set;
 
 pub use blob::BLob;
+pub use bulk_loader::BulkLoader;
+pub use chunked_dml::ChunkedDml;
 pub use clob::CLob;
-pub use connection::Connection;
-pub use hdb_response::HdbResponse;
+pub use connection::{Connection, ConnectionGuard};
+pub use db_connection::{DbConnection, DbPreparedStatement};
+pub use hdb_response::{HdbResponse, HdbResponseParts};
 pub use hdb_return_value::HdbReturnValue;
+#[cfg(feature = "mock")]
+pub use mock::{MockConnection, MockPreparedStatement, MockResponse};
 pub use nclob::NCLob;
 pub use prepared_statement::PreparedStatement;
 pub use result_set::ResultSet;
@@ -0,0 +1,182 @@
+use crate::{
+    sync::{Connection, HdbResponse, PreparedStatement, ResultSet},
+    AffectedRowCount, GenericConnection, GenericPreparedStatement, HdbResult,
+};
+use serde::{de::DeserializeOwned, ser::Serialize};
+use std::future::{ready, Future};
+
+/// The basic operations of a [`Connection`].
+///
+/// Application code that is written against this trait rather than against `Connection`
+/// can be unit-tested without a database, by using `MockConnection`
+/// (available with the feature `mock`).
+///
+/// Each implementation also implements [`GenericConnection`], the abstraction over
+/// the synchronous and the asynchronous driver.
+pub trait DbConnection {
+    /// The type of the prepared statements.
+    type PreparedStatement: DbPreparedStatement;
+
+    /// Executes a statement and expects a single `ResultSet`.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn query(&self, stmt: &str) -> HdbResult<ResultSet>;
+
+    /// Executes a statement and expects a single number of affected rows.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn dml(&self, stmt: &str) -> HdbResult<AffectedRowCount>;
+
+    /// Executes a statement and expects a plain success.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn exec(&self, stmt: &str) -> HdbResult<()>;
+
+    /// Prepares a statement.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn prepare(&self, stmt: &str) -> HdbResult<Self::PreparedStatement>;
+
+    /// Commits the current transaction.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn commit(&self) -> HdbResult<()>;
+
+    /// Rolls back the current transaction.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn rollback(&self) -> HdbResult<()>;
+}
+
+/// The basic operations of a [`PreparedStatement`].
+pub trait DbPreparedStatement {
+    /// Executes the statement with the given input.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn execute<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<HdbResponse>;
+
+    /// Adds the given input to the batch.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn add_batch<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<()>;
+
+    /// Executes the statement with the collected batch.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    fn execute_batch(&mut self) -> HdbResult<HdbResponse>;
+}
+
+impl DbConnection for Connection {
+    type PreparedStatement = PreparedStatement;
+
+    fn query(&self, stmt: &str) -> HdbResult<ResultSet> {
+        Connection::query(self, stmt)
+    }
+    fn dml(&self, stmt: &str) -> HdbResult<AffectedRowCount> {
+        Connection::dml(self, stmt)
+    }
+    fn exec(&self, stmt: &str) -> HdbResult<()> {
+        Connection::exec(self, stmt)
+    }
+    fn prepare(&self, stmt: &str) -> HdbResult<PreparedStatement> {
+        Connection::prepare(self, stmt)
+    }
+    fn commit(&self) -> HdbResult<()> {
+        Connection::commit(self)
+    }
+    fn rollback(&self) -> HdbResult<()> {
+        Connection::rollback(self)
+    }
+}
+
+impl DbPreparedStatement for PreparedStatement {
+    fn execute<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<HdbResponse> {
+        PreparedStatement::execute(self, input)
+    }
+    fn add_batch<T: serde::ser::Serialize>(&mut self, input: &T) -> HdbResult<()> {
+        PreparedStatement::add_batch(self, input)
+    }
+    fn execute_batch(&mut self) -> HdbResult<HdbResponse> {
+        PreparedStatement::execute_batch(self)
+    }
+}
+
+// The synchronous operations are done when the methods are called.
+impl<C: DbConnection + Sync> GenericConnection for C
+where
+    C::PreparedStatement: Send,
+{
+    type PreparedStatement = C::PreparedStatement;
+
+    fn query_into<T>(&self, stmt: &str) -> impl Future<Output = HdbResult<T>> + Send
+    where
+        T: DeserializeOwned + Send,
+    {
+        ready(DbConnection::query(self, stmt).and_then(ResultSet::try_into))
+    }
+    fn dml(&self, stmt: &str) -> impl Future<Output = HdbResult<AffectedRowCount>> + Send {
+        ready(DbConnection::dml(self, stmt))
+    }
+    fn exec(&self, stmt: &str) -> impl Future<Output = HdbResult<()>> + Send {
+        ready(DbConnection::exec(self, stmt))
+    }
+    fn prepare(
+        &self,
+        stmt: &str,
+    ) -> impl Future<Output = HdbResult<Self::PreparedStatement>> + Send {
+        ready(DbConnection::prepare(self, stmt))
+    }
+    fn commit(&self) -> impl Future<Output = HdbResult<()>> + Send {
+        ready(DbConnection::commit(self))
+    }
+    fn rollback(&self) -> impl Future<Output = HdbResult<()>> + Send {
+        ready(DbConnection::rollback(self))
+    }
+}
+
+impl<P: DbPreparedStatement> GenericPreparedStatement for P {
+    fn query_into<I, T>(&mut self, input: &I) -> impl Future<Output = HdbResult<T>> + Send
+    where
+        I: Serialize + Sync,
+        T: DeserializeOwned + Send,
+    {
+        ready(
+            DbPreparedStatement::execute(self, input)
+                .and_then(HdbResponse::into_result_set)
+                .and_then(ResultSet::try_into),
+        )
+    }
+    fn dml<I>(&mut self, input: &I) -> impl Future<Output = HdbResult<AffectedRowCount>> + Send
+    where
+        I: Serialize + Sync,
+    {
+        ready(
+            DbPreparedStatement::execute(self, input)
+                .and_then(HdbResponse::into_affected_row_count),
+        )
+    }
+    fn add_batch<I: Serialize>(&mut self, input: &I) -> HdbResult<()> {
+        DbPreparedStatement::add_batch(self, input)
+    }
+    fn execute_batch(&mut self) -> impl Future<Output = HdbResult<Vec<AffectedRowCount>>> + Send {
+        ready(DbPreparedStatement::execute_batch(self).and_then(HdbResponse::into_affected_rows))
+    }
+}
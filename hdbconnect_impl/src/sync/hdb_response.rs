@@ -1,12 +1,13 @@
 use crate::{
     base::InternalReturnValue,
+    conn::ConnectionCore,
     impl_err,
     protocol::{
-        parts::{ExecutionResult, OutputParameters},
+        parts::{AffectedRowCount, ExecutionResult, OutputParameters},
         ReplyType,
     },
     sync::{HdbReturnValue, ResultSet},
-    usage_err, HdbError, HdbResult,
+    usage_err, HdbError, HdbResult, ServerError, StatementMetrics,
 };
 
 /// Represents all possible non-error responses to a database command.
@@ -73,6 +74,27 @@ use crate::{
 pub struct HdbResponse {
     /// The return values: Result sets, output parameters, etc.
     return_values: Vec<HdbReturnValue>,
+    // Warnings that the server sent with the response
+    warnings: Vec<ServerError>,
+    // Resource consumption that the server reported with the response
+    statement_metrics: StatementMetrics,
+}
+
+/// The content of an [`HdbResponse`], sorted by kind.
+///
+/// Is produced by [`HdbResponse::into_parts`] and simplifies the evaluation of responses
+/// to procedure calls, which can contain a mix of different return values.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct HdbResponseParts {
+    /// The result sets, in the order in which they were returned.
+    pub result_sets: Vec<ResultSet>,
+    /// The output parameters, if any were returned.
+    pub output_parameters: Option<OutputParameters>,
+    /// The lists of affected-rows counters, in the order in which they were returned.
+    pub affected_rows: Vec<Vec<AffectedRowCount>>,
+    /// The warnings that the server sent with the response.
+    pub warnings: Vec<ServerError>,
 }
 
 impl HdbResponse {
@@ -136,6 +158,8 @@ impl HdbResponse {
         match single(int_return_values)? {
             InternalReturnValue::RsState((rs_state, a_rsmd)) => Ok(Self {
                 return_values: vec![HdbReturnValue::ResultSet(ResultSet::new(a_rsmd, rs_state))],
+                warnings: Vec::new(),
+                statement_metrics: StatementMetrics::default(),
             }),
             _ => Err(impl_err!(
                 "Wrong InternalReturnValue, a single ResultSet was expected",
@@ -146,11 +170,13 @@ impl HdbResponse {
     fn rows_affected(int_return_values: Vec<InternalReturnValue>) -> HdbResult<Self> {
         match single(int_return_values)? {
             InternalReturnValue::ExecutionResults(execution_results) => {
-                let mut vec_i = Vec::<usize>::new();
+                let mut vec_i = Vec::<AffectedRowCount>::new();
                 for er in execution_results {
                     match er {
-                        ExecutionResult::RowsAffected(i) => vec_i.push(i),
-                        ExecutionResult::SuccessNoInfo => vec_i.push(0),
+                        ExecutionResult::RowsAffected(i) => vec_i.push(AffectedRowCount::Count(i)),
+                        ExecutionResult::SuccessNoInfo => {
+                            vec_i.push(AffectedRowCount::SuccessNoInfo);
+                        }
                         ExecutionResult::Failure(_) => {
                             return Err(impl_err!("Found unexpected ExecutionResult::Failure",));
                         }
@@ -159,6 +185,8 @@ impl HdbResponse {
                 }
                 Ok(Self {
                     return_values: vec![HdbReturnValue::AffectedRows(vec_i)],
+                    warnings: Vec::new(),
+                    statement_metrics: StatementMetrics::default(),
                 })
             }
             _ => Err(impl_err!(
@@ -181,11 +209,15 @@ impl HdbResponse {
                             } else {
                                 Ok(Self {
                                     return_values: vec![HdbReturnValue::Success],
+                                    warnings: Vec::new(),
+                                    statement_metrics: StatementMetrics::default(),
                                 })
                             }
                         }
                         ExecutionResult::SuccessNoInfo => Ok(Self {
                             return_values: vec![HdbReturnValue::Success],
+                            warnings: Vec::new(),
+                            statement_metrics: StatementMetrics::default(),
                         }),
                         ExecutionResult::Failure(_) => {
                             Err(impl_err!("Found unexpected returnvalue ExecutionFailed",))
@@ -208,11 +240,15 @@ impl HdbResponse {
         for irv in int_return_values {
             match irv {
                 InternalReturnValue::ExecutionResults(execution_results) => {
-                    let mut vec_i = Vec::<usize>::new();
+                    let mut vec_i = Vec::<AffectedRowCount>::new();
                     for er in execution_results {
                         match er {
-                            ExecutionResult::RowsAffected(i) => vec_i.push(i),
-                            ExecutionResult::SuccessNoInfo => vec_i.push(0),
+                            ExecutionResult::RowsAffected(i) => {
+                                vec_i.push(AffectedRowCount::Count(i));
+                            }
+                            ExecutionResult::SuccessNoInfo => {
+                                vec_i.push(AffectedRowCount::SuccessNoInfo);
+                            }
                             ExecutionResult::Failure(_) => {
                                 return Err(impl_err!(
                                     "Found unexpected returnvalue 'ExecutionFailed'",
@@ -237,7 +273,97 @@ impl HdbResponse {
                 }
             }
         }
-        Ok(Self { return_values })
+        Ok(Self {
+            return_values,
+            warnings: Vec::new(),
+            statement_metrics: StatementMetrics::default(),
+        })
+    }
+
+    #[cfg(feature = "mock")]
+    pub(crate) fn new(return_values: Vec<HdbReturnValue>) -> Self {
+        Self {
+            return_values,
+            warnings: Vec::new(),
+            statement_metrics: StatementMetrics::default(),
+        }
+    }
+
+    // The total number of affected rows, if the response contains such counts.
+    pub(crate) fn affected_rows_count(&self) -> Option<AffectedRowCount> {
+        let mut o_count = None;
+        for rv in &self.return_values {
+            if let HdbReturnValue::AffectedRows(counts) = rv {
+                let count = o_count.get_or_insert(AffectedRowCount::Count(0));
+                *count = std::iter::once(*count).chain(counts.iter().copied()).sum();
+            }
+        }
+        o_count
+    }
+
+    // Takes the warnings and metrics that the connection received with the last reply.
+    pub(crate) fn with_server_feedback(mut self, conn_core: &ConnectionCore) -> Self {
+        self.warnings.clone_from(&conn_core.warnings);
+        self.statement_metrics = conn_core.statement_metrics;
+        self
+    }
+
+    // Adds the response to a further chunk of the same batch;
+    // the affected-rows counters are appended to the ones of this response.
+    pub(crate) fn append_batch_response(&mut self, other: HdbResponse) {
+        for return_value in other.return_values {
+            if let HdbReturnValue::AffectedRows(counts) = return_value {
+                if let Some(HdbReturnValue::AffectedRows(merged)) = self
+                    .return_values
+                    .iter_mut()
+                    .find(|rv| matches!(rv, HdbReturnValue::AffectedRows(_)))
+                {
+                    merged.extend(counts);
+                } else {
+                    self.return_values
+                        .push(HdbReturnValue::AffectedRows(counts));
+                }
+            } else {
+                self.return_values.push(return_value);
+            }
+        }
+        self.warnings.extend(other.warnings);
+        self.statement_metrics = other.statement_metrics;
+    }
+
+    /// Splits the response into its result sets, output parameters,
+    /// affected-rows counters, and warnings.
+    ///
+    /// Explicit success indications are dropped, as they carry no further information.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Evaluation` if the response contains more than one set of output parameters.
+    pub fn into_parts(self) -> HdbResult<HdbResponseParts> {
+        let mut parts = HdbResponseParts {
+            result_sets: Vec::new(),
+            output_parameters: None,
+            affected_rows: Vec::new(),
+            warnings: self.warnings,
+        };
+        for return_value in self.return_values {
+            match return_value {
+                HdbReturnValue::ResultSet(rs) => parts.result_sets.push(rs),
+                HdbReturnValue::AffectedRows(vec_i) => parts.affected_rows.push(vec_i),
+                HdbReturnValue::OutputParameters(op) => {
+                    if parts.output_parameters.is_some() {
+                        return Err(HdbError::Evaluation("More than one OutputParameters"));
+                    }
+                    parts.output_parameters = Some(op);
+                }
+                HdbReturnValue::Success => {}
+                #[cfg(feature = "dist_tx")]
+                HdbReturnValue::XaTransactionIds(_) => {
+                    return Err(HdbError::Evaluation("Unexpected XaTransactionIds"));
+                }
+            }
+        }
+        Ok(parts)
     }
 
     /// Returns the number of return values.
@@ -246,6 +372,28 @@ impl HdbResponse {
         self.return_values.len()
     }
 
+    /// Returns the warnings that the server sent with the response,
+    /// like implicit conversions or "not all rows affected".
+    ///
+    /// The warnings are retained until the response is consumed,
+    /// so they can be recorded together with the results.
+    #[must_use]
+    pub fn warnings(&self) -> &[ServerError] {
+        &self.warnings
+    }
+
+    /// Returns `true` if the server sent warnings with the response.
+    #[must_use]
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Returns the resource consumption that the server reported for the execution.
+    #[must_use]
+    pub fn statement_metrics(&self) -> StatementMetrics {
+        self.statement_metrics
+    }
+
     /// Turns itself into a single result set.
     ///
     /// # Errors
@@ -261,10 +409,18 @@ impl HdbResponse {
     /// # Errors
     ///
     /// `HdbError::Evaluation` if information would get lost.
-    pub fn into_affected_rows(self) -> HdbResult<Vec<usize>> {
+    pub fn into_affected_rows(self) -> HdbResult<Vec<AffectedRowCount>> {
         self.into_single_retval()?.into_affected_rows()
     }
 
+    // For DML statements, which report a single number of affected rows.
+    pub(crate) fn into_affected_row_count(self) -> HdbResult<AffectedRowCount> {
+        match self.into_affected_rows()?.as_slice() {
+            [count] => Ok(*count),
+            _ => Err(usage_err!("number of affected-rows-counts <> 1")),
+        }
+    }
+
     /// Turns itself into a Vector of numbers (each number representing a
     /// number of affected rows).
     ///
@@ -346,7 +502,7 @@ impl HdbResponse {
     /// # Errors
     ///
     /// `HdbError` if there is no further set of affected rows counters.
-    pub fn get_affected_rows(&mut self) -> HdbResult<Vec<usize>> {
+    pub fn get_affected_rows(&mut self) -> HdbResult<Vec<AffectedRowCount>> {
         if let Some(i) = self.find_affected_rows() {
             self.return_values.remove(i).into_affected_rows()
         } else {
@@ -454,3 +610,51 @@ impl std::fmt::Display for HdbResponse {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::HdbResponse;
+    use crate::{
+        base::InternalReturnValue,
+        protocol::{parts::ExecutionResults, ReplyType},
+        AffectedRowCount,
+    };
+
+    fn response(code: i32) -> HdbResponse {
+        let bytes = code.to_le_bytes().to_vec();
+        let ers = ExecutionResults::parse(1, &mut std::io::Cursor::new(bytes)).unwrap();
+        HdbResponse::try_new(
+            vec![InternalReturnValue::ExecutionResults(ers)],
+            ReplyType::Update,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_affected_row_count() {
+        let count = response(5).into_affected_row_count().unwrap();
+        assert_eq!(count, AffectedRowCount::Count(5));
+        assert_eq!(count, 5);
+
+        let count = response(-2).into_affected_row_count().unwrap();
+        assert_eq!(count, AffectedRowCount::SuccessNoInfo);
+        assert_eq!(count.count(), None);
+        assert_ne!(count, 0);
+    }
+
+    #[test]
+    fn test_append_batch_response() {
+        let mut merged = response(1);
+        merged.append_batch_response(response(1));
+        merged.append_batch_response(response(-2));
+        assert_eq!(merged.count(), 1);
+        assert_eq!(
+            merged.into_affected_rows().unwrap(),
+            vec![
+                AffectedRowCount::Count(1),
+                AffectedRowCount::Count(1),
+                AffectedRowCount::SuccessNoInfo
+            ]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::protocol::parts::OutputParameters;
+use crate::protocol::parts::{AffectedRowCount, OutputParameters};
 use crate::{HdbError, HdbResult};
 
 #[cfg(feature = "dist_tx")]
@@ -10,7 +10,7 @@ pub enum HdbReturnValue {
     /// A result set of a query.
     ResultSet(crate::sync::ResultSet),
     /// A list of numbers of affected rows.
-    AffectedRows(Vec<usize>),
+    AffectedRows(Vec<AffectedRowCount>),
     /// Values of output parameters of a procedure call.
     OutputParameters(OutputParameters),
     /// Indication that a db call was successful.
@@ -38,7 +38,7 @@ impl HdbReturnValue {
     /// # Errors
     ///
     /// `HdbError::Evaluation` for other variants than `HdbReturnValue::AffectedRows`.
-    pub fn into_affected_rows(self) -> HdbResult<Vec<usize>> {
+    pub fn into_affected_rows(self) -> HdbResult<Vec<AffectedRowCount>> {
         match self {
             Self::AffectedRows(array) => Ok(array),
             _ => Err(HdbError::Evaluation("Not a HdbReturnValue::AffectedRows")),
@@ -88,7 +88,10 @@ impl HdbReturnValue {
     pub fn is_success(&self) -> bool {
         match *self {
             Self::Success => true,
-            Self::AffectedRows(ref vec) => vec.len() == 1 && vec.first() == Some(&0),
+            Self::AffectedRows(ref vec) => matches!(
+                vec.as_slice(),
+                [AffectedRowCount::Count(0) | AffectedRowCount::SuccessNoInfo]
+            ),
             _ => false,
         }
     }
@@ -0,0 +1,310 @@
+use crate::{
+    protocol::parts::Severity,
+    sync::{DbConnection, DbPreparedStatement, HdbResponse, HdbReturnValue, ResultSet},
+    usage_err, AffectedRowCount, HdbError, HdbResult, ResultSetBuilder, ServerError,
+};
+use std::sync::{Arc, Mutex};
+
+/// A canned response of a [`MockConnection`].
+#[derive(Clone, Debug)]
+pub enum MockResponse {
+    /// A result set, built from literal data.
+    ResultSet(ResultSetBuilder),
+    /// A number of affected rows.
+    AffectedRows(u64),
+    /// A plain success.
+    Success,
+    /// An error, as if the server had responded with the given error code and text.
+    Error {
+        /// The error code.
+        code: i32,
+        /// The error text.
+        text: String,
+    },
+}
+
+/// An implementation of [`DbConnection`] that serves canned responses, without a database.
+///
+/// Responses are registered per statement text. The statements that are executed
+/// (also via prepared statements, and including `COMMIT` and `ROLLBACK`)
+/// are recorded and can be inspected with [`MockConnection::executed_statements`].
+/// Executing a statement for which no response was registered fails with `HdbError::Usage`.
+///
+/// ```rust
+/// use hdbconnect::{DbConnection, HdbResult, HdbValue, MockConnection, MockResponse};
+/// use hdbconnect::{ResultSetBuilder, TypeId};
+///
+/// // the application code under test
+/// fn count_users<C: DbConnection>(conn: &C) -> HdbResult<i64> {
+///     conn.query("SELECT COUNT(*) FROM USERS")?.try_into()
+/// }
+///
+/// let conn = MockConnection::new().with_response(
+///     "SELECT COUNT(*) FROM USERS",
+///     MockResponse::ResultSet(
+///         ResultSetBuilder::new()
+///             .column("COUNT(*)", TypeId::BIGINT)
+///             .row(vec![HdbValue::BIGINT(42)]),
+///     ),
+/// );
+/// assert_eq!(count_users(&conn).unwrap(), 42);
+/// assert_eq!(conn.executed_statements(), vec!["SELECT COUNT(*) FROM USERS"]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MockConnection {
+    responses: Arc<Vec<(String, MockResponse)>>,
+    executed: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockConnection {
+    /// Creates a new instance without responses.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the response for the given statement.
+    ///
+    /// The response is returned whenever the statement is executed.
+    /// A later registration for the same statement replaces the earlier one.
+    #[must_use]
+    pub fn with_response<S: AsRef<str>>(mut self, stmt: S, response: MockResponse) -> Self {
+        let stmt = stmt.as_ref().trim().to_string();
+        let responses = Arc::make_mut(&mut self.responses);
+        responses.retain(|(s, _)| *s != stmt);
+        responses.push((stmt, response));
+        self
+    }
+
+    /// Returns the statements that were executed so far, in their order.
+    #[must_use]
+    pub fn executed_statements(&self) -> Vec<String> {
+        self.executed
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .clone()
+    }
+
+    // Records the execution of the statement and returns its response.
+    fn respond(&self, stmt: &str) -> HdbResult<MockResponse> {
+        let stmt = stmt.trim();
+        self.executed
+            .lock()
+            .map_err(|_| HdbError::Poison)?
+            .push(stmt.to_string());
+        match self.responses.iter().find(|(s, _)| s == stmt) {
+            Some((_, MockResponse::Error { code, text })) => Err(HdbError::from(ServerError::new(
+                *code,
+                0,
+                Severity::Error,
+                b"HY000".to_vec(),
+                text.clone(),
+            ))),
+            Some((_, response)) => Ok(response.clone()),
+            None => Err(usage_err!(
+                "MockConnection: no response registered for \"{stmt}\""
+            )),
+        }
+    }
+}
+
+impl DbConnection for MockConnection {
+    type PreparedStatement = MockPreparedStatement;
+
+    fn query(&self, stmt: &str) -> HdbResult<ResultSet> {
+        match self.respond(stmt)? {
+            MockResponse::ResultSet(builder) => ResultSet::try_from(builder),
+            _ => Err(usage_err!(
+                "MockConnection: the response for \"{stmt}\" is not a result set"
+            )),
+        }
+    }
+
+    fn dml(&self, stmt: &str) -> HdbResult<AffectedRowCount> {
+        match self.respond(stmt)? {
+            MockResponse::AffectedRows(count) => Ok(AffectedRowCount::Count(count)),
+            _ => Err(usage_err!(
+                "MockConnection: the response for \"{stmt}\" is not a number of affected rows"
+            )),
+        }
+    }
+
+    fn exec(&self, stmt: &str) -> HdbResult<()> {
+        match self.respond(stmt)? {
+            MockResponse::Success => Ok(()),
+            _ => Err(usage_err!(
+                "MockConnection: the response for \"{stmt}\" is not a success"
+            )),
+        }
+    }
+
+    fn prepare(&self, stmt: &str) -> HdbResult<MockPreparedStatement> {
+        Ok(MockPreparedStatement {
+            conn: self.clone(),
+            stmt: stmt.to_string(),
+            batch_size: 0,
+        })
+    }
+
+    fn commit(&self) -> HdbResult<()> {
+        self.respond("COMMIT").or_else(ignore_missing).map(|_| ())
+    }
+
+    fn rollback(&self) -> HdbResult<()> {
+        self.respond("ROLLBACK").or_else(ignore_missing).map(|_| ())
+    }
+}
+
+// Commit and rollback succeed also if no response was registered.
+fn ignore_missing(e: HdbError) -> HdbResult<MockResponse> {
+    match e {
+        HdbError::Usage(_) => Ok(MockResponse::Success),
+        e => Err(e),
+    }
+}
+
+/// The prepared statement of a [`MockConnection`].
+///
+/// Each execution returns the response that is registered for the statement text.
+/// The input parameters are not evaluated.
+#[derive(Debug)]
+pub struct MockPreparedStatement {
+    conn: MockConnection,
+    stmt: String,
+    batch_size: usize,
+}
+
+impl MockPreparedStatement {
+    fn respond(&self, count: usize) -> HdbResult<HdbResponse> {
+        let return_value = match self.conn.respond(&self.stmt)? {
+            MockResponse::ResultSet(builder) => {
+                HdbReturnValue::ResultSet(ResultSet::try_from(builder)?)
+            }
+            MockResponse::AffectedRows(affected) => {
+                HdbReturnValue::AffectedRows(vec![AffectedRowCount::Count(affected); count])
+            }
+            // errors are already returned by respond()
+            MockResponse::Success | MockResponse::Error { .. } => HdbReturnValue::Success,
+        };
+        Ok(HdbResponse::new(vec![return_value]))
+    }
+}
+
+impl DbPreparedStatement for MockPreparedStatement {
+    fn execute<T: serde::ser::Serialize>(&mut self, _input: &T) -> HdbResult<HdbResponse> {
+        self.respond(1)
+    }
+
+    fn add_batch<T: serde::ser::Serialize>(&mut self, _input: &T) -> HdbResult<()> {
+        self.batch_size += 1;
+        Ok(())
+    }
+
+    fn execute_batch(&mut self) -> HdbResult<HdbResponse> {
+        if self.batch_size == 0 {
+            return Err(usage_err!("The batch is empty and cannot be executed"));
+        }
+        let count = std::mem::take(&mut self.batch_size);
+        self.respond(count)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MockConnection, MockResponse};
+    use crate::{
+        sync::{DbConnection, DbPreparedStatement},
+        AffectedRowCount, HdbError, HdbValue, ResultSetBuilder, TypeId,
+    };
+
+    #[test]
+    fn test_mock_connection() {
+        let conn = MockConnection::new()
+            .with_response(
+                "select ID from T",
+                MockResponse::ResultSet(
+                    ResultSetBuilder::new()
+                        .column("ID", TypeId::INT)
+                        .row(vec![HdbValue::INT(1)])
+                        .row(vec![HdbValue::INT(2)]),
+                ),
+            )
+            .with_response("delete from T", MockResponse::AffectedRows(2))
+            .with_response("insert into T values(?)", MockResponse::AffectedRows(1))
+            .with_response(
+                "drop table T",
+                MockResponse::Error {
+                    code: 259,
+                    text: "invalid table name".to_string(),
+                },
+            );
+
+        let ids: Vec<i32> = conn.query("select ID from T").unwrap().try_into().unwrap();
+        assert_eq!(ids, vec![1, 2]);
+        assert_eq!(conn.dml("delete from T").unwrap(), 2);
+        match conn.exec("drop table T") {
+            Err(HdbError::DbError { source }) => assert_eq!(source.code(), 259),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert!(conn.exec("unknown").is_err());
+
+        let mut stmt = conn.prepare("insert into T values(?)").unwrap();
+        stmt.add_batch(&1).unwrap();
+        stmt.add_batch(&2).unwrap();
+        let affected = stmt.execute_batch().unwrap().into_affected_rows().unwrap();
+        assert_eq!(affected, vec![1, 1]);
+        conn.commit().unwrap();
+
+        assert_eq!(
+            conn.executed_statements(),
+            vec![
+                "select ID from T",
+                "delete from T",
+                "drop table T",
+                "unknown",
+                "insert into T values(?)",
+                "COMMIT"
+            ]
+        );
+    }
+
+    // Library code that is written once for both driver variants.
+    #[cfg(feature = "async")]
+    #[test]
+    fn test_generic_connection() {
+        use crate::{GenericConnection, GenericPreparedStatement, HdbResult};
+
+        async fn load<C: GenericConnection>(
+            conn: &C,
+        ) -> HdbResult<(Vec<i32>, Vec<AffectedRowCount>)> {
+            let ids: Vec<i32> = conn.query_into("select ID from T").await?;
+            let mut stmt = conn.prepare("insert into T values(?)").await?;
+            stmt.add_batch(&1)?;
+            stmt.add_batch(&2)?;
+            let affected = stmt.execute_batch().await?;
+            conn.commit().await?;
+            Ok((ids, affected))
+        }
+
+        let conn = MockConnection::new()
+            .with_response(
+                "select ID from T",
+                MockResponse::ResultSet(
+                    ResultSetBuilder::new()
+                        .column("ID", TypeId::INT)
+                        .row(vec![HdbValue::INT(1)]),
+                ),
+            )
+            .with_response("insert into T values(?)", MockResponse::AffectedRows(1));
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        assert_eq!(
+            runtime.block_on(load(&conn)).unwrap(),
+            (vec![1], vec![AffectedRowCount::Count(1); 2])
+        );
+        assert!(runtime
+            .block_on(GenericConnection::exec(&conn, "unknown"))
+            .is_err());
+    }
+}
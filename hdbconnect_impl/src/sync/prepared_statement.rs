@@ -1,16 +1,18 @@
 use crate::{
     base::{new_am_sync, InternalReturnValue, PreparedStatementCore, AM},
-    conn::AmConnCore,
+    conn::{
+        check_statement, AmConnCore, CommandOptions, CursorHoldability, CURRENT_IDENTITY_VALUE,
+    },
     impl_err,
     protocol::{
         parts::{
             HdbValue, LobFlags, ParameterDescriptors, ParameterRows, ResultSetMetadata, TypeId,
         },
-        MessageType, Part, PartKind, Request, ServerUsage,
+        MessageType, Part, PartKind, Request, ServerUsage, MAX_SEGMENTS,
     },
     sync::HdbResponse,
     types_impl::lob::SyncLobWriter,
-    usage_err, ConnectionConfiguration, HdbResult,
+    usage_err, ConnectionConfiguration, HdbError, HdbResult, QueryOptions,
 };
 use std::{io::Write, sync::Arc};
 
@@ -76,6 +78,8 @@ use std::{io::Write, sync::Arc};
 pub struct PreparedStatement {
     am_ps_core: AM<PreparedStatementCore>,
     config: ConnectionConfiguration,
+    sql: String,
+    statement_id: u64,
     server_usage: ServerUsage,
     a_descriptors: Arc<ParameterDescriptors>,
     o_a_rsmd: Option<Arc<ResultSetMetadata>>,
@@ -136,9 +140,34 @@ impl<'a> PreparedStatement {
         if self.a_descriptors.has_in() {
             let mut par_rows = ParameterRows::new();
             par_rows.push(input, &self.a_descriptors)?;
-            return self.execute_parameter_rows(Some(par_rows));
+            return self.execute_parameter_rows(Some(par_rows), None);
         }
-        self.execute_parameter_rows(None)
+        self.execute_parameter_rows(None, None)
+    }
+
+    /// Executes the statement like
+    /// [`PreparedStatement::execute()`](crate::PreparedStatement::execute),
+    /// using the given options rather than the connection's configuration.
+    ///
+    /// See [`QueryOptions`](crate::QueryOptions) for the scope of the single options.
+    ///
+    /// # Errors
+    ///
+    /// Several variants of `HdbError` can occur.
+    pub fn execute_with<T: serde::ser::Serialize>(
+        &mut self,
+        input: &T,
+        options: QueryOptions,
+    ) -> HdbResult<HdbResponse> {
+        trace!("PreparedStatement::execute_with()");
+        let o_rows = if self.a_descriptors.has_in() {
+            let mut par_rows = ParameterRows::new();
+            par_rows.push(input, &self.a_descriptors)?;
+            Some(par_rows)
+        } else {
+            None
+        };
+        self.execute_parameter_rows(o_rows, Some(options))
     }
 
     /// Consumes the given `HdbValue`s as a row of parameters for immediate execution.
@@ -190,8 +219,23 @@ impl<'a> PreparedStatement {
     ///
     /// Several variants of `HdbError` can occur.
     pub fn execute_row(&'a mut self, hdb_values: Vec<HdbValue<'a>>) -> HdbResult<HdbResponse> {
+        let o_error_context = self.error_context(Some(1));
+        #[cfg(feature = "otel")]
+        let span = self.statement_span()?;
+        #[cfg(feature = "otel")]
+        let _entered = span.enter();
+        let result = self
+            .execute_row_impl(hdb_values)
+            .map_err(|e| Self::add_error_context(e, o_error_context));
+        #[cfg(feature = "otel")]
+        crate::conn::otel::record_outcome(&span, &result, HdbResponse::affected_rows_count);
+        result
+    }
+
+    fn execute_row_impl(&'a mut self, hdb_values: Vec<HdbValue<'a>>) -> HdbResult<HdbResponse> {
         if self.a_descriptors.has_in() {
             let ps_core_guard = self.am_ps_core.lock_sync()?;
+            self.check_statement(&ps_core_guard.am_conn_core)?;
             let mut request = Request::new(MessageType::Execute, self.config.command_options());
 
             request.push(Part::StatementId(ps_core_guard.statement_id));
@@ -240,9 +284,11 @@ impl<'a> PreparedStatement {
                 .remove_first_of_kind(PartKind::WriteLobReply);
 
             let (mut internal_return_values, replytype) = (
-                main_reply
-                    .parts
-                    .into_internal_return_values_sync(&ps_core_guard.am_conn_core, None)?,
+                main_reply.parts.into_internal_return_values_sync(
+                    &ps_core_guard.am_conn_core,
+                    None,
+                    None,
+                )?,
                 main_reply.replytype,
             );
 
@@ -286,9 +332,11 @@ impl<'a> PreparedStatement {
                     rs_state.inject_ps_core_sync(Arc::clone(&self.am_ps_core))?;
                 }
             }
-            HdbResponse::try_new(internal_return_values, replytype)
+            let response = HdbResponse::try_new(internal_return_values, replytype)?;
+            let conn_core = ps_core_guard.am_conn_core.lock_sync()?;
+            Ok(response.with_server_feedback(&conn_core))
         } else {
-            self.execute_parameter_rows(None)
+            self.execute_parameter_rows(None, None)
         }
     }
 
@@ -331,6 +379,11 @@ impl<'a> PreparedStatement {
         ))
     }
 
+    // Discards the rows that were added to the batch.
+    pub(crate) fn clear_batch(&mut self) {
+        self.batch = ParameterRows::new();
+    }
+
     /// Returns the number of parameter rows that were already added to the batch.
     #[must_use]
     pub fn current_batch_size(&self) -> usize {
@@ -344,6 +397,10 @@ impl<'a> PreparedStatement {
     /// If the statement does not need input and the batch is empty,
     /// a single execution is triggered.
     ///
+    /// A batch that exceeds the number of rows the server accepts with a single request,
+    /// or the maximum request size, is split and executed with several roundtrips,
+    /// whose responses are merged. With auto-commit, each of them is committed on its own.
+    ///
     /// # Errors
     ///
     /// Several variants of `HdbError` can occur.
@@ -357,7 +414,91 @@ impl<'a> PreparedStatement {
             self.batch.count()
         );
         std::mem::swap(&mut self.batch, &mut batch2);
-        self.execute_parameter_rows(Some(batch2))
+        let (max_rows, max_size) = {
+            let ps_core = self.am_ps_core.lock_sync()?;
+            let conn_core = ps_core.am_conn_core.lock_sync()?;
+            conn_core.batch_limits()
+        };
+        let mut o_response: Option<HdbResponse> = None;
+        for rows in batch2.split(max_rows, max_size, &self.a_descriptors)? {
+            let response = self.execute_parameter_rows(Some(rows), None)?;
+            match o_response {
+                Some(ref mut merged) => merged.append_batch_response(response),
+                None => o_response = Some(response),
+            }
+        }
+        o_response.ok_or_else(|| impl_err!("execute_batch(): no chunk was executed"))
+    }
+
+    // Executes the statement for each of the rows, and returns for each of them the value
+    // that was generated for an IDENTITY column, if any.
+    // Each execution is followed by a query of the generated value; both are sent
+    // as segments of a request message, so that a few roundtrips suffice for all rows.
+    pub(crate) fn execute_returning_identities<T: serde::ser::Serialize>(
+        &mut self,
+        rows: &[T],
+    ) -> HdbResult<Vec<Option<i64>>> {
+        let mut parameter_rows = ParameterRows::new();
+        for row in rows {
+            parameter_rows.push(row, &self.a_descriptors)?;
+        }
+        let ps_core_guard = self.am_ps_core.lock_sync()?;
+        let am_conn_core = &ps_core_guard.am_conn_core;
+        self.check_statement(am_conn_core)?;
+        let (max_rows, max_size) = am_conn_core.lock_sync()?.batch_limits();
+        let command_options = self.config.command_options();
+
+        let mut ids = Vec::with_capacity(rows.len());
+        for chunk in parameter_rows.split(
+            max_rows.min(MAX_SEGMENTS / 2),
+            max_size,
+            &self.a_descriptors,
+        )? {
+            let expected = ids.len() + chunk.count();
+            let mut o_request: Option<Request> = None;
+            for row in chunk.split(1, usize::MAX, &self.a_descriptors)? {
+                let mut execute = Request::new(MessageType::Execute, command_options);
+                execute.push(Part::StatementId(ps_core_guard.statement_id));
+                execute.push(Part::ParameterRows(row));
+                let mut query = Request::new(MessageType::ExecuteDirect, CommandOptions::EMPTY);
+                query.push(Part::Command(CURRENT_IDENTITY_VALUE));
+                let request = match o_request {
+                    Some(ref mut request) => {
+                        request.append_segment(execute);
+                        request
+                    }
+                    None => o_request.insert(execute),
+                };
+                request.append_segment(query);
+            }
+            let Some(request) = o_request else {
+                continue;
+            };
+
+            let mut reply =
+                am_conn_core.full_send_sync(request, None, Some(&self.a_descriptors), &mut None)?;
+            let following = reply.take_following();
+            for (i, mut reply) in std::iter::once(reply).chain(following).enumerate() {
+                reply.handle_db_error(&mut *am_conn_core.lock_sync()?)?;
+                if i % 2 == 1 {
+                    let (internal_return_values, replytype) =
+                        reply.into_internal_return_values_sync(am_conn_core, None, None)?;
+                    ids.push(
+                        HdbResponse::try_new(internal_return_values, replytype)?
+                            .into_result_set()?
+                            .try_into()?,
+                    );
+                }
+            }
+            if ids.len() != expected {
+                return Err(impl_err!(
+                    "server replied to {} of {} inserts",
+                    ids.len(),
+                    expected
+                ));
+            }
+        }
+        Ok(ids)
     }
 
     /// Descriptors of all parameters of the prepared statement (in, out, inout).
@@ -366,11 +507,81 @@ impl<'a> PreparedStatement {
         Arc::clone(&self.a_descriptors)
     }
 
-    fn execute_parameter_rows(&mut self, o_rows: Option<ParameterRows>) -> HdbResult<HdbResponse> {
+    /// Metadata of the result set that the prepared statement produces, if any.
+    #[must_use]
+    pub fn result_metadata(&self) -> Option<Arc<ResultSetMetadata>> {
+        self.o_a_rsmd.clone()
+    }
+
+    /// Describes the statement without executing it: returns the descriptors of all parameters,
+    /// and the metadata of the result set that the statement produces, if any.
+    ///
+    /// Both are provided by the server when the statement is prepared, so no roundtrip is needed.
+    #[must_use]
+    pub fn describe(&self) -> (Arc<ParameterDescriptors>, Option<Arc<ResultSetMetadata>>) {
+        (self.parameter_descriptors(), self.result_metadata())
+    }
+
+    /// The SQL statement that was prepared.
+    #[must_use]
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The id with which the server identifies the prepared statement.
+    ///
+    /// The id is unique only within the session, and can be used e.g. for correlating
+    /// the statement with monitoring views like `M_PREPARED_STATEMENTS`.
+    #[must_use]
+    pub fn server_statement_id(&self) -> u64 {
+        self.statement_id
+    }
+
+    fn execute_parameter_rows(
+        &mut self,
+        o_rows: Option<ParameterRows>,
+        o_options: Option<QueryOptions>,
+    ) -> HdbResult<HdbResponse> {
         trace!("PreparedStatement::execute_parameter_rows()");
+        let o_error_context = self.error_context(o_rows.as_ref().map(ParameterRows::count));
+        #[cfg(feature = "otel")]
+        let span = self.statement_span()?;
+        #[cfg(feature = "otel")]
+        let _entered = span.enter();
+        let result = self
+            .execute_parameter_rows_impl(o_rows, o_options)
+            .map_err(|e| Self::add_error_context(e, o_error_context));
+        #[cfg(feature = "otel")]
+        crate::conn::otel::record_outcome(&span, &result, HdbResponse::affected_rows_count);
+        result
+    }
 
+    #[cfg(feature = "otel")]
+    fn statement_span(&self) -> HdbResult<tracing::Span> {
+        let ps_core = self.am_ps_core.lock_sync()?;
+        let conn_core = ps_core.am_conn_core.lock_sync()?;
+        Ok(crate::conn::otel::statement_span(&self.sql, &conn_core))
+    }
+
+    // Lets the statement hook of the connection, if any, decide about the execution.
+    fn check_statement(&self, am_conn_core: &AmConnCore) -> HdbResult<()> {
+        let o_hook = am_conn_core.lock_sync()?.statement_hook();
+        check_statement(o_hook, &self.sql, self.a_descriptors.iter_in().count())
+    }
+
+    fn execute_parameter_rows_impl(
+        &mut self,
+        o_rows: Option<ParameterRows>,
+        o_options: Option<QueryOptions>,
+    ) -> HdbResult<HdbResponse> {
         let ps_core_guard = self.am_ps_core.lock_sync()?;
-        let mut request = Request::new(MessageType::Execute, self.config.command_options());
+        self.check_statement(&ps_core_guard.am_conn_core)?;
+        let command_options =
+            QueryOptions::applied_to(o_options.as_ref(), &self.config).command_options();
+        let mut request = Request::new(MessageType::Execute, command_options);
+        if let Some(options) = o_options {
+            request.set_options(options);
+        }
         request.push(Part::StatementId(ps_core_guard.statement_id));
         if let Some(rows) = o_rows {
             request.push(Part::ParameterRows(rows));
@@ -384,7 +595,11 @@ impl<'a> PreparedStatement {
                 Some(&self.a_descriptors),
                 &mut None,
             )?
-            .into_internal_return_values_sync(&ps_core_guard.am_conn_core, None)?;
+            .into_internal_return_values_sync(
+                &ps_core_guard.am_conn_core,
+                None,
+                o_options.as_ref(),
+            )?;
 
         // inject statement id
         for rv in &mut internal_return_values {
@@ -393,7 +608,55 @@ impl<'a> PreparedStatement {
             }
         }
 
-        HdbResponse::try_new(internal_return_values, replytype)
+        let response = HdbResponse::try_new(internal_return_values, replytype)?;
+        let conn_core = ps_core_guard.am_conn_core.lock_sync()?;
+        Ok(response.with_server_feedback(&conn_core))
+    }
+
+    /// Sets the statement's cursor holdability, which overrides the connection's one
+    /// for all subsequent executions of this statement.
+    ///
+    /// With [`CursorHoldability::CommitAndRollback`], e.g., the result sets of a report
+    /// survive also a rollback of unrelated work on the same connection.
+    ///
+    /// # Errors
+    ///
+    /// Only `HdbError::Poison` can occur.
+    pub fn set_cursor_holdability(&mut self, holdability: CursorHoldability) -> HdbResult<()> {
+        self.config.set_cursor_holdability(holdability);
+        Ok(())
+    }
+    /// Returns the statement's cursor holdability.
+    ///
+    /// # Errors
+    ///
+    /// Only `HdbError::Poison` can occur.
+    pub fn cursor_holdability(&self) -> HdbResult<CursorHoldability> {
+        Ok(self.config.cursor_holdability())
+    }
+
+    // Returns the statement text and a redacted parameter summary,
+    // if these are configured to be added to errors.
+    fn error_context(&self, o_row_count: Option<usize>) -> Option<(String, Option<String>)> {
+        if self.config.is_sql_in_errors() {
+            Some((
+                self.sql.clone(),
+                o_row_count.map(|row_count| self.a_descriptors.redacted_summary(row_count)),
+            ))
+        } else {
+            None
+        }
+    }
+
+    fn add_error_context(
+        e: HdbError,
+        o_error_context: Option<(String, Option<String>)>,
+    ) -> HdbError {
+        if let Some((statement, o_parameters)) = o_error_context {
+            e.with_statement(statement, o_parameters)
+        } else {
+            e
+        }
     }
 
     /// Provides information about the the server-side resource consumption that
@@ -406,6 +669,38 @@ impl<'a> PreparedStatement {
     // Prepare a statement.
     pub(crate) fn try_new(am_conn_core: AmConnCore, stmt: &str) -> HdbResult<Self> {
         let config = am_conn_core.lock_sync()?.configuration().clone();
+        let sql_in_errors = config.is_sql_in_errors();
+        Self::try_new_impl(am_conn_core, config, stmt).map_err(|e| {
+            if sql_in_errors {
+                e.with_statement(stmt.to_string(), None)
+            } else {
+                e
+            }
+        })
+    }
+
+    // Lets the server-side statement, once it is not used anymore, be dropped
+    // together with the next request, rather than with a roundtrip of its own.
+    pub(crate) fn defer_drop(&self) -> HdbResult<()> {
+        self.am_ps_core.lock_sync()?.deferred_drop = true;
+        Ok(())
+    }
+
+    // Creates another handle for the same server-side statement, e.g. for a statement
+    // from the statement cache, with the given configuration and an empty batch.
+    pub(crate) fn reuse(&self, config: ConnectionConfiguration) -> Self {
+        let mut reused = self.clone();
+        reused.config = config;
+        reused.server_usage = ServerUsage::default();
+        reused.batch = ParameterRows::new();
+        reused
+    }
+
+    fn try_new_impl(
+        am_conn_core: AmConnCore,
+        config: ConnectionConfiguration,
+        stmt: &str,
+    ) -> HdbResult<Self> {
         let mut request = Request::new(MessageType::Prepare, config.command_options());
         request.push(Part::Command(stmt));
 
@@ -451,9 +746,12 @@ impl<'a> PreparedStatement {
         }
 
         let statement_id = o_stmt_id.ok_or_else(|| impl_err!("No StatementId received"))?;
+        let session_id = am_conn_core.lock_sync()?.session_id();
         let am_ps_core = new_am_sync(PreparedStatementCore {
             am_conn_core,
             statement_id,
+            session_id,
+            deferred_drop: false,
         });
         debug!(
             "PreparedStatement created with parameter descriptors = {:?}",
@@ -462,6 +760,8 @@ impl<'a> PreparedStatement {
         Ok(Self {
             am_ps_core,
             config,
+            sql: stmt.to_string(),
+            statement_id,
             server_usage,
             batch: ParameterRows::new(),
             a_descriptors,
@@ -470,3 +770,91 @@ impl<'a> PreparedStatement {
         })
     }
 }
+
+#[cfg(all(test, feature = "mock", feature = "test-utils"))]
+mod test {
+    use crate::{
+        test_utils::{
+            replay::{connection, execution_reply, prepare_reply},
+            PartBuilder, ReplyBuilder,
+        },
+        HdbError,
+    };
+
+    // A reply segment to the INSERT, and one to the query of the IDENTITY value,
+    // whose result set (kind 5) with a nullable BIGINT column is closed (LAST | CLOSED).
+    fn insert_and_identity_segments(o_id: Option<i64>) -> [ReplyBuilder; 2] {
+        let row = PartBuilder::new(5, 1).with_attributes(0b_0001_0001);
+        let row = match o_id {
+            Some(id) => row.push_u8(1).push_i64(id),
+            None => row.push_u8(0),
+        };
+        [
+            execution_reply(2, 1),
+            ReplyBuilder::new()
+                .with_reply_type(5)
+                .with_part(
+                    // ResultSetMetadata (kind 48) for a nullable column of type BIGINT (4)
+                    PartBuilder::new(48, 1)
+                        .push_u8(0b_0000_0010) // nullable
+                        .push_u8(4)
+                        .push_i16(0) // scale
+                        .push_i16(19) // precision
+                        .push_i16(0)
+                        .push_i32(-1) // no table name
+                        .push_i32(-1) // no schema name
+                        .push_i32(0) // column name
+                        .push_i32(0) // display name
+                        .push_u8(2)
+                        .push_bytes(b"ID"),
+                )
+                .with_part(PartBuilder::new(13, 1).push_u64(17))
+                .with_part(row),
+        ]
+    }
+
+    // Each row is inserted with a segment of its own, followed by a segment that queries
+    // the generated IDENTITY value; a single roundtrip suffices for all rows.
+    #[test]
+    fn test_insert_rows_returning_ids() {
+        #[derive(serde::Serialize)]
+        struct Order {
+            amount: i32,
+        }
+
+        let [first, second] = insert_and_identity_segments(Some(7));
+        let [third, fourth] = insert_and_identity_segments(Some(8));
+        let [fifth, sixth] = insert_and_identity_segments(None);
+        let connection = connection([
+            prepare_reply(42),
+            first
+                .with_segment(second)
+                .with_segment(third)
+                .with_segment(fourth),
+            // dropping the prepared statement
+            ReplyBuilder::new(),
+            prepare_reply(42),
+            fifth.with_segment(sixth),
+            ReplyBuilder::new(),
+        ]);
+
+        let orders = [Order { amount: 10 }, Order { amount: 20 }];
+        assert_eq!(
+            connection
+                .insert_rows_returning_ids("ORDERS", &orders)
+                .unwrap(),
+            vec![7, 8]
+        );
+        assert_eq!(connection.statistics().unwrap().call_count(), 3);
+
+        // a table without IDENTITY column
+        assert!(matches!(
+            connection.insert_rows_returning_ids("ORDERS", &orders[..1]),
+            Err(HdbError::Usage(_))
+        ));
+        assert!(connection
+            .insert_rows_returning_ids("ORDERS", &Vec::<Order>::new())
+            .unwrap()
+            .is_empty());
+    }
+}
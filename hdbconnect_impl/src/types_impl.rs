@@ -5,5 +5,51 @@ pub mod daydate;
 pub mod longdate;
 pub mod seconddate;
 pub mod secondtime;
+pub mod typed_null;
+pub mod wkb;
 
 pub mod lob;
+
+use crate::{usage_err, HdbResult};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+// Interprets a timezone-agnostic HANA timestamp as local time of a server
+// with the given UTC offset, and returns the point in time in UTC.
+fn to_utc_offset_date_time(
+    (year, month, day): (i32, u8, u8),
+    (hour, minute, second): (u8, u8, u8),
+    nanosecond: u32,
+    server_offset: UtcOffset,
+) -> HdbResult<OffsetDateTime> {
+    let date = Month::try_from(month)
+        .and_then(|month| Date::from_calendar_date(year, month, day))
+        .map_err(|e| usage_err!("Cannot convert date {year:04}-{month:02}-{day:02}: {e}"))?;
+    let time = Time::from_hms_nano(hour, minute, second, nanosecond)
+        .map_err(|e| usage_err!("Cannot convert time {hour:02}:{minute:02}:{second:02}: {e}"))?;
+    PrimitiveDateTime::new(date, time)
+        .assume_offset(server_offset)
+        .checked_to_offset(UtcOffset::UTC)
+        .ok_or_else(|| usage_err!("Timestamp out of range after conversion to UTC"))
+}
+
+#[cfg(feature = "chrono")]
+fn to_chrono_utc(odt: OffsetDateTime) -> HdbResult<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::from_timestamp(odt.unix_timestamp(), odt.nanosecond())
+        .ok_or_else(|| usage_err!("Timestamp {odt} is out of range for chrono"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::to_utc_offset_date_time;
+    use time::{macros::datetime, UtcOffset};
+
+    #[test]
+    fn test_to_utc_offset_date_time() {
+        let offset = UtcOffset::from_hms(2, 0, 0).unwrap();
+        assert_eq!(
+            to_utc_offset_date_time((2024, 3, 1), (1, 30, 0), 500, offset).unwrap(),
+            datetime!(2024-02-29 23:30:00.000000500 UTC)
+        );
+        assert!(to_utc_offset_date_time((2024, 2, 30), (0, 0, 0), 0, offset).is_err());
+    }
+}
@@ -1,7 +1,9 @@
-use crate::{impl_err, HdbResult, HdbValue};
+use crate::{impl_err, usage_err, HdbResult, HdbValue};
 use byteorder::{LittleEndian, ReadBytesExt};
 
 const NULL_REPRESENTATION: i32 = 3_652_062;
+const UNIX_EPOCH: i32 = 719_164; // 1970-01-01
+const DAY_FACTOR: i64 = 86_400;
 
 const ZEITENWENDE: i32 = 1_721_424;
 const JGREG: i32 = 2_299_161;
@@ -29,14 +31,36 @@ impl std::cmp::PartialEq<DayDate> for DayDate {
 }
 
 impl DayDate {
-    pub(crate) fn new(raw: i32) -> Self {
-        assert!((0..NULL_REPRESENTATION).contains(&raw));
-        Self(raw)
+    // Returns None if the raw value is not in the valid range
+    // (0 for the empty date, or 0001-01-01 to 9999-12-31).
+    pub(crate) fn checked_new(raw: i32) -> Option<Self> {
+        (0..NULL_REPRESENTATION).contains(&raw).then_some(Self(raw))
     }
     pub(crate) fn ref_raw(&self) -> &i32 {
         &self.0
     }
 
+    /// Creates a value from the number of seconds since 1970-01-01 00:00:00 UTC;
+    /// the time of day is dropped.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the result is not between 0001-01-01 and 9999-12-31.
+    pub fn from_unix_timestamp(seconds: i64) -> HdbResult<Self> {
+        i32::try_from(seconds.div_euclid(DAY_FACTOR))
+            .ok()
+            .and_then(|days| days.checked_add(UNIX_EPOCH + 1))
+            .and_then(Self::checked_new)
+            .ok_or_else(|| usage_err!("Unix timestamp {seconds} is out of the range of DAYDATE"))
+    }
+
+    /// Returns the number of seconds since 1970-01-01 00:00:00 UTC
+    /// at the beginning of the day.
+    #[must_use]
+    pub fn to_unix_timestamp(&self) -> i64 {
+        i64::from((self.0 - 1).max(0) - UNIX_EPOCH) * DAY_FACTOR
+    }
+
     // Convert into tuple of "elements".
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_sign_loss)]
@@ -88,6 +112,75 @@ pub(crate) fn parse_daydate(
             Err(impl_err!("found NULL value for NOT NULL DAYDATE column",))
         }
     } else {
-        Ok(HdbValue::DAYDATE(DayDate::new(i)))
+        DayDate::checked_new(i)
+            .map(HdbValue::DAYDATE)
+            .ok_or_else(|| impl_err!("received invalid DAYDATE value {i}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DayDate, NULL_REPRESENTATION};
+
+    // HANA uses the Julian calendar before 1582-10-15, and the Gregorian calendar after.
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        let is_leap_year = if year < 1582 {
+            year % 4 == 0
+        } else {
+            (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+        };
+        match month {
+            2 if is_leap_year => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    fn next_day((year, month, day): (i32, u32, u32)) -> (i32, u32, u32) {
+        if (year, month, day) == (1582, 10, 4) {
+            (1582, 10, 15)
+        } else if day < days_in_month(year, month) {
+            (year, month, day + 1)
+        } else if month < 12 {
+            (year, month + 1, 1)
+        } else {
+            (year + 1, 1, 1)
+        }
+    }
+
+    #[test]
+    fn test_all_days_are_consecutive() {
+        assert_eq!(DayDate::checked_new(0).unwrap().as_ymd(), (1, 1, 1));
+        let mut expected = (1, 1, 1);
+        for raw in 1..NULL_REPRESENTATION {
+            assert_eq!(
+                DayDate::checked_new(raw).unwrap().as_ymd(),
+                expected,
+                "raw value {raw}"
+            );
+            expected = next_day(expected);
+        }
+        assert_eq!(expected, (10_000, 1, 1));
+        assert!(DayDate::checked_new(NULL_REPRESENTATION).is_none());
+        assert!(DayDate::checked_new(-1).is_none());
+    }
+
+    #[test]
+    fn test_unix_timestamp() {
+        let epoch = DayDate::from_unix_timestamp(86_399).unwrap();
+        assert_eq!(epoch.as_ymd(), (1970, 1, 1));
+        assert_eq!(epoch.to_unix_timestamp(), 0);
+        assert_eq!(
+            DayDate::from_unix_timestamp(-1).unwrap().as_ymd(),
+            (1969, 12, 31)
+        );
+        let last = DayDate::checked_new(NULL_REPRESENTATION - 1).unwrap();
+        assert_eq!(
+            DayDate::from_unix_timestamp(last.to_unix_timestamp()).unwrap(),
+            last
+        );
+        assert!(DayDate::from_unix_timestamp(last.to_unix_timestamp() + 86_400).is_err());
+        assert!(DayDate::from_unix_timestamp(i64::MIN).is_err());
     }
 }
@@ -1,9 +1,9 @@
 use crate::{
     impl_err,
     types_impl::wire_decimal::{big_decimal_to_wire_decimal, wire_decimal_to_hdbvalue},
-    HdbResult, HdbValue, TypeId,
+    usage_err, HdbResult, HdbValue, TypeId,
 };
-use bigdecimal::BigDecimal;
+use bigdecimal::{BigDecimal, RoundingMode, Zero};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use num::{FromPrimitive, ToPrimitive};
 use num_bigint::BigInt;
@@ -72,6 +72,38 @@ fn parse_null(nullable: bool, rdr: &mut dyn std::io::Read) -> HdbResult<bool> {
     }
 }
 
+// Verifies that the value fits into the precision and scale of a parameter,
+// i.e. that it does not have more digits before the decimal point than precision - scale.
+// Additional fractional digits are not an error, they are rounded away, like with emit();
+// the rounding can add a digit before the decimal point, e.g. 999.999 becomes 1000.00.
+// Floating-point decimals (without precision and scale) are not checked.
+// This applies to all decimal types, also to those with fixed size (FIXED8, FIXED12, FIXED16).
+pub(crate) fn check_fits(
+    big_decimal: &BigDecimal,
+    precision: i16,
+    scale: i16,
+    o_name: Option<&str>,
+) -> HdbResult<()> {
+    if precision <= 0 || scale < 0 || scale > precision {
+        return Ok(());
+    }
+    let integer_part = round(big_decimal, scale).abs().with_scale(0);
+    let integer_digits = if integer_part.is_zero() {
+        0
+    } else {
+        integer_part.digits()
+    };
+    let max_integer_digits = u64::from(precision.unsigned_abs() - scale.unsigned_abs());
+    if integer_digits > max_integer_digits {
+        return Err(usage_err!(
+            "Value {big_decimal} does not fit into parameter {}of type DECIMAL({precision},{scale}), \
+             which allows at most {max_integer_digits} digits before the decimal point",
+            o_name.map_or_else(String::new, |name| format!("{name} "))
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) fn emit(
     big_decimal: &BigDecimal,
     type_id: TypeId,
@@ -86,7 +118,7 @@ pub(crate) fn emit(
         }
         TypeId::FIXED8 => {
             trace!("emit FIXED8");
-            let bd = big_decimal.with_scale(i64::from(scale));
+            let bd = round(big_decimal, scale);
             let (bigint, _exponent) = bd.as_bigint_and_exponent();
             w.write_i64::<LittleEndian>(
                 bigint
@@ -98,7 +130,7 @@ pub(crate) fn emit(
             trace!("emit FIXED12");
             // if we get less than 12 bytes, we need to append bytes with either value
             // 0_u8 or 255_u8, depending on the value of the highest bit of the last byte.
-            let bd = big_decimal.with_scale(i64::from(scale));
+            let bd = round(big_decimal, scale);
             let (bigint, _exponent) = bd.as_bigint_and_exponent();
             let mut bytes = bigint.to_signed_bytes_le();
             let l = bytes.len();
@@ -117,7 +149,7 @@ pub(crate) fn emit(
         }
         TypeId::FIXED16 => {
             trace!("emit FIXED16");
-            let bd = big_decimal.with_scale(i64::from(scale));
+            let bd = round(big_decimal, scale);
             let (bigint, _exponent) = bd.as_bigint_and_exponent();
             w.write_i128::<LittleEndian>(
                 bigint
@@ -129,3 +161,74 @@ pub(crate) fn emit(
     }
     Ok(())
 }
+
+// Rounds the value to the given number of fractional digits, as the server does
+fn round(big_decimal: &BigDecimal, scale: i16) -> BigDecimal {
+    big_decimal.with_scale_round(i64::from(scale), RoundingMode::HalfUp)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check_fits, emit};
+    use crate::TypeId;
+    use bigdecimal::BigDecimal;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_check_fits() {
+        let fits = |value: &str, precision, scale| {
+            check_fits(
+                &BigDecimal::from_str(value).unwrap(),
+                precision,
+                scale,
+                None,
+            )
+            .is_ok()
+        };
+        assert!(fits("999.99", 5, 2));
+        assert!(fits("-999.99", 5, 2));
+        assert!(fits("0.12345", 5, 2));
+        assert!(fits("999.994", 5, 2));
+        // rounding adds a digit before the decimal point
+        assert!(!fits("999.995", 5, 2));
+        assert!(!fits("999.999", 5, 2));
+        assert!(!fits("-999.999", 5, 2));
+        assert!(!fits("1000", 5, 2));
+        assert!(!fits("-1000.5", 5, 2));
+        assert!(fits("0.5", 2, 2));
+        assert!(!fits("1.5", 2, 2));
+        // floating-point decimals are not checked
+        assert!(fits("1e100", 34, 32767));
+
+        let error = check_fits(
+            &BigDecimal::from_str("1234.5").unwrap(),
+            5,
+            2,
+            Some("PRICE"),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("1234.5"), "{error}");
+        assert!(error.contains("PRICE of type DECIMAL(5,2)"), "{error}");
+    }
+
+    #[test]
+    fn test_emit_fixed() {
+        let emitted = |value: &str, type_id| {
+            let mut buf = Vec::new();
+            emit(&BigDecimal::from_str(value).unwrap(), type_id, 2, &mut buf).unwrap();
+            buf
+        };
+        assert_eq!(emitted("1.235", TypeId::FIXED8), 124_i64.to_le_bytes());
+        assert_eq!(emitted("-1.235", TypeId::FIXED8), (-124_i64).to_le_bytes());
+        assert_eq!(emitted("1.234", TypeId::FIXED16), 123_i128.to_le_bytes());
+        assert_eq!(
+            emitted("999.999", TypeId::FIXED12),
+            100_000_i128.to_le_bytes()[..12]
+        );
+        assert_eq!(
+            emitted("-0.01", TypeId::FIXED12),
+            (-1_i128).to_le_bytes()[..12]
+        );
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
         util, MessageType, Part, PartKind, Reply, ReplyType, Request,
     },
     types_impl::lob::lob_writer_util::{get_utf8_tail_len, LobWriteMode},
-    HdbResult, ServerUsage,
+    usage_err, HdbResult, ServerUsage,
 };
 use std::sync::Arc;
 use tokio::io::{AsyncRead, AsyncReadExt};
@@ -74,6 +74,9 @@ where
                 }
                 len -= tail_len;
             }
+            if read_done && !utf8_tail.is_empty() {
+                return Err(usage_err!("stream ending with invalid utf-8"));
+            }
             cesu8::to_cesu8(std::str::from_utf8(&buf[0..len]).map_err(util::io_error)?)
         } else {
             std::borrow::Cow::Borrowed(&buf[0..len])
@@ -208,7 +211,7 @@ async fn evaluate_dbprocedure_call_reply(
     let locator_ids = evaluate_dbprocedure_call_reply1(&mut reply, server_usage)?;
     let mut proc_result = reply
         .parts
-        .into_internal_return_values_async(am_conn_core, Some(server_usage))
+        .into_internal_return_values_async(am_conn_core, Some(server_usage), None)
         .await?;
 
     internal_return_values.append(&mut proc_result);
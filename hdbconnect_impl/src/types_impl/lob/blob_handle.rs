@@ -2,9 +2,9 @@
 use crate::usage_err;
 
 #[cfg(feature = "async")]
-use super::fetch::fetch_a_lob_chunk_async;
+use super::fetch::{fetch_a_lob_chunk_async, lob_read_length_async};
 #[cfg(feature = "sync")]
-use super::fetch::fetch_a_lob_chunk_sync;
+use super::fetch::{fetch_a_lob_chunk_sync, lob_read_length_sync};
 use super::LobBuf;
 use crate::{
     base::{RsCore, XMutexed, OAM},
@@ -104,10 +104,7 @@ impl BLobHandle {
         }
 
         let read_length = std::cmp::min(
-            self.am_conn_core
-                .lock_sync()?
-                .configuration()
-                .lob_read_length(),
+            lob_read_length_sync(&self.am_conn_core, &self.o_am_rscore)?,
             (self.total_byte_length - self.acc_byte_length as u64) as u32,
         );
 
@@ -122,6 +119,9 @@ impl BLobHandle {
         self.acc_byte_length += reply_len;
 
         self.data.append(&reply_data);
+        self.am_conn_core
+            .lock_sync()?
+            .recycle_lob_buffer(reply_data);
         if reply_is_last_data {
             self.is_data_complete = true;
             self.o_am_rscore = None;
@@ -146,11 +146,7 @@ impl BLobHandle {
         }
 
         let read_length = std::cmp::min(
-            self.am_conn_core
-                .lock_async()
-                .await
-                .configuration()
-                .lob_read_length(),
+            lob_read_length_async(&self.am_conn_core, &self.o_am_rscore).await,
             (self.total_byte_length - self.acc_byte_length as u64) as u32,
         );
 
@@ -165,6 +161,10 @@ impl BLobHandle {
 
         self.acc_byte_length += reply_data.len();
         self.data.append(&reply_data);
+        self.am_conn_core
+            .lock_async()
+            .await
+            .recycle_lob_buffer(reply_data);
         if reply_is_last_data {
             self.is_data_complete = true;
             self.o_am_rscore = None;
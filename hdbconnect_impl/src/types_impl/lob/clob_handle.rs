@@ -1,7 +1,7 @@
 #[cfg(feature = "async")]
-use super::fetch::fetch_a_lob_chunk_async;
+use super::fetch::{fetch_a_lob_chunk_async, lob_read_length_async};
 #[cfg(feature = "sync")]
-use super::fetch::fetch_a_lob_chunk_sync;
+use super::fetch::{fetch_a_lob_chunk_sync, lob_read_length_sync};
 use super::{CharLobSlice, LobBuf, UTF_BUFFER_SIZE};
 use crate::{
     base::{RsCore, OAM},
@@ -115,10 +115,7 @@ impl CLobHandle {
         }
 
         let read_length = std::cmp::min(
-            self.am_conn_core
-                .lock_sync()?
-                .configuration()
-                .lob_read_length(),
+            lob_read_length_sync(&self.am_conn_core, &self.o_am_rscore)?,
             (self.total_byte_length - self.acc_byte_length as u64) as u32,
         );
 
@@ -131,6 +128,9 @@ impl CLobHandle {
         )?;
         self.acc_byte_length += reply_data.len();
         self.cesu8.append(&reply_data);
+        self.am_conn_core
+            .lock_sync()?
+            .recycle_lob_buffer(reply_data);
 
         if reply_is_last_data {
             self.is_data_complete = true;
@@ -157,11 +157,7 @@ impl CLobHandle {
         }
 
         let read_length = std::cmp::min(
-            self.am_conn_core
-                .lock_async()
-                .await
-                .configuration()
-                .lob_read_length(),
+            lob_read_length_async(&self.am_conn_core, &self.o_am_rscore).await,
             (self.total_byte_length - self.acc_byte_length as u64) as u32,
         );
 
@@ -176,6 +172,10 @@ impl CLobHandle {
 
         self.acc_byte_length += reply_data.len();
         self.cesu8.append(&reply_data);
+        self.am_conn_core
+            .lock_async()
+            .await
+            .recycle_lob_buffer(reply_data);
 
         if reply_is_last_data {
             self.is_data_complete = true;
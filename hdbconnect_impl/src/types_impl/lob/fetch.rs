@@ -1,4 +1,5 @@
 use crate::{
+    base::{RsCore, OAM},
     conn::{AmConnCore, CommandOptions},
     impl_err,
     protocol::{
@@ -8,6 +9,39 @@ use crate::{
     HdbResult,
 };
 
+// Returns the lob read length of the result set the LOB belongs to, if it was overridden,
+// or else the one of the connection.
+#[cfg(feature = "sync")]
+#[allow(clippy::ref_option)]
+pub(crate) fn lob_read_length_sync(
+    am_conn_core: &AmConnCore,
+    o_am_rscore: &OAM<RsCore>,
+) -> HdbResult<u32> {
+    if let Some(am_rscore) = o_am_rscore {
+        if let Some(lob_read_length) = am_rscore.lock_sync()?.lob_read_length() {
+            return Ok(lob_read_length);
+        }
+    }
+    Ok(am_conn_core.lock_sync()?.configuration().lob_read_length())
+}
+#[cfg(feature = "async")]
+#[allow(clippy::ref_option)]
+pub(crate) async fn lob_read_length_async(
+    am_conn_core: &AmConnCore,
+    o_am_rscore: &OAM<RsCore>,
+) -> u32 {
+    if let Some(am_rscore) = o_am_rscore {
+        if let Some(lob_read_length) = am_rscore.lock_async().await.lob_read_length() {
+            return lob_read_length;
+        }
+    }
+    am_conn_core
+        .lock_async()
+        .await
+        .configuration()
+        .lob_read_length()
+}
+
 // Note that requested_length and offset count either bytes (BLOB, CLOB), or 1-2-3-chars (NCLOB)
 #[cfg(feature = "sync")]
 pub(crate) fn fetch_a_lob_chunk_sync(
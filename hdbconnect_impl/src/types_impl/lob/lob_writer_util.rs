@@ -6,60 +6,58 @@ pub(crate) enum LobWriteMode {
     Last,
 }
 
+// Returns the number of bytes at the end that belong to an incomplete utf-8 character;
+// these have to be kept back until the rest of the character is available.
 pub(crate) fn get_utf8_tail_len(bytes: &[u8]) -> HdbResult<usize> {
-    match bytes.last() {
-        None | Some(0..=127) => Ok(0),
-        Some(0xC0..=0xDF) => Ok(1),
-        Some(_) => {
-            let len = bytes.len();
-            for i in 0..len - 1 {
-                let index = len - 2 - i;
-                let utf8_char_start = get_utf8_char_start(&bytes[index..]);
-                if let Some(char_len) = match utf8_char_start {
-                    Utf8CharType::One => Some(1),
-                    Utf8CharType::Two => Some(2),
-                    Utf8CharType::Three => Some(3),
-                    Utf8CharType::Four => Some(4),
-                    Utf8CharType::NotAStart | Utf8CharType::Illegal | Utf8CharType::Empty => None,
-                } {
-                    return Ok(match (index + char_len).cmp(&len) {
-                        std::cmp::Ordering::Greater => len - index,
-                        std::cmp::Ordering::Equal => 0,
-                        std::cmp::Ordering::Less => len - index - char_len,
-                    });
-                }
-            }
-            Err(impl_err!("no valid utf8 cutoff point found!"))
-        }
+    //   1: 0000_0000 to 0111_1111 (00 to 7F)
+    //cont: 1000_0000 to 1011_1111 (80 to BF)
+    //   2: 1100_0000 to 1101_1111 (C0 to DF)
+    //   3: 1110_0000 to 1110_1111 (E0 to EF)
+    //   4: 1111_0000 to 1111_0111 (F0 to F7)
+    // ill: 1111_1000 to 1111_1111 (F8 to FF)
+    for (back, byte) in bytes.iter().rev().take(4).enumerate() {
+        let char_len = match byte {
+            0x00..=0x7F => 1,
+            0x80..=0xBF => continue,
+            0xC0..=0xDF => 2,
+            0xE0..=0xEF => 3,
+            0xF0..=0xF7 => 4,
+            _ => break,
+        };
+        // back + 1 bytes are available, starting with the character start
+        return Ok(if char_len > back + 1 { back + 1 } else { 0 });
+    }
+    if bytes.is_empty() {
+        Ok(0)
+    } else {
+        Err(impl_err!("no valid utf8 cutoff point found!"))
     }
 }
 
-enum Utf8CharType {
-    Empty,
-    Illegal,
-    NotAStart,
-    One,   // ...plain ascii
-    Two,   // ...two-byte char
-    Three, // ...three-byte char
-    Four,  // ...four-byte char
-}
+#[cfg(test)]
+mod test {
+    use super::get_utf8_tail_len;
+
+    #[test]
+    fn test_chunked_cesu8_conversion() {
+        let text = "Grüße, 日本語 und 🦀🦀 – ein längerer Text";
+        let expected = cesu8::to_cesu8(text);
+        for chunk_size in 4..=text.len() {
+            let mut converted = Vec::<u8>::new();
+            let mut tail = Vec::<u8>::new();
+            for chunk in text.as_bytes().chunks(chunk_size) {
+                let mut buf = std::mem::take(&mut tail);
+                buf.extend_from_slice(chunk);
+                let tail_len = get_utf8_tail_len(&buf).unwrap();
+                tail = buf.split_off(buf.len() - tail_len);
+                converted.extend_from_slice(&cesu8::to_cesu8(std::str::from_utf8(&buf).unwrap()));
+            }
+            assert!(tail.is_empty());
+            assert_eq!(converted, expected.as_ref(), "chunk size {chunk_size}");
+        }
 
-//   1: 0000_0000 to 0111_1111 (00 to 7F)
-//cont: 1000_0000 to 1011_1111 (80 to BF)
-//   2: 1100_0000 to 1101_1111 (C0 to DF)
-//   3: 1110_0000 to 1110_1111 (E0 to EF)
-//   4: 1111_0000 to 1111_0111 (F0 to F7)
-// ill: 1111_1000 to 1111_1111 (F8 to FF)
-fn get_utf8_char_start(bytes: &[u8]) -> Utf8CharType {
-    match bytes.len() {
-        0 => Utf8CharType::Empty,
-        _ => match bytes[0] {
-            0x00..=0x7F => Utf8CharType::One,
-            0x80..=0xBF => Utf8CharType::NotAStart,
-            0xC0..=0xDF => Utf8CharType::Two,
-            0xE0..=0xEF => Utf8CharType::Three,
-            0xF0..=0xF7 => Utf8CharType::Four,
-            _ => Utf8CharType::Illegal,
-        },
+        assert_eq!(get_utf8_tail_len(&[0xF0]).unwrap(), 1);
+        assert_eq!(get_utf8_tail_len(&[b'a', 0xF0, 0x9F, 0xA6]).unwrap(), 3);
+        assert!(get_utf8_tail_len(&[0x80, 0x80, 0x80, 0x80]).is_err());
     }
 }
@@ -1,8 +1,8 @@
 #[cfg(feature = "async")]
-use super::fetch::fetch_a_lob_chunk_async;
+use super::fetch::{fetch_a_lob_chunk_async, lob_read_length_async};
 
 #[cfg(feature = "sync")]
-use super::fetch::fetch_a_lob_chunk_sync;
+use super::fetch::{fetch_a_lob_chunk_sync, lob_read_length_sync};
 
 use super::{CharLobSlice, LobBuf, UTF_BUFFER_SIZE};
 use crate::{
@@ -126,10 +126,7 @@ impl NCLobHandle {
         }
 
         let read_length = std::cmp::min(
-            self.am_conn_core
-                .lock_sync()?
-                .configuration()
-                .lob_read_length(),
+            lob_read_length_sync(&self.am_conn_core, &self.o_am_rscore)?,
             (self.total_char_length - self.acc_char_length as u64) as u32,
         );
 
@@ -144,6 +141,9 @@ impl NCLobHandle {
         self.acc_byte_length += reply_data.len();
         self.acc_char_length += count_1_2_3_sequence_starts(&reply_data);
         self.cesu8.append(&reply_data);
+        self.am_conn_core
+            .lock_sync()?
+            .recycle_lob_buffer(reply_data);
 
         if reply_is_last_data {
             self.is_data_complete = true;
@@ -170,11 +170,7 @@ impl NCLobHandle {
         }
 
         let read_length = std::cmp::min(
-            self.am_conn_core
-                .lock_async()
-                .await
-                .configuration()
-                .lob_read_length(),
+            lob_read_length_async(&self.am_conn_core, &self.o_am_rscore).await,
             (self.total_char_length - self.acc_char_length as u64) as u32,
         );
 
@@ -190,6 +186,10 @@ impl NCLobHandle {
         self.acc_byte_length += reply_data.len();
         self.acc_char_length += count_1_2_3_sequence_starts(&reply_data);
         self.cesu8.append(&reply_data);
+        self.am_conn_core
+            .lock_async()
+            .await
+            .recycle_lob_buffer(reply_data);
 
         if reply_is_last_data {
             self.is_data_complete = true;
@@ -135,9 +135,11 @@ impl<'a> SyncLobWriter<'a> {
 
     fn evaluate_dbprocedure_call_reply(&mut self, mut reply: Reply) -> HdbResult<Vec<u64>> {
         let locator_ids = self.evaluate_dbprocedure_call_reply1(&mut reply)?;
-        let internal_return_values = reply
-            .parts
-            .into_internal_return_values_sync(&self.am_conn_core, Some(&mut self.server_usage))?;
+        let internal_return_values = reply.parts.into_internal_return_values_sync(
+            &self.am_conn_core,
+            Some(&mut self.server_usage),
+            None,
+        )?;
 
         self.proc_result = Some(internal_return_values);
         Ok(locator_ids)
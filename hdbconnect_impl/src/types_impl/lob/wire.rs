@@ -207,6 +207,11 @@ fn parse_lob_2(
     let chunk_length = rdr.read_u32::<LittleEndian>()?; // I4
 
     if is_data_included {
+        if u64::from(chunk_length) > total_byte_length {
+            return Err(impl_err!(
+                "corrupt reply: LOB chunk length {chunk_length} exceeds total length {total_byte_length}"
+            ));
+        }
         let data = util_sync::parse_bytes(chunk_length as usize, rdr)?; // B[chunk_length]
         Ok((total_char_length, total_byte_length, locator_id, data))
     } else {
@@ -1,11 +1,13 @@
-use crate::{impl_err, HdbResult, HdbValue};
+use crate::{impl_err, usage_err, HdbResult, HdbValue, ParameterDescriptors, TypeId};
 use byteorder::{LittleEndian, ReadBytesExt};
+use time::{OffsetDateTime, UtcOffset};
 
 const NULL_REPRESENTATION: i64 = 3_155_380_704_000_000_001;
 const SECOND_FACTOR: i64 = 10_000_000;
 const MINUTE_FACTOR: i64 = 600_000_000; // 10_000_000 * 60;
 const HOUR_FACTOR: i64 = 36_000_000_000; // 10_000_000 * 60 * 60;
 const DAY_FACTOR: i64 = 864_000_000_000; // 10_000_000 * 60 * 60 * 24;
+const UNIX_EPOCH: i64 = 719_164 * DAY_FACTOR; // 1970-01-01 00:00:00
 
 const ZEITENWENDE: i64 = 1_721_424;
 const JGREG: i64 = 2_299_161;
@@ -36,13 +38,102 @@ impl std::cmp::PartialEq<LongDate> for LongDate {
 }
 
 impl LongDate {
-    pub(crate) fn new(raw: i64) -> Self {
-        Self(raw)
+    // Returns None if the raw value is not in the valid range
+    // (0 for the empty value, or 0001-01-01 00:00:00 to 9999-12-31 23:59:59.9999999).
+    pub(crate) fn checked_new(raw: i64) -> Option<Self> {
+        (0..NULL_REPRESENTATION).contains(&raw).then_some(Self(raw))
     }
     pub(crate) fn ref_raw(&self) -> &i64 {
         &self.0
     }
 
+    /// Creates a value from the number of seconds since 1970-01-01 00:00:00 UTC.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the result is not between 0001-01-01 and 9999-12-31.
+    pub fn from_unix_timestamp(seconds: i64) -> HdbResult<Self> {
+        seconds
+            .checked_mul(SECOND_FACTOR)
+            .and_then(|ticks| ticks.checked_add(UNIX_EPOCH + 1))
+            .and_then(Self::checked_new)
+            .ok_or_else(|| usage_err!("Unix timestamp {seconds} is out of the range of LONGDATE"))
+    }
+
+    /// Creates a value from the number of nanoseconds since 1970-01-01 00:00:00 UTC;
+    /// LONGDATE has a precision of 100 nanoseconds, so the value is rounded down.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the result is not between 0001-01-01 and 9999-12-31.
+    pub fn from_unix_timestamp_nanos(nanos: i128) -> HdbResult<Self> {
+        i64::try_from(nanos.div_euclid(100))
+            .ok()
+            .and_then(|ticks| ticks.checked_add(UNIX_EPOCH + 1))
+            .and_then(Self::checked_new)
+            .ok_or_else(|| usage_err!("Unix timestamp {nanos}ns is out of the range of LONGDATE"))
+    }
+
+    /// Creates a value that represents the given point in time in UTC,
+    /// like the validity periods of system-versioned tables;
+    /// LONGDATE has a precision of 100 nanoseconds, so the value is rounded down.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the result is not between 0001-01-01 and 9999-12-31.
+    pub fn from_offset_date_time(timestamp: OffsetDateTime) -> HdbResult<Self> {
+        Self::from_unix_timestamp_nanos(timestamp.unix_timestamp_nanos())
+    }
+
+    /// Returns the number of seconds since 1970-01-01 00:00:00 UTC,
+    /// interpreting the value as UTC; the fraction of second is dropped.
+    #[must_use]
+    pub fn to_unix_timestamp(&self) -> i64 {
+        (self.ticks() - UNIX_EPOCH).div_euclid(SECOND_FACTOR)
+    }
+
+    /// Returns the number of nanoseconds since 1970-01-01 00:00:00 UTC,
+    /// interpreting the value as UTC.
+    #[must_use]
+    pub fn to_unix_timestamp_nanos(&self) -> i128 {
+        i128::from(self.ticks() - UNIX_EPOCH) * 100
+    }
+
+    // The number of 100ns-ticks since 0001-01-01 00:00:00.
+    fn ticks(&self) -> i64 {
+        (self.0 - 1).max(0)
+    }
+
+    /// Converts the value, as local time of a server with the given UTC offset, into a point
+    /// in time with offset UTC, see [time zones](crate::types#timestamps-and-time-zones).
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the value cannot be represented.
+    pub fn to_offset_date_time(&self, server_offset: UtcOffset) -> HdbResult<OffsetDateTime> {
+        let (year, month, day, hour, minute, second, fraction) = self.as_ymd_hms_f();
+        super::to_utc_offset_date_time(
+            (year, month, day),
+            (hour, minute, second),
+            fraction * 100,
+            server_offset,
+        )
+    }
+
+    /// Like [`to_offset_date_time`](Self::to_offset_date_time), but returns a `chrono::DateTime`.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the value cannot be represented.
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    #[cfg(feature = "chrono")]
+    pub fn to_utc_date_time(
+        &self,
+        server_offset: UtcOffset,
+    ) -> HdbResult<chrono::DateTime<chrono::Utc>> {
+        super::to_chrono_utc(self.to_offset_date_time(server_offset)?)
+    }
+
     // Convert into tuple of "elements".
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_precision_loss)]
@@ -105,6 +196,141 @@ pub(crate) fn parse_longdate(
             Err(impl_err!("found NULL value for NOT NULL LONGDATE column",))
         }
     } else {
-        Ok(HdbValue::LONGDATE(LongDate::new(i)))
+        LongDate::checked_new(i)
+            .map(HdbValue::LONGDATE)
+            .ok_or_else(|| impl_err!("received invalid LONGDATE value {i}"))
+    }
+}
+
+// The values for `Connection::query_as_of()`: the point in time is bound to each input parameter,
+// which must be a timestamp; the server describes TIMESTAMP parameters as LONGDATE.
+pub(crate) fn as_of_values(
+    as_of: &LongDate,
+    descriptors: &ParameterDescriptors,
+) -> HdbResult<Vec<HdbValue<'static>>> {
+    if !descriptors.has_in() {
+        return Err(usage_err!(
+            "query_as_of: the statement has no parameter for the point in time"
+        ));
+    }
+    descriptors
+        .iter_in()
+        .enumerate()
+        .map(|(i, descriptor)| match descriptor.type_id() {
+            TypeId::LONGDATE => Ok(HdbValue::LONGDATE(as_of.clone())),
+            type_id => Err(usage_err!(
+                "query_as_of: parameter {} ({}) is of type {type_id}, \
+                 but the point in time can only be bound to LONGDATE or TIMESTAMP parameters",
+                i + 1,
+                descriptor.name().unwrap_or("unnamed"),
+            )),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{as_of_values, LongDate, DAY_FACTOR, NULL_REPRESENTATION};
+    use crate::types::DayDate;
+    use crate::{HdbError, HdbValue, ParameterDescriptors};
+    use time::{OffsetDateTime, UtcOffset};
+
+    #[test]
+    fn test_days_match_daydate() {
+        for day in 0..(NULL_REPRESENTATION - 1) / DAY_FACTOR {
+            let (year, month, day_of_month) = DayDate::checked_new(i32::try_from(day + 1).unwrap())
+                .unwrap()
+                .as_ymd();
+            let date = (
+                year,
+                u8::try_from(month).unwrap(),
+                u8::try_from(day_of_month).unwrap(),
+            );
+            let first = LongDate::checked_new(day * DAY_FACTOR + 1).unwrap();
+            let (y, m, d, hour, minute, second, fraction) = first.as_ymd_hms_f();
+            assert_eq!(
+                ((y, m, d), (hour, minute, second, fraction)),
+                (date, (0, 0, 0, 0))
+            );
+            let last = LongDate::checked_new((day + 1) * DAY_FACTOR).unwrap();
+            let (y, m, d, hour, minute, second, fraction) = last.as_ymd_hms_f();
+            assert_eq!(
+                ((y, m, d), (hour, minute, second, fraction)),
+                (date, (23, 59, 59, 9_999_999))
+            );
+        }
+    }
+
+    #[test]
+    fn test_boundaries() {
+        let max = LongDate::checked_new(NULL_REPRESENTATION - 1).unwrap();
+        assert_eq!(max.to_string(), "9999-12-31T23:59:59.9999999");
+        assert_eq!(
+            LongDate::checked_new(0).unwrap().to_string(),
+            "0001-01-01T00:00:00.0000000"
+        );
+        assert!(LongDate::checked_new(NULL_REPRESENTATION).is_none());
+        assert!(LongDate::checked_new(-1).is_none());
+
+        // shifting the maximum to UTC leaves the supported range
+        let offset = UtcOffset::from_hms(-5, 0, 0).unwrap();
+        assert!(max.to_offset_date_time(offset).is_err());
+        assert!(max.to_offset_date_time(UtcOffset::UTC).is_ok());
+    }
+
+    #[test]
+    fn test_unix_timestamp() {
+        for seconds in [-12_219_292_800, -1, 0, 1_700_000_000, 253_402_300_799] {
+            let longdate = LongDate::from_unix_timestamp(seconds).unwrap();
+            assert_eq!(longdate.to_unix_timestamp(), seconds);
+            assert_eq!(
+                longdate.to_offset_date_time(UtcOffset::UTC).unwrap(),
+                OffsetDateTime::from_unix_timestamp(seconds).unwrap()
+            );
+        }
+        let nanos = -1_234_567_800;
+        let longdate = LongDate::from_unix_timestamp_nanos(nanos).unwrap();
+        assert_eq!(longdate.to_unix_timestamp_nanos(), nanos);
+        assert_eq!(longdate.to_unix_timestamp(), -2);
+        assert_eq!(longdate.to_string(), "1969-12-31T23:59:58.7654322");
+        assert!(LongDate::from_unix_timestamp(253_402_300_800).is_err());
+        assert!(LongDate::from_unix_timestamp(i64::MAX).is_err());
+        assert!(LongDate::from_unix_timestamp_nanos(i128::MIN).is_err());
+
+        let timestamp = OffsetDateTime::from_unix_timestamp(1_700_000_000)
+            .unwrap()
+            .to_offset(UtcOffset::from_hms(2, 0, 0).unwrap());
+        assert_eq!(
+            LongDate::from_offset_date_time(timestamp)
+                .unwrap()
+                .to_string(),
+            "2023-11-14T22:13:20.0000000"
+        );
+    }
+
+    #[test]
+    fn test_as_of_values() {
+        // a nullable IN parameter of type LONGDATE, and one of type INT
+        let parse = |bytes: Vec<u8>| {
+            ParameterDescriptors::parse(bytes.len() / 16, &mut std::io::Cursor::new(bytes)).unwrap()
+        };
+        let longdate = [2, 61, 1, 0, 255, 255, 255, 255, 27, 0, 7, 0, 0, 0, 0, 0];
+        let int = [2, 3, 1, 0, 255, 255, 255, 255, 10, 0, 0, 0, 0, 0, 0, 0];
+        let as_of = LongDate::from_unix_timestamp(1_700_000_000).unwrap();
+
+        let values = as_of_values(&as_of, &parse([longdate, longdate].concat())).unwrap();
+        assert_eq!(values.len(), 2);
+        assert!(values
+            .iter()
+            .all(|value| matches!(value, HdbValue::LONGDATE(ld) if *ld == as_of)));
+
+        assert!(matches!(
+            as_of_values(&as_of, &parse([longdate, int].concat())),
+            Err(HdbError::Usage(_))
+        ));
+        assert!(matches!(
+            as_of_values(&as_of, &parse(vec![])),
+            Err(HdbError::Usage(_))
+        ));
     }
 }
@@ -1,5 +1,6 @@
-use crate::{impl_err, HdbResult, HdbValue};
+use crate::{impl_err, usage_err, HdbResult, HdbValue};
 use byteorder::{LittleEndian, ReadBytesExt};
+use time::{OffsetDateTime, UtcOffset};
 
 const NULL_REPRESENTATION: i64 = 315_538_070_401;
 
@@ -7,6 +8,7 @@ const SECOND_FACTOR: i64 = 1;
 const MINUTE_FACTOR: i64 = 60;
 const HOUR_FACTOR: i64 = 3_600;
 const DAY_FACTOR: i64 = 86_400;
+const UNIX_EPOCH: i64 = 719_164 * DAY_FACTOR; // 1970-01-01 00:00:00
 
 const ZEITENWENDE: i64 = 1_721_424;
 const JGREG: i64 = 2_299_161;
@@ -37,14 +39,60 @@ impl std::cmp::PartialEq<SecondDate> for SecondDate {
 }
 
 impl SecondDate {
-    pub(crate) fn new(raw: i64) -> Self {
-        Self(raw)
+    // Returns None if the raw value is not in the valid range
+    // (0 for the empty value, or 0001-01-01 00:00:00 to 9999-12-31 23:59:59).
+    pub(crate) fn checked_new(raw: i64) -> Option<Self> {
+        (0..NULL_REPRESENTATION).contains(&raw).then_some(Self(raw))
     }
 
     pub(crate) fn ref_raw(&self) -> &i64 {
         &self.0
     }
 
+    /// Creates a value from the number of seconds since 1970-01-01 00:00:00 UTC.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the result is not between 0001-01-01 and 9999-12-31.
+    pub fn from_unix_timestamp(seconds: i64) -> HdbResult<Self> {
+        seconds
+            .checked_add(UNIX_EPOCH + 1)
+            .and_then(Self::checked_new)
+            .ok_or_else(|| usage_err!("Unix timestamp {seconds} is out of the range of SECONDDATE"))
+    }
+
+    /// Returns the number of seconds since 1970-01-01 00:00:00 UTC,
+    /// interpreting the value as UTC.
+    #[must_use]
+    pub fn to_unix_timestamp(&self) -> i64 {
+        (self.0 - 1).max(0) - UNIX_EPOCH
+    }
+
+    /// Converts the value, as local time of a server with the given UTC offset, into a point
+    /// in time with offset UTC, see [time zones](crate::types#timestamps-and-time-zones).
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the value cannot be represented.
+    pub fn to_offset_date_time(&self, server_offset: UtcOffset) -> HdbResult<OffsetDateTime> {
+        let (year, month, day, hour, minute, second) = self.as_ymd_hms();
+        super::to_utc_offset_date_time((year, month, day), (hour, minute, second), 0, server_offset)
+    }
+
+    /// Like [`to_offset_date_time`](Self::to_offset_date_time), but returns a `chrono::DateTime`.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the value cannot be represented.
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+    #[cfg(feature = "chrono")]
+    pub fn to_utc_date_time(
+        &self,
+        server_offset: UtcOffset,
+    ) -> HdbResult<chrono::DateTime<chrono::Utc>> {
+        super::to_chrono_utc(self.to_offset_date_time(server_offset)?)
+    }
+
     // Convert into tuple of "elements".
     #[allow(clippy::cast_possible_truncation)]
     #[allow(clippy::cast_precision_loss)]
@@ -105,6 +153,37 @@ pub(crate) fn parse_seconddate(
             Err(impl_err!("found NULL value for NOT NULL SECONDDATE column",))
         }
     } else {
-        Ok(HdbValue::SECONDDATE(SecondDate::new(i)))
+        SecondDate::checked_new(i)
+            .map(HdbValue::SECONDDATE)
+            .ok_or_else(|| impl_err!("received invalid SECONDDATE value {i}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{SecondDate, NULL_REPRESENTATION};
+
+    #[test]
+    fn test_boundaries() {
+        assert_eq!(
+            SecondDate::checked_new(NULL_REPRESENTATION - 1)
+                .unwrap()
+                .to_string(),
+            "9999-12-31T23:59:59"
+        );
+        assert_eq!(
+            SecondDate::checked_new(1).unwrap().to_string(),
+            "0001-01-01T00:00:00"
+        );
+        assert!(SecondDate::checked_new(NULL_REPRESENTATION).is_none());
+        assert!(SecondDate::checked_new(-1).is_none());
+    }
+
+    #[test]
+    fn test_unix_timestamp() {
+        let seconddate = SecondDate::from_unix_timestamp(1_700_000_000).unwrap();
+        assert_eq!(seconddate.to_string(), "2023-11-14T22:13:20");
+        assert_eq!(seconddate.to_unix_timestamp(), 1_700_000_000);
+        assert!(SecondDate::from_unix_timestamp(253_402_300_800).is_err());
     }
 }
@@ -1,10 +1,12 @@
-use crate::{impl_err, HdbResult, HdbValue};
+use crate::{impl_err, usage_err, HdbResult, HdbValue};
 use byteorder::{LittleEndian, ReadBytesExt};
+use std::time::Duration;
 
 const NULL_REPRESENTATION: i32 = 86_402;
 
 const MINUTE_FACTOR: u32 = 60;
 const HOUR_FACTOR: u32 = 3_600;
+const DAY_FACTOR: i64 = 86_400;
 
 /// Implementation of HANA's `SecondTime`.
 ///
@@ -32,16 +34,79 @@ impl std::cmp::PartialEq<SecondTime> for SecondTime {
 }
 
 impl SecondTime {
+    // Returns None if the raw value is not in the valid range.
     #[allow(clippy::cast_sign_loss)]
-    pub(crate) fn new(raw: i32) -> Self {
-        assert!((0..NULL_REPRESENTATION).contains(&raw));
-        Self(raw as u32)
+    pub(crate) fn checked_new(raw: i32) -> Option<Self> {
+        (0..NULL_REPRESENTATION)
+            .contains(&raw)
+            .then_some(Self(raw as u32))
     }
 
     pub(crate) fn ref_raw(&self) -> &u32 {
         &self.0
     }
 
+    /// Creates a value from the time of day of the given number of seconds
+    /// since 1970-01-01 00:00:00 UTC.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn from_unix_timestamp(seconds: i64) -> Self {
+        // rem_euclid is in 0..86_400
+        Self(seconds.rem_euclid(DAY_FACTOR) as u32 + 1)
+    }
+
+    /// Returns the number of seconds since midnight, which is the Unix timestamp
+    /// of the time of day on 1970-01-01.
+    #[must_use]
+    pub fn to_unix_timestamp(&self) -> i64 {
+        i64::from(self.0.max(1) - 1)
+    }
+
+    /// Creates a value from the given time since midnight; subseconds are ignored.
+    ///
+    /// # Errors
+    ///
+    /// `HdbError::Usage` if the duration is a day or longer.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_duration(since_midnight: Duration) -> HdbResult<Self> {
+        let seconds = since_midnight.as_secs();
+        if i64::try_from(seconds).is_ok_and(|seconds| seconds < DAY_FACTOR) {
+            Ok(Self(seconds as u32 + 1))
+        } else {
+            Err(usage_err!(
+                "duration of {seconds} seconds cannot be converted into a time of day"
+            ))
+        }
+    }
+
+    /// Returns the time since midnight.
+    #[must_use]
+    pub fn to_duration(&self) -> Duration {
+        Duration::from_secs(u64::from(self.0.max(1) - 1))
+    }
+
+    /// Creates a value from the given `time::Time`; subseconds are ignored.
+    #[must_use]
+    pub fn from_time(time: time::Time) -> Self {
+        let (hour, minute, second) = time.as_hms();
+        Self(
+            u32::from(hour) * HOUR_FACTOR
+                + u32::from(minute) * MINUTE_FACTOR
+                + u32::from(second)
+                + 1,
+        )
+    }
+
+    /// Converts into a `time::Time`.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn to_time(&self) -> time::Time {
+        let (hour, minute, second) = self.as_hms();
+        // as_hms() delivers values in the valid ranges
+        time::Time::from_hms(hour as u8, minute as u8, second as u8).unwrap_or(time::Time::MIDNIGHT)
+    }
+
     /// Convert into tuple of "elements".
     pub(crate) fn as_hms(&self) -> (u32, u32, u32) {
         let mut second = if self.0 == 0 { 0 } else { self.0 - 1 };
@@ -66,6 +131,41 @@ pub(crate) fn parse_secondtime(
             Err(impl_err!("found NULL value for NOT NULL SECONDTIME column",))
         }
     } else {
-        Ok(HdbValue::SECONDTIME(SecondTime::new(i)))
+        SecondTime::checked_new(i)
+            .map(HdbValue::SECONDTIME)
+            .ok_or_else(|| impl_err!("received invalid SECONDTIME value {i}"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SecondTime;
+    use std::time::Duration;
+    use time::macros::time;
+
+    #[test]
+    fn test_unix_timestamp() {
+        let secondtime = SecondTime::from_unix_timestamp(1_700_000_000);
+        assert_eq!(secondtime.to_string(), "22:13:20");
+        assert_eq!(secondtime.to_unix_timestamp(), 80_000);
+        assert_eq!(SecondTime::from_unix_timestamp(-1).to_string(), "23:59:59");
+    }
+
+    #[test]
+    fn test_duration_and_time() {
+        let secondtime = SecondTime::from_duration(Duration::from_millis(30_615_500)).unwrap();
+        assert_eq!(secondtime.to_string(), "08:30:15");
+        assert_eq!(secondtime.to_duration(), Duration::from_secs(30_615));
+        assert!(SecondTime::from_duration(Duration::from_secs(86_400)).is_err());
+
+        assert_eq!(secondtime.to_time(), time!(08:30:15));
+        assert_eq!(
+            SecondTime::from_time(time!(23:59:59.9)).to_string(),
+            "23:59:59"
+        );
+        assert_eq!(
+            SecondTime::checked_new(0).unwrap().to_time(),
+            time!(00:00:00)
+        );
     }
 }
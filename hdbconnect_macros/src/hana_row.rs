@@ -0,0 +1,227 @@
+use crate::column_accessor;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    ext::IdentExt, meta::ParseNestedMeta, Attribute, Data, DeriveInput, Expr, Fields, LitStr,
+    Token, Type,
+};
+
+// Generates, for each field that is deserialized, an accessor that is named after
+// the column from which serde takes the field's value, and the list of the columns
+// that must be present because their fields have no default.
+pub(crate) fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(ref data) = input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "HanaRow can only be derived for structs",
+        ));
+    };
+    let Fields::Named(ref fields) = data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "HanaRow requires a struct with named fields",
+        ));
+    };
+    let container = SerdeAttributes::parse(&input.attrs)?;
+
+    let mut accessors = Vec::<TokenStream2>::new();
+    let mut required = Vec::<String>::new();
+    for field in &fields.named {
+        let attributes = SerdeAttributes::parse(&field.attrs)?;
+        if attributes.flatten {
+            return Err(syn::Error::new_spanned(
+                field,
+                "HanaRow does not support #[serde(flatten)]",
+            ));
+        }
+        if attributes.skip {
+            continue;
+        }
+        let Some(ident) = field.ident.as_ref() else {
+            continue;
+        };
+        let column = match attributes.rename {
+            Some(rename) => rename,
+            None => apply_rename_rule(container.rename_all.as_deref(), &ident.unraw().to_string())
+                .map_err(|e| syn::Error::new_spanned(input, e))?,
+        };
+        if !(container.default || attributes.default || is_option(&field.ty)) {
+            required.push(column.clone());
+        }
+        let accessor = column_accessor(&column);
+        let ty = &field.ty;
+        accessors.push(quote!(
+            #[doc(hidden)]
+            pub fn #accessor(&self) -> &#ty {
+                &self.#ident
+            }
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote!(
+        #[allow(non_snake_case, clippy::all, clippy::pedantic)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(#accessors)*
+            #[doc(hidden)]
+            pub const __HANA_ROW_REQUIRED: &'static [&'static str] = &[#(#required),*];
+        }
+    ))
+}
+
+// The parts of the serde attributes that determine the column of a field.
+#[derive(Default)]
+struct SerdeAttributes {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    default: bool,
+    skip: bool,
+    flatten: bool,
+}
+impl SerdeAttributes {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut result = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    result.rename = deserialize_name(&meta)?;
+                } else if meta.path.is_ident("rename_all") {
+                    result.rename_all = deserialize_name(&meta)?;
+                } else if meta.path.is_ident("default") {
+                    result.default = true;
+                    skip_meta(&meta)?;
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_deserializing") {
+                    result.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    result.flatten = true;
+                } else {
+                    skip_meta(&meta)?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+// Handles both `rename = "..."` and `rename(deserialize = "...")`.
+fn deserialize_name(meta: &ParseNestedMeta) -> syn::Result<Option<String>> {
+    if meta.input.peek(Token![=]) {
+        return Ok(Some(meta.value()?.parse::<LitStr>()?.value()));
+    }
+    let mut o_name = None;
+    meta.parse_nested_meta(|inner| {
+        if inner.path.is_ident("deserialize") {
+            o_name = Some(inner.value()?.parse::<LitStr>()?.value());
+        } else {
+            skip_meta(&inner)?;
+        }
+        Ok(())
+    })?;
+    Ok(o_name)
+}
+
+fn skip_meta(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        meta.parse_nested_meta(|inner| skip_meta(&inner))?;
+    }
+    Ok(())
+}
+
+// Like serde does it for field names, which are expected to be in snake case.
+fn apply_rename_rule(o_rule: Option<&str>, field: &str) -> Result<String, String> {
+    let pascal = || {
+        field
+            .split('_')
+            .map(|part| {
+                let mut chars = part.chars();
+                chars
+                    .next()
+                    .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect::<String>()
+    };
+    Ok(match o_rule {
+        None | Some("lowercase" | "snake_case") => field.to_string(),
+        Some("UPPERCASE" | "SCREAMING_SNAKE_CASE") => field.to_ascii_uppercase(),
+        Some("PascalCase") => pascal(),
+        Some("camelCase") => {
+            let pascal = pascal();
+            let mut chars = pascal.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_lowercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        }
+        Some("kebab-case") => field.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => field.to_ascii_uppercase().replace('_', "-"),
+        Some(rule) => return Err(format!("unknown rename rule \"{rule}\"")),
+    })
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{apply_rename_rule, expand};
+    use crate::column_accessor;
+
+    #[test]
+    fn test_rename_rules() {
+        let rename = |rule| apply_rename_rule(rule, "first_name").unwrap();
+        assert_eq!(rename(None), "first_name");
+        assert_eq!(rename(Some("UPPERCASE")), "FIRST_NAME");
+        assert_eq!(rename(Some("PascalCase")), "FirstName");
+        assert_eq!(rename(Some("camelCase")), "firstName");
+        assert_eq!(rename(Some("SCREAMING-KEBAB-CASE")), "FIRST-NAME");
+        assert!(apply_rename_rule(Some("Whatever"), "id").is_err());
+    }
+
+    #[test]
+    fn test_expand() {
+        let input = syn::parse_str(
+            r#"
+            #[derive(serde::Deserialize, HanaRow)]
+            #[serde(rename_all = "UPPERCASE", deny_unknown_fields)]
+            struct Employee {
+                id: i32,
+                #[serde(rename = "EMP_NAME")]
+                name: Option<String>,
+                #[serde(default, rename(serialize = "x", deserialize = "SALARY"))]
+                salary: f64,
+                #[serde(skip)]
+                cache: Vec<u8>,
+            }"#,
+        )
+        .unwrap();
+        let expanded = expand(&input).unwrap().to_string();
+        for column in ["ID", "EMP_NAME", "SALARY"] {
+            assert!(
+                expanded.contains(&column_accessor(column).to_string()),
+                "{expanded}"
+            );
+        }
+        assert!(!expanded.contains("cache (& self)"), "{expanded}");
+        assert!(
+            expanded.contains(r#"__HANA_ROW_REQUIRED : & 'static [& 'static str] = & ["ID"]"#),
+            "{expanded}"
+        );
+
+        let input = syn::parse_str("struct Flat { #[serde(flatten)] inner: Inner }").unwrap();
+        assert!(expand(&input).is_err());
+        assert!(expand(&syn::parse_str("struct Tuple(i32);").unwrap()).is_err());
+    }
+}
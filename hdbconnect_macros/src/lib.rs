@@ -0,0 +1,247 @@
+//! Do not use this crate directly.
+//!
+//! This crate provides the procedural macros of `hdbconnect`;
+//! they are available there with the feature `macros`.
+
+#![deny(missing_debug_implementations)]
+#![deny(clippy::all)]
+#![deny(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+mod hana_row;
+
+use hdbconnect_impl::{sync::Connection, ResultSetMetadata, TypeId};
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
+use quote::{quote, quote_spanned};
+use std::sync::Arc;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, DeriveInput, LitStr, Path, Token,
+};
+
+/// The environment variable that provides the database url at build time.
+const DATABASE_URL: &str = "DATABASE_URL";
+
+/// Verifies at compile time that the result set of a query fits to a struct,
+/// and evaluates to the query string.
+///
+/// The first argument is the path of the driver crate (`hdbconnect` or `hdbconnect_async`),
+/// which provides the support code.
+/// See `hdbconnect::hana_query` for the documentation.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __hana_query(input: TokenStream) -> TokenStream {
+    let query = parse_macro_input!(input as QueryInput);
+    match query.expand() {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+/// Makes a struct usable as target of `hana_query!`.
+///
+/// See `hdbconnect::HanaRow` for the documentation.
+#[proc_macro_derive(HanaRow, attributes(serde))]
+pub fn derive_hana_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match hana_row::expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+// The name of the accessor that `HanaRow` generates for the field of the given column.
+//
+// Column names can contain arbitrary characters, so a hash keeps the names of different
+// columns apart after the replacement of the characters that are invalid in identifiers.
+fn column_accessor(column: &str) -> Ident {
+    let readable: String = column
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    // FNV-1a
+    let hash = column.bytes().fold(0x811c_9dc5_u32, |hash, b| {
+        (hash ^ u32::from(b)).wrapping_mul(0x0100_0193)
+    });
+    Ident::new(
+        &format!("__hana_column_{readable}_{hash:08x}"),
+        Span::call_site(),
+    )
+}
+
+// `__hana_query!($crate, TargetStruct, "SELECT ...")`
+struct QueryInput {
+    krate: Path,
+    target: Path,
+    sql: LitStr,
+}
+
+impl Parse for QueryInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let krate = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let target = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let sql = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { krate, target, sql })
+    }
+}
+
+impl QueryInput {
+    fn expand(&self) -> syn::Result<TokenStream2> {
+        let sql = &self.sql;
+        let Ok(url) = std::env::var(DATABASE_URL) else {
+            // without a database, the query cannot be verified;
+            // using a deprecated item is the only way to emit a warning on stable
+            return Ok(quote_spanned!(sql.span() => {
+                #[deprecated(
+                    note = "hana_query!: DATABASE_URL is not set, so the query is not verified"
+                )]
+                #[allow(non_upper_case_globals)]
+                const hana_query_not_verified: () = ();
+                let () = hana_query_not_verified;
+                #sql
+            }));
+        };
+
+        let metadata = describe(&url, &sql.value())
+            .map_err(|e| syn::Error::new(sql.span(), e))?
+            .ok_or_else(|| syn::Error::new(sql.span(), "the statement produces no result set"))?;
+        Ok(self.checks(&metadata))
+    }
+
+    // Code that compiles only if the target has a fitting field for each column,
+    // and a column for each field without default.
+    fn checks(&self, metadata: &ResultSetMetadata) -> TokenStream2 {
+        let Self { krate, target, sql } = self;
+        let mut columns = Vec::<String>::new();
+        let mut checks = Vec::<TokenStream2>::new();
+        for field_md in metadata {
+            let accessor = column_accessor(field_md.displayname());
+            let kind = Ident::new(column_kind(field_md.type_id()), Span::call_site());
+            let check = if field_md.is_nullable() {
+                quote!(nullable_column)
+            } else {
+                quote!(column)
+            };
+            checks.push(quote_spanned!(sql.span() =>
+                #krate::__macro_support::#check::<#krate::__macro_support::#kind, _>(
+                    #target::#accessor(__row)
+                );
+            ));
+            columns.push(field_md.displayname().to_string());
+        }
+
+        quote!({
+            #[allow(dead_code, clippy::all)]
+            fn __hana_query_check(__row: &#target) {
+                #(#checks)*
+            }
+            const _: () = #krate::__macro_support::assert_columns(
+                #target::__HANA_ROW_REQUIRED,
+                &[#(#columns),*],
+            );
+            #sql
+        })
+    }
+}
+
+fn describe(url: &str, sql: &str) -> Result<Option<Arc<ResultSetMetadata>>, String> {
+    let connection =
+        Connection::new(url).map_err(|e| format!("cannot connect to {DATABASE_URL}: {e}"))?;
+    let (_parameters, o_metadata) = connection
+        .describe(sql)
+        .map_err(|e| format!("cannot prepare the statement: {e}"))?;
+    Ok(o_metadata)
+}
+
+// Name of the column kind in `hdbconnect::__macro_support`.
+fn column_kind(type_id: TypeId) -> &'static str {
+    match type_id {
+        TypeId::TINYINT | TypeId::SMALLINT | TypeId::INT | TypeId::BIGINT => "Integer",
+        TypeId::DECIMAL | TypeId::FIXED8 | TypeId::FIXED12 | TypeId::FIXED16 => "Decimal",
+        TypeId::REAL | TypeId::DOUBLE => "Float",
+        TypeId::BOOLEAN => "Boolean",
+        TypeId::CHAR
+        | TypeId::VARCHAR
+        | TypeId::NCHAR
+        | TypeId::NVARCHAR
+        | TypeId::CLOB
+        | TypeId::NCLOB
+        | TypeId::STRING
+        | TypeId::NSTRING
+        | TypeId::TEXT
+        | TypeId::SHORTTEXT
+        | TypeId::BINTEXT
+        | TypeId::ALPHANUM => "Text",
+        TypeId::BINARY
+        | TypeId::VARBINARY
+        | TypeId::BLOB
+        | TypeId::BLOCATOR
+        | TypeId::BSTRING
+        | TypeId::GEOMETRY
+        | TypeId::POINT => "Binary",
+        TypeId::LONGDATE | TypeId::SECONDDATE | TypeId::DAYDATE | TypeId::SECONDTIME => "Temporal",
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{column_accessor, column_kind, QueryInput};
+    use hdbconnect_impl::{FieldMetadata, ResultSetMetadata, TypeId};
+
+    #[test]
+    fn test_parse_input() {
+        let input: QueryInput =
+            syn::parse_str(r#"::hdbconnect, crate::Employee, "SELECT ID FROM EMPLOYEES","#)
+                .unwrap();
+        assert_eq!(input.sql.value(), "SELECT ID FROM EMPLOYEES");
+        assert_eq!(input.target.segments.len(), 2);
+
+        assert!(syn::parse_str::<QueryInput>(r#"::hdbconnect, "SELECT ID""#).is_err());
+    }
+
+    #[test]
+    fn test_column_accessor() {
+        assert!(column_accessor("ID")
+            .to_string()
+            .starts_with("__hana_column_ID_"));
+        assert_ne!(column_accessor("A B"), column_accessor("A_B"));
+    }
+
+    #[test]
+    fn test_checks() {
+        let input: QueryInput =
+            syn::parse_str(r#"::hdbconnect, Employee, "SELECT ID, NAME FROM EMPLOYEES""#).unwrap();
+        let metadata = ResultSetMetadata::new(vec![
+            FieldMetadata::new_column("ID", TypeId::INT).with_nullable(false),
+            FieldMetadata::new_column("NAME", TypeId::NVARCHAR),
+        ]);
+        let checks = input.checks(&metadata).to_string();
+        assert!(
+            checks.contains(&format!(
+                "column :: < :: hdbconnect :: __macro_support :: Integer , _ > (Employee :: {} (__row))",
+                column_accessor("ID")
+            )),
+            "{checks}"
+        );
+        assert!(
+            checks.contains(&format!(
+                "nullable_column :: < :: hdbconnect :: __macro_support :: Text , _ > (Employee :: {} (__row))",
+                column_accessor("NAME")
+            )),
+            "{checks}"
+        );
+        assert!(checks.contains(r#"& ["ID" , "NAME"]"#), "{checks}");
+    }
+
+    #[test]
+    fn test_column_kind() {
+        assert_eq!(column_kind(TypeId::BIGINT), "Integer");
+        assert_eq!(column_kind(TypeId::FIXED12), "Decimal");
+        assert_eq!(column_kind(TypeId::NCLOB), "Text");
+        assert_eq!(column_kind(TypeId::LONGDATE), "Temporal");
+    }
+}